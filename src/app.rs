@@ -19,14 +19,37 @@ use anyhow::Result;
 use eframe::egui::{self, RichText};
 use tokio::task::JoinHandle;
 
+use crate::backup::{self, BackupNameVars, RetentionPolicy};
 use crate::config::Config;
 use crate::db::Database;
 use crate::game::{self, GameInfo};
 use crate::github::GitHubClient;
+use crate::library::{LibraryItem, LibraryKind};
+use crate::migration::ModInfo;
+use crate::mods::{self, ModError};
+use crate::soundpack::{self, ArchiveContent, SoundpackError};
 use crate::state::{
-    BackupState, ReleasesState, SoundpackState, StateEvent, Tab, UiState, UpdateParams, UpdateState,
+    BackupState, LaunchState, LauncherUpdateState, LibraryState, ReleasesState, RollbackParams,
+    SoundpackState, StateEvent, Tab, TilesetState, UiState, UpdateParams, UpdateState, WatchState,
 };
 use crate::task::{PollResult, poll_task};
+use crate::ui::theme::ThemePreset;
+use crate::update::InstallOptions;
+use crate::util::format_clock;
+
+/// Refine a game's version via hash lookup in the background
+fn spawn_version_refine(info: &GameInfo) -> JoinHandle<Result<GameInfo>> {
+    let info = info.clone();
+    let db_path = Database::db_path().ok();
+    tokio::spawn(async move {
+        // Open a separate database connection for the background task
+        let db = db_path.and_then(|_| Database::open().ok());
+        game::refine_version_with_hash(&info, db.as_ref())
+    })
+}
+
+/// Dropped archives and what each one was found to contain
+type DetectedDrops = Vec<(PathBuf, Result<Option<ArchiveContent>, SoundpackError>)>;
 
 /// Main application state
 pub struct PhoenixApp {
@@ -43,6 +66,15 @@ pub struct PhoenixApp {
     pub(crate) github_client: GitHubClient,
     /// Background task for refining game version via hash lookup
     version_refine_task: Option<JoinHandle<Result<GameInfo>>>,
+    /// Background task re-detecting the game after its directory changed
+    redetect_task: Option<JoinHandle<Result<Option<GameInfo>>>>,
+    /// Background task installing a mod archive dropped onto the window
+    mod_install_task: Option<JoinHandle<Result<ModInfo, ModError>>>,
+    /// Dropped archives waiting to be installed, with their content once
+    /// it's known
+    pending_drops: Vec<(PathBuf, Option<ArchiveContent>)>,
+    /// Background task detecting what dropped archives contain
+    drop_detect_task: Option<JoinHandle<DetectedDrops>>,
 
     // Grouped state
     /// UI state (theme, tabs, dialogs)
@@ -55,6 +87,16 @@ pub struct PhoenixApp {
     pub(crate) backup: BackupState,
     /// Soundpack state
     pub(crate) soundpack: SoundpackState,
+    /// Tileset state
+    pub(crate) tileset: TilesetState,
+    /// Library search state
+    pub(crate) library: LibraryState,
+    /// Game launch state
+    pub(crate) launch: LaunchState,
+    /// Launcher self-update state
+    pub(crate) launcher_update: LauncherUpdateState,
+    /// Game directory watcher
+    watch: WatchState,
 }
 
 impl PhoenixApp {
@@ -64,7 +106,7 @@ impl PhoenixApp {
 
         // Load configuration
         let phase_start = Instant::now();
-        let config = Config::load().unwrap_or_default();
+        let (config, config_recovery) = Config::load_reporting().unwrap_or_default();
         tracing::info!(
             "Config loaded in {:.1}ms",
             phase_start.elapsed().as_secs_f32() * 1000.0
@@ -99,17 +141,7 @@ impl PhoenixApp {
         }
 
         // Spawn background task to refine version via hash lookup
-        let version_refine_task = if let Some(ref info) = game_info {
-            let info_clone = info.clone();
-            let db_path = Database::db_path().ok();
-            Some(tokio::spawn(async move {
-                // Open a separate database connection for the background task
-                let db = db_path.and_then(|_| Database::open().ok());
-                game::refine_version_with_hash(&info_clone, db.as_ref())
-            }))
-        } else {
-            None
-        };
+        let version_refine_task = game_info.as_ref().map(spawn_version_refine);
 
         let status_message = if let Some(ref info) = game_info {
             format!("Game detected: {}", info.version_display())
@@ -117,8 +149,11 @@ impl PhoenixApp {
             "Ready".to_string()
         };
 
-        // Create GitHub client
-        let github_client = GitHubClient::default();
+        // Create GitHub client (shared by all downloads for proxy settings)
+        let github_client = GitHubClient::new(&config.network).unwrap_or_else(|e| {
+            tracing::error!("Invalid network settings, using defaults: {}", e);
+            GitHubClient::default()
+        });
 
         // Load theme
         let current_theme = config.launcher.theme.theme();
@@ -132,20 +167,42 @@ impl PhoenixApp {
             status_message,
             github_client,
             version_refine_task,
-            ui: UiState::new(current_theme),
+            redetect_task: None,
+            mod_install_task: None,
+            pending_drops: Vec::new(),
+            drop_detect_task: None,
+            ui: UiState {
+                config_recovery,
+                ..UiState::new(current_theme)
+            },
             releases: ReleasesState::default(),
             update: UpdateState::default(),
             backup: BackupState::default(),
             soundpack: SoundpackState::default(),
+            tileset: TilesetState::default(),
+            library: LibraryState::default(),
+            launch: LaunchState::default(),
+            launcher_update: LauncherUpdateState::default(),
+            watch: WatchState::default(),
         };
 
+        app.ui.record_status(&app.status_message);
+        app.soundpack.client = app.github_client.client().clone();
+        app.tileset.client = app.github_client.client().clone();
+        app.refresh_session_stats();
+
         // Migrate legacy data (save_backups folder, previous_version folder)
-        if let Some(ref game_dir) = app.config.game.directory {
-            crate::legacy::migrate(std::path::Path::new(game_dir));
+        if let Some(game_dir) = app.config.game.directory.clone() {
+            crate::legacy::migrate(std::path::Path::new(&game_dir));
+            app.restore_safe_mode(std::path::Path::new(&game_dir));
         }
 
         // Auto-fetch releases for current branch on startup
-        if let Some(event) = app.releases.fetch_for_branch(&branch, &app.github_client) {
+        if let Some(event) = app.releases.fetch_for_branch(
+            &branch,
+            &app.github_client,
+            app.config.updates.experimental_fetch_count,
+        ) {
             app.handle_event(event);
         }
 
@@ -157,11 +214,17 @@ impl PhoenixApp {
         app
     }
 
+    /// Show a message in the status bar and add it to the status history
+    pub(crate) fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = message.into();
+        self.ui.record_status(&self.status_message);
+    }
+
     /// Handle a single state event
     fn handle_event(&mut self, event: StateEvent) {
         match event {
             StateEvent::StatusMessage(msg) => {
-                self.status_message = msg;
+                self.set_status(msg);
             }
             StateEvent::RefreshGameInfo => {
                 self.refresh_game_info();
@@ -172,6 +235,11 @@ impl PhoenixApp {
             StateEvent::LogInfo(msg) => {
                 tracing::info!("{}", msg);
             }
+            StateEvent::ReleasesFetched { branch, ok } => {
+                if self.update.pending_stable_install {
+                    self.continue_switch_to_stable(branch.as_deref(), ok);
+                }
+            }
             StateEvent::ChangelogFetched { tag, body } => {
                 // Cache the changelog in the database
                 if let Some(ref db) = self.db {
@@ -182,6 +250,15 @@ impl PhoenixApp {
                     }
                 }
             }
+            StateEvent::RetryUpdate => {
+                if self.update.repairing {
+                    self.start_repair();
+                } else if self.update.cleaning {
+                    self.start_clean_reinstall();
+                } else {
+                    self.start_update();
+                }
+            }
             StateEvent::GameVersionRefined(info) => {
                 // Update with refined version info (may now show as stable)
                 let version_changed = self.game_info.as_ref().map(|g| g.version_display())
@@ -192,10 +269,74 @@ impl PhoenixApp {
                         info.version_display(),
                         info.is_stable()
                     );
-                    self.status_message = format!("Game detected: {}", info.version_display());
+                    self.set_status(format!("Game detected: {}", info.version_display()));
+                }
+                self.game_info = Some(info);
+            }
+            StateEvent::GameDirChanged => {
+                self.redetect_game();
+            }
+            StateEvent::SessionEnded {
+                started_at,
+                ended_at,
+                version,
+            } => {
+                if let Some(ref db) = self.db {
+                    if let Err(e) = db.record_session(started_at, ended_at, version.as_deref()) {
+                        tracing::warn!("Failed to record play session: {}", e);
+                    }
+                    self.refresh_session_stats();
+                }
+                self.backup
+                    .on_session_ended(self.config.backups.offer_safety_cleanup);
+            }
+        }
+    }
+
+    /// Re-detect the game after its directory changed on disk
+    fn redetect_game(&mut self) {
+        if self.redetect_task.is_some() {
+            return;
+        }
+        let Some(dir) = self.config.game.directory.clone() else {
+            return;
+        };
+
+        self.redetect_task = Some(tokio::task::spawn_blocking(move || {
+            game::detect_game_fast(&PathBuf::from(dir))
+        }));
+    }
+
+    /// Apply the result of re-detecting the game
+    fn apply_redetected_game(&mut self, detected: Result<Option<GameInfo>>) {
+        match detected {
+            Ok(Some(info)) => {
+                let previous = self.game_info.as_ref().map(|g| g.version_display());
+                if previous != Some(info.version_display()) {
+                    tracing::info!("Game changed on disk: {}", info.version_display());
+                    self.set_status(format!("Game detected: {}", info.version_display()));
                 }
+                self.version_refine_task = Some(spawn_version_refine(&info));
                 self.game_info = Some(info);
             }
+            Ok(None) => {
+                if self.game_info.take().is_some() {
+                    tracing::info!("Game no longer found in the game directory");
+                    self.set_status("No game detected - the game directory changed");
+                }
+            }
+            Err(e) => tracing::warn!("Failed to re-detect game: {}", e),
+        }
+    }
+
+    /// Reload play time statistics from the database
+    fn refresh_session_stats(&mut self) {
+        let Some(ref db) = self.db else {
+            return;
+        };
+        match db.session_stats(chrono::Utc::now().timestamp()) {
+            Ok(stats) => self.launch.stats = Some(stats),
+            Err(e) => tracing::warn!("Failed to load play session stats: {}", e),
         }
     }
 
@@ -213,8 +354,16 @@ impl PhoenixApp {
 
     /// Simple check: is the selected release different from the installed version?
     pub(crate) fn is_selected_release_different(&self) -> bool {
-        self.releases
-            .is_selected_different(&self.config.game.branch, self.game_info.as_ref())
+        self.releases.is_selected_different(
+            &self.config.game.branch,
+            self.game_info.as_ref(),
+            self.config.game.version_override(),
+        )
+    }
+
+    /// Check if the selected release has a build for this platform
+    pub(crate) fn selected_release_has_asset(&mut self) -> bool {
+        self.releases.selected_has_asset(&self.config.game.branch)
     }
 
     /// Check if we have releases for the given branch
@@ -224,40 +373,38 @@ impl PhoenixApp {
 
     /// Start fetching releases for a specific branch
     pub(crate) fn fetch_releases_for_branch(&mut self, branch: &str) {
-        if let Some(event) = self.releases.fetch_for_branch(branch, &self.github_client) {
+        if let Some(event) = self.releases.fetch_for_branch(
+            branch,
+            &self.github_client,
+            self.config.updates.experimental_fetch_count,
+        ) {
             self.handle_event(event);
         }
     }
 
-    /// Ensure changelog is available for the selected stable release.
-    /// Checks DB cache first, then fetches from GitHub API if needed.
+    /// Ensure the changelog is available for the selected release.
+    /// Checks the in-memory cache, then the DB cache, then fetches from GitHub API.
     pub(crate) fn ensure_changelog_for_selection(&mut self) {
-        // Only applies to stable branch
-        if self.config.game.branch != "stable" {
-            return;
-        }
-
         // Get the selected release
         let Some(idx) = self.releases.selected_idx else {
             return;
         };
-        let Some(release) = self.releases.stable.get(idx) else {
+        let Some(release) = self.current_releases().get(idx) else {
             return;
         };
+        let tag = release.tag_name.clone();
 
-        // If it already has a body, we're done
-        if release.body.is_some() {
+        // Already cached (or known to be unavailable) or on its way, we're done
+        if self.releases.has_changelog_result(&tag) || self.releases.changelog_loading {
             return;
         }
 
-        let tag = release.tag_name.clone();
-
         // Check DB cache first
         if let Some(ref db) = self.db
             && let Ok(Some(body)) = db.get_changelog(&tag)
         {
             tracing::debug!("Loaded changelog for {} from cache", tag);
-            self.releases.set_stable_release_body(&tag, body);
+            self.releases.set_changelog(&tag, body);
             return;
         }
 
@@ -283,6 +430,185 @@ impl PhoenixApp {
             }
         };
 
+        let install = InstallOptions::from_config(&self.config.updates);
+        self.start_install(release, false, false, install);
+    }
+
+    /// Switch to the stable branch and install its newest release.
+    ///
+    /// Stable releases are fetched first if needed; the install then starts
+    /// from [`Self::continue_switch_to_stable`] once they arrive.
+    pub(crate) fn switch_to_latest_stable(&mut self) {
+        if self.update.is_updating() {
+            return;
+        }
+
+        if self.config.game.branch != "stable" {
+            self.config.game.branch = "stable".to_string();
+            self.save_config();
+        }
+        self.update.error = None;
+
+        if self.has_releases_for_branch("stable") {
+            self.install_latest_stable();
+        } else {
+            self.releases.selected_idx = None;
+            self.update.pending_stable_install = true;
+            self.fetch_releases_for_branch("stable");
+        }
+    }
+
+    /// Resume a pending switch to stable after a release fetch finished
+    fn continue_switch_to_stable(&mut self, branch: Option<&str>, ok: bool) {
+        if self.config.game.branch != "stable" {
+            // The user picked another branch while waiting
+            self.update.pending_stable_install = false;
+        } else if !ok {
+            self.update.pending_stable_install = false;
+            self.update.error = Some("Could not fetch the stable releases".to_string());
+        } else if branch == Some("stable") {
+            self.update.pending_stable_install = false;
+            self.install_latest_stable();
+        } else {
+            // Another branch was still being fetched when the switch started
+            self.fetch_releases_for_branch("stable");
+        }
+    }
+
+    /// Select the newest stable release and install it unless it's installed already
+    fn install_latest_stable(&mut self) {
+        if !self.has_releases_for_branch("stable") {
+            self.update.error = Some("No stable releases found".to_string());
+            return;
+        }
+
+        self.releases.selected_idx = Some(0);
+        self.ensure_changelog_for_selection();
+        if self.game_info.is_some() && !self.is_selected_release_different() {
+            self.set_status("Already on the latest stable release");
+            return;
+        }
+        self.start_update();
+    }
+
+    /// Find the release matching the installed game, if it has been fetched
+    pub(crate) fn installed_release(&self) -> Option<&crate::github::Release> {
+        self.releases
+            .installed_release(&self.config.game.branch, self.game_info.as_ref()?)
+    }
+
+    /// Re-install the current version over the existing installation.
+    ///
+    /// Reuses the downloaded archive when present, and restores user data
+    /// through the same smart migration as a normal update.
+    pub(crate) fn start_repair(&mut self) {
+        if self.update.is_updating() {
+            return;
+        }
+
+        let Some(release) = self.installed_release().cloned() else {
+            self.update.error =
+                Some("The installed version was not found in the release list".to_string());
+            return;
+        };
+
+        let install = InstallOptions::from_config(&self.config.updates);
+        self.start_install(release, true, false, install);
+    }
+
+    /// Install the selected release from scratch, keeping only saves and config.
+    ///
+    /// Custom mods, tilesets, soundpacks and fonts are not migrated; they stay
+    /// in the installation archive unless the archives are deleted too.
+    pub(crate) fn start_clean_reinstall(&mut self) {
+        if self.update.is_updating() {
+            return;
+        }
+
+        let releases = self.current_releases();
+        let Some(release) = self
+            .releases
+            .selected_idx
+            .and_then(|i| releases.get(i))
+            .cloned()
+        else {
+            self.update.error = Some("No release selected".to_string());
+            return;
+        };
+
+        let mut install = InstallOptions::from_config(&self.config.updates);
+        install.clean = true;
+        install.remove_previous_version |= self.update.clean_delete_archives;
+        self.start_install(release, false, false, install);
+    }
+
+    /// Open the rollback dialog and list the archived installations
+    pub(crate) fn open_rollback(&mut self) {
+        let Some(dir) = self.config.game.directory.clone() else {
+            return;
+        };
+        self.update.archives = None;
+        self.update.rollback_selected = None;
+        self.update.refresh_archives(&PathBuf::from(dir));
+        self.update.show_rollback = true;
+    }
+
+    /// Roll back to the archive selected in the rollback dialog
+    pub(crate) fn start_rollback(&mut self) {
+        if self.update.is_updating() {
+            return;
+        }
+
+        let target = self
+            .update
+            .rollback_selected
+            .and_then(|i| self.update.archives.as_ref()?.get(i).cloned());
+        let (Some(target), Some(dir)) = (target, self.config.game.directory.clone()) else {
+            return;
+        };
+
+        let params = RollbackParams {
+            target,
+            game_dir: PathBuf::from(dir),
+            install: InstallOptions::from_config(&self.config.updates),
+        };
+
+        if let Some(event) = self.update.start_rollback(params) {
+            self.handle_event(event);
+        }
+    }
+
+    /// Retry the last failed update, repair or clean reinstall.
+    ///
+    /// The release that failed is installed again, reusing its download when
+    /// it is still intact.
+    pub(crate) fn retry_failed_update(&mut self) {
+        if !self.update.can_retry() {
+            return;
+        }
+        let Some(release) = self.update.last_release.clone() else {
+            return;
+        };
+
+        let mut install = InstallOptions::from_config(&self.config.updates);
+        if self.update.cleaning {
+            install.clean = true;
+            install.remove_previous_version |= self.update.clean_delete_archives;
+        }
+        let repair = self.update.repairing;
+        self.start_install(release, repair, true, install);
+    }
+
+    /// Download (if needed) and install a release.
+    ///
+    /// `repair` and `retry` reuse an already downloaded archive when possible.
+    fn start_install(
+        &mut self,
+        release: crate::github::Release,
+        repair: bool,
+        retry: bool,
+        install: InstallOptions,
+    ) {
         let asset = match GitHubClient::find_platform_asset(&release) {
             Some(a) => a.clone(),
             None => {
@@ -306,11 +632,15 @@ impl PhoenixApp {
             asset,
             game_dir,
             client: self.github_client.clone(),
-            prevent_save_move: self.config.updates.prevent_save_move,
-            remove_previous_version: self.config.updates.remove_previous_version,
+            install,
             backup_before_update: self.config.backups.backup_before_update,
             compression_level: self.config.backups.compression_level,
-            max_backups: self.config.backups.max_count,
+            retention: RetentionPolicy::from_config(&self.config.backups),
+            backup_name_template: self.config.backups.name_template.clone(),
+            branch: self.config.game.branch.clone(),
+            repair,
+            retry,
+            download_connections: self.config.updates.download_connections,
         };
 
         if let Some(event) = self.update.start(params) {
@@ -323,15 +653,15 @@ impl PhoenixApp {
         if let Some(ref dir) = self.config.game.directory {
             match game::detect_game_with_db(&PathBuf::from(dir), self.db.as_ref()) {
                 Ok(Some(info)) => {
-                    self.status_message = format!("Game updated to: {}", info.version_display());
+                    self.set_status(format!("Game updated to: {}", info.version_display()));
                     self.game_info = Some(info);
                 }
                 Ok(None) => {
-                    self.status_message = "Update complete, but game not detected".to_string();
+                    self.set_status("Update complete, but game not detected");
                     self.game_info = None;
                 }
                 Err(e) => {
-                    self.status_message = format!("Update complete, detection error: {}", e);
+                    self.set_status(format!("Update complete, detection error: {}", e));
                 }
             }
         }
@@ -350,26 +680,27 @@ impl PhoenixApp {
         {
             let path_str = path.to_string_lossy().to_string();
             self.config.game.directory = Some(path_str);
+            self.update.archive_size = None;
 
             // Try to detect game in selected directory
             match game::detect_game_with_db(&path, self.db.as_ref()) {
                 Ok(Some(info)) => {
-                    self.status_message = format!(
+                    self.set_status(format!(
                         "Game found: {} ({})",
                         info.executable
                             .file_name()
                             .unwrap_or_default()
                             .to_string_lossy(),
                         info.version_display()
-                    );
+                    ));
                     self.game_info = Some(info);
                 }
                 Ok(None) => {
-                    self.status_message = "No game executable found in directory".to_string();
+                    self.set_status("No game executable found in directory");
                     self.game_info = None;
                 }
                 Err(e) => {
-                    self.status_message = format!("Error detecting game: {}", e);
+                    self.set_status(format!("Error detecting game: {}", e));
                     self.game_info = None;
                 }
             }
@@ -386,19 +717,168 @@ impl PhoenixApp {
         }
     }
 
-    /// Launch the game
+    /// Rebuild the HTTP clients after the network settings changed
+    pub(crate) fn apply_network_settings(&mut self) {
+        match GitHubClient::new(&self.config.network) {
+            Ok(client) => {
+                self.soundpack.client = client.client().clone();
+                self.tileset.client = client.client().clone();
+                self.github_client = client;
+                self.save_config();
+            }
+            Err(e) => {
+                self.set_status(format!("Network settings not applied: {}", e));
+            }
+        }
+    }
+
+    /// Check GitHub for a newer launcher release
+    pub(crate) fn check_launcher_update(&mut self) {
+        self.launcher_update.check(&self.github_client);
+    }
+
+    /// Download and swap in the available launcher release
+    pub(crate) fn install_launcher_update(&mut self) {
+        if let Some(event) = self.launcher_update.install(&self.github_client) {
+            self.handle_event(event);
+        }
+    }
+
+    /// Re-enable mods left disabled by a safe-mode launch (e.g. if the
+    /// launcher was closed while the game was running)
+    fn restore_safe_mode(&mut self, game_dir: &std::path::Path) {
+        if !crate::safe_mode::is_active(game_dir) {
+            return;
+        }
+        match crate::safe_mode::restore(game_dir) {
+            Ok(count) => tracing::info!("Re-enabled {} mods left disabled by safe mode", count),
+            Err(e) => {
+                tracing::error!("Failed to re-enable mods: {}", e);
+                self.set_status(format!("Failed to re-enable mods: {}", e));
+            }
+        }
+    }
+
+    /// Launch the game with all mods disabled, re-enabling them when it exits
+    pub(crate) fn launch_game_safe_mode(&mut self) {
+        let (Some(info), Some(dir)) = (&self.game_info, &self.config.game.directory) else {
+            self.set_status("No game detected - select a valid game directory");
+            return;
+        };
+
+        let result = self.launch.start_safe_mode_session(
+            std::path::Path::new(dir),
+            &info.executable,
+            self.config.game.launch_params(),
+            Some(info.version_display().to_string()),
+        );
+        if result.is_ok() {
+            self.record_launch();
+        }
+        self.set_status(match result {
+            Ok(count) => format!("Game launched in safe mode ({} mods disabled)", count),
+            Err(e) => format!("Failed to launch in safe mode: {}", e),
+        });
+    }
+
+    /// Launch the game, first asking for confirmation if saves were last
+    /// played with a different branch or a newer build
     pub(crate) fn launch_game(&mut self) {
+        if let Some(warning) = self.launch_mismatch() {
+            self.ui.confirm_launch = Some(warning);
+            return;
+        }
+        self.launch_game_confirmed();
+    }
+
+    /// Save compatibility warning for launching the detected game, if any
+    fn launch_mismatch(&self) -> Option<String> {
+        let db = self.db.as_ref()?;
+        let dir = self.config.game.directory.as_deref()?;
+        let current = self.game_info.as_ref()?.version_info.as_ref()?;
+        let last = match db.last_launch(dir) {
+            Ok(last) => last?,
+            Err(e) => {
+                tracing::warn!("Failed to read last launched version: {}", e);
+                return None;
+            }
+        };
+        game::launch_mismatch(&last, current)
+    }
+
+    /// Remember the launched version so later launches can be checked against it
+    fn record_launch(&self) {
+        let (Some(db), Some(dir), Some(version)) = (
+            &self.db,
+            &self.config.game.directory,
+            self.game_info
+                .as_ref()
+                .and_then(|i| i.version_info.as_ref()),
+        ) else {
+            return;
+        };
+        if let Err(e) = db.record_launch(dir, version, chrono::Utc::now().timestamp()) {
+            tracing::warn!("Failed to record launched version: {}", e);
+        }
+    }
+
+    /// Launch the game without the save compatibility check
+    pub(crate) fn launch_game_confirmed(&mut self) {
+        if let Some(dir) = self.config.game.directory.clone() {
+            self.restore_safe_mode(std::path::Path::new(&dir));
+        }
         if let Some(ref info) = self.game_info {
-            match game::launch_game(&info.executable, &self.config.game.command_params) {
+            let params = self.config.game.launch_params();
+            let result = if self.config.launcher.keep_open {
+                self.launch.start_session(
+                    &info.executable,
+                    params,
+                    Some(info.version_display().to_string()),
+                )
+            } else {
+                game::launch_game(&info.executable, params)
+            };
+            match result {
                 Ok(()) => {
-                    self.status_message = "Game launched!".to_string();
+                    self.set_status("Game launched!");
+                    self.record_launch();
                 }
                 Err(e) => {
-                    self.status_message = format!("Failed to launch: {}", e);
+                    self.set_status(format!("Failed to launch: {}", e));
                 }
             }
         } else {
-            self.status_message = "No game detected - select a valid game directory".to_string();
+            self.set_status("No game detected - select a valid game directory");
+        }
+    }
+
+    /// Launch the game with its output captured, to diagnose startup failures
+    pub(crate) fn launch_game_diagnostic(&mut self) {
+        let Some(ref info) = self.game_info else {
+            self.set_status("No game detected - select a valid game directory");
+            return;
+        };
+
+        let executable = info.executable.clone();
+        let params = self.config.game.launch_params().to_string();
+        if let Some(event) = self.launch.start_diagnostic(&executable, &params) {
+            self.handle_event(event);
+        }
+    }
+
+    /// Copy the command line a launch would run, for external launchers
+    pub(crate) fn copy_launch_command(&mut self, ctx: &egui::Context) {
+        let Some(ref info) = self.game_info else {
+            self.set_status("No game detected - select a valid game directory");
+            return;
+        };
+
+        match game::launch_command_line(&info.executable, self.config.game.launch_params()) {
+            Ok(line) => {
+                ctx.copy_text(line);
+                self.set_status("Launch command copied to clipboard");
+            }
+            Err(e) => self.set_status(format!("Failed to build launch command: {}", e)),
         }
     }
 
@@ -411,7 +891,66 @@ impl PhoenixApp {
 
     /// Validate a backup name
     pub(crate) fn validate_backup_name(&self, name: &str) -> Result<(), String> {
-        self.backup.validate_name(name)
+        backup::validate_new_backup_name(name, &self.backup.list).map_err(|e| e.to_string())
+    }
+
+    /// Suggested name for a manual backup, expanded from the name template
+    pub(crate) fn default_backup_name(&self) -> String {
+        let vars = BackupNameVars {
+            version: self
+                .game_info
+                .as_ref()
+                .map(|info| info.version_display().to_string()),
+            branch: Some(self.config.game.branch.clone()),
+        };
+        let base = backup::expand_name_template(
+            &self.config.backups.name_template,
+            &vars,
+            chrono::Local::now(),
+        );
+
+        // Avoid suggesting a name that's already taken
+        let mut name = base.clone();
+        let mut counter = 2;
+        while self.backup.list.iter().any(|b| b.name == name) {
+            name = format!("{}{}", base, counter);
+            counter += 1;
+        }
+        name
+    }
+
+    /// Back up the running game's saves when a scheduled backup is due
+    fn backup_if_scheduled(&mut self, ctx: &egui::Context) {
+        let backups = &self.config.backups;
+        let game_running = backups.backup_on_interval
+            && self.launch.session_elapsed().is_some()
+            && !self.update.is_updating();
+        if !self
+            .backup
+            .scheduled_backup_due(ctx, game_running, backups.backup_interval_minutes)
+        {
+            return;
+        }
+        let Some(dir) = self.config.game.directory.clone() else {
+            return;
+        };
+
+        let vars = BackupNameVars {
+            version: self
+                .game_info
+                .as_ref()
+                .map(|info| info.version_display().to_string()),
+            branch: Some(self.config.game.branch.clone()),
+        };
+        if let Some(event) = self.backup.start_scheduled_backup(
+            std::path::Path::new(&dir),
+            &backups.name_template,
+            vars,
+            backups.compression_level,
+            RetentionPolicy::from_config(backups),
+        ) {
+            self.handle_event(event);
+        }
     }
 
     /// Start a manual backup
@@ -429,7 +968,7 @@ impl PhoenixApp {
         self.backup.refresh_list();
     }
 
-    /// Delete the selected backup
+    /// Delete the selected backups
     pub(crate) fn delete_selected_backup(&mut self) {
         if let Some(event) = self.backup.delete_selected() {
             self.handle_event(event);
@@ -447,6 +986,47 @@ impl PhoenixApp {
         }
     }
 
+    /// Extract the file or folder selected in the backup browser into `dest`
+    pub(crate) fn extract_backup_entry(&mut self, dest: PathBuf) {
+        if let Some(event) = self.backup.extract_selected_entry(dest) {
+            self.handle_event(event);
+        }
+    }
+
+    /// Save the note typed for the selected backup
+    pub(crate) fn save_backup_note(&mut self) {
+        if let Some(event) = self.backup.save_selected_note() {
+            self.handle_event(event);
+        }
+    }
+
+    /// Delete the safety backup offered after a restore
+    pub(crate) fn delete_offered_safety_backup(&mut self) {
+        if let Some(event) = self.backup.delete_offered_safety_backup() {
+            self.handle_event(event);
+        }
+    }
+
+    /// Delete safety backups older than the configured age
+    pub(crate) fn clean_safety_backups(&mut self) {
+        if let Some(event) = self
+            .backup
+            .clean_safety_backups(self.config.backups.safety_max_age_days)
+        {
+            self.handle_event(event);
+        }
+    }
+
+    /// Restore the confirmed external backup archive
+    pub(crate) fn restore_external_backup(&mut self, game_dir: &std::path::Path) {
+        if let Some(event) = self
+            .backup
+            .restore_external(game_dir, self.config.backups.compression_level)
+        {
+            self.handle_event(event);
+        }
+    }
+
     // Soundpack delegation methods
 
     /// Check if a soundpack operation is in progress
@@ -465,7 +1045,7 @@ impl PhoenixApp {
         repo_soundpack: crate::app_data::RepoSoundpack,
         game_dir: &std::path::Path,
     ) {
-        self.soundpack.install(repo_soundpack, game_dir);
+        self.soundpack.request_install(repo_soundpack, game_dir);
     }
 
     /// Install a soundpack from a local file
@@ -476,10 +1056,262 @@ impl PhoenixApp {
     ) {
         self.soundpack.install_from_file(archive_path, game_dir);
     }
+
+    // Tileset delegation methods
+
+    /// Check if a tileset operation is in progress
+    pub(crate) fn is_tileset_busy(&self) -> bool {
+        self.tileset.is_busy()
+    }
+
+    /// Refresh the installed tileset list
+    pub(crate) fn refresh_tileset_list(&mut self, game_dir: &std::path::Path) {
+        self.tileset.refresh_list(game_dir);
+    }
+
+    /// Install a tileset from the repository
+    pub(crate) fn install_tileset(
+        &mut self,
+        repo_tileset: crate::app_data::RepoTileset,
+        game_dir: &std::path::Path,
+    ) {
+        self.tileset.install(repo_tileset, game_dir);
+    }
+
+    /// Install a tileset from a local file
+    pub(crate) fn install_tileset_from_file(
+        &mut self,
+        archive_path: PathBuf,
+        game_dir: &std::path::Path,
+    ) {
+        self.tileset.install_from_file(archive_path, game_dir);
+    }
+
+    /// Install archives dropped onto the window.
+    ///
+    /// Each archive is routed by the metadata file it contains, which is
+    /// looked up in the background; on the Soundpacks tab every archive is
+    /// installed as a soundpack. Archives of the same kind are installed one
+    /// after another.
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped: Vec<PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+
+        let content = (self.ui.active_tab == Tab::Soundpacks).then_some(ArchiveContent::Soundpack);
+        self.pending_drops
+            .extend(dropped.into_iter().map(|path| (path, content)));
+
+        match poll_task(&mut self.drop_detect_task) {
+            PollResult::Complete(Ok(detected)) => {
+                for (path, content) in detected {
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    match content {
+                        Ok(Some(content)) => self.pending_drops.push((path, Some(content))),
+                        Ok(None) => {
+                            self.set_status(format!(
+                                "{} doesn't contain a soundpack, tileset or mod",
+                                name
+                            ));
+                        }
+                        Err(e) => {
+                            self.set_status(format!("Can't install {}: {}", name, e));
+                        }
+                    }
+                }
+            }
+            PollResult::Complete(Err(e)) => {
+                self.handle_event(StateEvent::LogError(format!(
+                    "Dropped file task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        // Start each archive whose kind has no install running; the rest
+        // wait for the previous install to finish
+        for (path, content) in std::mem::take(&mut self.pending_drops) {
+            match content {
+                Some(content) if !self.is_installing(content) => {
+                    self.install_dropped_file(path, content);
+                }
+                _ => self.pending_drops.push((path, content)),
+            }
+        }
+
+        if self.drop_detect_task.is_none() && self.pending_drops.iter().any(|(_, c)| c.is_none()) {
+            let paths: Vec<PathBuf> = self
+                .pending_drops
+                .extract_if(.., |(_, content)| content.is_none())
+                .map(|(path, _)| path)
+                .collect();
+            self.drop_detect_task = Some(tokio::task::spawn_blocking(move || {
+                paths
+                    .into_iter()
+                    .map(|path| {
+                        let content = soundpack::detect_archive_content(&path);
+                        (path, content)
+                    })
+                    .collect()
+            }));
+            ctx.request_repaint();
+        }
+    }
+
+    /// Switch to a tab, loading its list the first time it's shown
+    pub(crate) fn switch_tab(&mut self, tab: Tab) {
+        let previous_tab = self.ui.active_tab;
+        self.ui.active_tab = tab;
+        if tab == previous_tab {
+            return;
+        }
+
+        // Stop looking up repository sizes nobody is looking at, and any
+        // preview still playing
+        if previous_tab == Tab::Soundpacks {
+            self.soundpack.cancel_size_lookups();
+            self.soundpack.stop_preview();
+        }
+
+        let game_dir = self.config.game.directory.clone().map(PathBuf::from);
+        match tab {
+            Tab::Backups if self.backup.list.is_empty() && !self.backup.list_loading => {
+                self.refresh_backup_list();
+            }
+            Tab::Soundpacks if self.soundpack.list.is_empty() && !self.soundpack.list_loading => {
+                if let Some(dir) = game_dir {
+                    self.refresh_soundpack_list(&dir);
+                }
+            }
+            Tab::Tilesets if self.tileset.list.is_empty() && !self.tileset.list_loading => {
+                if let Some(dir) = game_dir {
+                    self.refresh_tileset_list(&dir);
+                }
+            }
+            Tab::Library if !self.library.loaded => {
+                if let Some(dir) = game_dir {
+                    self.library.rebuild(&dir);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Jump to a Library search result: select it in its tab, or open a
+    /// mod's folder (mods have no tab)
+    pub(crate) fn open_library_item(&mut self, item: &LibraryItem) {
+        let tab = match item.kind {
+            LibraryKind::Mod => {
+                if let Err(e) = open::that(&item.path) {
+                    self.set_status(format!("Failed to open {}: {}", item.name, e));
+                }
+                return;
+            }
+            LibraryKind::Soundpack => Tab::Soundpacks,
+            LibraryKind::Tileset => Tab::Tilesets,
+            LibraryKind::Backup => Tab::Backups,
+        };
+        self.library.pending_jump = Some(item.clone());
+        self.switch_tab(tab);
+        self.apply_library_jump();
+    }
+
+    /// Select the pending Library result once its tab's list has loaded
+    fn apply_library_jump(&mut self) {
+        let Some(item) = &self.library.pending_jump else {
+            return;
+        };
+
+        let (loading, found) = match item.kind {
+            LibraryKind::Mod => (false, None),
+            LibraryKind::Soundpack => (
+                self.soundpack.list_loading,
+                self.soundpack.list.iter().position(|s| s.path == item.path),
+            ),
+            LibraryKind::Tileset => (
+                self.tileset.list_loading,
+                self.tileset.list.iter().position(|t| t.path == item.path),
+            ),
+            LibraryKind::Backup => (
+                self.backup.list_loading,
+                self.backup.list.iter().position(|b| b.name == item.name),
+            ),
+        };
+        if loading {
+            return;
+        }
+
+        match (item.kind, found) {
+            (LibraryKind::Soundpack, Some(idx)) => self.soundpack.installed_idx = Some(idx),
+            (LibraryKind::Tileset, Some(idx)) => self.tileset.installed_idx = Some(idx),
+            (LibraryKind::Backup, Some(idx)) => self.backup.select_only(idx),
+            (_, None) => {
+                self.set_status(format!("{} is no longer installed", item.name));
+            }
+            _ => {}
+        }
+        self.library.pending_jump = None;
+    }
+
+    /// Whether an install of `content`'s kind is still running
+    fn is_installing(&self, content: ArchiveContent) -> bool {
+        match content {
+            ArchiveContent::Soundpack => self.soundpack.is_busy(),
+            ArchiveContent::Tileset => self.tileset.is_busy(),
+            ArchiveContent::Mod => self.mod_install_task.is_some(),
+        }
+    }
+
+    /// Install one dropped archive holding `content`
+    fn install_dropped_file(&mut self, path: PathBuf, content: ArchiveContent) {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Some(game_dir) = self.config.game.directory.clone().map(PathBuf::from) else {
+            self.set_status("Set a game directory before installing content");
+            return;
+        };
+
+        tracing::info!("Installing dropped archive {:?} as {:?}", path, content);
+        match content {
+            ArchiveContent::Soundpack => {
+                self.soundpack.install_from_file(path, &game_dir);
+                self.switch_tab(Tab::Soundpacks);
+            }
+            ArchiveContent::Tileset => {
+                self.tileset.install_from_file(path, &game_dir);
+                self.switch_tab(Tab::Tilesets);
+            }
+            ArchiveContent::Mod => {
+                self.mod_install_task = Some(tokio::spawn(mods::install_from_file(path, game_dir)));
+            }
+        }
+        self.set_status(format!("Installing {}...", name));
+    }
 }
 
 impl eframe::App for PhoenixApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Follow OS light/dark switches while the theme is Auto
+        if self.config.launcher.theme == ThemePreset::Auto {
+            let system = ctx.system_theme();
+            if system != self.ui.system_theme {
+                self.ui.system_theme = system;
+                self.ui.current_theme = ThemePreset::Auto.resolve(system).theme();
+                self.ui.theme_dirty = true;
+            }
+        }
+
         // Apply theme if needed
         if self.ui.theme_dirty {
             self.ui.current_theme.apply(ctx);
@@ -505,6 +1337,16 @@ impl eframe::App for PhoenixApp {
             PollResult::NoTask => {}
         }
 
+        // Poll game re-detection task
+        match poll_task(&mut self.redetect_task) {
+            PollResult::Complete(Ok(detected)) => self.apply_redetected_game(detected),
+            PollResult::Complete(Err(e)) => {
+                tracing::warn!("Game re-detection task panicked: {}", e);
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
         let release_events = self.releases.poll(ctx, &self.config.game.branch);
         self.handle_events(release_events);
 
@@ -516,10 +1358,54 @@ impl eframe::App for PhoenixApp {
 
         let backup_events = self.backup.poll(ctx);
         self.handle_events(backup_events);
+        self.backup
+            .verify_if_due(ctx, self.config.backups.verify_interval_hours);
+        self.backup_if_scheduled(ctx);
 
         let soundpack_events = self.soundpack.poll(ctx, game_dir_ref);
         self.handle_events(soundpack_events);
 
+        let tileset_events = self.tileset.poll(ctx, game_dir_ref);
+        self.handle_events(tileset_events);
+
+        let library_events = self.library.poll(ctx);
+        self.handle_events(library_events);
+        self.apply_library_jump();
+
+        let launch_events = self.launch.poll(ctx);
+        self.handle_events(launch_events);
+
+        let launcher_update_events = self.launcher_update.poll(ctx);
+        self.handle_events(launcher_update_events);
+
+        self.watch.watch(ctx, game_dir_ref);
+        let watch_events = self.watch.poll(ctx, self.update.is_updating());
+        self.handle_events(watch_events);
+
+        match poll_task(&mut self.mod_install_task) {
+            PollResult::Complete(Ok(Ok(info))) => {
+                self.handle_event(StateEvent::StatusMessage(format!(
+                    "Installed mod {}",
+                    info.id
+                )));
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                let msg = format!("Mod install failed: {}", e);
+                self.handle_event(StateEvent::LogError(msg.clone()));
+                self.handle_event(StateEvent::StatusMessage(msg));
+            }
+            PollResult::Complete(Err(e)) => {
+                self.handle_event(StateEvent::LogError(format!(
+                    "Mod install task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        self.handle_dropped_files(ctx);
+
         let theme = &self.ui.current_theme;
 
         // Top menu bar
@@ -541,15 +1427,48 @@ impl eframe::App for PhoenixApp {
                 });
             });
 
-        // Status bar at bottom
+        // Status bar at bottom; clicking it toggles the recent status history
+        let mut toggle_history = false;
         egui::TopBottomPanel::bottom("status_bar")
             .frame(egui::Frame::new().fill(theme.bg_darkest).inner_margin(8.0))
             .show(ctx, |ui| {
+                if self.ui.show_status_history {
+                    egui::ScrollArea::vertical()
+                        .max_height(160.0)
+                        .stick_to_bottom(true)
+                        .show(ui, |ui| {
+                            for entry in &self.ui.status_history {
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        RichText::new(entry.time.format("%H:%M:%S").to_string())
+                                            .monospace()
+                                            .color(theme.text_muted),
+                                    );
+                                    ui.label(RichText::new(&entry.message).color(theme.text_muted));
+                                });
+                            }
+                        });
+                    ui.separator();
+                }
                 ui.horizontal(|ui| {
-                    ui.label(RichText::new(&self.status_message).color(theme.text_muted));
+                    let text = match self.launch.session_elapsed() {
+                        Some(elapsed) => {
+                            RichText::new(format!("Game running… ({})", format_clock(elapsed)))
+                                .color(theme.success)
+                        }
+                        None => RichText::new(&self.status_message).color(theme.text_muted),
+                    };
+                    let response = ui
+                        .add(egui::Label::new(text).sense(egui::Sense::click()))
+                        .on_hover_text("Show recent status messages");
+                    toggle_history = response.clicked();
                 });
             });
 
+        if toggle_history {
+            self.ui.show_status_history = !self.ui.show_status_history;
+        }
+
         // Main content area
         egui::CentralPanel::default()
             .frame(egui::Frame::new().fill(theme.bg_dark).inner_margin(16.0))
@@ -560,6 +1479,8 @@ impl eframe::App for PhoenixApp {
                     crate::ui::render_tab(self, ui, Tab::Main, "Main");
                     crate::ui::render_tab(self, ui, Tab::Backups, "Backups");
                     crate::ui::render_tab(self, ui, Tab::Soundpacks, "Soundpacks");
+                    crate::ui::render_tab(self, ui, Tab::Tilesets, "Tilesets");
+                    crate::ui::render_tab(self, ui, Tab::Library, "Library");
                     crate::ui::render_tab(self, ui, Tab::Settings, "Settings");
                 });
 
@@ -570,11 +1491,22 @@ impl eframe::App for PhoenixApp {
                     Tab::Main => crate::ui::render_main_tab(self, ui),
                     Tab::Backups => crate::ui::render_backups_tab(self, ui),
                     Tab::Soundpacks => crate::ui::render_soundpacks_tab(self, ui),
+                    Tab::Tilesets => crate::ui::render_tilesets_tab(self, ui),
+                    Tab::Library => crate::ui::render_library_tab(self, ui),
                     Tab::Settings => crate::ui::render_settings_tab(self, ui),
                 }
             });
 
         // About dialog
         crate::ui::render_about_dialog(self, ctx);
+
+        // Corrupt config notice
+        crate::ui::render_config_recovery_dialog(self, ctx);
+
+        // Offer to delete the safety backup once a restore has been played
+        crate::ui::render_safety_cleanup_dialog(self, ctx);
+
+        // Hint shown while files are dragged over the window
+        crate::ui::render_drop_overlay(self, ctx);
     }
 }
@@ -16,6 +16,7 @@
 //! - `release_config.toml` - GitHub release version patterns
 //! - `stable_hashes.toml` - SHA256 hashes for stable version identification
 //! - `soundpacks.json` - Soundpack repository
+//! - `tilesets.json` - Tileset repository
 
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -27,6 +28,7 @@ const MIGRATION_CONFIG_TOML: &str = include_str!("../embedded/migration_config.t
 const LAUNCHER_CONFIG_TOML: &str = include_str!("../embedded/launcher_config.toml");
 const STABLE_RELEASES_TOML: &str = include_str!("../embedded/stable_releases.toml");
 const SOUNDPACKS_JSON: &str = include_str!("../embedded/soundpacks.json");
+const TILESETS_JSON: &str = include_str!("../embedded/tilesets.json");
 
 // ============================================================================
 // Game Configuration
@@ -80,11 +82,13 @@ pub struct DirectoriesConfig {
     pub save: String,
     pub data: String,
     pub sound: String,
+    pub gfx: String,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct VersionConfig {
     pub filename: String,
+    pub json_filename: String,
     pub commit_sha_prefix: String,
     pub commit_date_prefix: String,
     pub build_number_prefix: String,
@@ -131,6 +135,7 @@ pub struct MigrationConfig {
     pub soundpack: SoundpackMigrationConfig,
     pub download: DownloadConfig,
     pub export: ExportConfig,
+    pub verify: VerifyConfig,
 }
 
 #[derive(Debug, Deserialize)]
@@ -143,6 +148,7 @@ pub struct RestoreConfig {
 pub struct ArchiveConfig {
     pub directory: String,
     pub directory_old: String,
+    pub history: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -156,8 +162,8 @@ pub struct SoundpackMigrationConfig {
 pub struct DownloadConfig {
     pub temp_extension: String,
     pub progress_interval_ms: u64,
-    pub extraction_batch_size: usize,
     pub soundpack_extraction_batch: usize,
+    pub large_download_confirm_mb: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -165,6 +171,12 @@ pub struct ExportConfig {
     pub directories: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct VerifyConfig {
+    pub required_dirs: Vec<String>,
+    pub ignore_dirs: Vec<String>,
+}
+
 /// Get migration configuration (lazy-loaded)
 pub fn migration_config() -> &'static MigrationConfig {
     static CONFIG: OnceLock<MigrationConfig> = OnceLock::new();
@@ -199,8 +211,9 @@ pub struct WindowConfig {
 #[derive(Debug, Deserialize)]
 pub struct GithubConfig {
     pub api_base: String,
+    pub api_version: String,
     pub repository: String,
-    pub releases_per_page: u32,
+    pub launcher_repository: String,
     pub rate_limit_warning_threshold: u32,
 }
 
@@ -327,3 +340,34 @@ pub fn soundpacks_repository() -> &'static Vec<RepoSoundpack> {
         })
     })
 }
+
+// ============================================================================
+// Tileset Repository
+// ============================================================================
+
+/// Repository tileset entry (from embedded JSON)
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepoTileset {
+    /// Display name (shown in UI)
+    pub viewname: String,
+    /// Internal name (matches tileset.txt NAME)
+    pub name: String,
+    /// Download URL
+    pub url: String,
+    /// Homepage URL
+    pub homepage: String,
+    /// Optional pre-known size in bytes
+    pub size: Option<u64>,
+}
+
+/// Get the tilesets repository (lazy-loaded)
+///
+/// Returns a list of available tilesets from the embedded repository.
+pub fn tilesets_repository() -> &'static Vec<RepoTileset> {
+    static REPO: OnceLock<Vec<RepoTileset>> = OnceLock::new();
+    REPO.get_or_init(|| {
+        serde_json::from_str(TILESETS_JSON).unwrap_or_else(|e| {
+            panic!("Failed to parse tilesets.json: {}", e);
+        })
+    })
+}
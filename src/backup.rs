@@ -6,13 +6,18 @@
 //! - Restoring backups with optional pre-restore backup
 //! - Automatic backups before launch, after end, and before updates
 //! - Backup retention enforcement
+//! - Integrity verification of backup archives
+//! - Per-backup notes and archive checksums, kept in a JSON sidecar file
 //!
 //! Configuration loaded via `app_data::game_config()` and `app_data::launcher_config()`.
 
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{self, File};
-use std::io::{Read, Write};
-use std::path::{Path, PathBuf};
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
 use thiserror::Error;
 use tokio::sync::watch;
 use walkdir::WalkDir;
@@ -20,8 +25,8 @@ use zip::write::SimpleFileOptions;
 use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
 use crate::app_data::{game_config, launcher_config};
-use crate::config::Config;
-use crate::util::format_size;
+use crate::config::{BackupConfig, Config};
+use crate::util::{format_size, remove_all_with_progress};
 
 /// Errors that can occur during backup operations
 #[derive(Error, Debug)]
@@ -41,6 +46,18 @@ pub enum BackupError {
     #[error("No saves to backup")]
     NoSaves,
 
+    #[error("Not a Phoenix backup: {0}")]
+    InvalidArchive(String),
+
+    #[error("Backup is corrupt: {0}")]
+    Corrupt(String),
+
+    #[error("Not found in backup: {0}")]
+    EntryNotFound(String),
+
+    #[error("Failed to delete {0}")]
+    DeleteFailed(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -51,6 +68,9 @@ pub enum BackupError {
     Cancelled,
 }
 
+/// Name prefix of the safety backups taken before a restore
+pub const SAFETY_BACKUP_PREFIX: &str = "before_last_restore";
+
 /// Metadata about a backup file
 #[derive(Debug, Clone)]
 pub struct BackupInfo {
@@ -70,6 +90,10 @@ pub struct BackupInfo {
     pub modified: DateTime<Local>,
     /// Whether this is an automatic backup
     pub is_auto: bool,
+    /// User note describing the backup
+    pub note: Option<String>,
+    /// SHA-256 of the archive, recorded when the backup was created
+    pub checksum: Option<String>,
 }
 
 impl BackupInfo {
@@ -82,6 +106,11 @@ impl BackupInfo {
         }
     }
 
+    /// Whether this is a safety backup taken before a restore
+    pub fn is_safety_backup(&self) -> bool {
+        self.name.starts_with(SAFETY_BACKUP_PREFIX)
+    }
+
     /// Format compressed size for display
     pub fn compressed_size_display(&self) -> String {
         format_size(self.compressed_size)
@@ -93,10 +122,74 @@ impl BackupInfo {
     }
 }
 
+/// Sidecar file stored next to a backup archive as `<name>.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BackupMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+}
+
+/// Path of the sidecar file for a backup archive
+fn meta_path(archive: &Path) -> PathBuf {
+    archive.with_extension("json")
+}
+
+/// Read a backup's sidecar file (empty if missing or unreadable)
+fn read_meta(archive: &Path) -> BackupMeta {
+    fs::read_to_string(meta_path(archive))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Write a backup's sidecar file, removing it once there's nothing to store
+fn write_meta(archive: &Path, meta: &BackupMeta) -> Result<(), BackupError> {
+    let path = meta_path(archive);
+    if meta.note.is_none() && meta.checksum.is_none() {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        };
+    }
+    let json =
+        serde_json::to_string_pretty(meta).map_err(|e| BackupError::CreateFailed(e.to_string()))?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Remove a deleted backup's sidecar file, if it has one
+fn remove_meta(archive: &Path, backup_name: &str) {
+    if let Err(e) = fs::remove_file(meta_path(archive))
+        && e.kind() != ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove metadata of backup {}: {}", backup_name, e);
+    }
+}
+
+/// SHA-256 of a whole archive file, read in chunks
+fn archive_checksum(path: &Path) -> Result<String, BackupError> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Set or clear (with an empty `note`) the note of a backup
+pub fn set_backup_note(backup_name: &str, note: &str) -> Result<(), BackupError> {
+    let backup_file = existing_backup_file(backup_name)?;
+
+    let mut meta = read_meta(&backup_file);
+    let note = note.trim();
+    meta.note = (!note.is_empty()).then(|| note.to_string());
+    write_meta(&backup_file, &meta)
+}
+
 /// Type of automatic backup
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AutoBackupType {
     BeforeUpdate,
+    Scheduled,
 }
 
 impl AutoBackupType {
@@ -104,10 +197,138 @@ impl AutoBackupType {
     pub fn prefix(&self) -> &'static str {
         match self {
             Self::BeforeUpdate => "auto_before_update",
+            Self::Scheduled => "auto_scheduled",
+        }
+    }
+}
+
+/// Values substituted into a backup name template
+#[derive(Debug, Clone, Default)]
+pub struct BackupNameVars {
+    /// Game version (e.g. "0.H" or a release tag)
+    pub version: Option<String>,
+    /// Game branch (stable or experimental)
+    pub branch: Option<String>,
+}
+
+/// Retention rules for automatic backups.
+///
+/// The newest `max_count` auto-backups are always kept. If `max_age_days` is
+/// set, older ones are also kept while they're within that many days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Number of newest auto-backups always kept (0 = keep all)
+    pub max_count: u32,
+    /// Also keep auto-backups newer than this many days (0 = disabled)
+    pub max_age_days: u32,
+}
+
+impl RetentionPolicy {
+    /// Build the policy from the user's backup settings
+    pub fn from_config(config: &BackupConfig) -> Self {
+        Self {
+            max_count: config.max_count,
+            max_age_days: config.max_age_days,
+        }
+    }
+
+    /// Names of auto-backups the policy would delete, given the full list
+    pub fn expired(&self, backups: &[BackupInfo], now: DateTime<Local>) -> Vec<String> {
+        self.expired_with_newer(backups, now, 0)
+    }
+
+    /// Names of auto-backups that the next auto-backup's rotation will delete
+    pub fn expired_after_next(&self, backups: &[BackupInfo], now: DateTime<Local>) -> Vec<String> {
+        self.expired_with_newer(backups, now, 1)
+    }
+
+    /// Expired auto-backups, assuming `newer` more auto-backups are added first
+    fn expired_with_newer(
+        &self,
+        backups: &[BackupInfo],
+        now: DateTime<Local>,
+        newer: usize,
+    ) -> Vec<String> {
+        if self.max_count == 0 {
+            return Vec::new();
+        }
+
+        let mut auto: Vec<&BackupInfo> = backups.iter().filter(|b| b.is_auto).collect();
+        // Newest first, so the first max_count entries are always kept
+        auto.sort_by_key(|b| std::cmp::Reverse(b.modified));
+
+        let keep = (self.max_count as usize).saturating_sub(newer);
+        let max_age = chrono::Duration::days(self.max_age_days as i64);
+        auto.into_iter()
+            .skip(keep)
+            .filter(|b| self.max_age_days == 0 || now - b.modified > max_age)
+            .map(|b| b.name.clone())
+            .collect()
+    }
+}
+
+/// A file or folder inside a backup archive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackupEntry {
+    /// Last component of the path
+    pub name: String,
+    /// Path inside the archive, e.g. "save/World/master.gsav"
+    pub path: String,
+    /// Size in bytes (the total of everything inside, for folders)
+    pub size: u64,
+    /// Whether this entry is a folder (which may be empty)
+    pub is_dir: bool,
+    /// Contents of a folder, folders first then by name (empty for files)
+    pub children: Vec<BackupEntry>,
+}
+
+impl BackupEntry {
+    /// Number of files in this entry (1 for a file)
+    pub fn file_count(&self) -> usize {
+        if self.is_dir {
+            self.children.iter().map(BackupEntry::file_count).sum()
+        } else {
+            1
         }
     }
 }
 
+/// A file present in both backups with a different size
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SizeChange {
+    pub path: String,
+    pub size_a: u64,
+    pub size_b: u64,
+}
+
+/// Differences between two backups, read from their archive directories
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BackupDiff {
+    pub backup_a: String,
+    pub backup_b: String,
+    /// Worlds only in backup A
+    pub worlds_only_in_a: Vec<String>,
+    /// Worlds only in backup B
+    pub worlds_only_in_b: Vec<String>,
+    /// Character saves ("World/character") only in backup A
+    pub characters_only_in_a: Vec<String>,
+    /// Character saves ("World/character") only in backup B
+    pub characters_only_in_b: Vec<String>,
+    /// Files in both backups whose sizes differ
+    pub size_changes: Vec<SizeChange>,
+}
+
+impl BackupDiff {
+    /// True if the backups have the same worlds, characters, and file sizes
+    pub fn is_empty(&self) -> bool {
+        self.worlds_only_in_a.is_empty()
+            && self.worlds_only_in_b.is_empty()
+            && self.characters_only_in_a.is_empty()
+            && self.characters_only_in_b.is_empty()
+            && self.size_changes.is_empty()
+    }
+}
+
 /// Current phase of backup/restore operation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BackupPhase {
@@ -117,6 +338,7 @@ pub enum BackupPhase {
     Compressing,
     Extracting,
     Cleaning,
+    Deleting,
     Complete,
     Failed,
 }
@@ -130,6 +352,7 @@ impl BackupPhase {
             BackupPhase::Compressing => "Compressing saves...",
             BackupPhase::Extracting => "Extracting backup...",
             BackupPhase::Cleaning => "Cleaning up...",
+            BackupPhase::Deleting => "Deleting backup...",
             BackupPhase::Complete => "Complete!",
             BackupPhase::Failed => "Failed",
         }
@@ -149,7 +372,10 @@ impl BackupProgress {
     /// Calculate progress as a fraction (0.0 - 1.0)
     pub fn fraction(&self) -> f32 {
         match self.phase {
-            BackupPhase::Compressing | BackupPhase::Extracting => {
+            BackupPhase::Scanning
+            | BackupPhase::Compressing
+            | BackupPhase::Extracting
+            | BackupPhase::Deleting => {
                 if self.total_files == 0 {
                     0.0
                 } else {
@@ -167,6 +393,30 @@ pub fn backup_dir() -> PathBuf {
     Config::backups_dir().expect("Failed to get backups directory")
 }
 
+/// Archive of an existing backup in the backup directory.
+///
+/// Existing backups may predate the current naming rules, so rather than
+/// [`validate_backup_name`] this only rejects names that would point outside
+/// the backup directory.
+fn existing_backup_file(backup_name: &str) -> Result<PathBuf, BackupError> {
+    let mut components = Path::new(backup_name).components();
+    if !matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) {
+        return Err(BackupError::InvalidName(format!(
+            "'{}' is not a backup name",
+            backup_name
+        )));
+    }
+
+    let backup_file = backup_dir().join(format!("{}.zip", backup_name));
+    if !backup_file.exists() {
+        return Err(BackupError::BackupNotFound(backup_name.to_string()));
+    }
+    Ok(backup_file)
+}
+
 /// Get the old backup directory path (for migration)
 pub fn legacy_backup_dir(game_dir: &Path) -> PathBuf {
     game_dir.join(&launcher_config().legacy.old_backup_dir)
@@ -265,6 +515,7 @@ fn read_backup_info(path: &Path) -> Option<BackupInfo> {
         .to_string();
 
     let is_auto = name.starts_with(&launcher_cfg.backup.auto_backup_prefix);
+    let meta = read_meta(path);
 
     Some(BackupInfo {
         name,
@@ -275,6 +526,8 @@ fn read_backup_info(path: &Path) -> Option<BackupInfo> {
         characters_count,
         modified,
         is_auto,
+        note: meta.note,
+        checksum: meta.checksum,
     })
 }
 
@@ -307,6 +560,66 @@ fn validate_backup_name(name: &str) -> Result<(), BackupError> {
     Ok(())
 }
 
+/// Validate the name for a new backup.
+///
+/// Applies the same length and character rules as [`create_backup`] and also
+/// rejects names already used by one of `existing`. Shared by the Backups tab
+/// and `phoenix backup create`.
+pub fn validate_new_backup_name(name: &str, existing: &[BackupInfo]) -> Result<(), BackupError> {
+    validate_backup_name(name)?;
+
+    if existing.iter().any(|b| b.name == name) {
+        return Err(BackupError::InvalidName(format!(
+            "Backup '{}' already exists",
+            name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Make an arbitrary string a valid backup name.
+///
+/// Disallowed characters become '_', the result is trimmed and truncated to
+/// the maximum name length, and an empty result falls back to "backup".
+fn sanitize_backup_name(name: &str) -> String {
+    let config = launcher_config();
+    let allowed_chars = &config.backup.allowed_name_chars;
+
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || allowed_chars.contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(config.backup.max_name_length)
+        .collect();
+
+    let trimmed = sanitized.trim_matches(|c: char| !c.is_alphanumeric());
+    if trimmed.is_empty() {
+        "backup".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Expand a backup name template into a valid backup name.
+///
+/// Supported placeholders: `{date}` (YYYY-MM-DD), `{time}` (HHMMSS),
+/// `{version}` and `{branch}`. Unknown values expand to "unknown".
+pub fn expand_name_template(template: &str, vars: &BackupNameVars, now: DateTime<Local>) -> String {
+    let expanded = template
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H%M%S").to_string())
+        .replace("{version}", vars.version.as_deref().unwrap_or("unknown"))
+        .replace("{branch}", vars.branch.as_deref().unwrap_or("unknown"));
+
+    sanitize_backup_name(&expanded)
+}
+
 /// Create a backup of the save directory
 pub async fn create_backup(
     game_dir: &Path,
@@ -363,12 +676,19 @@ fn create_backup_sync(
     let backup_file = backup_path.join(format!("{}.zip", name));
 
     // Phase 1: Scan files
+    // Count first (directory entries only) so the scan can report a total
     let _ = progress_tx.send(BackupProgress {
         phase: BackupPhase::Scanning,
         ..Default::default()
     });
 
-    let mut files_to_backup: Vec<(PathBuf, String)> = Vec::new();
+    let expected_files = WalkDir::new(&save_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .count();
+
+    let mut files_to_backup: Vec<(PathBuf, String)> = Vec::with_capacity(expected_files);
 
     for entry in WalkDir::new(&save_dir).into_iter().filter_map(|e| e.ok()) {
         if entry.file_type().is_file() {
@@ -378,6 +698,14 @@ fn create_backup_sync(
                 .map_err(|e| BackupError::CreateFailed(e.to_string()))?;
             let relative_str = relative.to_string_lossy().replace('\\', "/");
 
+            // Files can appear between the count and the scan
+            let _ = progress_tx.send(BackupProgress {
+                phase: BackupPhase::Scanning,
+                files_processed: files_to_backup.len() + 1,
+                total_files: expected_files.max(files_to_backup.len() + 1),
+                current_file: relative_str.clone(),
+            });
+
             files_to_backup.push((path, relative_str));
         }
     }
@@ -395,8 +723,62 @@ fn create_backup_sync(
         ..Default::default()
     });
 
-    let file = File::create(&backup_file)?;
-    let mut zip = ZipWriter::new(file);
+    // Write to a temporary file so a failed backup never looks complete
+    let partial_file = backup_file.with_extension("zip.part");
+    let result = write_backup_zip(
+        &partial_file,
+        &files_to_backup,
+        compression_level,
+        &progress_tx,
+    )
+    .and_then(|()| fs::rename(&partial_file, &backup_file).map_err(BackupError::from));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&partial_file);
+        return Err(e);
+    }
+
+    // Record the checksum for later integrity checks; the backup is usable without it
+    let checksum = match archive_checksum(&backup_file) {
+        Ok(checksum) => Some(checksum),
+        Err(e) => {
+            tracing::warn!("Failed to compute checksum for {}: {}", name, e);
+            None
+        }
+    };
+    let meta = BackupMeta {
+        note: None,
+        checksum,
+    };
+    if let Err(e) = write_meta(&backup_file, &meta) {
+        tracing::warn!("Failed to record checksum for {}: {}", name, e);
+    }
+
+    // Complete
+    let _ = progress_tx.send(BackupProgress {
+        phase: BackupPhase::Complete,
+        files_processed: total_files,
+        total_files,
+        current_file: String::new(),
+    });
+
+    // Read back the info
+    read_backup_info(&backup_file)
+        .ok_or_else(|| BackupError::CreateFailed("Failed to read created backup info".to_string()))
+}
+
+/// Stream save files straight into a new ZIP archive.
+///
+/// Each file is copied through the compressor in chunks, so neither an
+/// uncompressed staging copy nor a whole file in memory is ever needed.
+fn write_backup_zip(
+    zip_path: &Path,
+    files: &[(PathBuf, String)],
+    compression_level: u8,
+    progress_tx: &watch::Sender<BackupProgress>,
+) -> Result<(), BackupError> {
+    let total_files = files.len();
+    let file = File::create(zip_path)?;
+    let mut zip = ZipWriter::new(std::io::BufWriter::new(file));
 
     // Configure compression
     let compression = if compression_level == 0 {
@@ -409,7 +791,7 @@ fn create_backup_sync(
         .compression_method(compression)
         .compression_level(Some(compression_level.min(9) as i64));
 
-    for (i, (path, relative)) in files_to_backup.iter().enumerate() {
+    for (i, (path, relative)) in files.iter().enumerate() {
         // Update progress
         let _ = progress_tx.send(BackupProgress {
             phase: BackupPhase::Compressing,
@@ -418,68 +800,499 @@ fn create_backup_sync(
             current_file: relative.clone(),
         });
 
-        // Read file content
-        let mut file_content = Vec::new();
         let mut file = File::open(path)?;
-        file.read_to_end(&mut file_content)?;
-
-        // Add to ZIP
         zip.start_file(relative, options)?;
-        zip.write_all(&file_content)?;
+        std::io::copy(&mut file, &mut zip)?;
     }
 
-    zip.finish()?;
+    zip.finish()?
+        .into_inner()
+        .map_err(|e| e.into_error())?
+        .sync_all()?;
+    Ok(())
+}
 
-    // Complete
-    let _ = progress_tx.send(BackupProgress {
-        phase: BackupPhase::Complete,
-        files_processed: total_files,
-        total_files,
-        current_file: String::new(),
-    });
+/// Read the file sizes listed in a backup's archive directory
+fn read_entry_sizes(path: &Path) -> Result<BTreeMap<String, u64>, BackupError> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut sizes = BTreeMap::new();
 
-    // Read back the info
-    read_backup_info(&backup_file)
-        .ok_or_else(|| BackupError::CreateFailed("Failed to read created backup info".to_string()))
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i)?;
+        if file.is_file() {
+            sizes.insert(file.name().to_string(), file.size());
+        }
+    }
+
+    Ok(sizes)
 }
 
-/// Delete a backup
-pub async fn delete_backup(backup_name: &str) -> Result<(), BackupError> {
-    let backup_path = backup_dir();
-    let backup_file = backup_path.join(format!("{}.zip", backup_name));
+/// Read the path and size of every entry in a backup archive, folders
+/// included (their paths end in `/`)
+fn read_entry_listing(path: &Path) -> Result<BTreeMap<String, u64>, BackupError> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut entries = BTreeMap::new();
 
-    if !backup_file.exists() {
-        return Err(BackupError::BackupNotFound(backup_name.to_string()));
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i)?;
+        entries.insert(file.name().to_string(), file.size());
     }
 
-    tokio::fs::remove_file(&backup_file).await?;
+    Ok(entries)
+}
+
+/// Build a folder tree from a flat listing of archive paths.
+///
+/// Folders are the paths ending in `/` and every parent of another path.
+fn entry_tree(entries: &BTreeMap<String, u64>) -> Vec<BackupEntry> {
+    #[derive(Default)]
+    struct Node<'a> {
+        size: u64,
+        is_dir: bool,
+        children: BTreeMap<&'a str, Node<'a>>,
+    }
+
+    fn into_entries(parent: &str, nodes: BTreeMap<&str, Node>) -> Vec<BackupEntry> {
+        let mut entries: Vec<BackupEntry> = nodes
+            .into_iter()
+            .map(|(name, node)| {
+                let path = if parent.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{}/{}", parent, name)
+                };
+                BackupEntry {
+                    name: name.to_string(),
+                    children: into_entries(&path, node.children),
+                    path,
+                    size: node.size,
+                    is_dir: node.is_dir,
+                }
+            })
+            .collect();
+        // Folders first; the map already ordered each group by name
+        entries.sort_by_key(|entry| !entry.is_dir);
+        entries
+    }
+
+    let mut root = Node::default();
+    for (path, &size) in entries {
+        let is_dir = path.ends_with('/');
+        let mut parts = path.split('/').filter(|p| !p.is_empty()).peekable();
+        let mut node = &mut root;
+        while let Some(part) = parts.next() {
+            node = node.children.entry(part).or_default();
+            node.size += size;
+            node.is_dir |= is_dir || parts.peek().is_some();
+        }
+    }
+    into_entries("", root.children)
+}
+
+/// List the files and folders inside a backup without extracting it
+pub async fn list_backup_entries(backup_name: &str) -> Result<Vec<BackupEntry>, BackupError> {
+    let backup_file = existing_backup_file(backup_name)?;
+
+    tokio::task::spawn_blocking(move || Ok(entry_tree(&read_entry_listing(&backup_file)?)))
+        .await
+        .map_err(|_| BackupError::Cancelled)?
+}
+
+/// `name` inside `dir`, or the first of "name (2)", "name (3)", ... that
+/// doesn't exist yet. A file keeps its extension after the number.
+fn unused_path(dir: &Path, name: &str, is_file: bool) -> PathBuf {
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if is_file && !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (name, String::new()),
+    };
+    let mut path = dir.join(name);
+    let mut counter = 2;
+
+    while path.exists() {
+        path = dir.join(format!("{} ({}){}", stem, counter, ext));
+        counter += 1;
+    }
+
+    path
+}
+
+/// Extract one file or folder from a backup archive into `dest`.
+///
+/// The selected entry lands directly in `dest` (a folder keeps its name and
+/// contents), and the current saves are left alone. Nothing in `dest` is
+/// overwritten: if the name is taken, the entry is extracted under a
+/// numbered one instead. Returns the number of files extracted and where
+/// the entry was extracted to.
+fn extract_entry_sync(
+    archive_path: &Path,
+    entry_path: &str,
+    dest: &Path,
+) -> Result<(usize, PathBuf), BackupError> {
+    let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+    let entry_path = entry_path.trim_end_matches('/');
+    let entry_name = entry_path.rsplit('/').next().unwrap_or(entry_path);
+    let folder_prefix = format!("{}/", entry_path);
+    let is_file = archive.index_for_name(entry_path).is_some();
+    let target = unused_path(dest, entry_name, is_file);
+    let mut found = false;
+    let mut extracted = 0;
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let name = file.name().to_string();
+        if name != entry_path && !name.starts_with(&folder_prefix) {
+            continue;
+        }
+
+        // Never write outside the destination
+        let Some(enclosed) = file.enclosed_name() else {
+            tracing::warn!("Skipping unsafe backup entry: {}", name);
+            continue;
+        };
+        found = true;
+        let out_path = match enclosed.strip_prefix(entry_path) {
+            Ok(relative) if !relative.as_os_str().is_empty() => target.join(relative),
+            _ => target.clone(),
+        };
+
+        if file.is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+        if let Some(dir) = out_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut outfile = File::create_new(&out_path)?;
+        std::io::copy(&mut file, &mut outfile)?;
+        extracted += 1;
+    }
+
+    if !found {
+        return Err(BackupError::EntryNotFound(entry_path.to_string()));
+    }
+
+    tracing::info!(
+        "Extracted {} files of '{}' from {:?} to {:?}",
+        extracted,
+        entry_path,
+        archive_path,
+        target
+    );
+    Ok((extracted, target))
+}
+
+/// Extract one file or folder from a backup (see [`extract_entry_sync`])
+pub async fn extract_from_backup(
+    backup_name: &str,
+    entry_path: &str,
+    dest: &Path,
+) -> Result<(usize, PathBuf), BackupError> {
+    let backup_file = existing_backup_file(backup_name)?;
+
+    let entry_path = entry_path.to_string();
+    let dest = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || extract_entry_sync(&backup_file, &entry_path, &dest))
+        .await
+        .map_err(|_| BackupError::Cancelled)?
+}
+
+/// Worlds and characters ("World/character") found in an entry listing
+fn worlds_and_characters(entries: &BTreeMap<String, u64>) -> (BTreeSet<String>, BTreeSet<String>) {
+    let world_cfg = &game_config().world;
+    let mut worlds = BTreeSet::new();
+    let mut characters = BTreeSet::new();
+
+    // Same layout rules as read_backup_info: save/WorldName/file
+    for name in entries.keys() {
+        let parts: Vec<&str> = name.split('/').collect();
+        if parts.len() != 3 {
+            continue;
+        }
+        let filename = parts[2];
+        if world_cfg.marker_files.iter().any(|f| f == filename) {
+            worlds.insert(parts[1].to_string());
+        }
+        if let Some(ext) = world_cfg
+            .save_extensions
+            .iter()
+            .find(|ext| filename.ends_with(ext.as_str()))
+        {
+            let character = &filename[..filename.len() - ext.len()];
+            characters.insert(format!("{}/{}", parts[1], character));
+        }
+    }
+
+    (worlds, characters)
+}
+
+/// Compare two entry listings
+fn diff_entries(
+    backup_a: &str,
+    a: &BTreeMap<String, u64>,
+    backup_b: &str,
+    b: &BTreeMap<String, u64>,
+) -> BackupDiff {
+    let (worlds_a, characters_a) = worlds_and_characters(a);
+    let (worlds_b, characters_b) = worlds_and_characters(b);
+
+    let size_changes = a
+        .iter()
+        .filter_map(|(path, &size_a)| {
+            let &size_b = b.get(path)?;
+            (size_a != size_b).then(|| SizeChange {
+                path: path.clone(),
+                size_a,
+                size_b,
+            })
+        })
+        .collect();
+
+    BackupDiff {
+        backup_a: backup_a.to_string(),
+        backup_b: backup_b.to_string(),
+        worlds_only_in_a: worlds_a.difference(&worlds_b).cloned().collect(),
+        worlds_only_in_b: worlds_b.difference(&worlds_a).cloned().collect(),
+        characters_only_in_a: characters_a.difference(&characters_b).cloned().collect(),
+        characters_only_in_b: characters_b.difference(&characters_a).cloned().collect(),
+        size_changes,
+    }
+}
+
+/// Compare two backups without extracting them
+pub async fn diff_backups(backup_a: &str, backup_b: &str) -> Result<BackupDiff, BackupError> {
+    let file_a = existing_backup_file(backup_a)?;
+    let file_b = existing_backup_file(backup_b)?;
+    let backup_a = backup_a.to_string();
+    let backup_b = backup_b.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        let a = read_entry_sizes(&file_a)?;
+        let b = read_entry_sizes(&file_b)?;
+        Ok(diff_entries(&backup_a, &a, &backup_b, &b))
+    })
+    .await
+    .map_err(|_| BackupError::Cancelled)?
+}
+
+/// Delete a backup, reporting files removed through `progress_tx`
+pub async fn delete_backup(
+    backup_name: &str,
+    progress_tx: watch::Sender<BackupProgress>,
+) -> Result<(), BackupError> {
+    let backup_file = existing_backup_file(backup_name)?;
+
+    let _ = progress_tx.send(BackupProgress {
+        phase: BackupPhase::Deleting,
+        current_file: backup_name.to_string(),
+        ..Default::default()
+    });
+
+    let current_file = backup_name.to_string();
+    let archive = backup_file.clone();
+    tokio::task::spawn_blocking(move || {
+        remove_all_with_progress(&archive, |files_processed, total_files| {
+            let _ = progress_tx.send(BackupProgress {
+                phase: BackupPhase::Deleting,
+                files_processed,
+                total_files,
+                current_file: current_file.clone(),
+            });
+        })
+    })
+    .await
+    .map_err(|_| BackupError::Cancelled)??;
+
+    remove_meta(&backup_file, backup_name);
 
     tracing::info!("Deleted backup: {}", backup_name);
     Ok(())
 }
 
-/// Restore a backup
+/// Delete several backups, reporting progress across all of them.
+///
+/// A backup that fails to delete doesn't stop the rest; the failures are
+/// returned together afterwards.
+pub async fn delete_backups(
+    backup_names: &[String],
+    progress_tx: watch::Sender<BackupProgress>,
+) -> Result<(), BackupError> {
+    let total_files = backup_names.len();
+    let mut failed = Vec::new();
+
+    for (files_processed, backup_name) in backup_names.iter().enumerate() {
+        let _ = progress_tx.send(BackupProgress {
+            phase: BackupPhase::Deleting,
+            files_processed,
+            total_files,
+            current_file: backup_name.clone(),
+        });
+        // Per-backup progress would reset the combined count
+        let (backup_tx, _) = watch::channel(BackupProgress::default());
+        if let Err(e) = delete_backup(backup_name, backup_tx).await {
+            tracing::warn!("Failed to delete backup {}: {}", backup_name, e);
+            failed.push(format!("{} ({})", backup_name, e));
+        }
+    }
+
+    let _ = progress_tx.send(BackupProgress {
+        phase: BackupPhase::Deleting,
+        files_processed: total_files,
+        total_files,
+        current_file: String::new(),
+    });
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(BackupError::DeleteFailed(failed.join(", ")))
+    }
+}
+
+/// Restore a backup into `game_dir`.
+///
+/// `game_dir` doesn't have to be the configured installation, so a backup can
+/// copy saves between installs. The safety backup (when requested) is always
+/// taken from the target's current saves; its name is returned if one was made.
 pub async fn restore_backup(
     game_dir: &Path,
     backup_name: &str,
     backup_current_first: bool,
     compression_level: u8,
     progress_tx: watch::Sender<BackupProgress>,
-) -> Result<(), BackupError> {
-    let backup_path = backup_dir();
-    let backup_file = backup_path.join(format!("{}.zip", backup_name));
+) -> Result<Option<String>, BackupError> {
+    let backup_file = existing_backup_file(backup_name)?;
+
+    restore_archive(
+        game_dir,
+        backup_file,
+        backup_current_first,
+        compression_level,
+        progress_tx,
+    )
+    .await
+}
 
-    if !backup_file.exists() {
-        return Err(BackupError::BackupNotFound(backup_name.to_string()));
+/// Read every file in a backup archive to check it isn't corrupt.
+///
+/// Decompressing an entry checks it against its stored CRC, so this catches
+/// truncated archives and bit-rot without extracting anything. If a checksum
+/// was recorded when the backup was created, the whole archive must also
+/// still match it. Returns the number of files checked. Reads the whole
+/// archive, so call it from a blocking context.
+pub fn verify_archive(path: &Path) -> Result<usize, BackupError> {
+    let mut archive = ZipArchive::new(File::open(path)?)
+        .map_err(|e| BackupError::Corrupt(format!("unreadable archive ({})", e)))?;
+    let mut file_count = 0;
+
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| BackupError::Corrupt(format!("entry {} is unreadable ({})", i, e)))?;
+        if !entry.is_file() {
+            continue;
+        }
+        std::io::copy(&mut entry, &mut std::io::sink())
+            .map_err(|e| BackupError::Corrupt(format!("'{}' is damaged ({})", entry.name(), e)))?;
+        file_count += 1;
     }
 
+    if let Some(expected) = read_meta(path).checksum
+        && archive_checksum(path)? != expected
+    {
+        return Err(BackupError::Corrupt(
+            "archive doesn't match the checksum recorded at creation".to_string(),
+        ));
+    }
+
+    Ok(file_count)
+}
+
+/// Verify a backup archive in the background (see [`verify_archive`])
+pub async fn verify_backup(path: &Path) -> Result<usize, BackupError> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || verify_archive(&path))
+        .await
+        .map_err(|_| BackupError::Cancelled)?
+}
+
+/// Check that an archive looks like a Phoenix backup.
+///
+/// Every entry must be a safe relative path inside the save directory, and
+/// at least one file must be present. Returns the number of files.
+pub fn validate_backup_archive(path: &Path) -> Result<usize, BackupError> {
+    let save_dir_name = &game_config().directories.save;
+    let mut archive = ZipArchive::new(File::open(path)?)
+        .map_err(|e| BackupError::InvalidArchive(format!("not a ZIP archive ({})", e)))?;
+    let mut file_count = 0;
+
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        let in_save_dir = entry
+            .enclosed_name()
+            .and_then(|p| p.components().next().map(|c| c.as_os_str().to_owned()))
+            .is_some_and(|first| first == save_dir_name.as_str());
+        if !in_save_dir {
+            return Err(BackupError::InvalidArchive(format!(
+                "unexpected entry '{}' (expected everything under {}/)",
+                entry.name(),
+                save_dir_name
+            )));
+        }
+        if entry.is_file() {
+            file_count += 1;
+        }
+    }
+
+    if file_count == 0 {
+        return Err(BackupError::InvalidArchive("archive is empty".to_string()));
+    }
+
+    Ok(file_count)
+}
+
+/// Restore a backup archive from anywhere on disk (e.g. one shared by
+/// another user), after checking that it looks like a Phoenix backup
+pub async fn restore_external_backup(
+    game_dir: &Path,
+    archive_path: &Path,
+    backup_current_first: bool,
+    compression_level: u8,
+    progress_tx: watch::Sender<BackupProgress>,
+) -> Result<Option<String>, BackupError> {
+    let path = archive_path.to_path_buf();
+    tokio::task::spawn_blocking(move || validate_backup_archive(&path))
+        .await
+        .map_err(|_| BackupError::Cancelled)??;
+
+    restore_archive(
+        game_dir,
+        archive_path.to_path_buf(),
+        backup_current_first,
+        compression_level,
+        progress_tx,
+    )
+    .await
+}
+
+/// Shared restore path: optionally back up current saves, then extract.
+///
+/// Returns the name of the safety backup, if one was created.
+async fn restore_archive(
+    game_dir: &Path,
+    backup_file: PathBuf,
+    backup_current_first: bool,
+    compression_level: u8,
+    progress_tx: watch::Sender<BackupProgress>,
+) -> Result<Option<String>, BackupError> {
+    let backup_path = backup_dir();
     let save_dir = game_dir.join(&game_config().directories.save);
+    let mut safety_backup = None;
 
     // Optionally backup current saves first
     if backup_current_first && save_dir.exists() {
         // Check if there are saves to backup
         if fs::read_dir(&save_dir)?.next().is_some() {
-            let pre_restore_name = generate_unique_name(&backup_path, "before_last_restore");
+            let pre_restore_name = generate_unique_name(&backup_path, SAFETY_BACKUP_PREFIX);
             tracing::info!("Backing up current saves as: {}", pre_restore_name);
 
             create_backup(
@@ -489,6 +1302,7 @@ pub async fn restore_backup(
                 progress_tx.clone(),
             )
             .await?;
+            safety_backup = Some(pre_restore_name);
         }
     }
 
@@ -496,7 +1310,9 @@ pub async fn restore_backup(
 
     tokio::task::spawn_blocking(move || restore_backup_sync(&game_dir, &backup_file, progress_tx))
         .await
-        .map_err(|_| BackupError::Cancelled)?
+        .map_err(|_| BackupError::Cancelled)??;
+
+    Ok(safety_backup)
 }
 
 /// Synchronous backup restoration (runs in spawn_blocking)
@@ -541,7 +1357,12 @@ fn restore_backup_sync(
             current_file: name.clone(),
         });
 
-        let out_path = game_dir.join(&name);
+        // Never write outside the game directory
+        let Some(relative) = file.enclosed_name() else {
+            tracing::warn!("Skipping unsafe backup entry: {}", name);
+            continue;
+        };
+        let out_path = game_dir.join(relative);
 
         if file.is_dir() {
             fs::create_dir_all(&out_path)?;
@@ -593,13 +1414,16 @@ fn rand_u64() -> u64 {
     duration.as_nanos() as u64
 }
 
-/// Create an automatic backup with deduplication
+/// Create an automatic backup with deduplication.
+///
+/// The name is the backup type's prefix followed by the expanded template.
 pub async fn create_auto_backup(
     game_dir: &Path,
     backup_type: AutoBackupType,
-    version_tag: Option<&str>,
+    name_template: &str,
+    name_vars: &BackupNameVars,
     compression_level: u8,
-    max_count: u32,
+    retention: RetentionPolicy,
     progress_tx: watch::Sender<BackupProgress>,
 ) -> Result<Option<BackupInfo>, BackupError> {
     let save_dir = game_dir.join(&game_config().directories.save);
@@ -619,15 +1443,8 @@ pub async fn create_auto_backup(
     let backup_path = backup_dir();
     fs::create_dir_all(&backup_path)?;
 
-    let base_name = if let Some(tag) = version_tag {
-        format!(
-            "{}_{}",
-            backup_type.prefix(),
-            tag.replace(['/', '\\', ':'], "_")
-        )
-    } else {
-        backup_type.prefix().to_string()
-    };
+    let expanded = expand_name_template(name_template, name_vars, Local::now());
+    let base_name = sanitize_backup_name(&format!("{}_{}", backup_type.prefix(), expanded));
 
     let name = generate_unique_name(&backup_path, &base_name);
 
@@ -637,13 +1454,13 @@ pub async fn create_auto_backup(
     let info = create_backup(game_dir, &name, compression_level, progress_tx).await?;
 
     // Enforce retention
-    enforce_retention(max_count).await?;
+    enforce_retention(retention).await?;
 
     Ok(Some(info))
 }
 
 /// Generate a unique backup name by appending numbers if needed
-fn generate_unique_name(backup_path: &Path, base_name: &str) -> String {
+pub fn generate_unique_name(backup_path: &Path, base_name: &str) -> String {
     let mut name = base_name.to_string();
     let mut counter = 2;
 
@@ -655,42 +1472,70 @@ fn generate_unique_name(backup_path: &Path, base_name: &str) -> String {
     name
 }
 
-/// Enforce backup retention policy (delete oldest auto-backups)
-pub async fn enforce_retention(max_count: u32) -> Result<usize, BackupError> {
-    if max_count == 0 {
-        return Ok(0);
-    }
-
-    let mut backups = list_backups().await?;
-
-    // Only consider auto-backups for retention
-    backups.retain(|b| b.is_auto);
-
-    if backups.len() <= max_count as usize {
-        return Ok(0);
-    }
-
-    // Sort by date, oldest first (we'll delete from the front)
-    backups.sort_by_key(|a| a.modified);
+/// Enforce backup retention policy (delete expired auto-backups)
+pub async fn enforce_retention(retention: RetentionPolicy) -> Result<usize, BackupError> {
+    let backups = list_backups().await?;
+    let expired = retention.expired(&backups, Local::now());
+    Ok(delete_expired(&backups, &expired, "old auto-backup").await)
+}
 
-    let to_delete = backups.len() - max_count as usize;
+/// Delete the `backups` named in `expired` along with their sidecar files,
+/// returning how many were removed. `kind` describes them in the log.
+async fn delete_expired(backups: &[BackupInfo], expired: &[String], kind: &str) -> usize {
     let mut deleted = 0;
-
-    for backup in backups.into_iter().take(to_delete) {
+    for backup in backups.iter().filter(|b| expired.contains(&b.name)) {
         if let Err(e) = tokio::fs::remove_file(&backup.path).await {
-            tracing::warn!("Failed to delete old backup {}: {}", backup.name, e);
-        } else {
-            tracing::info!("Deleted old auto-backup: {}", backup.name);
-            deleted += 1;
+            tracing::warn!("Failed to delete {} {}: {}", kind, backup.name, e);
+            continue;
         }
+        remove_meta(&backup.path, &backup.name);
+        tracing::info!("Deleted {}: {}", kind, backup.name);
+        deleted += 1;
+    }
+    deleted
+}
+
+/// Names of safety backups older than `max_age_days` (0 = none expire)
+pub fn expired_safety_backups(
+    backups: &[BackupInfo],
+    max_age_days: u32,
+    now: DateTime<Local>,
+) -> Vec<String> {
+    if max_age_days == 0 {
+        return Vec::new();
     }
 
-    Ok(deleted)
+    let max_age = chrono::Duration::days(max_age_days as i64);
+    backups
+        .iter()
+        .filter(|b| b.is_safety_backup() && now - b.modified > max_age)
+        .map(|b| b.name.clone())
+        .collect()
+}
+
+/// Delete safety backups older than `max_age_days`, returning how many were removed
+pub async fn clean_safety_backups(max_age_days: u32) -> Result<usize, BackupError> {
+    let backups = list_backups().await?;
+    let expired = expired_safety_backups(&backups, max_age_days, Local::now());
+    Ok(delete_expired(&backups, &expired, "old safety backup").await)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+    use std::io::Write;
+
+    #[test]
+    fn test_existing_backup_file_rejects_paths() {
+        for name in ["../outside", "..", "a/b", "a/..", "/abs", ""] {
+            assert!(
+                matches!(existing_backup_file(name), Err(BackupError::InvalidName(_))),
+                "{:?}",
+                name
+            );
+        }
+    }
 
     #[test]
     fn test_validate_backup_name() {
@@ -704,9 +1549,118 @@ mod tests {
         assert!(validate_backup_name("a".repeat(101).as_str()).is_err());
     }
 
+    /// A listed backup with the given name (other fields don't matter here)
+    fn listed_backup(name: &str) -> BackupInfo {
+        BackupInfo {
+            name: name.to_string(),
+            path: PathBuf::new(),
+            compressed_size: 0,
+            uncompressed_size: 0,
+            worlds_count: 0,
+            characters_count: 0,
+            modified: Local::now(),
+            is_auto: false,
+            note: None,
+            checksum: None,
+        }
+    }
+
+    #[test]
+    fn test_new_backup_name_rejects_empty() {
+        let err = validate_new_backup_name("", &[]).unwrap_err();
+        assert!(err.to_string().contains("cannot be empty"));
+    }
+
+    #[test]
+    fn test_new_backup_name_rejects_too_long() {
+        let max = launcher_config().backup.max_name_length;
+        assert!(validate_new_backup_name(&"a".repeat(max), &[]).is_ok());
+
+        let err = validate_new_backup_name(&"a".repeat(max + 1), &[]).unwrap_err();
+        assert!(err.to_string().contains("too long"));
+    }
+
+    #[test]
+    fn test_new_backup_name_rejects_bad_characters() {
+        for name in ["bad/name", "bad\\name", "bad:name", "bad.zip"] {
+            let err = validate_new_backup_name(name, &[]).unwrap_err();
+            assert!(err.to_string().contains("Invalid character"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_new_backup_name_rejects_duplicates() {
+        let existing = vec![listed_backup("before_update")];
+
+        assert!(validate_new_backup_name("after_update", &existing).is_ok());
+        let err = validate_new_backup_name("before_update", &existing).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn test_expand_name_template() {
+        let now = Local.with_ymd_and_hms(2024, 3, 9, 14, 5, 7).unwrap();
+        let vars = BackupNameVars {
+            version: Some("0.H-RELEASE".to_string()),
+            branch: Some("stable".to_string()),
+        };
+
+        assert_eq!(
+            expand_name_template("{date}_{version}_{branch}", &vars, now),
+            "2024-03-09_0_H-RELEASE_stable"
+        );
+        assert_eq!(
+            expand_name_template("{date} {time}", &vars, now),
+            "2024-03-09 140507"
+        );
+        assert_eq!(
+            expand_name_template("{version}", &BackupNameVars::default(), now),
+            "unknown"
+        );
+
+        // Whatever the template, the result is a valid name
+        for template in ["", "{}", "a/b:c", "{version}", &"x".repeat(200)] {
+            let name = expand_name_template(template, &vars, now);
+            assert!(validate_backup_name(&name).is_ok(), "{:?}", name);
+        }
+    }
+
     #[test]
     fn test_auto_backup_type_prefix() {
         assert_eq!(AutoBackupType::BeforeUpdate.prefix(), "auto_before_update");
+        assert_eq!(AutoBackupType::Scheduled.prefix(), "auto_scheduled");
+    }
+
+    #[test]
+    fn test_progress_fraction_covers_scanning() {
+        let progress = BackupProgress {
+            phase: BackupPhase::Scanning,
+            files_processed: 400,
+            total_files: 1600,
+            current_file: String::new(),
+        };
+        assert_eq!(progress.fraction(), 0.25);
+
+        let cleaning = BackupProgress {
+            phase: BackupPhase::Cleaning,
+            ..progress
+        };
+        assert_eq!(cleaning.fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_write_backup_zip_streams_files() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let save_file = temp.path().join("world.sav");
+        fs::write(&save_file, vec![b'x'; 256 * 1024]).unwrap();
+
+        let zip_path = temp.path().join("backup.zip");
+        let files = vec![(save_file, "save/World/world.sav".to_string())];
+        let (progress_tx, _rx) = watch::channel(BackupProgress::default());
+        write_backup_zip(&zip_path, &files, 6, &progress_tx).unwrap();
+
+        let sizes = read_entry_sizes(&zip_path).unwrap();
+        assert_eq!(sizes.get("save/World/world.sav"), Some(&(256 * 1024)));
     }
 
     #[test]
@@ -720,7 +1674,333 @@ mod tests {
             characters_count: 0,
             modified: Local::now(),
             is_auto: false,
+            note: None,
+            checksum: None,
         };
         assert!((info.compression_ratio() - 60.0).abs() < 0.1);
     }
+
+    #[test]
+    fn test_retention_policy_expired() {
+        let now = Local::now();
+        let backup = |name: &str, days_old: i64, is_auto: bool| BackupInfo {
+            name: name.to_string(),
+            path: PathBuf::new(),
+            compressed_size: 0,
+            uncompressed_size: 0,
+            worlds_count: 0,
+            characters_count: 0,
+            modified: now - chrono::Duration::days(days_old),
+            is_auto,
+            note: None,
+            checksum: None,
+        };
+        let backups = vec![
+            backup("auto_a", 1, true),
+            backup("auto_b", 3, true),
+            backup("auto_c", 10, true),
+            backup("auto_d", 20, true),
+            backup("manual", 30, false),
+        ];
+
+        // Count only: keep the newest two
+        let policy = RetentionPolicy {
+            max_count: 2,
+            max_age_days: 0,
+        };
+        assert_eq!(policy.expired(&backups, now), vec!["auto_c", "auto_d"]);
+
+        // Count plus age: recent backups beyond the count are kept too
+        let policy = RetentionPolicy {
+            max_count: 1,
+            max_age_days: 7,
+        };
+        assert_eq!(policy.expired(&backups, now), vec!["auto_c", "auto_d"]);
+
+        // Old backups within the count are never dropped
+        let policy = RetentionPolicy {
+            max_count: 4,
+            max_age_days: 2,
+        };
+        assert!(policy.expired(&backups, now).is_empty());
+        assert_eq!(policy.expired_after_next(&backups, now), vec!["auto_d"]);
+
+        // Zero count keeps everything
+        let policy = RetentionPolicy {
+            max_count: 0,
+            max_age_days: 7,
+        };
+        assert!(policy.expired(&backups, now).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_expired_removes_sidecars() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let backup = |name: &str| {
+            let path = temp.path().join(format!("{}.zip", name));
+            fs::write(&path, b"zip").unwrap();
+            fs::write(meta_path(&path), r#"{"note": "kept"}"#).unwrap();
+            BackupInfo {
+                path,
+                ..listed_backup(name)
+            }
+        };
+        let backups = vec![backup("auto_old"), backup("auto_new")];
+
+        let deleted = delete_expired(&backups, &["auto_old".to_string()], "old auto-backup").await;
+        assert_eq!(deleted, 1);
+        assert!(!backups[0].path.exists());
+        assert!(!meta_path(&backups[0].path).exists());
+        assert!(backups[1].path.exists());
+        assert!(meta_path(&backups[1].path).exists());
+    }
+
+    #[test]
+    fn test_expired_safety_backups() {
+        let now = Local::now();
+        let backup = |name: &str, days_old: i64| BackupInfo {
+            modified: now - chrono::Duration::days(days_old),
+            ..listed_backup(name)
+        };
+        let backups = vec![
+            backup("before_last_restore", 2),
+            backup("before_last_restore2", 40),
+            backup("manual", 40),
+        ];
+
+        assert_eq!(
+            expired_safety_backups(&backups, 30, now),
+            vec!["before_last_restore2"]
+        );
+        assert_eq!(expired_safety_backups(&backups, 1, now).len(), 2);
+        assert!(expired_safety_backups(&backups, 0, now).is_empty());
+    }
+
+    #[test]
+    fn test_diff_entries() {
+        let world = &game_config().world;
+        let marker = &world.marker_files[0];
+        let ext = &world.save_extensions[0];
+
+        let entries =
+            |items: &[(String, u64)]| -> BTreeMap<String, u64> { items.iter().cloned().collect() };
+        let a = entries(&[
+            (format!("save/Alpha/{}", marker), 10),
+            (format!("save/Alpha/Bob{}", ext), 100),
+            (format!("save/Alpha/Sue{}", ext), 50),
+        ]);
+        let b = entries(&[
+            (format!("save/Alpha/{}", marker), 10),
+            (format!("save/Alpha/Bob{}", ext), 120),
+            (format!("save/Beta/{}", marker), 10),
+        ]);
+
+        let diff = diff_entries("a", &a, "b", &b);
+        assert!(diff.worlds_only_in_a.is_empty());
+        assert_eq!(diff.worlds_only_in_b, vec!["Beta"]);
+        assert_eq!(diff.characters_only_in_a, vec!["Alpha/Sue"]);
+        assert!(diff.characters_only_in_b.is_empty());
+        assert_eq!(
+            diff.size_changes,
+            vec![SizeChange {
+                path: format!("save/Alpha/Bob{}", ext),
+                size_a: 100,
+                size_b: 120,
+            }]
+        );
+        assert!(diff_entries("a", &a, "a", &a).is_empty());
+    }
+
+    #[test]
+    fn test_entry_tree() {
+        let entries: BTreeMap<String, u64> = [
+            ("save/Alpha/master.gsav", 10),
+            ("save/Alpha/Bob.sav", 100),
+            ("save/Beta/master.gsav", 5),
+            ("save/readme.txt", 1),
+        ]
+        .into_iter()
+        .map(|(path, size)| (path.to_string(), size))
+        .collect();
+
+        let tree = entry_tree(&entries);
+        assert_eq!(tree.len(), 1);
+        let save = &tree[0];
+        assert_eq!(save.path, "save");
+        assert_eq!(save.size, 116);
+        assert_eq!(save.file_count(), 4);
+
+        // Folders first, then files, each by name
+        let names: Vec<&str> = save.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Alpha", "Beta", "readme.txt"]);
+        assert_eq!(save.children[0].path, "save/Alpha");
+        assert_eq!(save.children[0].children[0].path, "save/Alpha/Bob.sav");
+        assert!(!save.children[2].is_dir);
+    }
+
+    #[test]
+    fn test_entry_tree_empty_folder() {
+        let entries: BTreeMap<String, u64> = [("save/", 0), ("save/Empty/", 0), ("save/a.txt", 1)]
+            .into_iter()
+            .map(|(path, size)| (path.to_string(), size))
+            .collect();
+
+        let tree = entry_tree(&entries);
+        let save = &tree[0];
+        assert_eq!(save.size, 1);
+        let empty = &save.children[0];
+        assert_eq!(empty.path, "save/Empty");
+        assert!(empty.is_dir);
+        assert_eq!(empty.file_count(), 0);
+        assert!(!save.children[1].is_dir);
+    }
+
+    #[test]
+    fn test_extract_entry() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("backup.zip");
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        for (name, data) in [
+            ("save/Alpha/master.gsav", "world"),
+            ("save/Alpha/Bob.sav", "bob"),
+            ("save/Alphabet/other.sav", "other"),
+        ] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        // A single file lands directly in the destination
+        let dest = temp.path().join("one");
+        assert_eq!(
+            extract_entry_sync(&path, "save/Alpha/Bob.sav", &dest).unwrap(),
+            (1, dest.join("Bob.sav"))
+        );
+        assert_eq!(fs::read_to_string(dest.join("Bob.sav")).unwrap(), "bob");
+
+        // Existing files are never overwritten
+        fs::write(dest.join("Bob.sav"), "mine").unwrap();
+        let (_, extracted) = extract_entry_sync(&path, "save/Alpha/Bob.sav", &dest).unwrap();
+        assert_eq!(extracted, dest.join("Bob (2).sav"));
+        assert_eq!(fs::read_to_string(dest.join("Bob.sav")).unwrap(), "mine");
+
+        // A folder keeps its name, and similarly named siblings are left out
+        let dest = temp.path().join("folder");
+        assert_eq!(
+            extract_entry_sync(&path, "save/Alpha", &dest).unwrap(),
+            (2, dest.join("Alpha"))
+        );
+        assert!(dest.join("Alpha/master.gsav").exists());
+        assert!(!dest.join("Alphabet").exists());
+        let (_, extracted) = extract_entry_sync(&path, "save/Alpha", &dest).unwrap();
+        assert!(extracted.join("master.gsav").exists());
+        assert_eq!(extracted, dest.join("Alpha (2)"));
+
+        assert!(matches!(
+            extract_entry_sync(&path, "save/Missing", &dest),
+            Err(BackupError::EntryNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_backup_archive() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let save = &game_config().directories.save;
+
+        let write_zip = |name: &str, entries: &[&str]| {
+            let path = temp.path().join(name);
+            let mut zip = ZipWriter::new(File::create(&path).unwrap());
+            for entry in entries {
+                zip.start_file(*entry, SimpleFileOptions::default())
+                    .unwrap();
+                zip.write_all(b"data").unwrap();
+            }
+            zip.finish().unwrap();
+            path
+        };
+
+        let valid = write_zip("valid.zip", &[&format!("{}/World/master.gsav", save)]);
+        assert_eq!(validate_backup_archive(&valid).unwrap(), 1);
+
+        let outside = write_zip("outside.zip", &["mods/evil.json"]);
+        assert!(matches!(
+            validate_backup_archive(&outside),
+            Err(BackupError::InvalidArchive(_))
+        ));
+
+        let traversal = write_zip("traversal.zip", &[&format!("{}/../../evil", save)]);
+        assert!(validate_backup_archive(&traversal).is_err());
+
+        let empty = write_zip("empty.zip", &[]);
+        assert!(validate_backup_archive(&empty).is_err());
+
+        let not_zip = temp.path().join("not.zip");
+        fs::write(&not_zip, "hello").unwrap();
+        assert!(validate_backup_archive(&not_zip).is_err());
+    }
+
+    #[test]
+    fn test_verify_archive_detects_bit_rot() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("backup.zip");
+        let content = b"character save data";
+
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        zip.start_file("save/World/hero.sav", options).unwrap();
+        zip.write_all(content).unwrap();
+        zip.finish().unwrap();
+        assert_eq!(verify_archive(&path).unwrap(), 1);
+
+        // Flip one byte of the stored file data; the CRC no longer matches
+        let mut bytes = fs::read(&path).unwrap();
+        let offset = bytes
+            .windows(content.len())
+            .position(|w| w == content)
+            .unwrap();
+        bytes[offset] ^= 0xFF;
+        fs::write(&path, bytes).unwrap();
+        assert!(matches!(
+            verify_archive(&path),
+            Err(BackupError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn test_backup_meta_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("backup.zip");
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file("save/World/hero.sav", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"character save data").unwrap();
+        zip.finish().unwrap();
+
+        let meta = BackupMeta {
+            note: Some("before the big raid".to_string()),
+            checksum: Some(archive_checksum(&path).unwrap()),
+        };
+        write_meta(&path, &meta).unwrap();
+
+        let info = read_backup_info(&path).unwrap();
+        assert_eq!(info.note.as_deref(), Some("before the big raid"));
+        assert_eq!(info.checksum, meta.checksum);
+        assert_eq!(verify_archive(&path).unwrap(), 1);
+
+        // A checksum that no longer matches marks the archive corrupt
+        let stale = BackupMeta {
+            checksum: Some("0".repeat(64)),
+            ..meta
+        };
+        write_meta(&path, &stale).unwrap();
+        assert!(matches!(
+            verify_archive(&path),
+            Err(BackupError::Corrupt(_))
+        ));
+
+        // Nothing left to store removes the sidecar
+        write_meta(&path, &BackupMeta::default()).unwrap();
+        assert!(!meta_path(&path).exists());
+        assert_eq!(read_backup_info(&path).unwrap().note, None);
+    }
 }
@@ -7,11 +7,15 @@ use clap::Subcommand;
 use serde::Serialize;
 use tokio::sync::watch;
 
-use crate::backup::{self, BackupInfo, BackupProgress};
+use crate::app_data::game_config;
+use crate::backup::{self, BackupDiff, BackupInfo, BackupNameVars, BackupProgress};
 use crate::cli::output::{
-    OutputFormat, print_error, print_formatted, print_success, should_show_progress,
+    DryRunPlan, OutputFormat, print_dry_run, print_error, print_formatted, print_success,
+    should_show_progress,
 };
-use crate::config::Config;
+use crate::cli::{CliExit, NoGame, load_config};
+use crate::db::Database;
+use crate::game;
 use crate::util::format_size;
 
 #[derive(Subcommand, Debug)]
@@ -21,7 +25,7 @@ pub enum BackupCommands {
 
     /// Create a new backup
     Create {
-        /// Backup name (auto-generated if not specified)
+        /// Backup name (generated from backups.name_template if not specified)
         #[arg(long)]
         name: Option<String>,
 
@@ -39,9 +43,9 @@ pub enum BackupCommands {
         #[arg(long)]
         no_safety_backup: bool,
 
-        /// Show what would happen without doing it
-        #[arg(long)]
-        dry_run: bool,
+        /// Game directory to restore into (defaults to the configured one)
+        #[arg(long, value_name = "PATH")]
+        target: Option<PathBuf>,
     },
 
     /// Delete a backup
@@ -54,10 +58,41 @@ pub enum BackupCommands {
         keep: Option<usize>,
     },
 
-    /// Check backup archive integrity
+    /// Check backup archive integrity (all backups if no name is given)
     Verify {
         /// Backup name to verify
+        name: Option<String>,
+    },
+
+    /// Compare the worlds, characters, and file sizes of two backups
+    Diff {
+        /// First (usually older) backup
+        a: String,
+
+        /// Second (usually newer) backup
+        b: String,
+    },
+
+    /// Set a backup's note
+    Note {
+        /// Backup name
         name: String,
+
+        /// Note text (omit to clear the note)
+        note: Option<String>,
+    },
+
+    /// Extract a single file or folder from a backup without restoring it
+    Extract {
+        /// Backup name
+        name: String,
+
+        /// Path inside the backup, e.g. save/MyWorld
+        path: String,
+
+        /// Folder to extract into (defaults to the current directory)
+        #[arg(long)]
+        to: Option<PathBuf>,
     },
 }
 
@@ -71,12 +106,19 @@ struct BackupListResult {
 #[derive(Serialize)]
 struct BackupEntry {
     name: String,
+    path: String,
     compressed_size_bytes: u64,
     uncompressed_size_bytes: u64,
+    /// Space saved by compression, as a percentage (0-100)
+    compression_ratio: f32,
     worlds_count: u32,
     characters_count: u32,
+    /// RFC 3339 timestamp with the local UTC offset
     modified: String,
     is_auto: bool,
+    note: Option<String>,
+    /// SHA-256 of the archive, if recorded when it was created
+    checksum: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -97,7 +139,18 @@ struct BackupVerifyResult {
     error: Option<String>,
 }
 
-pub async fn run(command: BackupCommands, format: OutputFormat, quiet: bool) -> Result<()> {
+#[derive(Serialize)]
+struct BackupVerifyReport {
+    backups: Vec<BackupVerifyResult>,
+    corrupt_count: usize,
+}
+
+pub async fn run(
+    command: BackupCommands,
+    format: OutputFormat,
+    quiet: bool,
+    dry_run: bool,
+) -> Result<()> {
     match command {
         BackupCommands::List => list(format).await,
         BackupCommands::Create { name, compression } => {
@@ -106,10 +159,21 @@ pub async fn run(command: BackupCommands, format: OutputFormat, quiet: bool) ->
         BackupCommands::Restore {
             name,
             no_safety_backup,
-            dry_run,
-        } => restore(&name, !no_safety_backup, dry_run, format, quiet).await,
-        BackupCommands::Delete { name, keep } => delete(name, keep, quiet).await,
-        BackupCommands::Verify { name } => verify(&name, format).await,
+            target,
+        } => restore(&name, target, !no_safety_backup, dry_run, format, quiet).await,
+        BackupCommands::Delete { name, keep } => delete(name, keep, dry_run, format, quiet).await,
+        BackupCommands::Verify { name } => verify(name.as_deref(), format).await,
+        BackupCommands::Diff { a, b } => diff(&a, &b, format).await,
+        BackupCommands::Note { name, note } => {
+            backup::set_backup_note(&name, note.as_deref().unwrap_or_default())?;
+            let message = match note {
+                Some(_) => format!("Saved note for {}", name),
+                None => format!("Cleared note for {}", name),
+            };
+            print_success(&message, quiet);
+            Ok(())
+        }
+        BackupCommands::Extract { name, path, to } => extract(&name, &path, to, quiet).await,
     }
 }
 
@@ -122,12 +186,18 @@ async fn list(format: OutputFormat) -> Result<()> {
         .iter()
         .map(|b| BackupEntry {
             name: b.name.clone(),
+            path: b.path.to_string_lossy().to_string(),
             compressed_size_bytes: b.compressed_size,
             uncompressed_size_bytes: b.uncompressed_size,
+            compression_ratio: b.compression_ratio(),
             worlds_count: b.worlds_count,
             characters_count: b.characters_count,
-            modified: b.modified.format("%Y-%m-%d %H:%M:%S").to_string(),
+            modified: b
+                .modified
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
             is_auto: b.is_auto,
+            note: b.note.clone(),
+            checksum: b.checksum.clone(),
         })
         .collect();
 
@@ -158,7 +228,7 @@ fn format_backup_list(result: &BackupListResult) -> String {
 
     for backup in &result.backups {
         let auto_marker = if backup.is_auto { "*" } else { "" };
-        let date = &backup.modified[..10]; // Just the date part
+        let date = &backup.modified[..10]; // Just the date part (YYYY-MM-DD)
         lines.push(format!(
             "{:<30} {:>10} {:>12} {:>8}",
             format!("{}{}", backup.name, auto_marker),
@@ -166,6 +236,9 @@ fn format_backup_list(result: &BackupListResult) -> String {
             date,
             backup.worlds_count
         ));
+        if let Some(note) = &backup.note {
+            lines.push(format!("  {}", note));
+        }
     }
 
     lines.push(String::new());
@@ -181,19 +254,39 @@ async fn create(
     format: OutputFormat,
     quiet: bool,
 ) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured")?;
-
-    // Generate name if not provided
-    let backup_name = name.unwrap_or_else(|| {
-        let now = chrono::Local::now();
-        format!("backup-{}", now.format("%Y-%m-%d-%H%M%S"))
-    });
+        .ok_or(NoGame("No game directory configured"))?;
+
+    // Generate name from the template if not provided
+    let backup_name = match name {
+        Some(name) => {
+            // Same rules as the Backups tab, checked before any work starts
+            backup::validate_new_backup_name(&name, &backup::list_backups().await?)?;
+            name
+        }
+        None => {
+            let db = Database::open().ok();
+            let version = game::detect_game_with_db(&game_dir, db.as_ref())
+                .ok()
+                .flatten()
+                .map(|info| info.version_display().to_string());
+            let vars = BackupNameVars {
+                version,
+                branch: Some(config.game.branch.clone()),
+            };
+            let base = backup::expand_name_template(
+                &config.backups.name_template,
+                &vars,
+                chrono::Local::now(),
+            );
+            backup::generate_unique_name(&backup::backup_dir(), &base)
+        }
+    };
 
     // Create progress channel
     let (progress_tx, mut progress_rx) = watch::channel(BackupProgress::default());
@@ -239,18 +332,27 @@ async fn create(
 
 async fn restore(
     name: &str,
+    target: Option<PathBuf>,
     backup_current: bool,
     dry_run: bool,
     format: OutputFormat,
     quiet: bool,
 ) -> Result<()> {
-    let config = Config::load()?;
-    let game_dir = config
-        .game
-        .directory
-        .as_ref()
-        .map(PathBuf::from)
-        .context("No game directory configured")?;
+    let config = load_config()?;
+    let game_dir = match target {
+        Some(target) => {
+            if !target.is_dir() {
+                anyhow::bail!("Target directory not found: {}", target.display());
+            }
+            target
+        }
+        None => config
+            .game
+            .directory
+            .as_ref()
+            .map(PathBuf::from)
+            .ok_or(NoGame("No game directory configured"))?,
+    };
 
     // Find the backup
     let backups = backup::list_backups().await?;
@@ -260,13 +362,23 @@ async fn restore(
         .context(format!("Backup '{}' not found", name))?;
 
     if dry_run {
-        println!("Dry run - would restore backup: {}", name);
-        println!("  Size: {}", format_size(backup_info.compressed_size));
-        println!("  Worlds: {}", backup_info.worlds_count);
-        println!("  Characters: {}", backup_info.characters_count);
-        if backup_current {
-            println!("  Would create safety backup of current saves first");
+        let save_dir = game_dir.join(&game_config().directories.save);
+        let mut plan = DryRunPlan::new(format!("restore backup: {}", name));
+        plan.detail(format!(
+            "Backup size: {} ({} worlds, {} characters)",
+            format_size(backup_info.compressed_size),
+            backup_info.worlds_count,
+            backup_info.characters_count
+        ));
+        if backup_current && save_dir.exists() {
+            plan.detail("Back up current saves as before_last_restore first");
         }
+        plan.detail(format!(
+            "Replace {} with {} of saves from the backup",
+            save_dir.display(),
+            format_size(backup_info.uncompressed_size)
+        ));
+        print_dry_run(&plan, format);
         return Ok(());
     }
 
@@ -299,16 +411,52 @@ async fn restore(
     )
     .await?;
 
-    print_success(&format!("Restored backup: {}", name), quiet);
+    print_success(
+        &format!("Restored backup: {} into {}", name, game_dir.display()),
+        quiet,
+    );
 
     Ok(())
 }
 
-async fn delete(name: Option<String>, keep: Option<usize>, quiet: bool) -> Result<()> {
+/// Describe deleting `backups`, including the space it would reclaim
+fn delete_plan(action: String, backups: &[BackupInfo]) -> DryRunPlan {
+    let mut plan = DryRunPlan::new(action);
+    for backup in backups {
+        plan.detail(format!(
+            "Delete: {} ({})",
+            backup.name,
+            format_size(backup.compressed_size)
+        ));
+    }
+    let reclaimed: u64 = backups.iter().map(|b| b.compressed_size).sum();
+    plan.detail(format!("Space reclaimed: {}", format_size(reclaimed)));
+    plan
+}
+
+async fn delete(
+    name: Option<String>,
+    keep: Option<usize>,
+    dry_run: bool,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<()> {
     match (name, keep) {
         (Some(backup_name), _) => {
+            if dry_run {
+                let backups = backup::list_backups().await?;
+                let info = backups
+                    .into_iter()
+                    .find(|b| b.name == backup_name)
+                    .context(format!("Backup '{}' not found", backup_name))?;
+                let plan = delete_plan(format!("delete backup: {}", backup_name), &[info]);
+                print_dry_run(&plan, format);
+                return Ok(());
+            }
+
             // Delete specific backup
-            backup::delete_backup(&backup_name).await?;
+            let (progress_tx, _) = watch::channel(BackupProgress::default());
+            backup::delete_backup(&backup_name, progress_tx).await?;
             print_success(&format!("Deleted backup: {}", backup_name), quiet);
         }
         (None, Some(keep_count)) => {
@@ -333,8 +481,15 @@ async fn delete(name: Option<String>, keep: Option<usize>, quiet: bool) -> Resul
             let to_delete: Vec<_> = backups.into_iter().skip(keep_count).collect();
             let count = to_delete.len();
 
+            if dry_run {
+                let action = format!("delete {} old backups, keeping {}", count, keep_count);
+                print_dry_run(&delete_plan(action, &to_delete), format);
+                return Ok(());
+            }
+
             for backup in to_delete {
-                backup::delete_backup(&backup.name).await?;
+                let (progress_tx, _) = watch::channel(BackupProgress::default());
+                backup::delete_backup(&backup.name, progress_tx).await?;
             }
 
             print_success(&format!("Deleted {} old backups", count), quiet);
@@ -348,69 +503,159 @@ async fn delete(name: Option<String>, keep: Option<usize>, quiet: bool) -> Resul
     Ok(())
 }
 
-async fn verify(name: &str, format: OutputFormat) -> Result<()> {
+async fn verify(name: Option<&str>, format: OutputFormat) -> Result<()> {
     let backups = backup::list_backups().await?;
 
-    let backup_info = backups.iter().find(|b| b.name == name);
-
-    match backup_info {
-        Some(info) => {
-            // Try to read the archive to verify it
-            let verify_result = verify_archive(info).await;
+    let Some(name) = name else {
+        let mut results = Vec::new();
+        for info in &backups {
+            results.push(verify_one(info).await);
+        }
+        let report = BackupVerifyReport {
+            corrupt_count: results.iter().filter(|r| !r.valid).count(),
+            backups: results,
+        };
+        print_formatted(&report, format, format_verify_report);
+        if report.corrupt_count > 0 {
+            return Err(CliExit::Error.into());
+        }
+        return Ok(());
+    };
 
-            let result = BackupVerifyResult {
-                name: info.name.clone(),
-                valid: verify_result.is_ok(),
-                compressed_size_bytes: info.compressed_size,
-                uncompressed_size_bytes: info.uncompressed_size,
-                file_count: verify_result.as_ref().copied().unwrap_or(0),
-                error: verify_result.err().map(|e| e.to_string()),
-            };
+    let Some(info) = backups.iter().find(|b| b.name == name) else {
+        print_error(&format!("Backup '{}' not found", name));
+        return Err(anyhow::anyhow!("Backup not found"));
+    };
 
-            print_formatted(&result, format, |r| {
-                if r.valid {
-                    format!(
-                        "Backup '{}' is valid.\n  Files: {}\n  Size: {} (uncompressed: {})",
-                        r.name,
-                        r.file_count,
-                        format_size(r.compressed_size_bytes),
-                        format_size(r.uncompressed_size_bytes)
-                    )
-                } else {
-                    format!(
-                        "Backup '{}' is INVALID: {}",
-                        r.name,
-                        r.error.as_deref().unwrap_or("Unknown error")
-                    )
-                }
-            });
-        }
-        None => {
-            print_error(&format!("Backup '{}' not found", name));
-            return Err(anyhow::anyhow!("Backup not found"));
+    let result = verify_one(info).await;
+    print_formatted(&result, format, |r| {
+        if r.valid {
+            format!(
+                "Backup '{}' is valid.\n  Files: {}\n  Size: {} (uncompressed: {})",
+                r.name,
+                r.file_count,
+                format_size(r.compressed_size_bytes),
+                format_size(r.uncompressed_size_bytes)
+            )
+        } else {
+            format!(
+                "Backup '{}' is INVALID: {}",
+                r.name,
+                r.error.as_deref().unwrap_or("Unknown error")
+            )
         }
+    });
+
+    if !result.valid {
+        return Err(CliExit::Error.into());
+    }
+    Ok(())
+}
+
+/// Read a backup archive end to end and report whether it's intact
+async fn verify_one(info: &BackupInfo) -> BackupVerifyResult {
+    let verify_result = backup::verify_backup(&info.path).await;
+
+    BackupVerifyResult {
+        name: info.name.clone(),
+        valid: verify_result.is_ok(),
+        compressed_size_bytes: info.compressed_size,
+        uncompressed_size_bytes: info.uncompressed_size,
+        file_count: verify_result.as_ref().copied().unwrap_or(0),
+        error: verify_result.err().map(|e| e.to_string()),
     }
+}
 
+fn format_verify_report(report: &BackupVerifyReport) -> String {
+    if report.backups.is_empty() {
+        return "No backups found.".to_string();
+    }
+
+    let mut lines: Vec<String> = report
+        .backups
+        .iter()
+        .map(|r| match &r.error {
+            None => format!("  OK       {} ({} files)", r.name, r.file_count),
+            Some(error) => format!("  CORRUPT  {}: {}", r.name, error),
+        })
+        .collect();
+
+    lines.push(String::new());
+    lines.push(if report.corrupt_count == 0 {
+        format!("All {} backups are valid.", report.backups.len())
+    } else {
+        format!(
+            "{} of {} backups failed verification.",
+            report.corrupt_count,
+            report.backups.len()
+        )
+    });
+
+    lines.join("\n")
+}
+
+async fn extract(name: &str, path: &str, to: Option<PathBuf>, quiet: bool) -> Result<()> {
+    let dest = match to {
+        Some(dir) => dir,
+        None => std::env::current_dir().context("Failed to get current directory")?,
+    };
+    let (count, target) = backup::extract_from_backup(name, path, &dest).await?;
+    print_success(
+        &format!(
+            "Extracted {} files of {} to {}",
+            count,
+            path,
+            target.display()
+        ),
+        quiet,
+    );
     Ok(())
 }
 
-async fn verify_archive(info: &BackupInfo) -> Result<usize, anyhow::Error> {
-    let path = info.path.clone();
+async fn diff(a: &str, b: &str, format: OutputFormat) -> Result<()> {
+    let diff = backup::diff_backups(a, b).await?;
+    print_formatted(&diff, format, format_diff_text);
+    Ok(())
+}
 
-    tokio::task::spawn_blocking(move || {
-        let file = std::fs::File::open(&path)?;
-        let mut archive = zip::ZipArchive::new(file)?;
+fn format_diff_text(diff: &BackupDiff) -> String {
+    if diff.is_empty() {
+        return format!(
+            "No differences between '{}' and '{}'",
+            diff.backup_a, diff.backup_b
+        );
+    }
 
-        let mut count = 0;
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            // Read the file to verify it's not corrupted
-            let mut buf = Vec::new();
-            std::io::Read::read_to_end(&mut file, &mut buf)?;
-            count += 1;
+    let mut lines = vec![format!(
+        "Comparing '{}' -> '{}'",
+        diff.backup_a, diff.backup_b
+    )];
+
+    let sections = [
+        ("Worlds only in A", &diff.worlds_only_in_a),
+        ("Worlds only in B", &diff.worlds_only_in_b),
+        ("Characters only in A", &diff.characters_only_in_a),
+        ("Characters only in B", &diff.characters_only_in_b),
+    ];
+    for (label, items) in sections {
+        if items.is_empty() {
+            continue;
         }
+        lines.push(format!("\n{} ({}):", label, items.len()));
+        lines.extend(items.iter().map(|item| format!("  {}", item)));
+    }
 
-        Ok(count)
-    })
-    .await?
+    if !diff.size_changes.is_empty() {
+        lines.push(format!("\nSize changes ({}):", diff.size_changes.len()));
+        lines.extend(diff.size_changes.iter().map(|change| {
+            format!(
+                "  {}: {} -> {}",
+                change.path,
+                format_size(change.size_a),
+                format_size(change.size_b)
+            )
+        }));
+    }
+
+    lines.join("\n")
 }
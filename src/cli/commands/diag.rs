@@ -1,14 +1,19 @@
 //! Diagnostic and debugging commands
 
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
 use clap::Subcommand;
 use serde::Serialize;
 
 use crate::backup;
-use crate::cli::output::{OutputFormat, print_formatted, print_success};
+use crate::cli::output::{DryRunPlan, OutputFormat, print_dry_run, print_formatted, print_success};
+use crate::cli::{NoGame, load_config};
 use crate::config::Config;
 use crate::db::Database;
 use crate::game;
+use crate::github::GitHubClient;
+use crate::update::{self, ArchiveAction};
 use crate::util::format_size;
 
 #[derive(Subcommand, Debug)]
@@ -21,6 +26,12 @@ pub enum DiagCommands {
 
     /// Clear the version hash cache
     ClearCache,
+
+    /// Show runtime environment details for bug reports
+    Env,
+
+    /// Show which game directory entries the next update will archive or skip
+    ArchivePreview,
 }
 
 #[derive(Serialize)]
@@ -28,7 +39,30 @@ struct PathsResult {
     config_file: String,
     database: String,
     backups_dir: String,
+    logs_dir: String,
+    game_dir: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EnvResult {
+    os: String,
+    arch: String,
+    app_version: String,
+    data_dir: Option<String>,
+    download_dir: Option<String>,
     game_dir: Option<String>,
+    game_version: Option<String>,
+    free_space_bytes: Option<u64>,
+    rate_limit_remaining: Option<u32>,
+    rate_limit_reset_minutes: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct ArchivePreviewResult {
+    game_dir: String,
+    prevent_save_move: bool,
+    entries: Vec<update::ArchivePreviewEntry>,
+    archived_bytes: u64,
 }
 
 #[derive(Serialize)]
@@ -43,16 +77,23 @@ struct CheckResult {
     game_executable_found: bool,
 }
 
-pub async fn run(command: DiagCommands, format: OutputFormat, quiet: bool) -> Result<()> {
+pub async fn run(
+    command: DiagCommands,
+    format: OutputFormat,
+    quiet: bool,
+    dry_run: bool,
+) -> Result<()> {
     match command {
         DiagCommands::Paths => paths(format).await,
         DiagCommands::Check => check(format).await,
-        DiagCommands::ClearCache => clear_cache(quiet).await,
+        DiagCommands::ClearCache => clear_cache(dry_run, format, quiet).await,
+        DiagCommands::Env => env(format).await,
+        DiagCommands::ArchivePreview => archive_preview(format).await,
     }
 }
 
 async fn paths(format: OutputFormat) -> Result<()> {
-    let config = Config::load().ok();
+    let config = load_config().ok();
 
     let result = PathsResult {
         config_file: Config::config_path()
@@ -64,6 +105,9 @@ async fn paths(format: OutputFormat) -> Result<()> {
         backups_dir: Config::backups_dir()
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|_| "<error>".to_string()),
+        logs_dir: Config::logs_dir()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| "<error>".to_string()),
         game_dir: config.and_then(|c| c.game.directory),
     };
 
@@ -72,6 +116,7 @@ async fn paths(format: OutputFormat) -> Result<()> {
             format!("Config file:  {}", r.config_file),
             format!("Database:     {}", r.database),
             format!("Backups dir:  {}", r.backups_dir),
+            format!("Logs dir:     {}", r.logs_dir),
         ];
 
         if let Some(game_dir) = &r.game_dir {
@@ -90,7 +135,7 @@ async fn check(format: OutputFormat) -> Result<()> {
     // Check config file
     let config_path = Config::config_path().ok();
     let config_exists = config_path.as_ref().is_some_and(|p| p.exists());
-    let config = Config::load().ok();
+    let config = load_config().ok();
 
     // Check database
     let db = Database::open().ok();
@@ -164,6 +209,143 @@ async fn check(format: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+async fn env(format: OutputFormat) -> Result<()> {
+    let config = load_config().ok();
+    let db = Database::open().ok();
+
+    let data_dir = Config::data_dir().ok();
+    let network = config
+        .as_ref()
+        .map(|c| c.network.clone())
+        .unwrap_or_default();
+    let game_dir = config.and_then(|c| c.game.directory);
+
+    let game_version = game_dir.as_ref().and_then(|dir| {
+        game::detect_game_with_db(Path::new(dir), db.as_ref())
+            .ok()
+            .flatten()
+            .map(|info| info.version_display().to_string())
+    });
+
+    // Free space where updates are installed, or the data dir if no game is set
+    let free_space_bytes = game_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .filter(|p| p.exists())
+        .or_else(|| data_dir.clone())
+        .and_then(|p| fs4::available_space(p).ok());
+
+    let rate_limit = match GitHubClient::new(&network) {
+        Ok(client) => client.get_rate_limit().await.ok(),
+        Err(_) => None,
+    };
+
+    let result = EnvResult {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        data_dir: data_dir.map(|p| p.to_string_lossy().to_string()),
+        download_dir: update::download_dir()
+            .ok()
+            .map(|p| p.to_string_lossy().to_string()),
+        game_dir,
+        game_version,
+        free_space_bytes,
+        rate_limit_remaining: rate_limit.as_ref().and_then(|r| r.remaining),
+        rate_limit_reset_minutes: rate_limit.as_ref().and_then(|r| r.reset_in_minutes()),
+    };
+
+    print_formatted(&result, format, |r| {
+        let or_unknown = |v: &Option<String>| v.clone().unwrap_or_else(|| "<unknown>".to_string());
+
+        let rate_limit = match (r.rate_limit_remaining, r.rate_limit_reset_minutes) {
+            (Some(remaining), Some(reset)) => {
+                format!("{} remaining (resets in {} min)", remaining, reset)
+            }
+            (Some(remaining), None) => format!("{} remaining", remaining),
+            _ => "<unavailable>".to_string(),
+        };
+
+        [
+            format!("OS:            {} ({})", r.os, r.arch),
+            format!("Phoenix:       {}", r.app_version),
+            format!("Data dir:      {}", or_unknown(&r.data_dir)),
+            format!("Download dir:  {}", or_unknown(&r.download_dir)),
+            format!(
+                "Game dir:      {}",
+                r.game_dir.as_deref().unwrap_or("<not configured>")
+            ),
+            format!(
+                "Game version:  {}",
+                r.game_version.as_deref().unwrap_or("<not detected>")
+            ),
+            format!(
+                "Free space:    {}",
+                r.free_space_bytes
+                    .map(format_size)
+                    .unwrap_or_else(|| "<unknown>".to_string())
+            ),
+            format!("GitHub API:    {}", rate_limit),
+        ]
+        .join("\n")
+    });
+
+    Ok(())
+}
+
+async fn archive_preview(format: OutputFormat) -> Result<()> {
+    let config = load_config()?;
+    let game_dir = config.game.directory.clone().ok_or(NoGame(
+        "No game directory configured. Configure it in settings first.",
+    ))?;
+    let prevent_save_move = config.updates.prevent_save_move;
+
+    let entries = update::preview_archive(Path::new(&game_dir), prevent_save_move).await?;
+    let archived_bytes = entries
+        .iter()
+        .filter(|e| e.action == ArchiveAction::Move)
+        .map(|e| e.size)
+        .sum();
+
+    let result = ArchivePreviewResult {
+        game_dir,
+        prevent_save_move,
+        entries,
+        archived_bytes,
+    };
+
+    print_formatted(&result, format, |r| {
+        let mut lines = vec![format!("Archive preview for {}", r.game_dir), String::new()];
+        for entry in &r.entries {
+            let action = match entry.action {
+                ArchiveAction::Move => "archive",
+                ArchiveAction::SkipArchive => "skip (Phoenix archive)",
+                ArchiveAction::SkipDownload => "skip (partial download)",
+                ArchiveAction::SkipSaves => "skip (prevent_save_move)",
+            };
+            let name = if entry.is_dir {
+                format!("{}/", entry.name)
+            } else {
+                entry.name.clone()
+            };
+            lines.push(format!(
+                "  {:<24} {:>10}  {}",
+                action,
+                format_size(entry.size),
+                name
+            ));
+        }
+        lines.push(String::new());
+        lines.push(format!(
+            "{} will be moved to the archive",
+            format_size(r.archived_bytes)
+        ));
+        lines.join("\n")
+    });
+
+    Ok(())
+}
+
 fn print_status_line(lines: &mut Vec<String>, ok: bool, message: &str) {
     if ok {
         lines.push(format!("[OK] {}", message));
@@ -172,8 +354,19 @@ fn print_status_line(lines: &mut Vec<String>, ok: bool, message: &str) {
     }
 }
 
-async fn clear_cache(quiet: bool) -> Result<()> {
+async fn clear_cache(dry_run: bool, format: OutputFormat, quiet: bool) -> Result<()> {
     let db = Database::open()?;
+
+    if dry_run {
+        let mut plan = DryRunPlan::new("clear the version hash cache");
+        plan.detail(format!(
+            "Delete {} cached hashes from {}",
+            db.count_cached_versions()?,
+            Database::db_path()?.display()
+        ));
+        print_dry_run(&plan, format);
+        return Ok(());
+    }
     let count = db.clear_hash_cache()?;
 
     print_success(&format!("Cleared {} cached hashes", count), quiet);
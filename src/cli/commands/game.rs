@@ -15,9 +15,11 @@ use crate::app_data::migration_config;
 use crate::cli::output::{
     OutputFormat, print_error, print_formatted, print_success, should_show_progress,
 };
+use crate::cli::{CliExit, NoGame, load_config};
 use crate::config::Config;
 use crate::db::Database;
 use crate::game::{self, GameInfo};
+use crate::integrity::{self, DriftReport};
 use crate::util::format_size;
 
 #[derive(Subcommand, Debug)]
@@ -34,6 +36,14 @@ pub enum GameCommands {
         /// Additional command-line parameters
         #[arg(long)]
         params: Option<String>,
+
+        /// Launch profile to use (uses the selected profile if not specified)
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Print the command line instead of launching
+        #[arg(long)]
+        print: bool,
     },
 
     /// Show detailed game information
@@ -53,6 +63,12 @@ pub enum GameCommands {
         #[arg(long, default_value = "6")]
         compression: u8,
     },
+
+    /// Record the installation's file hashes as a baseline for `game verify`
+    Snapshot,
+
+    /// Compare the installation against its baseline snapshot
+    Verify,
 }
 
 /// JSON-serializable game detection result
@@ -71,17 +87,23 @@ struct DetectResult {
 pub async fn run(command: GameCommands, format: OutputFormat, quiet: bool) -> Result<()> {
     match command {
         GameCommands::Detect { dir } => detect(dir, format, quiet).await,
-        GameCommands::Launch { params } => launch(params, quiet).await,
+        GameCommands::Launch {
+            params,
+            profile,
+            print,
+        } => launch(params, profile, print, quiet).await,
         GameCommands::Info { dir } => info(dir, format, quiet).await,
         GameCommands::Export {
             output,
             compression,
         } => export(output, compression, format, quiet).await,
+        GameCommands::Snapshot => snapshot(format, quiet).await,
+        GameCommands::Verify => verify(format, quiet).await,
     }
 }
 
 async fn detect(dir: Option<PathBuf>, format: OutputFormat, quiet: bool) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let game_dir = get_game_dir(dir, &config)?;
 
     // Open database for version lookup
@@ -112,36 +134,74 @@ async fn detect(dir: Option<PathBuf>, format: OutputFormat, quiet: bool) -> Resu
             if !quiet {
                 print_error("Game executable not found");
             }
+            return Err(CliExit::NoGame.into());
         }
     }
 
     Ok(())
 }
 
-async fn launch(params: Option<String>, quiet: bool) -> Result<()> {
-    let config = Config::load()?;
+async fn launch(
+    params: Option<String>,
+    profile: Option<String>,
+    print: bool,
+    quiet: bool,
+) -> Result<()> {
+    let mut config = load_config()?;
+    if let Some(name) = profile {
+        if !config.game.launch_profiles.iter().any(|p| p.name == name) {
+            anyhow::bail!("Unknown launch profile: {}", name);
+        }
+        config.game.selected_profile = Some(name);
+    }
+
     let game_dir = get_game_dir(None, &config)?;
 
     // Open database for version lookup
     let db = Database::open().ok();
 
     let game_info = game::detect_game_with_db(&game_dir, db.as_ref())?
-        .context("No game detected. Configure game directory first.")?;
+        .ok_or(NoGame("No game detected. Configure game directory first."))?;
 
-    // Combine configured params with CLI params
+    // Combine the selected profile's params with CLI params
+    let configured_params = config.game.launch_params();
     let combined_params = match params {
         Some(p) => {
-            if config.game.command_params.is_empty() {
+            if configured_params.is_empty() {
                 p
             } else {
-                format!("{} {}", config.game.command_params, p)
+                format!("{} {}", configured_params, p)
             }
         }
-        None => config.game.command_params.clone(),
+        None => configured_params.to_string(),
     };
 
+    if print {
+        println!(
+            "{}",
+            game::launch_command_line(&game_info.executable, &combined_params)?
+        );
+        return Ok(());
+    }
+
+    // The CLI doesn't prompt, but still warns about save compatibility
+    let dir_key = game_dir.to_string_lossy().to_string();
+    if !quiet
+        && let (Some(db), Some(current)) = (&db, &game_info.version_info)
+        && let Ok(Some(last)) = db.last_launch(&dir_key)
+        && let Some(warning) = game::launch_mismatch(&last, current)
+    {
+        eprintln!("Warning: {}", warning);
+    }
+
     game::launch_game(&game_info.executable, &combined_params)?;
 
+    if let (Some(db), Some(current)) = (&db, &game_info.version_info)
+        && let Err(e) = db.record_launch(&dir_key, current, chrono::Utc::now().timestamp())
+    {
+        eprintln!("Warning: Failed to record launched version: {}", e);
+    }
+
     print_success(
         &format!("Launched: {}", game_info.executable.display()),
         quiet,
@@ -151,7 +211,7 @@ async fn launch(params: Option<String>, quiet: bool) -> Result<()> {
 }
 
 async fn info(dir: Option<PathBuf>, format: OutputFormat, _quiet: bool) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let game_dir = get_game_dir(dir, &config)?;
 
     // Open database for version lookup
@@ -182,6 +242,7 @@ async fn info(dir: Option<PathBuf>, format: OutputFormat, _quiet: bool) -> Resul
         }
         None => {
             print_error(&format!("No game detected in: {}", game_dir.display()));
+            return Err(CliExit::NoGame.into());
         }
     }
 
@@ -203,7 +264,10 @@ struct GameInfoResult {
 
 fn get_game_dir(dir: Option<PathBuf>, config: &Config) -> Result<PathBuf> {
     dir.or_else(|| config.game.directory.as_ref().map(PathBuf::from))
-        .context("No game directory specified. Use --dir or configure in settings.")
+        .ok_or(NoGame(
+            "No game directory specified. Use --dir or configure in settings.",
+        ))
+        .map_err(Into::into)
 }
 
 fn build_detect_result(game_info: &GameInfo, game_dir: &Path, _config: &Config) -> DetectResult {
@@ -313,13 +377,15 @@ async fn export(
     format: OutputFormat,
     quiet: bool,
 ) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured. Set it in Phoenix settings first.")?;
+        .ok_or(NoGame(
+            "No game directory configured. Set it in Phoenix settings first.",
+        ))?;
 
     // Determine output path
     let output_path = match output {
@@ -479,3 +545,119 @@ fn export_sync(
 
     Ok((total_files, compressed_size, uncompressed_size))
 }
+
+#[derive(Serialize)]
+struct SnapshotResult {
+    directory: String,
+    version: Option<String>,
+    file_count: usize,
+}
+
+/// Hash the configured installation in a blocking task
+async fn hash_game_dir(
+    game_dir: &Path,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<integrity::FileHashes> {
+    if should_show_progress(quiet, format) {
+        eprintln!("Hashing game files...");
+    }
+    let dir = game_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || integrity::hash_installation(&dir)).await?
+}
+
+async fn snapshot(format: OutputFormat, quiet: bool) -> Result<()> {
+    let config = load_config()?;
+    let game_dir = get_game_dir(None, &config)?;
+
+    let db = Database::open()?;
+    let version = game::detect_game_with_db(&game_dir, Some(&db))?
+        .ok_or(NoGame("No game detected. Configure game directory first."))?
+        .version_display()
+        .to_string();
+
+    let files = hash_game_dir(&game_dir, format, quiet).await?;
+    let dir_key = game_dir.to_string_lossy().to_string();
+    db.store_install_snapshot(&dir_key, Some(&version), &files)?;
+
+    let result = SnapshotResult {
+        directory: dir_key,
+        version: Some(version),
+        file_count: files.len(),
+    };
+
+    print_formatted(&result, format, |r| {
+        format!(
+            "Recorded {} file hashes for {} ({})",
+            r.file_count,
+            r.version.as_deref().unwrap_or("Unknown"),
+            r.directory
+        )
+    });
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct VerifyResult {
+    directory: String,
+    snapshot_version: Option<String>,
+    snapshot_created_on: String,
+    clean: bool,
+    #[serde(flatten)]
+    drift: DriftReport,
+}
+
+async fn verify(format: OutputFormat, quiet: bool) -> Result<()> {
+    let config = load_config()?;
+    let game_dir = get_game_dir(None, &config)?;
+    let dir_key = game_dir.to_string_lossy().to_string();
+
+    let db = Database::open()?;
+    let snapshot = db
+        .get_install_snapshot(&dir_key)?
+        .context("No snapshot for this game directory. Run `game snapshot` first.")?;
+
+    let current = hash_game_dir(&game_dir, format, quiet).await?;
+    let drift = integrity::compare(&snapshot.files, &current);
+
+    let result = VerifyResult {
+        directory: dir_key,
+        snapshot_version: snapshot.version,
+        snapshot_created_on: snapshot.created_on,
+        clean: drift.is_clean(),
+        drift,
+    };
+
+    print_formatted(&result, format, format_verify_text);
+
+    Ok(())
+}
+
+fn format_verify_text(result: &VerifyResult) -> String {
+    let mut lines = vec![format!(
+        "Snapshot: {} ({})",
+        result.snapshot_version.as_deref().unwrap_or("Unknown"),
+        result.snapshot_created_on
+    )];
+
+    if result.clean {
+        lines.push("Installation matches the snapshot.".to_string());
+        return lines.join("\n");
+    }
+
+    let sections = [
+        ("Missing", &result.drift.missing),
+        ("Modified", &result.drift.modified),
+        ("Extra", &result.drift.extra),
+    ];
+    for (label, files) in sections {
+        if files.is_empty() {
+            continue;
+        }
+        lines.push(format!("{} ({}):", label, files.len()));
+        lines.extend(files.iter().map(|f| format!("  {}", f)));
+    }
+
+    lines.join("\n")
+}
@@ -8,19 +8,28 @@ use serde::Serialize;
 use tokio::sync::watch;
 
 use crate::cli::output::{
-    OutputFormat, print_error, print_formatted, print_success, should_show_progress,
+    DryRunPlan, OutputFormat, print_dry_run, print_error, print_formatted, print_success,
+    should_show_progress,
 };
-use crate::config::Config;
+use crate::cli::{NoGame, load_config};
 use crate::github::GitHubClient;
-use crate::soundpack::{self, SoundpackProgress};
+use crate::soundpack::{self, InstalledSoundpack, SoundpackProgress};
 use crate::util::format_size;
 
 #[derive(Subcommand, Debug)]
 pub enum SoundpackCommands {
-    /// List installed soundpacks
-    List,
+    /// List installed soundpacks, or the repository's with --repo
+    List {
+        /// List installed soundpacks (the default)
+        #[arg(long, conflicts_with = "repo")]
+        installed: bool,
 
-    /// List soundpacks available for download
+        /// List soundpacks available for download
+        #[arg(long)]
+        repo: bool,
+    },
+
+    /// List soundpacks available for download (same as `list --repo`)
     Available,
 
     /// Install a soundpack
@@ -43,6 +52,10 @@ pub enum SoundpackCommands {
     Enable {
         /// Soundpack name to enable
         name: String,
+
+        /// Disable all other soundpacks (the game plays only one)
+        #[arg(long)]
+        only: bool,
     },
 
     /// Disable a soundpack
@@ -58,6 +71,7 @@ struct InstalledEntry {
     view_name: String,
     enabled: bool,
     size_bytes: u64,
+    path: PathBuf,
 }
 
 #[derive(Serialize)]
@@ -70,7 +84,11 @@ struct InstalledListResult {
 #[derive(Serialize)]
 struct AvailableEntry {
     name: String,
+    viewname: String,
+    /// Same as `viewname`; kept for scripts written against older output
     description: String,
+    download_type: String,
+    url: String,
     size_bytes: Option<u64>,
 }
 
@@ -79,25 +97,71 @@ struct AvailableListResult {
     soundpacks: Vec<AvailableEntry>,
 }
 
-pub async fn run(command: SoundpackCommands, format: OutputFormat, quiet: bool) -> Result<()> {
+/// Outcome of an install, delete, enable or disable
+#[derive(Serialize)]
+struct SoundpackActionResult {
+    action: &'static str,
+    name: String,
+    view_name: String,
+    path: PathBuf,
+    enabled: bool,
+    size_bytes: u64,
+    /// Other soundpacks disabled by `enable --only` (or `single_soundpack`)
+    disabled_others: usize,
+}
+
+impl SoundpackActionResult {
+    fn new(action: &'static str, soundpack: &InstalledSoundpack) -> Self {
+        Self {
+            action,
+            name: soundpack.name.clone(),
+            view_name: soundpack.view_name.clone(),
+            path: soundpack.path.clone(),
+            enabled: soundpack.enabled,
+            size_bytes: soundpack.size,
+            disabled_others: 0,
+        }
+    }
+
+    /// Print as JSON, or `message` as text unless quiet
+    fn print(&self, message: &str, format: OutputFormat, quiet: bool) {
+        match format {
+            OutputFormat::Json => print_formatted(self, format, |_| String::new()),
+            OutputFormat::Text => print_success(message, quiet),
+        }
+    }
+}
+
+pub async fn run(
+    command: SoundpackCommands,
+    format: OutputFormat,
+    quiet: bool,
+    dry_run: bool,
+) -> Result<()> {
     match command {
-        SoundpackCommands::List => list(format).await,
-        SoundpackCommands::Available => available(format).await,
+        SoundpackCommands::List { repo: true, .. } | SoundpackCommands::Available => {
+            available(format).await
+        }
+        SoundpackCommands::List { .. } => list(format).await,
         SoundpackCommands::Install { name, file } => install(name, file, format, quiet).await,
-        SoundpackCommands::Delete { name } => delete(&name, quiet).await,
-        SoundpackCommands::Enable { name } => set_enabled(&name, true, quiet).await,
-        SoundpackCommands::Disable { name } => set_enabled(&name, false, quiet).await,
+        SoundpackCommands::Delete { name } => delete(&name, dry_run, format, quiet).await,
+        SoundpackCommands::Enable { name, only } => {
+            set_enabled(&name, true, only, format, quiet).await
+        }
+        SoundpackCommands::Disable { name } => {
+            set_enabled(&name, false, false, format, quiet).await
+        }
     }
 }
 
 async fn list(format: OutputFormat) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured")?;
+        .ok_or(NoGame("No game directory configured"))?;
 
     let soundpacks = soundpack::list_installed_soundpacks(&game_dir).await?;
 
@@ -110,6 +174,7 @@ async fn list(format: OutputFormat) -> Result<()> {
             view_name: s.view_name.clone(),
             enabled: s.enabled,
             size_bytes: s.size,
+            path: s.path.clone(),
         })
         .collect();
 
@@ -126,16 +191,20 @@ async fn list(format: OutputFormat) -> Result<()> {
 
         let mut lines = vec![format!("Installed soundpacks ({}):\n", r.total_count)];
 
-        lines.push(format!("{:<30} {:>10} {:>8}", "NAME", "SIZE", "STATUS"));
-        lines.push("-".repeat(52));
+        lines.push(format!(
+            "{:<30} {:>10} {:>8}  {}",
+            "NAME", "SIZE", "STATUS", "PATH"
+        ));
+        lines.push("-".repeat(70));
 
         for sp in &r.soundpacks {
             let status = if sp.enabled { "enabled" } else { "disabled" };
             lines.push(format!(
-                "{:<30} {:>10} {:>8}",
+                "{:<30} {:>10} {:>8}  {}",
                 sp.view_name,
                 format_size(sp.size_bytes),
-                status
+                status,
+                sp.path.display()
             ));
         }
 
@@ -155,7 +224,10 @@ async fn available(format: OutputFormat) -> Result<()> {
         .iter()
         .map(|s| AvailableEntry {
             name: s.name.clone(),
+            viewname: s.viewname.clone(),
             description: s.viewname.clone(),
+            download_type: s.download_type.clone(),
+            url: s.url.clone(),
             size_bytes: s.size,
         })
         .collect();
@@ -171,8 +243,11 @@ async fn available(format: OutputFormat) -> Result<()> {
 
         let mut lines = vec!["Available soundpacks:\n".to_string()];
 
-        lines.push(format!("{:<30} {:>10} {}", "NAME", "SIZE", "DESCRIPTION"));
-        lines.push("-".repeat(70));
+        lines.push(format!(
+            "{:<30} {:>10} {:<8} {:<30} {}",
+            "NAME", "SIZE", "TYPE", "DESCRIPTION", "URL"
+        ));
+        lines.push("-".repeat(100));
 
         for sp in &r.soundpacks {
             let size = sp
@@ -185,7 +260,16 @@ async fn available(format: OutputFormat) -> Result<()> {
             } else {
                 sp.description.clone()
             };
-            lines.push(format!("{:<30} {:>10} {}", sp.name, size, desc));
+            // Browser downloads have to be fetched by hand
+            let kind = if sp.download_type == "browser_download" {
+                "browser"
+            } else {
+                "direct"
+            };
+            lines.push(format!(
+                "{:<30} {:>10} {:<8} {:<30} {}",
+                sp.name, size, kind, desc, sp.url
+            ));
         }
 
         lines.join("\n")
@@ -200,13 +284,13 @@ async fn install(
     format: OutputFormat,
     quiet: bool,
 ) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured")?;
+        .ok_or(NoGame("No game directory configured"))?;
 
     // Create progress channel
     let (progress_tx, mut progress_rx) = watch::channel(SoundpackProgress::default());
@@ -241,64 +325,68 @@ async fn install(
         });
     }
 
-    match (name, file) {
+    let installed = match (name, file) {
         (_, Some(archive_path)) => {
             // Install from local file
-            let result =
-                soundpack::install_from_file(archive_path.clone(), game_dir, progress_tx).await?;
-
-            print_success(
-                &format!(
-                    "Installed soundpack: {} ({})",
-                    result.view_name,
-                    format_size(result.size)
-                ),
-                quiet,
-            );
+            soundpack::install_from_file(archive_path.clone(), game_dir, progress_tx).await?
         }
         (Some(name), None) => {
             // Install from repository
             let repo = soundpack::load_repository();
             let repo_pack = repo
                 .iter()
-                .find(|s| s.name.to_lowercase() == name.to_lowercase())
+                .find(|s| {
+                    s.name.to_lowercase() == name.to_lowercase()
+                        || s.viewname.to_lowercase() == name.to_lowercase()
+                })
                 .context(format!("Soundpack '{}' not found in repository", name))?;
 
-            let client = GitHubClient::new()?;
-            let result = soundpack::install_soundpack(
+            // These hosts need a browser (login or click-through pages)
+            if repo_pack.download_type == "browser_download" {
+                anyhow::bail!(
+                    "{} can only be downloaded in a browser: {}\n\
+                     Download it, then run `phoenix soundpack install --file <archive>`",
+                    repo_pack.viewname,
+                    repo_pack.url
+                );
+            }
+
+            let client = GitHubClient::new(&config.network)?;
+            soundpack::install_soundpack(
                 client.client().clone(),
                 repo_pack.clone(),
                 game_dir,
                 progress_tx,
             )
-            .await?;
-
-            print_success(
-                &format!(
-                    "Installed soundpack: {} ({})",
-                    result.view_name,
-                    format_size(result.size)
-                ),
-                quiet,
-            );
+            .await?
         }
         (None, None) => {
             print_error("Specify a soundpack name or use --file");
             return Err(anyhow::anyhow!("No soundpack specified"));
         }
-    }
+    };
+
+    SoundpackActionResult::new("installed", &installed).print(
+        &format!(
+            "Installed soundpack: {} ({})",
+            installed.view_name,
+            format_size(installed.size)
+        ),
+        format,
+        quiet,
+    );
 
     Ok(())
 }
 
-async fn delete(name: &str, quiet: bool) -> Result<()> {
-    let config = Config::load()?;
+async fn delete(name: &str, dry_run: bool, format: OutputFormat, quiet: bool) -> Result<()> {
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured")?;
+        .ok_or(NoGame("No game directory configured"))?;
 
     let soundpacks = soundpack::list_installed_soundpacks(&game_dir).await?;
 
@@ -308,9 +396,19 @@ async fn delete(name: &str, quiet: bool) -> Result<()> {
     });
 
     match found {
+        Some(sp) if dry_run => {
+            let mut plan = DryRunPlan::new(format!("delete soundpack: {}", sp.view_name));
+            plan.detail(format!("Delete: {}", sp.path.display()));
+            plan.detail(format!("Space reclaimed: {}", format_size(sp.size)));
+            print_dry_run(&plan, format);
+        }
         Some(sp) => {
             soundpack::delete_soundpack(sp.path.clone()).await?;
-            print_success(&format!("Deleted soundpack: {}", sp.view_name), quiet);
+            SoundpackActionResult::new("deleted", sp).print(
+                &format!("Deleted soundpack: {}", sp.view_name),
+                format,
+                quiet,
+            );
         }
         None => {
             print_error(&format!("Soundpack '{}' not found", name));
@@ -321,14 +419,22 @@ async fn delete(name: &str, quiet: bool) -> Result<()> {
     Ok(())
 }
 
-async fn set_enabled(name: &str, enabled: bool, quiet: bool) -> Result<()> {
-    let config = Config::load()?;
+/// Enable or disable a soundpack by name. With `exclusive` (or the
+/// `single_soundpack` setting), enabling it disables the others.
+async fn set_enabled(
+    name: &str,
+    enabled: bool,
+    exclusive: bool,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<()> {
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured")?;
+        .ok_or(NoGame("No game directory configured"))?;
 
     let soundpacks = soundpack::list_installed_soundpacks(&game_dir).await?;
 
@@ -338,10 +444,38 @@ async fn set_enabled(name: &str, enabled: bool, quiet: bool) -> Result<()> {
     });
 
     match found {
+        Some(sp) if enabled && (exclusive || config.game.single_soundpack) => {
+            let disabled = soundpack::set_active_soundpack(&game_dir, &sp.path).await?;
+            let mut result = SoundpackActionResult::new("enabled", sp);
+            result.enabled = true;
+            result.disabled_others = disabled;
+            result.print(
+                &format!(
+                    "Enabled soundpack: {} (disabled {} others)",
+                    sp.view_name, disabled
+                ),
+                format,
+                quiet,
+            );
+        }
         Some(sp) => {
             soundpack::set_soundpack_enabled(&sp.path, enabled).await?;
-            let action = if enabled { "Enabled" } else { "Disabled" };
-            print_success(&format!("{} soundpack: {}", action, sp.view_name), quiet);
+            let action = if enabled { "enabled" } else { "disabled" };
+            let mut result = SoundpackActionResult::new(action, sp);
+            result.enabled = enabled;
+            result.print(
+                &format!(
+                    "{} soundpack: {}",
+                    if enabled { "Enabled" } else { "Disabled" },
+                    sp.view_name
+                ),
+                format,
+                quiet,
+            );
+            let others = soundpacks.iter().filter(|s| s.enabled && s.path != sp.path);
+            if enabled && others.count() > 0 && !quiet {
+                eprintln!("Warning: other soundpacks are enabled too; the game plays only one");
+            }
         }
         None => {
             print_error(&format!("Soundpack '{}' not found", name));
@@ -1,20 +1,25 @@
 //! Update management commands
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use clap::Subcommand;
 use serde::Serialize;
 use tokio::sync::watch;
 
+use crate::app_data::migration_config;
+use crate::backup::{self, AutoBackupType, BackupNameVars, BackupProgress, RetentionPolicy};
 use crate::cli::output::{
-    OutputFormat, print_error, print_formatted, print_success, should_show_progress,
+    DryRunPlan, OutputFormat, print_dry_run, print_error, print_formatted, print_success,
+    should_show_progress,
 };
-use crate::config::Config;
+use crate::cli::{CliExit, NoGame, load_config};
 use crate::db::Database;
 use crate::game;
 use crate::github::GitHubClient;
-use crate::update::{self, UpdateProgress};
+use crate::self_update::{self as launcher, LauncherUpdate};
+use crate::state::is_installed_release;
+use crate::update::{self, InstallOptions, MigrationSummary, UpdateProgress};
 use crate::util::format_size;
 
 #[derive(Subcommand, Debug)]
@@ -55,7 +60,15 @@ pub enum UpdateCommands {
     },
 
     /// Install a downloaded update
-    Install,
+    Install {
+        /// Fetch, download, and install the newest release instead
+        #[arg(long)]
+        latest: bool,
+
+        /// Branch to install from with --latest (stable or experimental)
+        #[arg(long, requires = "latest")]
+        branch: Option<String>,
+    },
 
     /// Download and install in one step
     Apply {
@@ -67,9 +80,18 @@ pub enum UpdateCommands {
         #[arg(long)]
         remove_old: bool,
 
-        /// Show what would happen without doing it
+        /// Clean reinstall: keep only saves and config, not custom mods,
+        /// tilesets, soundpacks or fonts
         #[arg(long)]
-        dry_run: bool,
+        clean: bool,
+    },
+
+    /// Update the Phoenix launcher itself
+    #[command(name = "self")]
+    SelfUpdate {
+        /// Only check for a newer launcher, don't install it
+        #[arg(long)]
+        check: bool,
     },
 }
 
@@ -97,7 +119,12 @@ struct ReleasesResult {
     releases: Vec<ReleaseEntry>,
 }
 
-pub async fn run(command: UpdateCommands, format: OutputFormat, quiet: bool) -> Result<()> {
+pub async fn run(
+    command: UpdateCommands,
+    format: OutputFormat,
+    quiet: bool,
+    dry_run: bool,
+) -> Result<()> {
     match command {
         UpdateCommands::Check => check(format).await,
         UpdateCommands::Releases {
@@ -109,23 +136,30 @@ pub async fn run(command: UpdateCommands, format: OutputFormat, quiet: bool) ->
             changelog(tag, no_cache, format, quiet).await
         }
         UpdateCommands::Download { version } => download(version, format, quiet).await,
-        UpdateCommands::Install => install(format, quiet).await,
+        UpdateCommands::Install { latest, branch } => {
+            if latest {
+                install_latest(branch, dry_run, format, quiet).await
+            } else {
+                install(dry_run, format, quiet).await
+            }
+        }
         UpdateCommands::Apply {
             keep_saves,
             remove_old,
-            dry_run,
-        } => apply(keep_saves, remove_old, dry_run, format, quiet).await,
+            clean,
+        } => apply(keep_saves, remove_old, clean, dry_run, format, quiet).await,
+        UpdateCommands::SelfUpdate { check } => self_update(check, format, quiet).await,
     }
 }
 
 async fn check(format: OutputFormat) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured")?;
+        .ok_or(NoGame("No game directory configured"))?;
 
     // Detect current version
     let db = Database::open().ok();
@@ -135,21 +169,26 @@ async fn check(format: OutputFormat) -> Result<()> {
         .map(|g| g.version_display().to_string());
 
     // Fetch latest release
-    let client = GitHubClient::new()?;
+    let client = GitHubClient::new(&config.network)?;
     let branch = &config.game.branch;
 
     let releases = if branch == "stable" {
         client.get_stable_releases().await?.data
     } else {
-        client.get_experimental_releases().await?.data
+        client
+            .get_experimental_releases(config.updates.experimental_fetch_count)
+            .await?
+            .data
     };
 
     let latest = releases.first();
     let latest_asset = latest.and_then(|r| GitHubClient::find_platform_asset(r));
 
     let latest_version = latest.map(|r| r.tag_name.clone());
-    let update_available = match (&current_version, &latest_version) {
-        (Some(current), Some(latest)) => current != latest,
+    let update_available = match (&current_info, latest) {
+        (Some(info), Some(latest)) => {
+            !is_installed_release(&latest.tag_name, info, config.game.version_override())
+        }
         _ => false,
     };
 
@@ -178,7 +217,11 @@ async fn check(format: OutputFormat) -> Result<()> {
         }
     });
 
-    Ok(())
+    match (&result.current_version, &result.latest_version) {
+        (None, _) => Err(CliExit::NoGame.into()),
+        (Some(_), Some(_)) if !result.update_available => Err(CliExit::UpToDate.into()),
+        _ => Ok(()),
+    }
 }
 
 async fn releases(
@@ -187,10 +230,10 @@ async fn releases(
     tags: Option<String>,
     format: OutputFormat,
 ) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let branch = branch.unwrap_or_else(|| config.game.branch.clone());
 
-    let client = GitHubClient::new()?;
+    let client = GitHubClient::new(&config.network)?;
 
     let releases = if let Some(tags_str) = tags {
         // Fetch specific tags directly
@@ -199,7 +242,10 @@ async fn releases(
     } else if branch == "stable" {
         client.get_stable_releases().await?.data
     } else {
-        client.get_experimental_releases().await?.data
+        client
+            .get_experimental_releases(config.updates.experimental_fetch_count)
+            .await?
+            .data
     };
 
     let entries: Vec<ReleaseEntry> = releases
@@ -282,7 +328,7 @@ async fn changelog(tag: String, no_cache: bool, format: OutputFormat, quiet: boo
         if !quiet {
             eprintln!("Fetching changelog from GitHub API...");
         }
-        let client = GitHubClient::new()?;
+        let client = GitHubClient::new(&load_config()?.network)?;
         let (release, _rate_limit) = client.get_release_by_tag(&tag).await;
 
         if let Some(release) = release {
@@ -314,15 +360,18 @@ async fn changelog(tag: String, no_cache: bool, format: OutputFormat, quiet: boo
 }
 
 async fn download(version: Option<String>, format: OutputFormat, quiet: bool) -> Result<()> {
-    let config = Config::load()?;
-    let client = GitHubClient::new()?;
+    let config = load_config()?;
+    let client = GitHubClient::new(&config.network)?;
 
     // Get releases
     let branch = &config.game.branch;
     let releases = if branch == "stable" {
         client.get_stable_releases().await?.data
     } else {
-        client.get_experimental_releases().await?.data
+        client
+            .get_experimental_releases(config.updates.experimental_fetch_count)
+            .await?
+            .data
     };
 
     // Find the release to download
@@ -368,9 +417,11 @@ async fn download(version: Option<String>, format: OutputFormat, quiet: bool) ->
 
     let result = update::download_asset(
         client.client().clone(),
-        asset.browser_download_url.clone(),
+        client.download_urls(&asset.browser_download_url),
         dest_path.clone(),
         progress_tx,
+        config.updates.download_connections,
+        None,
     )
     .await?;
 
@@ -394,14 +445,70 @@ fn is_supported_archive(path: &std::path::Path) -> bool {
     lower.ends_with(".zip") || lower.ends_with(".tar.gz") || lower.ends_with(".tgz")
 }
 
-async fn install(format: OutputFormat, quiet: bool) -> Result<()> {
-    let config = Config::load()?;
+/// Add the steps `update::install_update` takes on `game_dir` to a dry-run plan
+async fn add_install_steps(plan: &mut DryRunPlan, game_dir: &Path, options: &InstallOptions) {
+    let archive_dir = game_dir.join(&migration_config().archive.directory);
+
+    plan.detail(format!(
+        "Move the current installation into {}{}",
+        archive_dir.display(),
+        if options.prevent_save_move {
+            " (saves stay in place)"
+        } else {
+            ""
+        }
+    ));
+    if archive_dir.exists() && options.keep_archives > 1 && !options.remove_previous_version {
+        plan.detail(format!(
+            "Keep the previous installation archive for rollback (up to {} archives)",
+            options.keep_archives
+        ));
+    } else if archive_dir.exists() {
+        let size = game::calculate_dir_size(&archive_dir).unwrap_or(0);
+        plan.detail(format!(
+            "Delete the previous installation archive ({})",
+            format_size(size)
+        ));
+    }
+    plan.detail(format!(
+        "Extract the new version into {} and restore {}",
+        game_dir.display(),
+        if options.clean {
+            "only saves and config (clean reinstall)"
+        } else {
+            "saves and custom content"
+        }
+    ));
+    if !options.skip_restore_dirs.is_empty() {
+        plan.detail(format!(
+            "Leave {} in the archive (not carried over)",
+            options.skip_restore_dirs.join(", ")
+        ));
+    }
+    if !options.exclude.is_empty() {
+        let excluded: Vec<_> = options
+            .exclude
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        plan.detail(format!(
+            "Minimal install: leave out {}",
+            excluded.join(", ")
+        ));
+    }
+    if options.remove_previous_version {
+        plan.detail("Delete the archived installation once the update succeeds");
+    }
+}
+
+async fn install(dry_run: bool, format: OutputFormat, quiet: bool) -> Result<()> {
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured")?;
+        .ok_or(NoGame("No game directory configured"))?;
 
     // Look for downloaded update (either a .zip or a .tar.gz archive)
     let download_dir = std::env::temp_dir().join("phoenix");
@@ -428,6 +535,25 @@ async fn install(format: OutputFormat, quiet: bool) -> Result<()> {
     });
 
     let zip_path = archive_files[0].path();
+
+    if dry_run {
+        let size = archive_files[0].metadata().map(|m| m.len()).unwrap_or(0);
+        let mut plan = DryRunPlan::new("install a downloaded update");
+        plan.detail(format!(
+            "Archive: {} ({})",
+            zip_path.display(),
+            format_size(size)
+        ));
+        add_install_steps(
+            &mut plan,
+            &game_dir,
+            &InstallOptions::from_config(&config.updates),
+        )
+        .await;
+        print_dry_run(&plan, format);
+        return Ok(());
+    }
+
     println!("Installing: {}", zip_path.display());
 
     // Create progress channel
@@ -450,16 +576,18 @@ async fn install(format: OutputFormat, quiet: bool) -> Result<()> {
         });
     }
 
-    update::install_update(
+    let preserved = update::install_update(
         zip_path,
         game_dir,
         progress_tx,
-        config.updates.prevent_save_move,
-        config.updates.remove_previous_version,
+        InstallOptions::from_config(&config.updates),
     )
     .await?;
 
     print_success("Update installed successfully!", quiet);
+    if !preserved.is_empty() {
+        print_success(&preserved_lines(&preserved).join("\n"), quiet);
+    }
 
     Ok(())
 }
@@ -467,80 +595,61 @@ async fn install(format: OutputFormat, quiet: bool) -> Result<()> {
 async fn apply(
     keep_saves: bool,
     remove_old: bool,
+    clean: bool,
     dry_run: bool,
     format: OutputFormat,
     quiet: bool,
 ) -> Result<()> {
-    let config = Config::load()?;
+    let config = load_config()?;
     let game_dir = config
         .game
         .directory
         .as_ref()
         .map(PathBuf::from)
-        .context("No game directory configured")?;
+        .ok_or(NoGame("No game directory configured"))?;
 
-    let client = GitHubClient::new()?;
+    let client = GitHubClient::new(&config.network)?;
 
     // Get latest release
     let branch = &config.game.branch;
     let releases = if branch == "stable" {
         client.get_stable_releases().await?.data
     } else {
-        client.get_experimental_releases().await?.data
+        client
+            .get_experimental_releases(config.updates.experimental_fetch_count)
+            .await?
+            .data
     };
 
     let release = releases.first().context("No releases found")?;
     let asset = GitHubClient::find_platform_asset(release)
         .context("No compatible x64 graphical asset found")?;
 
+    let options = InstallOptions {
+        prevent_save_move: keep_saves || config.updates.prevent_save_move,
+        remove_previous_version: remove_old || config.updates.remove_previous_version,
+        clean,
+        ..InstallOptions::from_config(&config.updates)
+    };
+
     if dry_run {
-        println!("Dry run - would apply update:");
-        println!("  Version: {}", release.tag_name);
-        println!("  Size: {}", format_size(asset.size));
-        println!(
-            "  Keep saves in place: {}",
-            keep_saves || config.updates.prevent_save_move
-        );
-        println!(
-            "  Remove old version: {}",
-            remove_old || config.updates.remove_previous_version
-        );
+        let mut plan = DryRunPlan::new(format!("apply update: {}", release.tag_name));
+        plan.detail(format!(
+            "Download: {} ({})",
+            asset.name,
+            format_size(asset.size)
+        ));
+        add_install_steps(&mut plan, &game_dir, &options).await;
+        print_dry_run(&plan, format);
         return Ok(());
     }
 
     // Create progress channel
-    let (progress_tx, mut progress_rx) = watch::channel(UpdateProgress::default());
+    let (progress_tx, progress_rx) = watch::channel(UpdateProgress::default());
 
     // Spawn progress reporter (only if TTY and not quiet)
-    let show_progress = should_show_progress(quiet, format);
-    if show_progress {
-        tokio::spawn(async move {
-            while progress_rx.changed().await.is_ok() {
-                let p = progress_rx.borrow().clone();
-                match p.phase {
-                    update::UpdatePhase::Downloading => {
-                        if p.total_bytes > 0 {
-                            let percent =
-                                (p.bytes_downloaded as f64 / p.total_bytes as f64 * 100.0) as u32;
-                            eprint!(
-                                "\rDownloading: {}% ({})   ",
-                                percent,
-                                format_size(p.bytes_downloaded)
-                            );
-                        }
-                    }
-                    _ => {
-                        eprint!(
-                            "\r{}: {}/{}   ",
-                            p.phase.description(),
-                            p.files_extracted,
-                            p.total_files
-                        );
-                    }
-                }
-            }
-            eprintln!();
-        });
+    if should_show_progress(quiet, format) {
+        spawn_progress_reporter(progress_rx);
     }
 
     // Download, preserving the asset's archive extension (.zip / .tar.gz)
@@ -554,9 +663,11 @@ async fn apply(
 
     update::download_asset(
         client.client().clone(),
-        asset.browser_download_url.clone(),
+        client.download_urls(&asset.browser_download_url),
         zip_path.clone(),
         progress_tx.clone(),
+        config.updates.download_connections,
+        None,
     )
     .await?;
 
@@ -565,22 +676,321 @@ async fn apply(
         println!("Installing...");
     }
 
-    let prevent_save_move = keep_saves || config.updates.prevent_save_move;
-    let remove_previous = remove_old || config.updates.remove_previous_version;
+    let preserved = update::install_update(zip_path, game_dir, progress_tx, options).await?;
 
-    update::install_update(
-        zip_path,
+    print_success(
+        &format!("Update complete! Now running: {}", release.tag_name),
+        quiet,
+    );
+    if !preserved.is_empty() {
+        print_success(&preserved_lines(&preserved).join("\n"), quiet);
+    }
+
+    Ok(())
+}
+
+/// Print download and install progress to stderr until the channel closes
+fn spawn_progress_reporter(mut progress_rx: watch::Receiver<UpdateProgress>) {
+    tokio::spawn(async move {
+        while progress_rx.changed().await.is_ok() {
+            let p = progress_rx.borrow().clone();
+            match p.phase {
+                update::UpdatePhase::Downloading => {
+                    if p.total_bytes > 0 {
+                        let percent =
+                            (p.bytes_downloaded as f64 / p.total_bytes as f64 * 100.0) as u32;
+                        eprint!(
+                            "\rDownloading: {}% ({})   ",
+                            percent,
+                            format_size(p.bytes_downloaded)
+                        );
+                    }
+                }
+                update::UpdatePhase::Cleaning => {
+                    eprint!(
+                        "\r{}: {}/{}   ",
+                        p.phase.description(),
+                        p.files_removed,
+                        p.total_to_remove
+                    );
+                }
+                _ => {
+                    eprint!(
+                        "\r{}: {}/{}   ",
+                        p.phase.description(),
+                        p.files_extracted,
+                        p.total_files
+                    );
+                }
+            }
+        }
+        eprintln!();
+    });
+}
+
+/// Describe the custom content kept by an update, flagging suspect mods
+fn preserved_lines(summary: &MigrationSummary) -> Vec<String> {
+    let mut lines = vec![summary.to_string()];
+    if !summary.mods.is_empty() {
+        lines.push(format!("Custom mods: {}", summary.mods.join(", ")));
+    }
+    if !summary.replaced.is_empty() {
+        lines.push(format!(
+            "Replaced with your previous copy: {}",
+            summary.replaced.join(", ")
+        ));
+    }
+    if !summary.overwritten_mods.is_empty() {
+        let kept = if summary.archive_removed {
+            ""
+        } else {
+            " (edits kept in the archive)"
+        };
+        lines.push(format!(
+            "Overwritten by the new version{}: {}",
+            kept,
+            summary.overwritten_mods.join(", ")
+        ));
+    }
+    for warning in &summary.mod_warnings {
+        lines.push(format!("Warning: {}", warning));
+    }
+    for error in &summary.restore_errors {
+        lines.push(format!("Not restored: {}", error));
+    }
+    lines
+}
+
+#[derive(Serialize)]
+struct InstallLatestResult {
+    branch: String,
+    previous_version: Option<String>,
+    installed_version: String,
+    updated: bool,
+    backup: Option<String>,
+    preserved: Option<MigrationSummary>,
+}
+
+/// Headless one-shot update: fetch the newest release for a branch, then
+/// back up, download, and install it using the configured update settings.
+async fn install_latest(
+    branch: Option<String>,
+    dry_run: bool,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<()> {
+    let config = load_config()?;
+    let game_dir = config
+        .game
+        .directory
+        .as_ref()
+        .map(PathBuf::from)
+        .ok_or(NoGame("No game directory configured"))?;
+
+    let branch = branch.unwrap_or_else(|| config.game.branch.clone());
+    if branch != "stable" && branch != "experimental" {
+        anyhow::bail!(
+            "Unknown branch: {} (expected stable or experimental)",
+            branch
+        );
+    }
+
+    // Detect current version
+    let db = Database::open().ok();
+    let installed = game::detect_game_with_db(&game_dir, db.as_ref())?;
+    let previous_version = installed.as_ref().map(|g| g.version_display().to_string());
+
+    // Fetch the newest release
+    let client = GitHubClient::new(&config.network)?;
+    let releases = if branch == "stable" {
+        client.get_stable_releases().await?.data
+    } else {
+        client
+            .get_experimental_releases(config.updates.experimental_fetch_count)
+            .await?
+            .data
+    };
+
+    let release = releases.first().context("No releases found")?;
+    let asset = GitHubClient::find_platform_asset(release)
+        .context("No compatible x64 graphical asset found")?;
+
+    if installed.as_ref().is_some_and(|info| {
+        is_installed_release(&release.tag_name, info, config.game.version_override())
+    }) {
+        let result = InstallLatestResult {
+            branch,
+            previous_version,
+            installed_version: release.tag_name.clone(),
+            updated: false,
+            backup: None,
+            preserved: None,
+        };
+        print_formatted(&result, format, |r| {
+            format!("Already up to date: {}", r.installed_version)
+        });
+        return Err(CliExit::UpToDate.into());
+    }
+
+    if dry_run {
+        let mut plan = DryRunPlan::new(format!("install {} ({})", release.tag_name, branch));
+        if config.backups.backup_before_update {
+            plan.detail("Create a pre-update backup of the current saves");
+        }
+        plan.detail(format!(
+            "Download: {} ({})",
+            asset.name,
+            format_size(asset.size)
+        ));
+        add_install_steps(
+            &mut plan,
+            &game_dir,
+            &InstallOptions::from_config(&config.updates),
+        )
+        .await;
+        print_dry_run(&plan, format);
+        return Ok(());
+    }
+
+    // Fail fast (before backup or download) if the game is running
+    update::check_installation_access(&game_dir, config.updates.skip_access_check).await?;
+
+    // Pre-update backup (if enabled)
+    let mut backup_name = None;
+    if config.backups.backup_before_update {
+        if !quiet {
+            eprintln!("Creating pre-update backup...");
+        }
+        let backup_progress_tx = watch::channel(BackupProgress::default()).0;
+        match backup::create_auto_backup(
+            &game_dir,
+            AutoBackupType::BeforeUpdate,
+            &config.backups.name_template,
+            &BackupNameVars {
+                version: Some(release.tag_name.clone()),
+                branch: Some(branch.clone()),
+            },
+            config.backups.compression_level,
+            RetentionPolicy::from_config(&config.backups),
+            backup_progress_tx,
+        )
+        .await
+        {
+            Ok(info) => backup_name = info.map(|i| i.name),
+            Err(e) => {
+                // Same as the GUI: a failed backup doesn't block the update
+                tracing::warn!("Failed to create pre-update backup: {}", e);
+                if !quiet {
+                    eprintln!("Warning: pre-update backup failed: {}", e);
+                }
+            }
+        }
+    }
+
+    // Create progress channel
+    let (progress_tx, progress_rx) = watch::channel(UpdateProgress::default());
+    if should_show_progress(quiet, format) {
+        spawn_progress_reporter(progress_rx);
+    }
+
+    // Download
+    if !quiet {
+        eprintln!("Downloading {}...", release.tag_name);
+    }
+    let archive_path = update::download_dir()?.join(&asset.name);
+    let downloaded = update::download_asset(
+        client.client().clone(),
+        client.download_urls(&asset.browser_download_url),
+        archive_path,
+        progress_tx.clone(),
+        config.updates.download_connections,
+        None,
+    )
+    .await?;
+
+    // Install
+    let preserved = update::install_update(
+        downloaded.file_path,
         game_dir,
         progress_tx,
-        prevent_save_move,
-        remove_previous,
+        InstallOptions::from_config(&config.updates),
     )
     .await?;
 
-    print_success(
-        &format!("Update complete! Now running: {}", release.tag_name),
-        quiet,
-    );
+    let result = InstallLatestResult {
+        branch,
+        previous_version,
+        installed_version: release.tag_name.clone(),
+        updated: true,
+        backup: backup_name,
+        preserved: Some(preserved),
+    };
 
+    print_formatted(&result, format, |r| {
+        let mut lines = vec![format!(
+            "Installed {} ({} branch)",
+            r.installed_version, r.branch
+        )];
+        if let Some(previous) = &r.previous_version {
+            lines.push(format!("Previous version: {}", previous));
+        }
+        if let Some(backup) = &r.backup {
+            lines.push(format!("Pre-update backup: {}", backup));
+        }
+        if let Some(preserved) = r.preserved.as_ref().filter(|p| !p.is_empty()) {
+            lines.extend(preserved_lines(preserved));
+        }
+        lines.join("\n")
+    });
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct SelfUpdateResult {
+    current_version: String,
+    update: Option<LauncherUpdate>,
+    installed: bool,
+}
+
+/// Check for (and optionally install) a newer Phoenix launcher.
+///
+/// The running executable is swapped out; the new version runs next time.
+async fn self_update(check_only: bool, format: OutputFormat, quiet: bool) -> Result<()> {
+    let config = load_config()?;
+    let client = GitHubClient::new(&config.network)?;
+
+    let mut result = SelfUpdateResult {
+        current_version: launcher::current_version().to_string(),
+        update: launcher::check(&client).await?,
+        installed: false,
+    };
+
+    if let Some(ref launcher_update) = result.update
+        && !check_only
+    {
+        if !quiet {
+            eprintln!("Downloading Phoenix {}...", launcher_update.version);
+        }
+        let progress_tx = watch::channel(UpdateProgress::default()).0;
+        launcher::install(&client, launcher_update, progress_tx).await?;
+        result.installed = true;
+    }
+
+    print_formatted(&result, format, |r| match &r.update {
+        None => format!("Phoenix {} is up to date", r.current_version),
+        Some(u) if r.installed => format!(
+            "Updated Phoenix {} -> {}\nThe new version runs the next time Phoenix starts.",
+            r.current_version, u.version
+        ),
+        Some(u) => format!(
+            "Phoenix {} is available (current: {})\n{}\nRun 'phoenix update self' to install it.",
+            u.version, r.current_version, u.page_url
+        ),
+    });
+
+    if result.update.is_none() {
+        return Err(CliExit::UpToDate.into());
+    }
     Ok(())
 }
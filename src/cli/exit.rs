@@ -0,0 +1,114 @@
+//! Process exit codes for scripting.
+//!
+//! Every command returns `anyhow::Result<()>`; [`CliExit::from_error`] walks
+//! the error chain to pick a specific code so scripts can tell "no game" from
+//! "network down" without parsing messages. Commands that have already
+//! reported the outcome return a `CliExit` directly as the error, which is
+//! mapped to its code without printing anything further.
+
+use thiserror::Error;
+
+use crate::github::RateLimitExceeded;
+
+/// Exit code of a CLI invocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum CliExit {
+    /// The command succeeded
+    #[error("success")]
+    Success,
+    /// Any failure without a more specific code
+    #[error("command failed")]
+    Error,
+    /// Nothing to do: the newest version is already installed
+    #[error("already up to date")]
+    UpToDate,
+    /// No game directory is configured, or no game was found in it
+    #[error("no game detected")]
+    NoGame,
+    /// A network request failed (connection, timeout, bad response)
+    #[error("network error")]
+    Network,
+    /// The GitHub API rate limit is used up
+    #[error("rate limited")]
+    RateLimited,
+    /// The disk ran out of space
+    #[error("disk full")]
+    DiskFull,
+}
+
+impl CliExit {
+    /// Numeric process exit code (2 is left to clap for usage errors)
+    pub fn code(self) -> i32 {
+        match self {
+            CliExit::Success => 0,
+            CliExit::Error => 1,
+            CliExit::UpToDate => 3,
+            CliExit::NoGame => 10,
+            CliExit::Network => 11,
+            CliExit::RateLimited => 12,
+            CliExit::DiskFull => 13,
+        }
+    }
+
+    /// Pick the exit code for a command error from its cause chain
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(exit) = cause.downcast_ref::<CliExit>() {
+                return *exit;
+            }
+            if cause.is::<NoGame>() {
+                return CliExit::NoGame;
+            }
+            if cause.is::<RateLimitExceeded>() {
+                return CliExit::RateLimited;
+            }
+            if let Some(e) = cause.downcast_ref::<std::io::Error>()
+                && e.kind() == std::io::ErrorKind::StorageFull
+            {
+                return CliExit::DiskFull;
+            }
+            if cause.is::<reqwest::Error>() {
+                return CliExit::Network;
+            }
+        }
+        CliExit::Error
+    }
+}
+
+/// Error for a command that needs a game but has none configured or installed
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct NoGame(pub &'static str);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_exit_code_from_error_chain() {
+        let no_game = anyhow::Error::from(NoGame("No game directory configured"));
+        assert_eq!(CliExit::from_error(&no_game), CliExit::NoGame);
+
+        let disk_full: anyhow::Result<()> =
+            Err(std::io::Error::from(std::io::ErrorKind::StorageFull))
+                .context("Failed to extract update");
+        assert_eq!(
+            CliExit::from_error(&disk_full.unwrap_err()),
+            CliExit::DiskFull
+        );
+
+        let rate_limited = anyhow::Error::from(RateLimitExceeded {
+            reset_in_minutes: 12,
+        });
+        assert_eq!(CliExit::from_error(&rate_limited), CliExit::RateLimited);
+
+        let reported = anyhow::Error::from(CliExit::UpToDate);
+        assert_eq!(CliExit::from_error(&reported).code(), 3);
+
+        assert_eq!(
+            CliExit::from_error(&anyhow::anyhow!("something else")),
+            CliExit::Error
+        );
+    }
+}
@@ -3,13 +3,23 @@
 //! Provides command-line interface for all launcher operations.
 
 mod commands;
+mod exit;
 mod output;
 mod shell;
 
+use std::path::PathBuf;
+use std::sync::Mutex;
+
 use clap::{Parser, Subcommand};
 
+use crate::config::Config;
+
+pub use exit::{CliExit, NoGame};
 pub use output::OutputFormat;
 
+/// Game directory override from `--game-dir` for the current invocation
+static GAME_DIR_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
 /// Phoenix - CDDA Game Launcher
 #[derive(Parser, Debug)]
 #[command(name = "phoenix")]
@@ -19,6 +29,18 @@ pub struct Cli {
     #[command(flatten)]
     pub output: OutputOptions,
 
+    /// Use this game directory instead of the configured one (not saved)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub game_dir: Option<PathBuf>,
+
+    /// Load and save settings in this config file instead of the default one
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Show what a delete, restore, install, or clear command would do without doing it
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -95,18 +117,104 @@ pub enum Commands {
     Shell,
 }
 
-/// Run the CLI with parsed arguments
-pub async fn run(cli: Cli) -> anyhow::Result<()> {
+impl Commands {
+    /// Whether this command honours `--dry-run` (destructive commands only)
+    fn supports_dry_run(&self) -> bool {
+        use commands::backup::BackupCommands;
+        use commands::diag::DiagCommands;
+        use commands::soundpack::SoundpackCommands;
+        use commands::update::UpdateCommands;
+
+        matches!(
+            self,
+            Commands::Backup {
+                command: BackupCommands::Restore { .. } | BackupCommands::Delete { .. }
+            } | Commands::Update {
+                command: UpdateCommands::Install { .. } | UpdateCommands::Apply { .. }
+            } | Commands::Soundpack {
+                command: SoundpackCommands::Delete { .. }
+            } | Commands::Diag {
+                command: DiagCommands::ClearCache
+            }
+        )
+    }
+}
+
+/// Error message for `--dry-run` on a command that doesn't support it
+const DRY_RUN_UNSUPPORTED: &str =
+    "--dry-run is only supported by delete, restore, install, apply, and clear-cache commands";
+
+/// Get the current `--game-dir` override, if any
+fn game_dir_override() -> Option<PathBuf> {
+    GAME_DIR_OVERRIDE
+        .lock()
+        .map(|dir| dir.clone())
+        .unwrap_or_default()
+}
+
+/// Set (or clear) the `--game-dir` override
+fn set_game_dir_override(dir: Option<PathBuf>) {
+    if let Ok(mut current) = GAME_DIR_OVERRIDE.lock() {
+        *current = dir;
+    }
+}
+
+/// Load configuration, applying the `--game-dir` override if one was given.
+///
+/// The override only affects the returned value; callers that save the config
+/// (e.g. `config set`) must use `Config::load` so it is never persisted.
+pub(crate) fn load_config() -> anyhow::Result<Config> {
+    let mut config = Config::load()?;
+    if let Some(dir) = game_dir_override() {
+        config.game.directory = Some(dir.to_string_lossy().to_string());
+    }
+    Ok(config)
+}
+
+/// Print a command error (unless the command already reported it) and pick
+/// the exit code for it
+fn report_error(error: &anyhow::Error) -> CliExit {
+    if error.downcast_ref::<CliExit>().is_none() {
+        output::print_error(&format!("{:#}", error));
+    }
+    CliExit::from_error(error)
+}
+
+/// Run the CLI with parsed arguments, returning the process exit code
+pub async fn run(cli: Cli) -> CliExit {
+    match run_command(cli).await {
+        Ok(()) => CliExit::Success,
+        Err(e) => report_error(&e),
+    }
+}
+
+async fn run_command(cli: Cli) -> anyhow::Result<()> {
+    set_game_dir_override(cli.game_dir);
+    if cli.config.is_some() {
+        Config::set_path_override(cli.config);
+    }
+
     let format = cli.output.format();
     let quiet = cli.output.quiet;
+    let dry_run = cli.dry_run;
+
+    if dry_run && !cli.command.supports_dry_run() {
+        anyhow::bail!(DRY_RUN_UNSUPPORTED);
+    }
 
     match cli.command {
         Commands::Game { command } => commands::game::run(command, format, quiet).await,
-        Commands::Backup { command } => commands::backup::run(command, format, quiet).await,
-        Commands::Update { command } => commands::update::run(command, format, quiet).await,
-        Commands::Soundpack { command } => commands::soundpack::run(command, format, quiet).await,
+        Commands::Backup { command } => {
+            commands::backup::run(command, format, quiet, dry_run).await
+        }
+        Commands::Update { command } => {
+            commands::update::run(command, format, quiet, dry_run).await
+        }
+        Commands::Soundpack { command } => {
+            commands::soundpack::run(command, format, quiet, dry_run).await
+        }
         Commands::Config { command } => commands::config::run(command, format, quiet).await,
-        Commands::Diag { command } => commands::diag::run(command, format, quiet).await,
+        Commands::Diag { command } => commands::diag::run(command, format, quiet, dry_run).await,
         Commands::Shell => shell::run().await,
     }
 }
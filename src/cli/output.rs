@@ -10,7 +10,23 @@ pub enum OutputFormat {
     Json,
 }
 
-/// Print a serializable value as JSON or use custom text formatter
+/// Version of the JSON output shape. Bump whenever a command's JSON fields
+/// change incompatibly (renamed, removed, or retyped).
+///
+/// - 2: `backup list` timestamps are RFC 3339; entries gain `path` and
+///   `compression_ratio`
+pub const JSON_SCHEMA_VERSION: u32 = 2;
+
+/// Envelope around every JSON response
+#[derive(Serialize)]
+struct JsonEnvelope<'a, T> {
+    schema_version: u32,
+    data: &'a T,
+}
+
+/// Print a serializable value as JSON or use custom text formatter.
+///
+/// JSON output is wrapped as `{ "schema_version": .., "data": .. }`.
 pub fn print_formatted<T, F>(value: &T, format: OutputFormat, text_formatter: F)
 where
     T: Serialize,
@@ -19,7 +35,11 @@ where
     match format {
         OutputFormat::Text => println!("{}", text_formatter(value)),
         OutputFormat::Json => {
-            if let Ok(json) = serde_json::to_string_pretty(value) {
+            let envelope = JsonEnvelope {
+                schema_version: JSON_SCHEMA_VERSION,
+                data: value,
+            };
+            if let Ok(json) = serde_json::to_string_pretty(&envelope) {
                 println!("{}", json);
             }
         }
@@ -48,3 +68,41 @@ pub fn stderr_is_tty() -> bool {
 pub fn should_show_progress(quiet: bool, format: OutputFormat) -> bool {
     !quiet && format == OutputFormat::Text && stderr_is_tty()
 }
+
+/// What a destructive command would do, printed instead of acting under `--dry-run`
+#[derive(Debug, Serialize)]
+pub struct DryRunPlan {
+    dry_run: bool,
+    action: String,
+    details: Vec<String>,
+}
+
+impl DryRunPlan {
+    /// Start a plan for `action` (e.g. "delete backup: auto_20250101")
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            dry_run: true,
+            action: action.into(),
+            details: Vec::new(),
+        }
+    }
+
+    /// Add one step or fact to the plan
+    pub fn detail(&mut self, detail: impl Into<String>) {
+        self.details.push(detail.into());
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("Dry run - would {}", self.action);
+        for detail in &self.details {
+            text.push_str("\n  ");
+            text.push_str(detail);
+        }
+        text
+    }
+}
+
+/// Print a dry-run plan as JSON or text (never suppressed by `--quiet`)
+pub fn print_dry_run(plan: &DryRunPlan, format: OutputFormat) {
+    print_formatted(plan, format, DryRunPlan::to_text);
+}
@@ -12,7 +12,9 @@ use rustyline::validate::Validator;
 use rustyline::{Config, Editor, Helper};
 
 use super::commands;
-use super::{Cli, Commands};
+use super::{
+    Cli, Commands, DRY_RUN_UNSUPPORTED, game_dir_override, report_error, set_game_dir_override,
+};
 
 /// Command completer for the shell
 #[derive(Default)]
@@ -24,14 +26,17 @@ impl ShellCompleter {
     fn new() -> Self {
         Self {
             commands: vec![
-                ("game", vec!["detect", "launch", "info"]),
+                (
+                    "game",
+                    vec!["detect", "launch", "info", "export", "snapshot", "verify"],
+                ),
                 (
                     "backup",
-                    vec!["list", "create", "restore", "delete", "verify"],
+                    vec!["list", "create", "restore", "delete", "verify", "diff"],
                 ),
                 (
                     "update",
-                    vec!["check", "releases", "download", "install", "apply"],
+                    vec!["check", "releases", "download", "install", "apply", "self"],
                 ),
                 (
                     "soundpack",
@@ -45,7 +50,10 @@ impl ShellCompleter {
                     ],
                 ),
                 ("config", vec!["show", "get", "set", "path"]),
-                ("diag", vec!["paths", "check", "clear-cache"]),
+                (
+                    "diag",
+                    vec!["paths", "check", "clear-cache", "env", "archive-preview"],
+                ),
                 ("help", vec![]),
                 ("exit", vec![]),
                 ("quit", vec![]),
@@ -224,20 +232,46 @@ async fn run_command(args: Vec<String>) -> Result<bool> {
         return Ok(true);
     }
 
+    if cli.dry_run && !cli.command.supports_dry_run() {
+        println!("Error: {}", DRY_RUN_UNSUPPORTED);
+        return Ok(true);
+    }
+
+    // A per-command --game-dir applies to this command only
+    let session_game_dir = game_dir_override();
+    if cli.game_dir.is_some() {
+        set_game_dir_override(cli.game_dir.clone());
+    }
+    // Likewise a per-command --config
+    let session_config = crate::config::Config::path_override();
+    if cli.config.is_some() {
+        crate::config::Config::set_path_override(cli.config.clone());
+    }
+
     // Dispatch to the appropriate command handler
     let format = cli.output.format();
     let quiet = cli.output.quiet;
+    let dry_run = cli.dry_run;
 
     let result = match cli.command {
         Commands::Game { command } => commands::game::run(command, format, quiet).await,
-        Commands::Backup { command } => commands::backup::run(command, format, quiet).await,
-        Commands::Update { command } => commands::update::run(command, format, quiet).await,
-        Commands::Soundpack { command } => commands::soundpack::run(command, format, quiet).await,
+        Commands::Backup { command } => {
+            commands::backup::run(command, format, quiet, dry_run).await
+        }
+        Commands::Update { command } => {
+            commands::update::run(command, format, quiet, dry_run).await
+        }
+        Commands::Soundpack { command } => {
+            commands::soundpack::run(command, format, quiet, dry_run).await
+        }
         Commands::Config { command } => commands::config::run(command, format, quiet).await,
-        Commands::Diag { command } => commands::diag::run(command, format, quiet).await,
+        Commands::Diag { command } => commands::diag::run(command, format, quiet, dry_run).await,
         Commands::Shell => unreachable!(), // Already handled above
     };
 
+    set_game_dir_override(session_game_dir);
+    crate::config::Config::set_path_override(session_config);
+
     result?;
     Ok(true)
 }
@@ -250,18 +284,24 @@ Commands:
   game detect              Detect installed game version
   game launch [params]     Launch the game
   game info                Show detailed game information
+  game export              Export user data for external builds
+  game snapshot            Record file hashes as an integrity baseline
+  game verify              Report files changed since the snapshot
 
   backup list              List all backups
   backup create [name]     Create a new backup
-  backup restore <name>    Restore a backup
+  backup restore <name>    Restore a backup (--target <dir> for another install)
   backup delete <name>     Delete a backup
-  backup verify [name]     Verify backup integrity
+  backup verify [name]     Verify backup integrity (all if no name)
+  backup diff <a> <b>      Compare two backups
 
   update check             Check for available updates
   update releases          List available releases
   update download          Download an update
   update install           Install a downloaded update
+  update install --latest  Back up, download, and install the newest release
   update apply             Download and install in one step
+  update self [--check]    Update the Phoenix launcher itself
 
   soundpack list           List installed soundpacks
   soundpack available      List soundpacks in repository
@@ -278,6 +318,8 @@ Commands:
   diag paths               Show all data paths
   diag check               Verify installation health
   diag clear-cache         Clear the version hash cache
+  diag env                 Show runtime environment for bug reports
+  diag archive-preview     Show what the next update will archive or skip
 
   help                     Show this help
   exit, quit               Exit the shell
@@ -285,14 +327,16 @@ Commands:
 Flags (can be added to any command):
   --json                   Output in JSON format
   --quiet, -q              Suppress non-essential output
+  --dry-run                Show what delete/restore/install/apply/clear-cache would do
 "#
     );
 }
 
 /// Get the history file path
 fn history_path() -> Option<std::path::PathBuf> {
-    directories::ProjectDirs::from("com", "phoenix", "Phoenix")
-        .map(|dirs| dirs.data_dir().join("shell_history"))
+    crate::config::Config::data_dir()
+        .ok()
+        .map(|dir| dir.join("shell_history"))
 }
 
 /// Run the interactive shell
@@ -327,7 +371,9 @@ pub async fn run() -> Result<()> {
                 match run_command(args).await {
                     Ok(true) => continue, // Command succeeded, keep running
                     Ok(false) => break,   // Exit requested, break to save history
-                    Err(e) => eprintln!("Error: {}", e),
+                    Err(e) => {
+                        report_error(&e);
+                    }
                 }
             }
             Err(ReadlineError::Interrupted) => {
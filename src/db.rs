@@ -12,10 +12,11 @@
 //! enabling instant version identification.
 
 use anyhow::Result;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use std::path::PathBuf;
 
 use crate::app_data::stable_versions;
+use crate::integrity::FileHashes;
 
 /// Version information stored in the database
 #[derive(Debug, Clone)]
@@ -28,6 +29,49 @@ pub struct VersionInfo {
     pub released_on: Option<String>,
 }
 
+/// Stored baseline of an installation's file hashes
+#[derive(Debug, Clone)]
+pub struct InstallSnapshot {
+    /// Game version at the time of the snapshot
+    pub version: Option<String>,
+    /// When the snapshot was taken (SQLite datetime, UTC)
+    pub created_on: String,
+    /// File hashes keyed by relative path
+    pub files: FileHashes,
+}
+
+/// Cached content scan of a mod, tileset or soundpack directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanCacheEntry {
+    /// What was scanned ("mod", "tileset", "soundpack" or "soundpack_files"),
+    /// tagged with the scan format version (e.g. "mod@2")
+    pub kind: String,
+    /// Directory path relative to the installation
+    pub path: String,
+    /// Modification stamp of the directory when it was scanned
+    pub stamp: i64,
+    /// Scan result as JSON
+    pub data: String,
+}
+
+/// Aggregate play time statistics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Total play time across all sessions, in seconds
+    pub total_secs: u64,
+    /// Number of sessions started in the last 7 days
+    pub sessions_this_week: u32,
+    /// Game version played in the most recent session
+    pub last_version: Option<String>,
+}
+
+impl SessionStats {
+    /// Total play time in hours
+    pub fn total_hours(&self) -> f64 {
+        self.total_secs as f64 / 3600.0
+    }
+}
+
 /// Database manager for caching version information
 pub struct Database {
     conn: Connection,
@@ -36,13 +80,7 @@ pub struct Database {
 impl Database {
     /// Get the database file path
     pub fn db_path() -> Result<PathBuf> {
-        let dirs = directories::ProjectDirs::from("com", "phoenix", "Phoenix")
-            .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
-
-        let data_dir = dirs.data_dir();
-        std::fs::create_dir_all(data_dir)?;
-
-        Ok(data_dir.join("phoenix.db"))
+        Ok(crate::config::Config::data_dir()?.join("phoenix.db"))
     }
 
     /// Open or create the database
@@ -57,6 +95,16 @@ impl Database {
         Ok(db)
     }
 
+    /// Open a fresh in-memory database (for tests in other modules)
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        let db = Self {
+            conn: Connection::open_in_memory()?,
+        };
+        db.init_schema()?;
+        Ok(db)
+    }
+
     /// Initialize the database schema
     fn init_schema(&self) -> Result<()> {
         self.conn.execute_batch(
@@ -86,6 +134,48 @@ impl Database {
                 body TEXT NOT NULL,
                 fetched_on TEXT NOT NULL DEFAULT (datetime('now'))
             );
+
+            -- Baseline file hashes of a game installation (for drift detection)
+            CREATE TABLE IF NOT EXISTS install_snapshots (
+                game_dir TEXT PRIMARY KEY,
+                version TEXT,
+                created_on TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS install_snapshot_files (
+                game_dir TEXT NOT NULL,
+                path TEXT NOT NULL,
+                sha256 TEXT NOT NULL,
+                PRIMARY KEY (game_dir, path)
+            );
+
+            -- Game sessions tracked while the launcher stays open (Unix timestamps)
+            CREATE TABLE IF NOT EXISTS play_sessions (
+                id INTEGER PRIMARY KEY,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER NOT NULL,
+                duration_secs INTEGER NOT NULL,
+                version TEXT
+            );
+
+            -- Most recent launch per game directory (for save compatibility warnings)
+            CREATE TABLE IF NOT EXISTS game_launches (
+                game_dir TEXT PRIMARY KEY,
+                version TEXT NOT NULL,
+                stable INTEGER NOT NULL,
+                released_on TEXT,
+                launched_at INTEGER NOT NULL
+            );
+
+            -- Parsed mod/tileset/soundpack scans for migration planning, keyed by
+            -- path within the installation and invalidated by modification stamp
+            CREATE TABLE IF NOT EXISTS content_scan_cache (
+                kind TEXT NOT NULL,
+                path TEXT NOT NULL,
+                stamp INTEGER NOT NULL,
+                data TEXT NOT NULL,
+                PRIMARY KEY (kind, path)
+            );
             ",
         )?;
         Ok(())
@@ -150,6 +240,40 @@ impl Database {
         Ok(())
     }
 
+    /// Load every cached content scan
+    pub fn load_scan_cache(&self) -> Result<Vec<ScanCacheEntry>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT kind, path, stamp, data FROM content_scan_cache")?;
+        let entries = stmt
+            .query_map([], |row| {
+                Ok(ScanCacheEntry {
+                    kind: row.get(0)?,
+                    path: row.get(1)?,
+                    stamp: row.get(2)?,
+                    data: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(entries)
+    }
+
+    /// Store content scans, replacing any entries for the same paths
+    pub fn store_scan_cache(&self, entries: &[ScanCacheEntry]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO content_scan_cache (kind, path, stamp, data)
+                 VALUES (?, ?, ?, ?)",
+            )?;
+            for entry in entries {
+                stmt.execute(params![entry.kind, entry.path, entry.stamp, entry.data])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     /// Count the number of cached version entries
     pub fn count_cached_versions(&self) -> Result<usize> {
         let count: i64 = self
@@ -189,6 +313,139 @@ impl Database {
         )?;
         Ok(())
     }
+
+    /// Store a baseline snapshot for a game directory, replacing any previous one
+    pub fn store_install_snapshot(
+        &self,
+        game_dir: &str,
+        version: Option<&str>,
+        files: &FileHashes,
+    ) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM install_snapshot_files WHERE game_dir = ?",
+            params![game_dir],
+        )?;
+        tx.execute(
+            "INSERT OR REPLACE INTO install_snapshots (game_dir, version) VALUES (?, ?)",
+            params![game_dir, version],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO install_snapshot_files (game_dir, path, sha256) VALUES (?, ?, ?)",
+            )?;
+            for (path, sha256) in files {
+                stmt.execute(params![game_dir, path, sha256])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Get the baseline snapshot for a game directory
+    pub fn get_install_snapshot(&self, game_dir: &str) -> Result<Option<InstallSnapshot>> {
+        let header = self
+            .conn
+            .query_row(
+                "SELECT version, created_on FROM install_snapshots WHERE game_dir = ?",
+                params![game_dir],
+                |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+
+        let Some((version, created_on)) = header else {
+            return Ok(None);
+        };
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, sha256 FROM install_snapshot_files WHERE game_dir = ?")?;
+        let files = stmt
+            .query_map(params![game_dir], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<FileHashes>>()?;
+
+        Ok(Some(InstallSnapshot {
+            version,
+            created_on,
+            files,
+        }))
+    }
+
+    /// Record a finished game session (start and end as Unix timestamps)
+    pub fn record_session(
+        &self,
+        started_at: i64,
+        ended_at: i64,
+        version: Option<&str>,
+    ) -> Result<()> {
+        let duration = (ended_at - started_at).max(0);
+        self.conn.execute(
+            "INSERT INTO play_sessions (started_at, ended_at, duration_secs, version) VALUES (?, ?, ?, ?)",
+            params![started_at, ended_at, duration, version],
+        )?;
+        Ok(())
+    }
+
+    /// Remember the version launched from a game directory (Unix timestamp)
+    pub fn record_launch(&self, game_dir: &str, version: &VersionInfo, at: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO game_launches (game_dir, version, stable, released_on, launched_at)
+             VALUES (?, ?, ?, ?, ?)",
+            params![
+                game_dir,
+                version.version,
+                version.stable,
+                version.released_on,
+                at
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get the version most recently launched from a game directory
+    pub fn last_launch(&self, game_dir: &str) -> Result<Option<VersionInfo>> {
+        let version = self
+            .conn
+            .query_row(
+                "SELECT version, stable, released_on FROM game_launches WHERE game_dir = ?",
+                params![game_dir],
+                |row| {
+                    Ok(VersionInfo {
+                        version: row.get(0)?,
+                        stable: row.get(1)?,
+                        released_on: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(version)
+    }
+
+    /// Summarize recorded play sessions as of `now` (Unix timestamp)
+    pub fn session_stats(&self, now: i64) -> Result<SessionStats> {
+        let week_ago = now - 7 * 24 * 60 * 60;
+        let (total_secs, sessions_this_week) = self.conn.query_row(
+            "SELECT COALESCE(SUM(duration_secs), 0), COUNT(CASE WHEN started_at >= ? THEN 1 END)
+             FROM play_sessions",
+            params![week_ago],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, u32>(1)?)),
+        )?;
+        let last_version = self
+            .conn
+            .query_row(
+                "SELECT version FROM play_sessions ORDER BY ended_at DESC, id DESC LIMIT 1",
+                [],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?
+            .flatten();
+
+        Ok(SessionStats {
+            total_secs: total_secs.max(0) as u64,
+            sessions_this_week,
+            last_version,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -250,4 +507,110 @@ mod tests {
         let result = db.get_changelog("0.H-RELEASE").unwrap();
         assert_eq!(result.unwrap(), new_body);
     }
+
+    #[test]
+    fn test_install_snapshot_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        let db = Database { conn };
+        db.init_schema().unwrap();
+
+        assert!(db.get_install_snapshot("/games/cdda").unwrap().is_none());
+
+        let mut files = FileHashes::new();
+        files.insert("cataclysm-tiles.exe".to_string(), "abc".to_string());
+        files.insert("data/json/items.json".to_string(), "def".to_string());
+        db.store_install_snapshot("/games/cdda", Some("0.H"), &files)
+            .unwrap();
+
+        let snapshot = db.get_install_snapshot("/games/cdda").unwrap().unwrap();
+        assert_eq!(snapshot.version.as_deref(), Some("0.H"));
+        assert_eq!(snapshot.files, files);
+
+        // A new snapshot replaces the old file list
+        files.remove("cataclysm-tiles.exe");
+        db.store_install_snapshot("/games/cdda", None, &files)
+            .unwrap();
+        let snapshot = db.get_install_snapshot("/games/cdda").unwrap().unwrap();
+        assert_eq!(snapshot.files.len(), 1);
+        assert!(snapshot.version.is_none());
+    }
+
+    #[test]
+    fn test_session_stats() {
+        let conn = Connection::open_in_memory().unwrap();
+        let db = Database { conn };
+        db.init_schema().unwrap();
+
+        assert_eq!(db.session_stats(0).unwrap(), SessionStats::default());
+
+        let now = 100 * 24 * 60 * 60;
+        let day = 24 * 60 * 60;
+        db.record_session(now - 30 * day, now - 30 * day + 3600, Some("0.G"))
+            .unwrap();
+        db.record_session(now - day, now - day + 1800, Some("0.H"))
+            .unwrap();
+        db.record_session(now - 600, now, None).unwrap();
+
+        let stats = db.session_stats(now).unwrap();
+        assert_eq!(stats.total_secs, 3600 + 1800 + 600);
+        assert_eq!(stats.sessions_this_week, 2);
+        assert_eq!(stats.last_version, None);
+
+        db.record_session(now, now + 60, Some("0.H")).unwrap();
+        let stats = db.session_stats(now).unwrap();
+        assert_eq!(stats.last_version.as_deref(), Some("0.H"));
+    }
+
+    #[test]
+    fn test_last_launch_per_directory() {
+        let conn = Connection::open_in_memory().unwrap();
+        let db = Database { conn };
+        db.init_schema().unwrap();
+
+        assert!(db.last_launch("C:\\Games\\CDDA").unwrap().is_none());
+
+        let stable = VersionInfo {
+            version: "0.H".to_string(),
+            stable: true,
+            released_on: None,
+        };
+        let experimental = VersionInfo {
+            version: "2025-12-13 (302bb35)".to_string(),
+            stable: false,
+            released_on: Some("2025-12-13-1446".to_string()),
+        };
+        db.record_launch("C:\\Games\\CDDA", &stable, 100).unwrap();
+        db.record_launch("C:\\Games\\CDDA", &experimental, 200)
+            .unwrap();
+        db.record_launch("C:\\Games\\Other", &stable, 300).unwrap();
+
+        let last = db.last_launch("C:\\Games\\CDDA").unwrap().unwrap();
+        assert_eq!(last.version, experimental.version);
+        assert!(!last.stable);
+        assert_eq!(last.released_on, experimental.released_on);
+        assert!(db.last_launch("C:\\Games\\Other").unwrap().unwrap().stable);
+    }
+
+    #[test]
+    fn test_scan_cache_replaces_entries() {
+        let db = Database::open_in_memory().unwrap();
+        assert!(db.load_scan_cache().unwrap().is_empty());
+
+        let entry = |kind: &str, stamp, data: &str| ScanCacheEntry {
+            kind: kind.to_string(),
+            path: "data/mods/foo".to_string(),
+            stamp,
+            data: data.to_string(),
+        };
+        db.store_scan_cache(&[entry("mod", 1, "{}"), entry("tileset", 1, "null")])
+            .unwrap();
+        db.store_scan_cache(&[entry("mod", 2, "null")]).unwrap();
+
+        let mut entries = db.load_scan_cache().unwrap();
+        entries.sort_by(|a, b| a.kind.cmp(&b.kind));
+        assert_eq!(
+            entries,
+            vec![entry("mod", 2, "null"), entry("tileset", 1, "null")]
+        );
+    }
 }
@@ -0,0 +1,126 @@
+//! Shareable diagnostics report for bug reports.
+//!
+//! Collects the app version, platform, config, detected game, GitHub rate
+//! limit, and the tail of the in-memory log into one markdown block. The
+//! user's home directory is replaced with `~` throughout so reports don't
+//! leak account names.
+
+use std::path::Path;
+
+use crate::config::Config;
+use crate::game::GameInfo;
+use crate::github::RateLimitInfo;
+
+/// Number of log lines included in the report
+const LOG_TAIL_LINES: usize = 50;
+
+/// Replace the home directory in `text` with `~`.
+///
+/// Also matches the backslash-escaped form used inside TOML strings.
+fn redact_home(text: &str, home: Option<&Path>) -> String {
+    let Some(home) = home.map(|h| h.to_string_lossy().to_string()) else {
+        return text.to_string();
+    };
+    if home.is_empty() {
+        return text.to_string();
+    }
+
+    text.replace(&home.replace('\\', "\\\\"), "~")
+        .replace(&home, "~")
+}
+
+/// Build the diagnostics report as markdown
+pub fn build_report(
+    config: &Config,
+    game_info: Option<&GameInfo>,
+    rate_limit: &RateLimitInfo,
+    log_lines: &[String],
+) -> String {
+    let home = directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    redact_home(
+        &format_report(config, game_info, rate_limit, log_lines),
+        home.as_deref(),
+    )
+}
+
+/// Format the report without redaction
+fn format_report(
+    config: &Config,
+    game_info: Option<&GameInfo>,
+    rate_limit: &RateLimitInfo,
+    log_lines: &[String],
+) -> String {
+    let game = match game_info {
+        Some(info) => format!(
+            "{} ({})",
+            info.version_display(),
+            if info.is_stable() {
+                "stable"
+            } else {
+                "experimental"
+            }
+        ),
+        None => "not detected".to_string(),
+    };
+
+    let rate_limit = match (rate_limit.remaining, rate_limit.reset_in_minutes()) {
+        (Some(remaining), Some(reset)) => {
+            format!("{} remaining (resets in {} min)", remaining, reset)
+        }
+        (Some(remaining), None) => format!("{} remaining", remaining),
+        _ => "unknown".to_string(),
+    };
+
+    let config_toml = toml::to_string_pretty(&config.redacted())
+        .unwrap_or_else(|e| format!("# Failed to serialize config: {}", e));
+
+    let tail = &log_lines[log_lines.len().saturating_sub(LOG_TAIL_LINES)..];
+
+    let mut report = String::from("## Phoenix diagnostics\n\n");
+    report.push_str(&format!("- **Phoenix:** {}\n", env!("CARGO_PKG_VERSION")));
+    report.push_str(&format!(
+        "- **OS:** {} ({})\n",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    ));
+    report.push_str(&format!("- **Game:** {}\n", game));
+    report.push_str(&format!(
+        "- **Game directory:** {}\n",
+        config.game.directory.as_deref().unwrap_or("not configured")
+    ));
+    report.push_str(&format!("- **GitHub rate limit:** {}\n", rate_limit));
+
+    report.push_str("\n### Config\n\n```toml\n");
+    report.push_str(config_toml.trim_end());
+    report.push_str("\n```\n");
+
+    report.push_str(&format!("\n### Recent log ({} lines)\n\n```\n", tail.len()));
+    report.push_str(&tail.join("\n"));
+    report.push_str("\n```\n");
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_redacts_home_directory() {
+        let home = Path::new("/home/alice");
+        let mut config = Config::default();
+        config.game.directory = Some("/home/alice/games/cdda".to_string());
+        let logs = vec!["INFO loaded /home/alice/games/cdda".to_string()];
+
+        let report = redact_home(
+            &format_report(&config, None, &RateLimitInfo::default(), &logs),
+            Some(home),
+        );
+
+        assert!(!report.contains("alice"));
+        assert!(report.contains("- **Game directory:** ~/games/cdda"));
+        assert!(report.contains("INFO loaded ~/games/cdda"));
+        assert!(report.contains("### Config"));
+        assert!(report.contains("- **Game:** not detected"));
+    }
+}
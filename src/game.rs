@@ -3,11 +3,16 @@
 //! This module handles:
 //!
 //! - Detecting CDDA installations by finding game executables
-//! - Identifying game versions through multiple strategies:
+//! - Identifying game versions through multiple strategies, in order of
+//!   precedence:
 //!   1. SHA256 hash lookup (instant for known stable versions)
 //!   2. Database cache lookup (fast for previously seen builds)
 //!   3. VERSION.txt parsing (fallback for experimental builds)
-//! - Launching the game with optional command-line parameters
+//!   4. version.json parsing (for builds that ship it instead)
+//!   5. The executable's embedded version resource (Windows only, for
+//!      manual builds with neither file)
+//! - Launching the game with optional command-line parameters, or with
+//!   output captured for diagnosing startup failures
 //! - Calculating save directory sizes
 //!
 //! The version detection uses a 3-tier optimization to minimize disk I/O
@@ -17,7 +22,11 @@
 
 use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::app_data::game_config;
 use crate::db::{Database, VersionInfo};
@@ -67,8 +76,8 @@ pub fn detect_game_fast(directory: &Path) -> Result<Option<GameInfo>> {
         return Ok(None);
     };
 
-    // Try VERSION.txt (fast path)
-    let version_info = read_version_txt(directory, config);
+    // Try the version files (fast path)
+    let version_info = read_version_file(directory, config);
 
     // Calculate saves size
     let saves_dir = directory.join(&config.directories.save);
@@ -111,6 +120,10 @@ pub fn refine_version_with_hash(info: &GameInfo, db: Option<&Database>) -> Resul
     // If we found a stable version, use that; otherwise keep original
     let version_info = stable_version_info.or_else(|| info.version_info.clone());
 
+    // Last resort for builds without version files: the executable's version resource
+    #[cfg(windows)]
+    let version_info = version_info.or_else(|| read_executable_version(executable));
+
     Ok(GameInfo {
         executable: info.executable.clone(),
         version_info,
@@ -175,8 +188,19 @@ fn get_or_calculate_sha256(executable: &Path, db: Option<&Database>) -> Result<S
     Ok(sha256)
 }
 
+/// Read version info from VERSION.txt, falling back to version.json
+pub(crate) fn read_version_file(
+    directory: &Path,
+    config: &crate::app_data::GameConfig,
+) -> Option<VersionInfo> {
+    read_version_txt(directory, config).or_else(|| read_version_json(directory, config))
+}
+
 /// Read version info from VERSION.txt file (fallback for experimental builds)
-fn read_version_txt(directory: &Path, config: &crate::app_data::GameConfig) -> Option<VersionInfo> {
+pub(crate) fn read_version_txt(
+    directory: &Path,
+    config: &crate::app_data::GameConfig,
+) -> Option<VersionInfo> {
     let version_file = directory.join(&config.version.filename);
     if !version_file.exists() {
         return None;
@@ -208,6 +232,50 @@ fn read_version_txt(directory: &Path, config: &crate::app_data::GameConfig) -> O
     // Need at least the SHA to identify the version
     let sha = commit_sha?;
 
+    Some(version_from_commit(sha, build_number, commit_date, config))
+}
+
+/// Read version info from version.json.
+///
+/// Uses the same fields as VERSION.txt; without a commit sha, a plain
+/// `version` string is shown as is.
+fn read_version_json(
+    directory: &Path,
+    config: &crate::app_data::GameConfig,
+) -> Option<VersionInfo> {
+    let content = std::fs::read_to_string(directory.join(&config.version.json_filename)).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let field = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+
+    let build_number = field("build_number");
+    let commit_date = field("commit_date");
+    let sha = field("commit_sha").and_then(|sha| {
+        sha.get(..config.version.sha_display_length)
+            .map(str::to_string)
+    });
+    match sha {
+        Some(sha) => Some(version_from_commit(sha, build_number, commit_date, config)),
+        _ => Some(VersionInfo {
+            version: field("version")?,
+            stable: false,
+            released_on: build_number.or(commit_date),
+        }),
+    }
+}
+
+/// Build version info from a (truncated) commit sha and optional build number and date
+fn version_from_commit(
+    sha: String,
+    build_number: Option<String>,
+    commit_date: Option<String>,
+    config: &crate::app_data::GameConfig,
+) -> VersionInfo {
     // Build number format: "2025-12-13-1446" (YYYY-MM-DD-HHMM)
     // Extract date for display, but keep full build number for comparison
     let display_date = if let Some(ref bn) = build_number {
@@ -238,10 +306,57 @@ fn read_version_txt(directory: &Path, config: &crate::app_data::GameConfig) -> O
 
     // Store the full build number for precise version comparison
     // This allows distinguishing between multiple builds on the same day
-    Some(VersionInfo {
+    VersionInfo {
         version,
         stable: false,
         released_on: build_number.or(commit_date), // Prefer full build number
+    }
+}
+
+/// Read the product (or file) version from the executable's version resource
+#[cfg(windows)]
+fn read_executable_version(executable: &Path) -> Option<VersionInfo> {
+    let bytes = std::fs::read(executable).ok()?;
+    let version = parse_version_resource(&bytes)?;
+    tracing::debug!("Read version {} from {}", version, executable.display());
+    Some(VersionInfo {
+        version,
+        stable: false,
+        released_on: None,
+    })
+}
+
+/// Find the ProductVersion (or FileVersion) string in a PE version resource.
+///
+/// Each entry of the resource's string table is laid out as `wLength`,
+/// `wValueLength`, `wType`, the UTF-16 key, padding to a 32-bit boundary,
+/// then the UTF-16 value.
+#[cfg(any(windows, test))]
+fn parse_version_resource(bytes: &[u8]) -> Option<String> {
+    let utf16 = |s: &str| -> Vec<u8> {
+        s.encode_utf16()
+            .chain(std::iter::once(0))
+            .flat_map(u16::to_le_bytes)
+            .collect()
+    };
+
+    ["ProductVersion", "FileVersion"].iter().find_map(|key| {
+        let needle = utf16(key);
+        let key_pos = bytes.windows(needle.len()).position(|w| w == needle)?;
+        let start = key_pos.checked_sub(6)?;
+        let value_len = u16::from_le_bytes([bytes[start + 2], bytes[start + 3]]) as usize;
+        let value_start = start + (key_pos + needle.len() - start).next_multiple_of(4);
+
+        let units: Vec<u16> = bytes
+            .get(value_start..)?
+            .chunks_exact(2)
+            .take(value_len)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .take_while(|&c| c != 0)
+            .collect();
+        let value = String::from_utf16(&units).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
     })
 }
 
@@ -273,10 +388,159 @@ pub fn calculate_dir_size(path: &Path) -> Result<u64> {
     Ok(total)
 }
 
+/// How long a diagnostic launch waits for the game to exit
+const DIAGNOSTIC_WAIT: Duration = Duration::from_secs(10);
+
+/// Maximum output lines kept from a diagnostic launch
+const DIAGNOSTIC_MAX_LINES: usize = 50;
+
+/// Result of a diagnostic launch
+#[derive(Debug, Clone, Default)]
+pub struct LaunchDiagnostics {
+    /// Exit code, if the game exited normally within the wait
+    pub exit_code: Option<i32>,
+    /// Whether the game was still running when capture stopped
+    pub still_running: bool,
+    /// First lines of stdout and stderr, in arrival order
+    pub output: Vec<String>,
+}
+
+impl LaunchDiagnostics {
+    /// One-line description of how the launch ended
+    pub fn summary(&self) -> String {
+        if self.still_running {
+            format!("Game still running after {}s", DIAGNOSTIC_WAIT.as_secs())
+        } else if let Some(code) = self.exit_code {
+            format!("Game exited with code {}", code)
+        } else {
+            "Game was terminated abnormally".to_string()
+        }
+    }
+}
+
+/// Explain why launching `current` could harm saves last played with `last`.
+///
+/// Warns when the branch differs (stable vs experimental) or when `current`
+/// is an older build of the same branch. Builds are ordered by version for
+/// stable and by build number for experimental; if either is missing, only
+/// the branch is compared.
+pub fn launch_mismatch(last: &VersionInfo, current: &VersionInfo) -> Option<String> {
+    let branch = |v: &VersionInfo| if v.stable { "stable" } else { "experimental" };
+    if last.stable != current.stable {
+        return Some(format!(
+            "Saves in this folder were last played with {} {}, but this is {} {}. \
+             Saves may not load correctly on a different branch.",
+            branch(last),
+            last.version,
+            branch(current),
+            current.version
+        ));
+    }
+
+    let order_key = |v: &VersionInfo| {
+        if v.stable {
+            Some(v.version.clone())
+        } else {
+            v.released_on.clone()
+        }
+    };
+    match (order_key(last), order_key(current)) {
+        (Some(last_key), Some(current_key)) if current_key < last_key => Some(format!(
+            "This build ({}) is older than the one saves in this folder were last played \
+             with ({}). Older builds may fail to load newer saves.",
+            current.version, last.version
+        )),
+        _ => None,
+    }
+}
+
 /// Launch the game
 pub fn launch_game(executable: &Path, params: &str) -> Result<()> {
-    use std::process::Command;
+    spawn_game(executable, params)?;
+    Ok(())
+}
 
+/// Launch the game and return its process handle for tracking the session
+pub fn spawn_game(executable: &Path, params: &str) -> Result<std::process::Child> {
+    Ok(launch_command(executable, params)?.spawn()?)
+}
+
+/// Launch the game with stdout/stderr captured.
+///
+/// Waits up to [`DIAGNOSTIC_WAIT`] for the game to exit and returns the
+/// first lines of output with the exit code. A game that is still running
+/// afterwards is left running. Blocks, so call from a blocking context.
+pub fn diagnostic_launch(executable: &Path, params: &str) -> Result<LaunchDiagnostics> {
+    let mut child = launch_command(executable, params)?
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        let lines = Arc::clone(&lines);
+        readers.push(std::thread::spawn(move || {
+            capture_lines(stdout, "", &lines)
+        }));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        let lines = Arc::clone(&lines);
+        readers.push(std::thread::spawn(move || {
+            capture_lines(stderr, "[stderr] ", &lines)
+        }));
+    }
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if start.elapsed() >= DIAGNOSTIC_WAIT {
+            break None;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    };
+
+    // Give the readers a moment to drain what the game wrote before exiting
+    if status.is_some() {
+        let drain_start = Instant::now();
+        while !readers.iter().all(|r| r.is_finished())
+            && drain_start.elapsed() < Duration::from_secs(1)
+        {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    let output = lines.lock().map(|l| l.clone()).unwrap_or_default();
+    let diagnostics = LaunchDiagnostics {
+        exit_code: status.and_then(|s| s.code()),
+        still_running: status.is_none(),
+        output,
+    };
+    tracing::info!("Diagnostic launch: {}", diagnostics.summary());
+
+    Ok(diagnostics)
+}
+
+/// Read lines from a child pipe, keeping the first few.
+///
+/// Keeps draining after the limit so the child never blocks on a full pipe.
+fn capture_lines(reader: impl Read, prefix: &str, lines: &Mutex<Vec<String>>) {
+    for line in BufReader::new(reader).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if let Ok(mut lines) = lines.lock()
+            && lines.len() < DIAGNOSTIC_MAX_LINES
+        {
+            lines.push(format!("{}{}", prefix, line));
+        }
+    }
+}
+
+/// Build the command that starts the game from its own directory
+fn launch_command(executable: &Path, params: &str) -> Result<Command> {
     // Set working directory to game directory
     let working_dir = executable
         .parent()
@@ -289,12 +553,7 @@ pub fn launch_game(executable: &Path, params: &str) -> Result<()> {
     cmd.current_dir(working_dir);
 
     // Add user params
-    if !params.is_empty() {
-        // Split params by whitespace (simple approach)
-        for param in params.split_whitespace() {
-            cmd.arg(param);
-        }
-    }
+    cmd.args(parse_params(params));
 
     tracing::info!(
         "Launching game: {:?} with working dir: {:?}",
@@ -302,9 +561,127 @@ pub fn launch_game(executable: &Path, params: &str) -> Result<()> {
         working_dir
     );
 
-    cmd.spawn()?;
+    Ok(cmd)
+}
 
-    Ok(())
+/// The command line [`launch_game`] runs, for use by external launchers.
+///
+/// Arguments containing whitespace or quotes are double-quoted so the line
+/// splits back into the same arguments. The game must still be started from
+/// the executable's directory.
+pub fn launch_command_line(executable: &Path, params: &str) -> Result<String> {
+    let working_dir = executable
+        .parent()
+        .context("Executable has no parent directory")?;
+    let target = resolve_launch_target(executable, working_dir);
+
+    let mut parts = vec![quote_arg(&target.to_string_lossy())];
+    parts.extend(parse_params(params).iter().map(|arg| quote_arg(arg)));
+    Ok(parts.join(" "))
+}
+
+/// Quote an argument for a command line if [`parse_params`] would split it
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty()
+        && !arg.ends_with('\\')
+        && !arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+    {
+        return arg.to_string();
+    }
+
+    // Backslashes only need escaping before a quote, including the closing one
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        let escaped = match c {
+            '\\' => {
+                backslashes += 1;
+                continue;
+            }
+            '"' => backslashes * 2 + 1,
+            '\'' if backslashes > 0 => backslashes + 1,
+            _ => backslashes,
+        };
+        quoted.extend(std::iter::repeat_n('\\', escaped));
+        quoted.push(c);
+        backslashes = 0;
+    }
+    quoted.extend(std::iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// Split a parameter string into arguments, shell-words style.
+///
+/// Arguments are separated by whitespace. Single or double quotes group text
+/// containing spaces into one argument (e.g. `--world "My World"`), and an
+/// empty pair of quotes yields an empty argument. A backslash escapes a
+/// following quote or whitespace character; any other backslash is kept as-is
+/// so Windows paths like `C:\Games\CDDA` pass through unchanged. Backslashes
+/// before a double quote pair up as on Windows, so `"C:\Games\\"` is
+/// `C:\Games\`.
+pub fn parse_params(params: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = params.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        // Escapes apply outside quotes and inside double quotes
+        if c == '\\' && quote != Some('\'') {
+            let mut backslashes = 1;
+            while chars.next_if_eq(&'\\').is_some() {
+                backslashes += 1;
+            }
+            in_arg = true;
+
+            if chars.peek() == Some(&'"') {
+                current.extend(std::iter::repeat_n('\\', backslashes / 2));
+                if backslashes % 2 == 1 {
+                    current.push('"');
+                    chars.next();
+                }
+                continue;
+            }
+
+            current.extend(std::iter::repeat_n('\\', backslashes - 1));
+            if let Some(&next) = chars.peek()
+                && (next == '\'' || (quote.is_none() && next.is_whitespace()))
+            {
+                current.push(next);
+                chars.next();
+            } else {
+                current.push(c);
+            }
+            continue;
+        }
+
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+    }
+
+    args
 }
 
 /// Resolve the executable/script to actually launch.
@@ -333,6 +710,20 @@ mod tests {
     use super::*;
     use std::io::Write;
 
+    #[test]
+    fn test_capture_lines_keeps_first_lines() {
+        let text = (0..DIAGNOSTIC_MAX_LINES + 10)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lines = Mutex::new(Vec::new());
+        capture_lines(text.as_bytes(), "[stderr] ", &lines);
+
+        let lines = lines.into_inner().unwrap();
+        assert_eq!(lines.len(), DIAGNOSTIC_MAX_LINES);
+        assert_eq!(lines[0], "[stderr] line 0");
+    }
+
     #[test]
     fn test_calculate_sha256() {
         // Create a temp file with known content
@@ -356,6 +747,75 @@ mod tests {
         std::fs::remove_file(&temp_file).ok();
     }
 
+    #[test]
+    fn test_launch_command_line_round_trips() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let executable = temp_dir.path().join("My Game").join("cataclysm-tiles");
+        let params = r#"--world "My World" --userdir=C:\Games\CDDA --name 'say "hi"' """#;
+
+        let line = launch_command_line(&executable, params).unwrap();
+        let mut args = parse_params(&line);
+        assert_eq!(args.remove(0), executable.to_string_lossy());
+        assert_eq!(args, parse_params(params));
+    }
+
+    #[test]
+    fn test_quote_arg_round_trips_backslashes() {
+        for arg in [
+            r"C:\My Games\",
+            r"C:\My Games\\",
+            r"C:\Games\",
+            r"\\server\share dir\",
+            r#"say \"hi\""#,
+            r"it\'s here",
+            "Bob's World",
+        ] {
+            assert_eq!(
+                parse_params(&quote_arg(arg)),
+                vec![arg],
+                "{}",
+                quote_arg(arg)
+            );
+        }
+        assert_eq!(quote_arg(r"C:\My Games\"), r#""C:\My Games\\""#);
+    }
+
+    #[test]
+    fn test_parse_params_quoted() {
+        assert_eq!(
+            parse_params(r#"--world "My World" --worldmenu"#),
+            vec!["--world", "My World", "--worldmenu"]
+        );
+        assert_eq!(
+            parse_params("--world 'My World'"),
+            vec!["--world", "My World"]
+        );
+        // Quotes can join with adjacent text
+        assert_eq!(
+            parse_params(r#"--userdir="C:/My Games/CDDA""#),
+            vec!["--userdir=C:/My Games/CDDA"]
+        );
+    }
+
+    #[test]
+    fn test_parse_params_empty() {
+        assert!(parse_params("").is_empty());
+        assert!(parse_params("   ").is_empty());
+        // An explicit empty string is preserved as an argument
+        assert_eq!(parse_params(r#"--world """#), vec!["--world", ""]);
+    }
+
+    #[test]
+    fn test_parse_params_escapes() {
+        assert_eq!(parse_params(r"My\ World"), vec!["My World"]);
+        assert_eq!(parse_params(r#""say \"hi\"""#), vec![r#"say "hi""#]);
+        // Backslashes in Windows paths are kept
+        assert_eq!(
+            parse_params(r"--userdir C:\Games\CDDA"),
+            vec!["--userdir", r"C:\Games\CDDA"]
+        );
+    }
+
     #[test]
     fn test_calculate_dir_size() {
         // Create a temp directory with some files
@@ -500,6 +960,71 @@ mod tests {
         std::fs::remove_dir_all(&temp_dir).ok();
     }
 
+    #[test]
+    fn test_read_version_json() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = game_config();
+
+        std::fs::write(
+            temp_dir.path().join("version.json"),
+            r#"{"build_number": "2025-12-13-1446", "commit_sha": "302bb35a02fa115e"}"#,
+        )
+        .unwrap();
+        let info = read_version_file(temp_dir.path(), config).unwrap();
+        assert_eq!(info.version, "2025-12-13 (302bb35)");
+        assert_eq!(info.released_on, Some("2025-12-13-1446".to_string()));
+
+        // A plain version string without a commit sha
+        std::fs::write(
+            temp_dir.path().join("version.json"),
+            r#"{"version": "0.H-custom"}"#,
+        )
+        .unwrap();
+        let info = read_version_file(temp_dir.path(), config).unwrap();
+        assert_eq!(info.version, "0.H-custom");
+
+        // A sha that can't be cut to the display length falls back to it too
+        std::fs::write(
+            temp_dir.path().join("version.json"),
+            r#"{"commit_sha": "302bb3é", "version": "0.H-custom"}"#,
+        )
+        .unwrap();
+        let info = read_version_file(temp_dir.path(), config).unwrap();
+        assert_eq!(info.version, "0.H-custom");
+
+        // VERSION.txt takes precedence
+        std::fs::write(
+            temp_dir.path().join("VERSION.txt"),
+            "commit sha: abc1234567890def\ncommit date: 2024-01-15\n",
+        )
+        .unwrap();
+        let info = read_version_file(temp_dir.path(), config).unwrap();
+        assert_eq!(info.version, "2024-01-15 (abc1234)");
+    }
+
+    #[test]
+    fn test_parse_version_resource() {
+        // A String entry as it appears in a version resource's StringTable
+        let mut entry = Vec::new();
+        let key: Vec<u16> = "ProductVersion\0".encode_utf16().collect();
+        let value: Vec<u16> = "0.H-1234\0".encode_utf16().collect();
+        entry.extend_from_slice(&[0u8; 2]); // wLength (unused)
+        entry.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        entry.extend_from_slice(&1u16.to_le_bytes()); // wType: text
+        entry.extend(key.iter().flat_map(|c| c.to_le_bytes()));
+        while entry.len() % 4 != 0 {
+            entry.push(0);
+        }
+        entry.extend(value.iter().flat_map(|c| c.to_le_bytes()));
+
+        let mut bytes = vec![0xAAu8; 64];
+        bytes.extend_from_slice(&entry);
+        bytes.extend_from_slice(&[0u8; 16]);
+        assert_eq!(parse_version_resource(&bytes).as_deref(), Some("0.H-1234"));
+
+        assert_eq!(parse_version_resource(b"no version resource here"), None);
+    }
+
     #[test]
     fn test_game_info_version_display() {
         let info_with_version = GameInfo {
@@ -540,4 +1065,46 @@ mod tests {
         assert_eq!(info.version_display(), "abc1234");
         assert!(!info.is_stable());
     }
+
+    #[test]
+    fn test_launch_mismatch() {
+        let version = |version: &str, stable: bool, released_on: Option<&str>| VersionInfo {
+            version: version.to_string(),
+            stable,
+            released_on: released_on.map(str::to_string),
+        };
+        let stable_g = version("0.G", true, None);
+        let stable_h = version("0.H", true, None);
+        let older = version("2025-12-01 (abc1234)", false, Some("2025-12-01-0900"));
+        let newer = version("2025-12-13 (302bb35)", false, Some("2025-12-13-1446"));
+
+        // Same build or a newer one of the same branch is fine
+        assert!(launch_mismatch(&stable_h, &stable_h).is_none());
+        assert!(launch_mismatch(&stable_g, &stable_h).is_none());
+        assert!(launch_mismatch(&older, &newer).is_none());
+
+        // Switching branches warns either way
+        assert!(
+            launch_mismatch(&stable_h, &newer)
+                .unwrap()
+                .contains("branch")
+        );
+        assert!(
+            launch_mismatch(&newer, &stable_h)
+                .unwrap()
+                .contains("branch")
+        );
+
+        // Going back to an older build warns
+        assert!(
+            launch_mismatch(&stable_h, &stable_g)
+                .unwrap()
+                .contains("older")
+        );
+        assert!(launch_mismatch(&newer, &older).unwrap().contains("older"));
+
+        // Without a build number there's nothing to order by
+        let unknown = version("abc1234", false, None);
+        assert!(launch_mismatch(&newer, &unknown).is_none());
+    }
 }
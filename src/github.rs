@@ -14,15 +14,95 @@
 //!
 //! Rate limiting is tracked and exposed via `RateLimitInfo` for UI display.
 //!
+//! All HTTP clients (GitHub API, release, soundpack, and tileset downloads) are
+//! built through [`build_http_client`] so proxy settings apply everywhere.
+//!
 //! Configuration loaded via `app_data::launcher_config()` and `app_data::stable_releases_config()`.
 
-use anyhow::Result;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
 use serde::Deserialize;
 
 use crate::app_data::{launcher_config, stable_releases_config};
+use crate::config::NetworkConfig;
+
+/// User agent sent when `network.user_agent` isn't set (GitHub rejects
+/// API requests without one)
+pub const DEFAULT_USER_AGENT: &str = concat!("Phoenix-Launcher/", env!("CARGO_PKG_VERSION"));
+
+/// Media type for GitHub REST API responses
+const GITHUB_ACCEPT: &str = "application/vnd.github+json";
+
+/// Build an HTTP client with the configured proxy and timeouts.
+///
+/// Without a configured proxy, reqwest honors the standard `HTTP_PROXY`,
+/// `HTTPS_PROXY`, and `NO_PROXY` environment variables. The read timeout
+/// applies per read, so a stalled download fails instead of hanging while a
+/// slow but steady one still completes.
+pub fn build_http_client(network: &NetworkConfig) -> Result<reqwest::Client> {
+    let user_agent = network
+        .user_agent
+        .as_deref()
+        .map(str::trim)
+        .filter(|ua| !ua.is_empty())
+        .unwrap_or(DEFAULT_USER_AGENT);
+    let mut builder = reqwest::Client::builder()
+        .user_agent(user_agent)
+        .connect_timeout(Duration::from_secs(network.connect_timeout_secs.max(1)))
+        .read_timeout(Duration::from_secs(network.read_timeout_secs.max(1)));
+
+    if let Some(url) = network.proxy.as_deref().map(str::trim)
+        && !url.is_empty()
+    {
+        let mut proxy =
+            reqwest::Proxy::all(url).with_context(|| format!("Invalid proxy URL: {}", url))?;
+        if let Some(username) = network.proxy_username.as_deref() {
+            proxy = proxy.basic_auth(username, network.proxy_password.as_deref().unwrap_or(""));
+        }
+        tracing::info!("Using HTTP proxy {}", redact_proxy_url(url));
+        builder = builder.proxy(proxy);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Strip any `user:password@` credentials from a proxy URL for display
+pub fn redact_proxy_url(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(mut parsed) if !parsed.username().is_empty() || parsed.password().is_some() => {
+            let _ = parsed.set_username("");
+            let _ = parsed.set_password(None);
+            parsed.to_string()
+        }
+        _ => url.to_string(),
+    }
+}
+
+/// Rewrite a download URL to fetch it from a mirror.
+///
+/// The URL's scheme and host are swapped for the mirror's; a path on the
+/// mirror (e.g. `https://mirror.example.com/github`) is kept as a prefix.
+/// Returns `None` if either URL can't be parsed.
+pub fn mirror_url(url: &str, mirror: &str) -> Option<String> {
+    let url = reqwest::Url::parse(url).ok()?;
+    let mirror = reqwest::Url::parse(mirror.trim()).ok()?;
+    if !mirror.has_host() {
+        return None;
+    }
+
+    let mut mirrored = format!("{}{}", mirror.as_str().trim_end_matches('/'), url.path());
+    if let Some(query) = url.query() {
+        mirrored.push('?');
+        mirrored.push_str(query);
+    }
+    Some(mirrored)
+}
 
-/// User agent for API requests
-const USER_AGENT: &str = concat!("Phoenix-Launcher/", env!("CARGO_PKG_VERSION"));
+/// Whether `mirror` is a usable mirror base URL
+pub fn is_valid_mirror(mirror: &str) -> bool {
+    mirror_url("https://github.com/", mirror).is_some()
+}
 
 /// A GitHub release
 #[derive(Debug, Clone, Deserialize)]
@@ -34,12 +114,34 @@ pub struct Release {
     pub assets: Vec<ReleaseAsset>,
 }
 
+impl Release {
+    /// URL of the release page on GitHub
+    pub fn page_url(&self) -> String {
+        format!(
+            "https://github.com/{}/releases/tag/{}",
+            launcher_config().github.repository,
+            self.tag_name
+        )
+    }
+}
+
 /// An asset attached to a release
 #[derive(Debug, Clone, Deserialize)]
 pub struct ReleaseAsset {
     pub name: String,
     pub size: u64,
     pub browser_download_url: String,
+    /// Checksum GitHub publishes for the file, e.g. "sha256:<hex>"
+    /// (missing for assets uploaded before GitHub started computing them)
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+/// GitHub refused a request because the API rate limit is used up
+#[derive(Debug, thiserror::Error)]
+#[error("GitHub API rate limit exceeded (resets in {reset_in_minutes} min)")]
+pub struct RateLimitExceeded {
+    pub reset_in_minutes: i64,
 }
 
 /// GitHub API rate limit information
@@ -90,6 +192,9 @@ impl RateLimitInfo {
     }
 }
 
+/// Largest page size the GitHub releases API accepts
+const MAX_RELEASES_PER_PAGE: u32 = 100;
+
 /// Result of a GitHub API fetch including rate limit info
 #[derive(Debug)]
 pub struct FetchResult<T> {
@@ -101,14 +206,22 @@ pub struct FetchResult<T> {
 #[derive(Clone)]
 pub struct GitHubClient {
     client: reqwest::Client,
+    /// Mirror base URLs for release downloads
+    mirrors: Vec<String>,
 }
 
 impl GitHubClient {
-    /// Create a new GitHub API client
-    pub fn new() -> Result<Self> {
-        let client = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
-
-        Ok(Self { client })
+    /// Create a new GitHub API client using the given network settings
+    pub fn new(network: &NetworkConfig) -> Result<Self> {
+        let client = build_http_client(network)?;
+        let mirrors = network
+            .download_mirrors
+            .iter()
+            .map(|mirror| mirror.trim().to_string())
+            .filter(|mirror| !mirror.is_empty())
+            .collect();
+
+        Ok(Self { client, mirrors })
     }
 
     /// Get a reference to the underlying HTTP client
@@ -116,6 +229,35 @@ impl GitHubClient {
         &self.client
     }
 
+    /// URLs to try in order for a release download: the original URL, then
+    /// the same file on each configured mirror
+    pub fn download_urls(&self, url: &str) -> Vec<String> {
+        let mut urls = vec![url.to_string()];
+        for mirror in &self.mirrors {
+            match mirror_url(url, mirror) {
+                Some(mirrored) if !urls.contains(&mirrored) => urls.push(mirrored),
+                Some(_) => {}
+                None => tracing::warn!("Ignoring invalid download mirror: {}", mirror),
+            }
+        }
+        urls
+    }
+
+    /// Start a GET request to the GitHub API with the REST media type and
+    /// pinned API version.
+    ///
+    /// These headers go on each API request rather than the shared client,
+    /// which also downloads soundpacks and tilesets from other hosts.
+    fn api_get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client
+            .get(url)
+            .header(reqwest::header::ACCEPT, GITHUB_ACCEPT)
+            .header(
+                "X-GitHub-Api-Version",
+                &launcher_config().github.api_version,
+            )
+    }
+
     /// Fetch a release by tag name (returns None if tag doesn't exist)
     /// Also returns rate limit info from the response
     pub async fn get_release_by_tag(&self, tag: &str) -> (Option<Release>, RateLimitInfo) {
@@ -125,13 +267,7 @@ impl GitHubClient {
             github.api_base, github.repository, tag
         );
 
-        let response = match self
-            .client
-            .get(&url)
-            .header("Accept", "application/vnd.github.v3+json")
-            .send()
-            .await
-        {
+        let response = match self.api_get(&url).send().await {
             Ok(r) => r,
             Err(e) => {
                 tracing::debug!("Request failed for tag {}: {}", tag, e);
@@ -193,6 +329,7 @@ impl GitHubClient {
                         name: asset_name.clone(),
                         size: asset_size,
                         browser_download_url: asset_url.clone(),
+                        digest: None,
                     }],
                 });
             }
@@ -252,35 +389,65 @@ impl GitHubClient {
         })
     }
 
-    /// Fetch experimental releases (recent builds from releases list)
-    pub async fn get_experimental_releases(&self) -> Result<FetchResult<Vec<Release>>> {
+    /// Fetch the `count` most recent experimental releases.
+    ///
+    /// GitHub returns at most 100 releases per page, so larger counts follow
+    /// the `Link: rel="next"` header across pages. Paging stops early when the
+    /// rate limit runs low, leaving requests for the rest of the launcher; a
+    /// failed later page returns the releases fetched so far.
+    pub async fn get_experimental_releases(&self, count: u32) -> Result<FetchResult<Vec<Release>>> {
         let start = std::time::Instant::now();
         let github = &launcher_config().github;
-        let url = format!(
+        let wanted = count.max(1) as usize;
+        let mut next_url = Some(format!(
             "{}/repos/{}/releases?per_page={}",
-            github.api_base, github.repository, github.releases_per_page
-        );
+            github.api_base,
+            github.repository,
+            count.clamp(1, MAX_RELEASES_PER_PAGE)
+        ));
+        let mut releases: Vec<Release> = Vec::new();
+        let mut rate_limit = RateLimitInfo::default();
+        let mut pages = 0;
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Accept", "application/vnd.github.v3+json")
-            .send()
-            .await?;
+        while let Some(url) = next_url.take() {
+            let response = self.api_get(&url).send().await?;
 
-        // Extract rate limit info before consuming response
-        let rate_limit = RateLimitInfo::from_response(&response);
+            // Extract rate limit info before consuming response
+            rate_limit = RateLimitInfo::from_response(&response);
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            anyhow::bail!("GitHub API error: {} - {}", status, text);
+            if !response.status().is_success() {
+                let error = api_error(response, &rate_limit).await;
+                if releases.is_empty() {
+                    return Err(error);
+                }
+                tracing::warn!("Stopped fetching release pages: {}", error);
+                break;
+            }
+
+            let next = next_page_url(&response);
+            let page: Vec<Release> = response.json().await?;
+            releases.extend(page);
+            pages += 1;
+
+            if releases.len() >= wanted {
+                break;
+            }
+            if next.is_some() && rate_limit.is_low() {
+                tracing::warn!(
+                    "Rate limit low ({:?} remaining), stopping after {} releases",
+                    rate_limit.remaining,
+                    releases.len()
+                );
+                break;
+            }
+            next_url = next;
         }
 
-        let releases: Vec<Release> = response.json().await?;
+        releases.truncate(wanted);
         tracing::info!(
-            "Fetched {} experimental releases in {:.1}s",
+            "Fetched {} experimental releases ({} pages) in {:.1}s",
             releases.len(),
+            pages,
             start.elapsed().as_secs_f32()
         );
 
@@ -290,6 +457,39 @@ impl GitHubClient {
         })
     }
 
+    /// Fetch the latest published release of a repository
+    pub async fn get_latest_release(&self, repository: &str) -> Result<Release> {
+        let url = format!(
+            "{}/repos/{}/releases/latest",
+            launcher_config().github.api_base,
+            repository
+        );
+
+        let response = self.api_get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let rate_limit = RateLimitInfo::from_response(&response);
+            return Err(api_error(response, &rate_limit).await);
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Query the current API rate limit.
+    ///
+    /// The `/rate_limit` endpoint does not count against the limit itself.
+    pub async fn get_rate_limit(&self) -> Result<RateLimitInfo> {
+        let url = format!("{}/rate_limit", launcher_config().github.api_base);
+
+        let response = self.api_get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("GitHub API error: {}", response.status());
+        }
+
+        Ok(RateLimitInfo::from_response(&response))
+    }
+
     /// Find the graphical x64 asset for the current platform.
     ///
     /// Dispatches to [`find_windows_asset`](Self::find_windows_asset) or
@@ -371,6 +571,127 @@ impl GitHubClient {
 
 impl Default for GitHubClient {
     fn default() -> Self {
-        Self::new().expect("Failed to create HTTP client")
+        Self::new(&NetworkConfig::default()).expect("Failed to create HTTP client")
+    }
+}
+
+/// URL of the next page from a response's `Link` header, if there is one
+fn next_page_url(response: &reqwest::Response) -> Option<String> {
+    let link = response.headers().get(reqwest::header::LINK)?;
+    parse_next_link(link.to_str().ok()?)
+}
+
+/// Find the `rel="next"` target in a `Link` header value like
+/// `<https://...&page=2>; rel="next", <https://...&page=5>; rel="last"`
+fn parse_next_link(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        let is_next = params
+            .split(';')
+            .any(|param| param.trim() == r#"rel="next""#);
+        is_next.then(|| {
+            target
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string()
+        })
+    })
+}
+
+/// Turn an unsuccessful API response into an error, recognising rate limiting
+async fn api_error(response: reqwest::Response, rate_limit: &RateLimitInfo) -> anyhow::Error {
+    let status = response.status();
+    let rate_limited = matches!(status.as_u16(), 403 | 429) && rate_limit.remaining == Some(0);
+    if rate_limited {
+        return RateLimitExceeded {
+            reset_in_minutes: rate_limit.reset_in_minutes().unwrap_or(0),
+        }
+        .into();
+    }
+
+    let text = response.text().await.unwrap_or_default();
+    anyhow::anyhow!("GitHub API error: {} - {}", status, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_next_link() {
+        let link = r#"<https://api.github.com/repositories/1/releases?per_page=100&page=2>; rel="next", <https://api.github.com/repositories/1/releases?per_page=100&page=9>; rel="last""#;
+        assert_eq!(
+            parse_next_link(link).as_deref(),
+            Some("https://api.github.com/repositories/1/releases?per_page=100&page=2")
+        );
+
+        // The last page only links backwards
+        let last = r#"<https://api.github.com/repositories/1/releases?page=8>; rel="prev", <https://api.github.com/repositories/1/releases?page=1>; rel="first""#;
+        assert_eq!(parse_next_link(last), None);
+        assert_eq!(parse_next_link(""), None);
+    }
+
+    #[test]
+    fn test_mirror_url() {
+        let url =
+            "https://github.com/CleverRaven/Cataclysm-DDA/releases/download/0.H-RELEASE/cdda.zip";
+        assert_eq!(
+            mirror_url(url, "https://mirror.example.com").as_deref(),
+            Some(
+                "https://mirror.example.com/CleverRaven/Cataclysm-DDA/releases/download/0.H-RELEASE/cdda.zip"
+            )
+        );
+        assert_eq!(
+            mirror_url(url, " https://mirror.example.com/gh/ ").as_deref(),
+            Some(
+                "https://mirror.example.com/gh/CleverRaven/Cataclysm-DDA/releases/download/0.H-RELEASE/cdda.zip"
+            )
+        );
+        assert_eq!(
+            mirror_url("https://example.com/a.zip?x=1", "http://m.example.com:8080").as_deref(),
+            Some("http://m.example.com:8080/a.zip?x=1")
+        );
+        assert_eq!(mirror_url(url, "not a url"), None);
+        assert_eq!(mirror_url("not a url", "https://mirror.example.com"), None);
+    }
+
+    #[test]
+    fn test_download_urls_with_mirrors() {
+        let network = NetworkConfig {
+            download_mirrors: vec![
+                "https://one.example.com".to_string(),
+                " ".to_string(),
+                "bogus".to_string(),
+                "https://github.com".to_string(),
+                "https://two.example.com".to_string(),
+            ],
+            ..Default::default()
+        };
+        let client = GitHubClient::new(&network).unwrap();
+        assert_eq!(
+            client.download_urls("https://github.com/o/r/a.zip"),
+            vec![
+                "https://github.com/o/r/a.zip",
+                "https://one.example.com/o/r/a.zip",
+                "https://two.example.com/o/r/a.zip",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_api_requests_pin_version() {
+        let client = GitHubClient::new(&NetworkConfig::default()).unwrap();
+        let request = client
+            .api_get("https://api.github.com/rate_limit")
+            .build()
+            .unwrap();
+
+        let headers = request.headers();
+        assert_eq!(headers[reqwest::header::ACCEPT], GITHUB_ACCEPT);
+        assert_eq!(
+            headers["X-GitHub-Api-Version"],
+            launcher_config().github.api_version.as_str()
+        );
     }
 }
@@ -0,0 +1,146 @@
+//! Installation integrity checking against a stored hash baseline.
+//!
+//! GitHub releases don't publish a per-file manifest, so the baseline is a
+//! snapshot of the install's own file hashes, taken right after a clean
+//! install and stored in the [`Database`](crate::db::Database). Comparing a
+//! later scan to the snapshot reports files that went missing, were modified,
+//! or appeared since (e.g. leftovers from an older version).
+//!
+//! User data directories (saves, config, the update archive) are excluded so
+//! normal play doesn't show up as drift.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::app_data::migration_config;
+use crate::game::calculate_sha256;
+
+/// File hashes keyed by path relative to the game directory ('/' separated)
+pub type FileHashes = BTreeMap<String, String>;
+
+/// Differences between a baseline snapshot and the current installation
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct DriftReport {
+    /// Files in the baseline that no longer exist
+    pub missing: Vec<String>,
+    /// Files whose contents changed since the baseline
+    pub modified: Vec<String>,
+    /// Files that weren't in the baseline
+    pub extra: Vec<String>,
+}
+
+impl DriftReport {
+    /// True if the installation matches the baseline exactly
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.modified.is_empty() && self.extra.is_empty()
+    }
+}
+
+/// Top-level directories excluded from integrity checks
+fn ignored_dirs() -> Vec<&'static str> {
+    let config = migration_config();
+    config
+        .verify
+        .ignore_dirs
+        .iter()
+        .chain([
+            &config.archive.directory,
+            &config.archive.directory_old,
+            &config.archive.history,
+        ])
+        .map(String::as_str)
+        .collect()
+}
+
+/// Hash every game file in the installation.
+///
+/// This reads every file, so call it from a blocking context.
+pub fn hash_installation(game_dir: &Path) -> Result<FileHashes> {
+    let ignored = ignored_dirs();
+    let mut hashes = FileHashes::new();
+
+    let walker = WalkDir::new(game_dir).into_iter().filter_entry(|entry| {
+        entry.depth() != 1
+            || !entry.file_type().is_dir()
+            || !ignored.iter().any(|dir| entry.file_name() == *dir)
+    });
+
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(game_dir)?;
+        let key = relative.to_string_lossy().replace('\\', "/");
+        hashes.insert(key, calculate_sha256(entry.path())?);
+    }
+
+    Ok(hashes)
+}
+
+/// Compare the current file hashes against a baseline
+pub fn compare(baseline: &FileHashes, current: &FileHashes) -> DriftReport {
+    let mut report = DriftReport::default();
+
+    for (path, hash) in baseline {
+        match current.get(path) {
+            None => report.missing.push(path.clone()),
+            Some(current_hash) if current_hash != hash => report.modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+
+    report.extra = current
+        .keys()
+        .filter(|path| !baseline.contains_key(*path))
+        .cloned()
+        .collect();
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_hash_installation_skips_user_data() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("data/json")).unwrap();
+        fs::create_dir_all(temp.path().join("save/World")).unwrap();
+        fs::write(temp.path().join("data/json/items.json"), "[]").unwrap();
+        fs::write(temp.path().join("save/World/world.sav"), "save").unwrap();
+
+        let hashes = hash_installation(temp.path()).unwrap();
+        assert_eq!(
+            hashes.keys().collect::<Vec<_>>(),
+            vec!["data/json/items.json"]
+        );
+    }
+
+    #[test]
+    fn test_compare_reports_drift() {
+        let baseline: FileHashes = [("a", "1"), ("b", "2"), ("c", "3")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let current: FileHashes = [("a", "1"), ("b", "changed"), ("d", "4")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let report = compare(&baseline, &current);
+        assert_eq!(report.missing, vec!["c"]);
+        assert_eq!(report.modified, vec!["b"]);
+        assert_eq!(report.extra, vec!["d"]);
+        assert!(!report.is_clean());
+        assert!(compare(&baseline, &baseline).is_clean());
+    }
+}
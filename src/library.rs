@@ -0,0 +1,174 @@
+//! Unified index of managed content for the Library search.
+//!
+//! Combines installed mods, soundpacks, tilesets and backups into one flat
+//! list so they can be filtered by name from a single search box. The index
+//! is rebuilt on demand from the existing scanners; nothing is cached.
+
+use std::path::PathBuf;
+
+use crate::backup;
+use crate::migration;
+use crate::mods;
+use crate::soundpack;
+use crate::tileset;
+
+/// Kind of content a library item is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LibraryKind {
+    Mod,
+    Soundpack,
+    Tileset,
+    Backup,
+}
+
+impl LibraryKind {
+    /// Display label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Mod => "Mod",
+            Self::Soundpack => "Soundpack",
+            Self::Tileset => "Tileset",
+            Self::Backup => "Backup",
+        }
+    }
+}
+
+/// A single searchable piece of content
+#[derive(Debug, Clone)]
+pub struct LibraryItem {
+    /// What kind of content this is
+    pub kind: LibraryKind,
+    /// Name shown in results and matched against the query
+    pub name: String,
+    /// Secondary text (internal name, note, origin), also matched
+    pub detail: String,
+    /// Directory or archive on disk
+    pub path: PathBuf,
+}
+
+impl LibraryItem {
+    /// Whether the item matches a lowercase search query
+    fn matches(&self, query: &str) -> bool {
+        self.name.to_lowercase().contains(query) || self.detail.to_lowercase().contains(query)
+    }
+}
+
+/// Build the library index for a game directory.
+///
+/// A source that fails to load is logged and left out, so one unreadable
+/// folder doesn't hide everything else.
+pub async fn build_index(game_dir: PathBuf) -> Vec<LibraryItem> {
+    let mut items = Vec::new();
+
+    let mod_dirs = [
+        (game_dir.join("data").join("mods"), "Bundled"),
+        (mods::user_mods_dir(&game_dir), "User"),
+    ];
+    let scanned = tokio::task::spawn_blocking(move || {
+        mod_dirs
+            .into_iter()
+            .flat_map(|(dir, origin)| {
+                migration::scan_mods_directory(&dir)
+                    .into_values()
+                    .map(move |info| LibraryItem {
+                        kind: LibraryKind::Mod,
+                        name: info.id,
+                        detail: origin.to_string(),
+                        path: info.path,
+                    })
+            })
+            .collect::<Vec<_>>()
+    })
+    .await;
+    match scanned {
+        Ok(mods) => items.extend(mods),
+        Err(e) => tracing::warn!("Library: mod scan failed: {}", e),
+    }
+
+    match soundpack::list_installed_soundpacks(&game_dir).await {
+        Ok(soundpacks) => items.extend(soundpacks.into_iter().map(|s| LibraryItem {
+            kind: LibraryKind::Soundpack,
+            name: s.view_name,
+            detail: s.name,
+            path: s.path,
+        })),
+        Err(e) => tracing::warn!("Library: soundpack scan failed: {}", e),
+    }
+
+    match tileset::list_installed_tilesets(&game_dir).await {
+        Ok(tilesets) => items.extend(tilesets.into_iter().map(|t| LibraryItem {
+            kind: LibraryKind::Tileset,
+            name: t.view_name,
+            detail: t.name,
+            path: t.path,
+        })),
+        Err(e) => tracing::warn!("Library: tileset scan failed: {}", e),
+    }
+
+    match backup::list_backups().await {
+        Ok(backups) => items.extend(backups.into_iter().map(|b| LibraryItem {
+            kind: LibraryKind::Backup,
+            detail: b.note.unwrap_or_default(),
+            name: b.name,
+            path: b.path,
+        })),
+        Err(e) => tracing::warn!("Library: backup scan failed: {}", e),
+    }
+
+    items.sort_by(|a, b| {
+        a.kind
+            .cmp(&b.kind)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    items
+}
+
+/// Items matching `query` (case-insensitive, by name or detail).
+/// An empty query matches everything.
+pub fn search<'a>(items: &'a [LibraryItem], query: &str) -> Vec<&'a LibraryItem> {
+    let query = query.trim().to_lowercase();
+    items.iter().filter(|item| item.matches(&query)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: LibraryKind, name: &str, detail: &str) -> LibraryItem {
+        LibraryItem {
+            kind,
+            name: name.to_string(),
+            detail: detail.to_string(),
+            path: PathBuf::from(name),
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let items = vec![
+            item(LibraryKind::Mod, "aftershock", "User"),
+            item(LibraryKind::Soundpack, "CO.AG SoundPack", "CO.AG"),
+            item(LibraryKind::Tileset, "UltiCa", "UltimateCataclysm"),
+            item(
+                LibraryKind::Backup,
+                "auto_2025-01-01",
+                "before the big raid",
+            ),
+        ];
+
+        let names = |query: &str| -> Vec<&str> {
+            search(&items, query)
+                .iter()
+                .map(|i| i.name.as_str())
+                .collect()
+        };
+
+        assert_eq!(names("").len(), 4);
+        assert_eq!(names("ULTI"), vec!["UltiCa"]);
+        assert_eq!(names("  co.ag "), vec!["CO.AG SoundPack"]);
+        // Details match too: a tileset's internal name, a backup's note
+        assert_eq!(names("ultimate"), vec!["UltiCa"]);
+        assert_eq!(names("raid"), vec!["auto_2025-01-01"]);
+        assert!(names("zzz").is_empty());
+    }
+}
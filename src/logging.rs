@@ -0,0 +1,171 @@
+//! Log capture for the in-app log viewer and on-disk log files.
+//!
+//! The GUI build hides its console, so recent `tracing` output is also kept in
+//! an in-memory ring buffer. [`buffer_layer`] returns a formatting layer that
+//! writes into the buffer, and [`recent_lines`] reads it back; [`LogSnapshot`]
+//! keeps a copy for display that is only refreshed when the buffer changes.
+//!
+//! [`file_layer`] additionally writes logs to `<data_dir>/logs/`, rotated
+//! daily, so the previous session's log survives a crash or failed update.
+
+use std::collections::VecDeque;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::Layer;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::config::Config;
+
+/// Maximum number of log lines kept in memory
+const MAX_LINES: usize = 1000;
+
+/// Number of daily log files to keep on disk
+const MAX_LOG_FILES: usize = 7;
+
+/// Global ring buffer of formatted log lines
+fn buffer() -> &'static Mutex<VecDeque<String>> {
+    static BUFFER: OnceLock<Mutex<VecDeque<String>>> = OnceLock::new();
+    BUFFER.get_or_init(|| Mutex::new(VecDeque::with_capacity(MAX_LINES)))
+}
+
+/// Bumped whenever the ring buffer changes
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Append lines to a ring buffer, dropping the oldest beyond `max`
+fn push_lines(lines: &mut VecDeque<String>, text: &str, max: usize) {
+    for line in text.lines().filter(|l| !l.is_empty()) {
+        if lines.len() == max {
+            lines.pop_front();
+        }
+        lines.push_back(line.to_string());
+    }
+}
+
+/// Writer that appends each formatted event to the ring buffer
+struct BufferWriter;
+
+impl io::Write for BufferWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        if let Ok(mut lines) = buffer().lock() {
+            push_lines(&mut lines, &text, MAX_LINES);
+            GENERATION.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `MakeWriter` handing out [`BufferWriter`]s
+struct BufferMakeWriter;
+
+impl<'a> MakeWriter<'a> for BufferMakeWriter {
+    type Writer = BufferWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        BufferWriter
+    }
+}
+
+/// Create a `tracing` layer that captures formatted log lines in memory
+pub fn buffer_layer<S>() -> impl Layer<S>
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(BufferMakeWriter)
+}
+
+/// Directory the log files are written to, created on first use.
+///
+/// Resolved once so the UI can link to it without touching the disk every
+/// frame.
+pub fn logs_dir() -> Option<&'static Path> {
+    static LOGS_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+    LOGS_DIR.get_or_init(|| Config::logs_dir().ok()).as_deref()
+}
+
+/// Create a `tracing` layer that writes to a daily-rotated log file.
+///
+/// Returns `None` if the log directory can't be created. The returned guard
+/// flushes buffered lines on drop and must be kept alive until exit.
+pub fn file_layer<S>() -> Option<(impl Layer<S>, WorkerGuard)>
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    let logs_dir = logs_dir()?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("phoenix")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(logs_dir)
+        .ok()?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(writer);
+
+    Some((layer, guard))
+}
+
+/// Snapshot of the captured log lines, oldest first
+pub fn recent_lines() -> Vec<String> {
+    buffer()
+        .lock()
+        .map(|lines| lines.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Discard all captured log lines
+pub fn clear() {
+    if let Ok(mut lines) = buffer().lock() {
+        lines.clear();
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Copy of the captured log lines for display, refreshed only when the
+/// buffer has changed since the last look
+#[derive(Debug, Default)]
+pub struct LogSnapshot {
+    /// Buffer generation the copy was taken at
+    generation: Option<u64>,
+    lines: Vec<String>,
+}
+
+impl LogSnapshot {
+    /// The captured log lines, oldest first
+    pub fn lines(&mut self) -> &[String] {
+        let generation = GENERATION.load(Ordering::Relaxed);
+        if self.generation != Some(generation) {
+            self.lines = recent_lines();
+            self.generation = Some(generation);
+        }
+        &self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_lines_drops_oldest() {
+        let mut lines = VecDeque::new();
+        push_lines(&mut lines, "one\ntwo\n", 3);
+        push_lines(&mut lines, "three\n\nfour\n", 3);
+        assert_eq!(lines, vec!["two", "three", "four"]);
+    }
+}
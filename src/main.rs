@@ -7,19 +7,28 @@ mod backup;
 mod cli;
 mod config;
 mod db;
+mod diagnostics;
 mod game;
 mod github;
+mod integrity;
 mod legacy;
+mod library;
+mod logging;
 mod migration;
+mod mods;
+mod safe_mode;
+mod self_update;
 mod soundpack;
 mod state;
 mod task;
+mod tileset;
 mod ui;
 mod update;
 mod util;
 
 use anyhow::Result;
 use clap::Parser;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -67,6 +76,33 @@ fn acquire_single_instance() -> Option<HANDLE> {
     }
 }
 
+/// Bring the already-running instance's window to the foreground.
+///
+/// Finds the window by its title and restores it if minimized.
+/// Returns false if the window couldn't be found (e.g. it's still starting).
+#[cfg(windows)]
+fn focus_existing_instance() -> bool {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        FindWindowW, IsIconic, SW_RESTORE, SetForegroundWindow, ShowWindow,
+    };
+
+    let title: Vec<u16> = format!("{}\0", launcher_config().window.title)
+        .encode_utf16()
+        .collect();
+
+    unsafe {
+        let Ok(hwnd) = FindWindowW(PCWSTR::null(), PCWSTR(title.as_ptr())) else {
+            return false;
+        };
+
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+
+        SetForegroundWindow(hwnd).as_bool()
+    }
+}
+
 /// RAII guard holding the single-instance lock on non-Windows platforms.
 ///
 /// The advisory file lock lives as long as the underlying `File` is open, so
@@ -131,9 +167,28 @@ fn acquire_single_instance() -> Option<InstanceGuard> {
 
 /// Check if we should run in CLI mode based on command-line arguments
 fn should_run_cli() -> bool {
-    let args: Vec<String> = std::env::args().collect();
+    let args: Vec<String> = std::env::args().skip(1).collect();
     // CLI mode if we have arguments beyond just the executable name
-    args.len() > 1
+    // (`--config` alone still opens the GUI)
+    let (_, rest) = split_config_arg(&args);
+    !rest.is_empty()
+}
+
+/// Separate a `--config <PATH>` or `--config=<PATH>` argument from the rest
+fn split_config_arg(args: &[String]) -> (Option<PathBuf>, Vec<&String>) {
+    let mut config = None;
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--config" {
+            config = iter.next().map(PathBuf::from);
+        } else if let Some(path) = arg.strip_prefix("--config=") {
+            config = Some(PathBuf::from(path));
+        } else {
+            rest.push(arg);
+        }
+    }
+    (config, rest)
 }
 
 /// Check if --verbose or -v flag is present in command-line arguments
@@ -164,6 +219,10 @@ fn attach_console() {
 async fn main() -> Result<()> {
     // Determine mode before doing anything else
     let cli_mode = should_run_cli();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let (Some(path), _) = split_config_arg(&args) {
+        config::Config::set_path_override(Some(path));
+    }
     let verbose = is_verbose();
 
     // In CLI mode, we need a console for output
@@ -174,6 +233,12 @@ async fn main() -> Result<()> {
     // Initialize logging
     // In CLI mode: warn by default, debug if --verbose
     // In GUI mode: debug always (for troubleshooting)
+    // File logging is optional; the guard flushes the log file on exit
+    let (file_layer, _log_guard) = match logging::file_layer() {
+        Some((layer, guard)) => (Some(layer), Some(guard)),
+        None => (None, None),
+    };
+
     tracing_subscriber::registry()
         .with(tracing_subscriber::EnvFilter::new(
             std::env::var("RUST_LOG").unwrap_or_else(|_| {
@@ -189,18 +254,30 @@ async fn main() -> Result<()> {
             }),
         ))
         .with(tracing_subscriber::fmt::layer())
+        .with(logging::buffer_layer())
+        .with(file_layer)
         .init();
 
+    if let Some(dir) = config::portable_dir() {
+        tracing::info!("Portable mode: storing data in {:?}", dir);
+    }
+
+    // Remove the executable replaced by a previous self-update
+    self_update::cleanup_previous();
+
     // Run CLI or GUI
     if cli_mode {
-        run_cli().await
+        let exit = run_cli().await;
+        // process::exit skips destructors, so flush the log file first
+        drop(_log_guard);
+        std::process::exit(exit.code());
     } else {
         run_gui().await
     }
 }
 
 /// Run the CLI interface
-async fn run_cli() -> Result<()> {
+async fn run_cli() -> cli::CliExit {
     let cli = cli::Cli::parse();
     cli::run(cli).await
 }
@@ -214,9 +291,10 @@ async fn run_gui() -> Result<()> {
         Some(lock) => lock,
         None => {
             tracing::error!("Phoenix is already running. Exiting.");
-            // Show a message box on Windows
+            // Surface the running instance on Windows, falling back to a
+            // message box if its window can't be found
             #[cfg(windows)]
-            {
+            if !focus_existing_instance() {
                 use windows::Win32::UI::WindowsAndMessaging::{
                     MB_ICONINFORMATION, MB_OK, MessageBoxW,
                 };
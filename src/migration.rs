@@ -2,10 +2,68 @@
 //!
 //! This module handles identity-based detection of custom mods, tilesets,
 //! soundpacks, and fonts to avoid overwriting new official content with old versions.
+//! A [`MigrationMode`] can opt into also carrying official content forward.
 
 use crate::app_data::{game_config, migration_config};
+use crate::db::{Database, ScanCacheEntry};
+use crate::game::calculate_sha256;
+use crate::integrity::FileHashes;
+use crate::util::{decode_text, parse_info_field};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread::{self, ScopedJoinHandle};
+use std::time::UNIX_EPOCH;
+
+/// How much of the previous installation's mods, tilesets and soundpacks an
+/// update carries forward
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationMode {
+    /// Only content the new build doesn't ship
+    #[default]
+    CustomOnly,
+    /// Also official content whose files differ from the new build's copy
+    CustomAndModified,
+    /// All previous content, replacing the new build's copies
+    Everything,
+}
+
+impl MigrationMode {
+    /// Get all modes, least to most aggressive
+    pub fn all() -> &'static [MigrationMode] {
+        &[
+            MigrationMode::CustomOnly,
+            MigrationMode::CustomAndModified,
+            MigrationMode::Everything,
+        ]
+    }
+
+    /// Get display name for the mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            MigrationMode::CustomOnly => "Custom content only",
+            MigrationMode::CustomAndModified => "Custom + modified official",
+            MigrationMode::Everything => "Everything",
+        }
+    }
+
+    /// Config value for the mode
+    pub fn key(&self) -> &'static str {
+        match self {
+            MigrationMode::CustomOnly => "custom_only",
+            MigrationMode::CustomAndModified => "custom_and_modified",
+            MigrationMode::Everything => "everything",
+        }
+    }
+
+    /// Parse a config value
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|mode| mode.key() == key)
+    }
+}
 
 /// Get files to skip during config restoration
 pub fn config_skip_files() -> &'static [String] {
@@ -13,12 +71,34 @@ pub fn config_skip_files() -> &'static [String] {
 }
 
 /// Represents a mod with its identifier and path
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModInfo {
     /// Unique mod identifier from modinfo.json
     pub id: String,
-    /// Path to the mod directory
+    /// Path to the mod directory (not stored in the scan cache)
+    #[serde(skip)]
     pub path: PathBuf,
+    /// Mod ids listed in the modinfo.json `dependencies` field
+    pub dependencies: Vec<String>,
+    /// Whether modinfo.json marks the mod as obsolete
+    pub obsolete: bool,
+    /// Whether this is a core mod the game can't run without (e.g. "dda")
+    pub core: bool,
+}
+
+/// A restored custom mod that may not work with the new build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModWarning {
+    /// Mod identifier
+    pub id: String,
+    /// Why the mod may be incompatible
+    pub reason: String,
+}
+
+impl std::fmt::Display for ModWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.id, self.reason)
+    }
 }
 
 /// Represents a tileset with its name and path
@@ -56,6 +136,17 @@ pub struct SoundpackMergeInfo {
     pub custom_files: Vec<PathBuf>,
 }
 
+/// Official content whose previous copy replaces the new build's
+#[derive(Debug, Clone)]
+pub struct ReplacedContent {
+    /// Mod id, tileset or soundpack name
+    pub name: String,
+    /// Directory in the previous version
+    pub old_path: PathBuf,
+    /// Directory in the new version
+    pub new_path: PathBuf,
+}
+
 /// Result of analyzing directories for custom content
 #[derive(Debug, Default)]
 pub struct MigrationPlan {
@@ -75,6 +166,13 @@ pub struct MigrationPlan {
     pub custom_data_fonts: Vec<PathBuf>,
     /// Whether to restore user-default-mods.json
     pub restore_user_default_mods: bool,
+    /// Custom mods that may be incompatible with the new build
+    pub mod_warnings: Vec<ModWarning>,
+    /// Official mods, tilesets and soundpacks carried forward by the migration mode
+    pub replaced: Vec<ReplacedContent>,
+    /// Ids of official mods whose previous copy differs from the new build's
+    /// and isn't carried forward, so any in-place edits are overwritten
+    pub overwritten_mods: Vec<String>,
 }
 
 /// Parse modinfo.json to extract the mod identifier.
@@ -100,38 +198,46 @@ pub fn parse_mod_ident(mod_dir: &Path) -> Option<ModInfo> {
     let content = std::fs::read_to_string(&file_path).ok()?;
     let json: serde_json::Value = serde_json::from_str(&content).ok()?;
 
-    // Try as single object
-    if let Some(obj) = json.as_object()
-        && obj.get("type").and_then(|v| v.as_str()) == Some("MOD_INFO")
-        && let Some(id) = obj.get("id").and_then(|v| v.as_str())
-    {
-        return Some(ModInfo {
-            id: id.to_string(),
-            path: mod_dir.to_path_buf(),
-        });
-    }
+    // Single object, or an array where the first MOD_INFO entry wins
+    let items = match json.as_array() {
+        Some(arr) => arr.iter().collect(),
+        None => vec![&json],
+    };
 
-    // Try as array - find first MOD_INFO entry
-    if let Some(arr) = json.as_array() {
-        for item in arr {
-            if let Some(obj) = item.as_object()
-                && obj.get("type").and_then(|v| v.as_str()) == Some("MOD_INFO")
-                && let Some(id) = obj.get("id").and_then(|v| v.as_str())
-            {
-                return Some(ModInfo {
-                    id: id.to_string(),
-                    path: mod_dir.to_path_buf(),
-                });
-            }
+    items.into_iter().find_map(|item| {
+        let obj = item.as_object()?;
+        if obj.get("type").and_then(|v| v.as_str()) != Some("MOD_INFO") {
+            return None;
         }
-    }
-
-    None
+        let id = obj.get("id").and_then(|v| v.as_str())?;
+
+        let dependencies = obj
+            .get("dependencies")
+            .and_then(|v| v.as_array())
+            .map(|deps| {
+                deps.iter()
+                    .filter_map(|dep| dep.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(ModInfo {
+            id: id.to_string(),
+            path: mod_dir.to_path_buf(),
+            dependencies,
+            obsolete: obj
+                .get("obsolete")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            core: obj.get("core").and_then(|v| v.as_bool()).unwrap_or(false),
+        })
+    })
 }
 
 /// Parse a tileset.txt or soundpack.txt file to extract the NAME field.
 ///
-/// Format: `NAME <name>` where name may contain spaces and commas are stripped.
+/// Format: `NAME <name>` where name may contain spaces; see
+/// [`parse_info_field`] for quoting and comments.
 fn parse_asset_name(asset_dir: &Path, filename: &str, disabled_filename: &str) -> Option<String> {
     let normal_file = asset_dir.join(filename);
     let disabled_file = asset_dir.join(disabled_filename);
@@ -144,24 +250,12 @@ fn parse_asset_name(asset_dir: &Path, filename: &str, disabled_filename: &str) -
         return None;
     };
 
-    // Read file - use lossy conversion for latin1 compatibility
     let content = std::fs::read(&file_path).ok()?;
-    let text = String::from_utf8_lossy(&content);
+    let text = decode_text(&content);
 
     let name_field = &game_config().metadata.name_field;
-    for line in text.lines() {
-        if line.starts_with(name_field) {
-            // Find first space after the name field
-            if let Some(space_idx) = line.find(' ') {
-                let name = line[space_idx..].trim().replace(',', "");
-                if !name.is_empty() {
-                    return Some(name);
-                }
-            }
-        }
-    }
-
-    None
+    text.lines()
+        .find_map(|line| parse_info_field(line, name_field, true))
 }
 
 /// Parse tileset.txt to get tileset info
@@ -190,75 +284,47 @@ pub fn parse_soundpack_info(soundpack_dir: &Path) -> Option<SoundpackInfo> {
     })
 }
 
-/// Scan a mods directory and build a map of mod_id -> ModInfo
-pub fn scan_mods_directory(mods_dir: &Path) -> HashMap<String, ModInfo> {
-    let mut mods = HashMap::new();
-
-    if !mods_dir.exists() || !mods_dir.is_dir() {
-        return mods;
+/// Parse every subdirectory of `dir` with `parse`, keyed by `key`.
+///
+/// Only the first entry with a given key is kept (like Python does).
+fn scan_subdirectories<T>(
+    dir: &Path,
+    mut parse: impl FnMut(&Path) -> Option<T>,
+    key: impl Fn(&T) -> &str,
+) -> HashMap<String, T> {
+    let mut found = HashMap::new();
+
+    if !dir.exists() || !dir.is_dir() {
+        return found;
     }
 
-    if let Ok(entries) = std::fs::read_dir(mods_dir) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
         for entry in entries.filter_map(|e| e.ok()) {
             let path = entry.path();
             if path.is_dir()
-                && let Some(mod_info) = parse_mod_ident(&path)
+                && let Some(info) = parse(&path)
             {
-                // Only insert first occurrence (like Python does)
-                mods.entry(mod_info.id.clone()).or_insert(mod_info);
+                found.entry(key(&info).to_string()).or_insert(info);
             }
         }
     }
 
-    mods
+    found
+}
+
+/// Scan a mods directory and build a map of mod_id -> ModInfo
+pub fn scan_mods_directory(mods_dir: &Path) -> HashMap<String, ModInfo> {
+    scan_subdirectories(mods_dir, parse_mod_ident, |m| &m.id)
 }
 
 /// Scan a tilesets directory (gfx/) and build a map of name -> TilesetInfo
 pub fn scan_tilesets_directory(gfx_dir: &Path) -> HashMap<String, TilesetInfo> {
-    let mut tilesets = HashMap::new();
-
-    if !gfx_dir.exists() || !gfx_dir.is_dir() {
-        return tilesets;
-    }
-
-    if let Ok(entries) = std::fs::read_dir(gfx_dir) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let path = entry.path();
-            if path.is_dir()
-                && let Some(tileset_info) = parse_tileset_info(&path)
-            {
-                tilesets
-                    .entry(tileset_info.name.clone())
-                    .or_insert(tileset_info);
-            }
-        }
-    }
-
-    tilesets
+    scan_subdirectories(gfx_dir, parse_tileset_info, |t| &t.name)
 }
 
 /// Scan a soundpacks directory (data/sound/) and build a map of name -> SoundpackInfo
 pub fn scan_soundpacks_directory(sound_dir: &Path) -> HashMap<String, SoundpackInfo> {
-    let mut soundpacks = HashMap::new();
-
-    if !sound_dir.exists() || !sound_dir.is_dir() {
-        return soundpacks;
-    }
-
-    if let Ok(entries) = std::fs::read_dir(sound_dir) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let path = entry.path();
-            if path.is_dir()
-                && let Some(soundpack_info) = parse_soundpack_info(&path)
-            {
-                soundpacks
-                    .entry(soundpack_info.name.clone())
-                    .or_insert(soundpack_info);
-            }
-        }
-    }
-
-    soundpacks
+    scan_subdirectories(sound_dir, parse_soundpack_info, |s| &s.name)
 }
 
 /// Scan font directory and return set of filenames
@@ -292,6 +358,40 @@ pub fn find_custom_mods(
         .collect()
 }
 
+/// Flag custom mods that declare themselves obsolete or depend on mods
+/// the new build no longer ships.
+///
+/// `available` holds every mod id present after the update (official and
+/// restored custom mods).
+pub fn find_mod_warnings(custom_mods: &[ModInfo], available: &HashSet<String>) -> Vec<ModWarning> {
+    let mut warnings = Vec::new();
+
+    for mod_info in custom_mods {
+        if mod_info.obsolete {
+            warnings.push(ModWarning {
+                id: mod_info.id.clone(),
+                reason: "marked obsolete".to_string(),
+            });
+        }
+
+        let missing: Vec<&str> = mod_info
+            .dependencies
+            .iter()
+            .filter(|dep| !available.contains(*dep))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            warnings.push(ModWarning {
+                id: mod_info.id.clone(),
+                reason: format!("requires missing mods: {}", missing.join(", ")),
+            });
+        }
+    }
+
+    warnings.sort_by(|a, b| a.id.cmp(&b.id));
+    warnings
+}
+
 /// Find custom tilesets that exist in old version but not in new version
 pub fn find_custom_tilesets(
     old_tilesets: &HashMap<String, TilesetInfo>,
@@ -375,12 +475,22 @@ pub fn find_custom_soundpack_files(old_soundpack: &Path, new_soundpack: &Path) -
 pub fn find_soundpack_merges(
     old_soundpacks: &HashMap<String, SoundpackInfo>,
     new_soundpacks: &HashMap<String, SoundpackInfo>,
+) -> Vec<SoundpackMergeInfo> {
+    find_soundpack_merges_with(old_soundpacks, new_soundpacks, find_custom_soundpack_files)
+}
+
+/// [`find_soundpack_merges`] with the custom file lookup (old soundpack path,
+/// new soundpack path) supplied by the caller, e.g. backed by the scan cache.
+fn find_soundpack_merges_with(
+    old_soundpacks: &HashMap<String, SoundpackInfo>,
+    new_soundpacks: &HashMap<String, SoundpackInfo>,
+    custom_files: impl Fn(&Path, &Path) -> Vec<PathBuf>,
 ) -> Vec<SoundpackMergeInfo> {
     let mut merges = Vec::new();
 
     for (name, old_info) in old_soundpacks {
         if let Some(new_info) = new_soundpacks.get(name) {
-            let custom_files = find_custom_soundpack_files(&old_info.path, &new_info.path);
+            let custom_files = custom_files(&old_info.path, &new_info.path);
 
             if !custom_files.is_empty() {
                 tracing::debug!(
@@ -402,6 +512,73 @@ pub fn find_soundpack_merges(
     merges
 }
 
+/// Whether the files under `dir` differ from the pristine hashes recorded
+/// for the installation at `root`: files added, removed or changed since
+/// the snapshot was taken
+pub fn modified_since_snapshot(root: &Path, dir: &Path, baseline: &FileHashes) -> bool {
+    let Ok(relative) = dir.strip_prefix(root) else {
+        return false;
+    };
+    let prefix = format!("{}/", relative.to_string_lossy().replace('\\', "/"));
+
+    let files: Vec<(String, PathBuf)> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let key = e
+                .path()
+                .strip_prefix(root)
+                .ok()?
+                .to_string_lossy()
+                .replace('\\', "/");
+            Some((key, e.into_path()))
+        })
+        .collect();
+
+    // Added or removed files, then contents
+    let recorded = baseline
+        .range(prefix.clone()..)
+        .take_while(|(key, _)| key.starts_with(&prefix))
+        .count();
+    if recorded != files.len() || files.iter().any(|(key, _)| !baseline.contains_key(key)) {
+        return true;
+    }
+    files.iter().any(|(key, path)| {
+        calculate_sha256(path).map_or(true, |hash| baseline.get(key) != Some(&hash))
+    })
+}
+
+/// Find content present in both versions that `mode` carries forward.
+///
+/// `is_modified` tells whether the previous copy was edited by the user.
+fn find_replaced<T>(
+    old: &HashMap<String, T>,
+    new: &HashMap<String, T>,
+    path: impl Fn(&T) -> &Path,
+    mode: MigrationMode,
+    is_modified: impl Fn(&Path) -> bool,
+) -> Vec<ReplacedContent> {
+    if mode == MigrationMode::CustomOnly {
+        return Vec::new();
+    }
+
+    let mut replaced: Vec<ReplacedContent> = old
+        .iter()
+        .filter_map(|(name, old_info)| {
+            let new_info = new.get(name)?;
+            let (old_path, new_path) = (path(old_info), path(new_info));
+            (mode == MigrationMode::Everything || is_modified(old_path)).then(|| ReplacedContent {
+                name: name.clone(),
+                old_path: old_path.to_path_buf(),
+                new_path: new_path.to_path_buf(),
+            })
+        })
+        .collect();
+    replaced.sort_by(|a, b| a.name.cmp(&b.name));
+    replaced
+}
+
 /// Find custom fonts (filenames in old but not in new)
 pub fn find_custom_fonts(
     old_fonts: &HashSet<String>,
@@ -414,16 +591,361 @@ pub fn find_custom_fonts(
         .collect()
 }
 
-/// Analyze old and new game directories to create a migration plan
-pub fn create_migration_plan(previous_version_dir: &Path, game_dir: &Path) -> MigrationPlan {
+/// Version of the cached scan results, part of every cache key. Bump it
+/// whenever parsing changes what a scan returns (e.g. how names are read),
+/// so entries recorded by an older parser are scanned again.
+const SCAN_CACHE_VERSION: u32 = 3;
+
+/// Cache key kind for `kind` under the current [`SCAN_CACHE_VERSION`]
+fn versioned_kind(kind: &str) -> String {
+    format!("{}@{}", kind, SCAN_CACHE_VERSION)
+}
+
+/// Scan cache loaded from the database before planning.
+///
+/// Loading it up front lets the content directories be scanned on several
+/// threads (a database connection can't be shared between them).
+struct ScanCache {
+    entries: HashMap<(String, String), (i64, String)>,
+}
+
+impl ScanCache {
+    fn load(db: &Database) -> Self {
+        let entries = db
+            .load_scan_cache()
+            .inspect_err(|e| tracing::debug!("Failed to load scan cache: {}", e))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| ((entry.kind, entry.path), (entry.stamp, entry.data)))
+            .collect();
+        Self { entries }
+    }
+
+    fn get<T: DeserializeOwned>(&self, kind: &str, path: &str, stamp: i64) -> Option<T> {
+        let (cached_stamp, data) = self
+            .entries
+            .get(&(versioned_kind(kind), path.to_string()))?;
+        if *cached_stamp != stamp {
+            return None;
+        }
+        serde_json::from_str(data).ok()
+    }
+}
+
+/// Content scans of one installation, backed by the scan cache when available.
+///
+/// Parsed identities and soundpack file sets are cached under the content
+/// directory's path relative to the installation, so the archived previous
+/// version reuses what was recorded while it was the current one. An entry
+/// is only used while the directory's modification stamp is unchanged.
+/// Fresh scans are collected per installation and written back once
+/// planning is done.
+struct Scanner<'a> {
+    root: &'a Path,
+    cache: Option<&'a ScanCache>,
+    fresh: Mutex<Vec<ScanCacheEntry>>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(root: &'a Path, cache: Option<&'a ScanCache>) -> Self {
+        Self {
+            root,
+            cache,
+            fresh: Mutex::default(),
+        }
+    }
+
+    /// Look `dir` up in the cache under `kind`, computing and recording the
+    /// value on a miss. `stamp` is the modification stamp for `dir`.
+    fn cached<T: Serialize + DeserializeOwned>(
+        &self,
+        kind: &str,
+        dir: &Path,
+        stamp: impl FnOnce() -> Option<i64>,
+        compute: impl FnOnce() -> T,
+    ) -> T {
+        let Some(cache) = self.cache else {
+            return compute();
+        };
+        let (Ok(relative), Some(stamp)) = (dir.strip_prefix(self.root), stamp()) else {
+            return compute();
+        };
+        let key = relative.to_string_lossy().into_owned();
+
+        if let Some(value) = cache.get(kind, &key, stamp) {
+            return value;
+        }
+
+        let value = compute();
+        if let Ok(data) = serde_json::to_string(&value) {
+            self.fresh.lock().unwrap().push(ScanCacheEntry {
+                kind: versioned_kind(kind),
+                path: key,
+                stamp,
+                data,
+            });
+        }
+        value
+    }
+
+    fn mods(&self, mods_dir: &Path) -> HashMap<String, ModInfo> {
+        if self.cache.is_none() {
+            return scan_mods_directory(mods_dir);
+        }
+        let metadata = &game_config().metadata;
+        scan_subdirectories(
+            mods_dir,
+            |dir| {
+                let stamp = || {
+                    modified_stamp(&[
+                        dir.to_path_buf(),
+                        dir.join(&metadata.mod_info),
+                        dir.join(&metadata.mod_info_disabled),
+                    ])
+                };
+                let info: Option<ModInfo> = self.cached("mod", dir, stamp, || parse_mod_ident(dir));
+                info.map(|info| ModInfo {
+                    path: dir.to_path_buf(),
+                    ..info
+                })
+            },
+            |m| &m.id,
+        )
+    }
+
+    fn tilesets(&self, gfx_dir: &Path) -> HashMap<String, TilesetInfo> {
+        if self.cache.is_none() {
+            return scan_tilesets_directory(gfx_dir);
+        }
+        let info_file = &game_config().metadata.tileset_info;
+        scan_subdirectories(
+            gfx_dir,
+            |dir| {
+                let stamp = || {
+                    modified_stamp(&[
+                        dir.to_path_buf(),
+                        dir.join(info_file),
+                        dir.join(format!("{}.disabled", info_file)),
+                    ])
+                };
+                let name = self.cached("tileset", dir, stamp, || {
+                    parse_tileset_info(dir).map(|t| t.name)
+                });
+                name.map(|name| TilesetInfo {
+                    name,
+                    path: dir.to_path_buf(),
+                })
+            },
+            |t| &t.name,
+        )
+    }
+
+    fn soundpacks(&self, sound_dir: &Path) -> HashMap<String, SoundpackInfo> {
+        if self.cache.is_none() {
+            return scan_soundpacks_directory(sound_dir);
+        }
+        let metadata = &game_config().metadata;
+        scan_subdirectories(
+            sound_dir,
+            |dir| {
+                let stamp = || {
+                    modified_stamp(&[
+                        dir.to_path_buf(),
+                        dir.join(&metadata.soundpack_info),
+                        dir.join(&metadata.soundpack_info_disabled),
+                    ])
+                };
+                let name = self.cached("soundpack", dir, stamp, || {
+                    parse_soundpack_info(dir).map(|s| s.name)
+                });
+                name.map(|name| SoundpackInfo {
+                    name,
+                    path: dir.to_path_buf(),
+                })
+            },
+            |s| &s.name,
+        )
+    }
+
+    /// Soundpack file set, invalidated when any directory in the soundpack
+    /// changes (files added, removed or renamed)
+    fn soundpack_files(&self, soundpack_dir: &Path) -> HashSet<PathBuf> {
+        self.cached(
+            "soundpack_files",
+            soundpack_dir,
+            || tree_modified_stamp(soundpack_dir),
+            || scan_soundpack_files(soundpack_dir),
+        )
+    }
+}
+
+/// Latest modification time (nanoseconds since the epoch) of the existing
+/// `paths`, or `None` if none of them exist
+fn modified_stamp(paths: &[PathBuf]) -> Option<i64> {
+    paths
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok()?.modified().ok())
+        .filter_map(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_nanos() as i64)
+        .max()
+}
+
+/// Latest modification time of `dir` and every directory below it
+fn tree_modified_stamp(dir: &Path) -> Option<i64> {
+    let mut stamp = modified_stamp(&[dir.to_path_buf()])?;
+    for entry in std::fs::read_dir(dir).ok()?.filter_map(|e| e.ok()) {
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            stamp = stamp.max(tree_modified_stamp(&entry.path())?);
+        }
+    }
+    Some(stamp)
+}
+
+/// Wait for a scan thread, re-raising its panic on this thread
+fn join<T>(handle: ScopedJoinHandle<'_, T>) -> T {
+    handle
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+/// Run the scans of the previous and new installation side by side
+fn scan_both<T: Send>(old: impl FnOnce() -> T + Send, new: impl FnOnce() -> T + Send) -> (T, T) {
+    thread::scope(|s| {
+        let old = s.spawn(old);
+        let new = new();
+        (join(old), new)
+    })
+}
+
+/// Analyze old and new game directories to create a migration plan.
+///
+/// The content directories are independent, so they are scanned
+/// concurrently. With a database, mods, tilesets and soundpacks that haven't
+/// changed since they were last scanned are taken from the scan cache
+/// instead of reparsed.
+///
+/// With a `mode` other than [`MigrationMode::CustomOnly`], official content
+/// is also listed in [`MigrationPlan::replaced`]. User edits to official
+/// content are found by comparing the previous copy to `baseline`, the file
+/// hashes recorded when the previous version was installed; without one,
+/// no official content counts as edited.
+pub fn create_migration_plan(
+    previous_version_dir: &Path,
+    game_dir: &Path,
+    db: Option<&Database>,
+    mode: MigrationMode,
+    baseline: Option<&FileHashes>,
+) -> MigrationPlan {
     let mut plan = MigrationPlan::default();
+    let is_modified = |dir: &Path| {
+        baseline.is_some_and(|files| modified_since_snapshot(previous_version_dir, dir, files))
+    };
+    let cache = db.map(ScanCache::load);
+    let old = Scanner::new(previous_version_dir, cache.as_ref());
+    let new = Scanner::new(game_dir, cache.as_ref());
 
-    // === MODS (data/mods/) ===
     let old_mods_dir = previous_version_dir.join("data").join("mods");
     let new_mods_dir = game_dir.join("data").join("mods");
+    let old_font_dir = previous_version_dir.join("font");
+    let old_data_font_dir = previous_version_dir.join("data").join("font");
+
+    let (mods, user_mods, tilesets, soundpacks, fonts, data_fonts) = thread::scope(|s| {
+        // === MODS (data/mods/) ===
+        let mods = s.spawn(|| scan_both(|| old.mods(&old_mods_dir), || new.mods(&new_mods_dir)));
+
+        // === USER MODS (mods/) ===
+        let user_mods = s.spawn(|| {
+            scan_both(
+                || old.mods(&previous_version_dir.join("mods")),
+                || new.mods(&game_dir.join("mods")),
+            )
+        });
+
+        // === TILESETS (gfx/) ===
+        let tilesets = s.spawn(|| {
+            scan_both(
+                || old.tilesets(&previous_version_dir.join("gfx")),
+                || new.tilesets(&game_dir.join("gfx")),
+            )
+        });
 
-    let old_mods = scan_mods_directory(&old_mods_dir);
-    let new_mods = scan_mods_directory(&new_mods_dir);
+        // === SOUNDPACKS (data/sound/) ===
+        let soundpacks = s.spawn(|| {
+            let (old_soundpacks, new_soundpacks) = scan_both(
+                || old.soundpacks(&previous_version_dir.join("data").join("sound")),
+                || new.soundpacks(&game_dir.join("data").join("sound")),
+            );
+
+            // Custom soundpacks (not in new version at all)
+            let custom = find_custom_soundpacks(&old_soundpacks, &new_soundpacks);
+
+            // Soundpacks in both versions that have custom files to merge
+            let merges = if cache.is_some() {
+                find_soundpack_merges_with(
+                    &old_soundpacks,
+                    &new_soundpacks,
+                    |old_path, new_path| {
+                        let (old_files, new_files) = scan_both(
+                            || old.soundpack_files(old_path),
+                            || new.soundpack_files(new_path),
+                        );
+                        old_files.difference(&new_files).cloned().collect()
+                    },
+                )
+            } else {
+                find_soundpack_merges(&old_soundpacks, &new_soundpacks)
+            };
+            let replaced = find_replaced(
+                &old_soundpacks,
+                &new_soundpacks,
+                |s| &s.path,
+                mode,
+                is_modified,
+            );
+            (custom, merges, replaced)
+        });
+
+        // === FONTS (font/) ===
+        let fonts = s.spawn(|| {
+            let (old_fonts, new_fonts) = scan_both(
+                || scan_fonts_directory(&old_font_dir),
+                || scan_fonts_directory(&game_dir.join("font")),
+            );
+            find_custom_fonts(&old_fonts, &new_fonts, &old_font_dir)
+        });
+
+        // === DATA FONTS (data/font/) ===
+        let data_fonts = s.spawn(|| {
+            let (old_fonts, new_fonts) = scan_both(
+                || scan_fonts_directory(&old_data_font_dir),
+                || scan_fonts_directory(&game_dir.join("data").join("font")),
+            );
+            find_custom_fonts(&old_fonts, &new_fonts, &old_data_font_dir)
+        });
+
+        (
+            join(mods),
+            join(user_mods),
+            join(tilesets),
+            join(soundpacks),
+            join(fonts),
+            join(data_fonts),
+        )
+    });
+
+    if let Some(db) = db {
+        // Both installations cache under the same relative paths; store the
+        // new one last so its scans win, as it's the one archived next time
+        let mut fresh = old.fresh.into_inner().unwrap();
+        fresh.extend(new.fresh.into_inner().unwrap());
+        if !fresh.is_empty()
+            && let Err(e) = db.store_scan_cache(&fresh)
+        {
+            tracing::debug!("Failed to store {} content scans: {}", fresh.len(), e);
+        }
+    }
+
+    let (old_mods, new_mods) = mods;
     plan.custom_mods = find_custom_mods(&old_mods, &new_mods);
 
     tracing::info!(
@@ -432,24 +954,33 @@ pub fn create_migration_plan(previous_version_dir: &Path, game_dir: &Path) -> Mi
         old_mods.len()
     );
 
-    // === USER MODS (mods/) ===
-    let old_user_mods_dir = previous_version_dir.join("mods");
-    let new_user_mods_dir = game_dir.join("mods");
-
-    let old_user_mods = scan_mods_directory(&old_user_mods_dir);
-    let new_user_mods = scan_mods_directory(&new_user_mods_dir);
+    let (old_user_mods, new_user_mods) = user_mods;
     plan.custom_user_mods = find_custom_mods(&old_user_mods, &new_user_mods);
 
     if !plan.custom_user_mods.is_empty() {
         tracing::info!("Found {} custom user mods", plan.custom_user_mods.len());
     }
 
-    // === TILESETS (gfx/) ===
-    let old_gfx_dir = previous_version_dir.join("gfx");
-    let new_gfx_dir = game_dir.join("gfx");
+    // Check restored mods against everything that will be installed
+    let restored_mods: Vec<ModInfo> = plan
+        .custom_mods
+        .iter()
+        .chain(&plan.custom_user_mods)
+        .cloned()
+        .collect();
+    let available: HashSet<String> = new_mods
+        .keys()
+        .chain(new_user_mods.keys())
+        .cloned()
+        .chain(restored_mods.iter().map(|m| m.id.clone()))
+        .collect();
+    plan.mod_warnings = find_mod_warnings(&restored_mods, &available);
+
+    for warning in &plan.mod_warnings {
+        tracing::warn!("Custom mod may be incompatible: {}", warning);
+    }
 
-    let old_tilesets = scan_tilesets_directory(&old_gfx_dir);
-    let new_tilesets = scan_tilesets_directory(&new_gfx_dir);
+    let (old_tilesets, new_tilesets) = tilesets;
     plan.custom_tilesets = find_custom_tilesets(&old_tilesets, &new_tilesets);
 
     tracing::info!(
@@ -458,18 +989,15 @@ pub fn create_migration_plan(previous_version_dir: &Path, game_dir: &Path) -> Mi
         old_tilesets.len()
     );
 
-    // === SOUNDPACKS (data/sound/) ===
-    let old_sound_dir = previous_version_dir.join("data").join("sound");
-    let new_sound_dir = game_dir.join("data").join("sound");
-
-    let old_soundpacks = scan_soundpacks_directory(&old_sound_dir);
-    let new_soundpacks = scan_soundpacks_directory(&new_sound_dir);
-
-    // Custom soundpacks (not in new version at all)
-    plan.custom_soundpacks = find_custom_soundpacks(&old_soundpacks, &new_soundpacks);
-
-    // Soundpacks in both versions that have custom files to merge
-    plan.soundpack_merges = find_soundpack_merges(&old_soundpacks, &new_soundpacks);
+    let replaced_soundpacks;
+    (
+        plan.custom_soundpacks,
+        plan.soundpack_merges,
+        replaced_soundpacks,
+    ) = soundpacks;
+    // A replaced soundpack brings its custom files along
+    plan.soundpack_merges
+        .retain(|merge| !replaced_soundpacks.iter().any(|r| r.name == merge.name));
 
     tracing::info!(
         "Found {} custom soundpacks and {} soundpacks with custom files to merge",
@@ -477,31 +1005,62 @@ pub fn create_migration_plan(previous_version_dir: &Path, game_dir: &Path) -> Mi
         plan.soundpack_merges.len()
     );
 
-    // === FONTS (font/) ===
-    let old_font_dir = previous_version_dir.join("font");
-    let new_font_dir = game_dir.join("font");
-
-    let old_fonts = scan_fonts_directory(&old_font_dir);
-    let new_fonts = scan_fonts_directory(&new_font_dir);
-    plan.custom_fonts = find_custom_fonts(&old_fonts, &new_fonts, &old_font_dir);
-
+    plan.custom_fonts = fonts;
     if !plan.custom_fonts.is_empty() {
         tracing::info!("Found {} custom fonts", plan.custom_fonts.len());
     }
 
-    // === DATA FONTS (data/font/) ===
-    let old_data_font_dir = previous_version_dir.join("data").join("font");
-    let new_data_font_dir = game_dir.join("data").join("font");
-
-    let old_data_fonts = scan_fonts_directory(&old_data_font_dir);
-    let new_data_fonts = scan_fonts_directory(&new_data_font_dir);
-    plan.custom_data_fonts =
-        find_custom_fonts(&old_data_fonts, &new_data_fonts, &old_data_font_dir);
-
+    plan.custom_data_fonts = data_fonts;
     if !plan.custom_data_fonts.is_empty() {
         tracing::info!("Found {} custom data fonts", plan.custom_data_fonts.len());
     }
 
+    // === Official content carried forward by the migration mode ===
+    plan.replaced = find_replaced(&old_mods, &new_mods, |m| &m.path, mode, is_modified);
+    plan.replaced.extend(find_replaced(
+        &old_user_mods,
+        &new_user_mods,
+        |m| &m.path,
+        mode,
+        is_modified,
+    ));
+    plan.replaced.extend(find_replaced(
+        &old_tilesets,
+        &new_tilesets,
+        |t| &t.path,
+        mode,
+        is_modified,
+    ));
+    plan.replaced.extend(replaced_soundpacks);
+    if !plan.replaced.is_empty() {
+        tracing::info!(
+            "Carrying forward {} official items ({:?})",
+            plan.replaced.len(),
+            mode
+        );
+    }
+
+    // === Official mods the user edited that are left to the new build ===
+    // (in the other modes every edited mod is carried forward instead)
+    if mode == MigrationMode::CustomOnly {
+        plan.overwritten_mods = find_replaced(
+            &old_mods,
+            &new_mods,
+            |m| &m.path,
+            MigrationMode::CustomAndModified,
+            is_modified,
+        )
+        .into_iter()
+        .map(|mod_info| mod_info.name)
+        .collect();
+        if !plan.overwritten_mods.is_empty() {
+            tracing::warn!(
+                "Edited official mods will be overwritten by the new build: {}",
+                plan.overwritten_mods.join(", ")
+            );
+        }
+    }
+
     // === user-default-mods.json ===
     let old_user_default_mods = old_mods_dir.join("user-default-mods.json");
     let new_user_default_mods = new_mods_dir.join("user-default-mods.json");
@@ -646,6 +1205,46 @@ mod tests {
         assert_eq!(result.unwrap().name, "Custom Soundpack");
     }
 
+    #[test]
+    fn test_parse_tileset_name_quoted_and_commented() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let quoted = temp_dir.path().join("quoted");
+        fs::create_dir(&quoted).unwrap();
+        fs::write(quoted.join("tileset.txt"), "NAME \"Foo, Bar\"\nVIEW Foo").unwrap();
+        assert_eq!(parse_tileset_info(&quoted).unwrap().name, "Foo, Bar");
+
+        let commented = temp_dir.path().join("commented");
+        fs::create_dir(&commented).unwrap();
+        fs::write(
+            commented.join("soundpack.txt"),
+            "# Soundpack metadata\nNAME CC-Sounds # renamed in 0.G\n",
+        )
+        .unwrap();
+        assert_eq!(parse_soundpack_info(&commented).unwrap().name, "CC-Sounds");
+    }
+
+    #[test]
+    fn test_parse_asset_name_bom_and_latin1() {
+        let temp_dir = TempDir::new().unwrap();
+        let soundpack_dir = temp_dir.path().join("bom_soundpack");
+        fs::create_dir(&soundpack_dir).unwrap();
+        fs::write(
+            soundpack_dir.join("soundpack.txt"),
+            "\u{FEFF}NAME CC-Sounds\u{200B}\r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            parse_soundpack_info(&soundpack_dir).unwrap().name,
+            "CC-Sounds"
+        );
+
+        let tileset_dir = temp_dir.path().join("latin1_tileset");
+        fs::create_dir(&tileset_dir).unwrap();
+        fs::write(tileset_dir.join("tileset.txt"), b"NAME Tuiles \xE9t\xE9\n").unwrap();
+        assert_eq!(parse_tileset_info(&tileset_dir).unwrap().name, "Tuiles été");
+    }
+
     #[test]
     fn test_find_custom_mods_set_difference() {
         let mut old_mods = HashMap::new();
@@ -654,6 +1253,9 @@ mod tests {
             ModInfo {
                 id: "official_mod".to_string(),
                 path: PathBuf::from("/old/official_mod"),
+                dependencies: Vec::new(),
+                obsolete: false,
+                core: false,
             },
         );
         old_mods.insert(
@@ -661,6 +1263,9 @@ mod tests {
             ModInfo {
                 id: "custom_mod".to_string(),
                 path: PathBuf::from("/old/custom_mod"),
+                dependencies: Vec::new(),
+                obsolete: false,
+                core: false,
             },
         );
 
@@ -670,6 +1275,9 @@ mod tests {
             ModInfo {
                 id: "official_mod".to_string(),
                 path: PathBuf::from("/new/official_mod"),
+                dependencies: Vec::new(),
+                obsolete: false,
+                core: false,
             },
         );
 
@@ -679,6 +1287,28 @@ mod tests {
         assert_eq!(custom[0].id, "custom_mod");
     }
 
+    #[test]
+    fn test_find_mod_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_dir = temp_dir.path().join("stale_mod");
+        fs::create_dir(&mod_dir).unwrap();
+        fs::write(
+            mod_dir.join("modinfo.json"),
+            r#"[{"type": "MOD_INFO", "id": "stale", "dependencies": ["dda", "gone"], "obsolete": true}]"#,
+        )
+        .unwrap();
+
+        let mod_info = parse_mod_ident(&mod_dir).unwrap();
+        assert_eq!(mod_info.dependencies, vec!["dda", "gone"]);
+        assert!(mod_info.obsolete);
+
+        let available: HashSet<String> = ["dda".to_string()].into_iter().collect();
+        let warnings = find_mod_warnings(&[mod_info], &available);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].reason, "marked obsolete");
+        assert_eq!(warnings[1].reason, "requires missing mods: gone");
+    }
+
     #[test]
     fn test_find_custom_tilesets_set_difference() {
         let mut old_tilesets = HashMap::new();
@@ -807,13 +1437,100 @@ mod tests {
         )
         .unwrap();
 
-        let plan = create_migration_plan(&previous_dir, &game_dir);
+        let plan = create_migration_plan(
+            &previous_dir,
+            &game_dir,
+            None,
+            MigrationMode::CustomOnly,
+            None,
+        );
 
         // Should find only custom_mod as needing restoration
         assert_eq!(plan.custom_mods.len(), 1);
         assert_eq!(plan.custom_mods[0].id, "my_custom_mod");
     }
 
+    #[test]
+    fn test_migration_plan_modes() {
+        let temp_dir = TempDir::new().unwrap();
+        let previous_dir = temp_dir.path().join(".phoenix_archive");
+        let game_dir = temp_dir.path().join("game");
+
+        // Three official mods in both versions: the user edited one, the
+        // update changed another
+        for (id, new_items) in [("edited", "[]"), ("updated", "[1]"), ("same", "[]")] {
+            for (root, items) in [(&previous_dir, "[]"), (&game_dir, new_items)] {
+                let dir = root.join("data/mods").join(id);
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(
+                    dir.join("modinfo.json"),
+                    format!(r#"{{"type": "MOD_INFO", "id": "{}"}}"#, id),
+                )
+                .unwrap();
+                fs::write(dir.join("items.json"), items).unwrap();
+            }
+        }
+        let baseline = crate::integrity::hash_installation(&previous_dir).unwrap();
+        fs::write(previous_dir.join("data/mods/edited/items.json"), "[2]").unwrap();
+
+        let plan =
+            |mode, baseline| create_migration_plan(&previous_dir, &game_dir, None, mode, baseline);
+        let replaced = |mode| -> Vec<String> {
+            plan(mode, Some(&baseline))
+                .replaced
+                .into_iter()
+                .map(|r| r.name)
+                .collect()
+        };
+
+        assert!(replaced(MigrationMode::CustomOnly).is_empty());
+        assert_eq!(replaced(MigrationMode::CustomAndModified), vec!["edited"]);
+        assert_eq!(
+            replaced(MigrationMode::Everything),
+            vec!["edited", "same", "updated"]
+        );
+
+        // Only the default mode reports the edited mod as overwritten
+        let overwritten = |mode| plan(mode, Some(&baseline)).overwritten_mods;
+        assert_eq!(overwritten(MigrationMode::CustomOnly), vec!["edited"]);
+        assert!(overwritten(MigrationMode::CustomAndModified).is_empty());
+
+        // Without a snapshot nothing counts as edited
+        let unknown = plan(MigrationMode::CustomAndModified, None);
+        assert!(unknown.replaced.is_empty());
+        assert!(
+            plan(MigrationMode::CustomOnly, None)
+                .overwritten_mods
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_modified_since_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let dir = root.join("gfx/tiles");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/file.txt"), "abc").unwrap();
+        fs::write(root.join("gfx/other.txt"), "abc").unwrap();
+        let baseline = crate::integrity::hash_installation(root).unwrap();
+        assert!(!modified_since_snapshot(root, &dir, &baseline));
+
+        // Same size, different contents
+        fs::write(dir.join("sub/file.txt"), "xyz").unwrap();
+        assert!(modified_since_snapshot(root, &dir, &baseline));
+
+        // Extra file
+        fs::write(dir.join("sub/file.txt"), "abc").unwrap();
+        fs::write(dir.join("extra.txt"), "").unwrap();
+        assert!(modified_since_snapshot(root, &dir, &baseline));
+
+        // Removed file
+        fs::remove_file(dir.join("extra.txt")).unwrap();
+        fs::remove_file(dir.join("sub/file.txt")).unwrap();
+        assert!(modified_since_snapshot(root, &dir, &baseline));
+    }
+
     #[test]
     fn test_scan_soundpack_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -880,7 +1597,13 @@ mod tests {
         fs::write(new_soundpack.join("soundpack.txt"), "NAME CC-Sounds\n").unwrap();
         fs::write(new_soundpack.join("soundset.json"), "{}").unwrap();
 
-        let plan = create_migration_plan(&previous_dir, &game_dir);
+        let plan = create_migration_plan(
+            &previous_dir,
+            &game_dir,
+            None,
+            MigrationMode::CustomOnly,
+            None,
+        );
 
         // Should have no custom soundpacks (same NAME exists in both)
         assert!(plan.custom_soundpacks.is_empty());
@@ -890,4 +1613,145 @@ mod tests {
         assert_eq!(plan.soundpack_merges[0].name, "CC-Sounds");
         assert_eq!(plan.soundpack_merges[0].custom_files.len(), 1);
     }
+
+    #[test]
+    fn test_create_migration_plan_uses_scan_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let previous_dir = temp_dir.path().join(".phoenix_archive");
+        let game_dir = temp_dir.path().join("game");
+        let db = Database::open_in_memory().unwrap();
+
+        let custom_mod = previous_dir.join("data/mods/custom");
+        fs::create_dir_all(&custom_mod).unwrap();
+        fs::write(
+            custom_mod.join("modinfo.json"),
+            r#"[{"type": "MOD_INFO", "id": "custom"}]"#,
+        )
+        .unwrap();
+        let old_soundpack = previous_dir.join("data/sound/CC-Sounds");
+        fs::create_dir_all(&old_soundpack).unwrap();
+        fs::write(old_soundpack.join("soundpack.txt"), "NAME CC-Sounds\n").unwrap();
+        fs::write(old_soundpack.join("soundset.json"), "{}").unwrap();
+        fs::write(old_soundpack.join("custom.ogg"), b"custom").unwrap();
+        // Soundpacks are matched by name, so a different directory keeps the
+        // two installs' file sets under separate cache keys
+        let new_soundpack = game_dir.join("data/sound/CC-Sounds-2");
+        fs::create_dir_all(&new_soundpack).unwrap();
+        fs::write(new_soundpack.join("soundpack.txt"), "NAME CC-Sounds\n").unwrap();
+        fs::write(new_soundpack.join("soundset.json"), "{}").unwrap();
+
+        let uncached = create_migration_plan(
+            &previous_dir,
+            &game_dir,
+            None,
+            MigrationMode::CustomOnly,
+            None,
+        );
+        let first = create_migration_plan(
+            &previous_dir,
+            &game_dir,
+            Some(&db),
+            MigrationMode::CustomOnly,
+            None,
+        );
+        assert_eq!(first.custom_mods.len(), 1);
+        assert_eq!(first.soundpack_merges.len(), 1);
+        assert_eq!(first.custom_mods[0].id, uncached.custom_mods[0].id);
+        assert_eq!(first.custom_mods[0].path, custom_mod);
+
+        // The cached scan is served while the directory is unchanged
+        let stamp = modified_stamp(&[
+            custom_mod.clone(),
+            custom_mod.join("modinfo.json"),
+            custom_mod.join("modinfo.json.disabled"),
+        ])
+        .unwrap();
+        let cached = ModInfo {
+            id: "renamed".to_string(),
+            path: PathBuf::new(),
+            dependencies: Vec::new(),
+            obsolete: false,
+            core: false,
+        };
+        let entry = |stamp, data: String| ScanCacheEntry {
+            kind: versioned_kind("mod"),
+            path: "data/mods/custom".to_string(),
+            stamp,
+            data,
+        };
+        let data = serde_json::to_string(&Some(cached)).unwrap();
+
+        // Entries from an older scan format are ignored
+        let outdated = ScanCacheEntry {
+            kind: "mod".to_string(),
+            ..entry(stamp, data.clone())
+        };
+        db.store_scan_cache(&[outdated]).unwrap();
+        let ignored = create_migration_plan(
+            &previous_dir,
+            &game_dir,
+            Some(&db),
+            MigrationMode::CustomOnly,
+            None,
+        );
+        assert_eq!(ignored.custom_mods[0].id, "custom");
+
+        db.store_scan_cache(&[entry(stamp, data)]).unwrap();
+        let second = create_migration_plan(
+            &previous_dir,
+            &game_dir,
+            Some(&db),
+            MigrationMode::CustomOnly,
+            None,
+        );
+        assert_eq!(second.custom_mods[0].id, "renamed");
+        assert_eq!(second.custom_mods[0].path, custom_mod);
+        assert_eq!(second.soundpack_merges[0].custom_files.len(), 1);
+
+        // A different stamp invalidates the entry
+        db.store_scan_cache(&[entry(stamp - 1, "null".to_string())])
+            .unwrap();
+        let third = create_migration_plan(
+            &previous_dir,
+            &game_dir,
+            Some(&db),
+            MigrationMode::CustomOnly,
+            None,
+        );
+        assert_eq!(third.custom_mods[0].id, "custom");
+    }
+
+    #[test]
+    fn test_scan_cache_keeps_new_installation_scans() {
+        let temp_dir = TempDir::new().unwrap();
+        let previous_dir = temp_dir.path().join(".phoenix_archive");
+        let game_dir = temp_dir.path().join("game");
+        let db = Database::open_in_memory().unwrap();
+
+        for (root, id) in [(&previous_dir, "old"), (&game_dir, "new")] {
+            let mod_dir = root.join("data/mods/shared");
+            fs::create_dir_all(&mod_dir).unwrap();
+            fs::write(
+                mod_dir.join("modinfo.json"),
+                format!(r#"[{{"type": "MOD_INFO", "id": "{}"}}]"#, id),
+            )
+            .unwrap();
+        }
+
+        create_migration_plan(
+            &previous_dir,
+            &game_dir,
+            Some(&db),
+            MigrationMode::CustomOnly,
+            None,
+        );
+
+        let entries = db.load_scan_cache().unwrap();
+        let shared = entries
+            .iter()
+            .find(|entry| entry.path == "data/mods/shared")
+            .unwrap();
+        let info: Option<ModInfo> = serde_json::from_str(&shared.data).unwrap();
+        assert_eq!(info.unwrap().id, "new");
+    }
 }
@@ -0,0 +1,127 @@
+//! Installing mods from local archives.
+//!
+//! Mods are copied into the game's user `mods/` directory, which updates
+//! carry over to the new version. Extraction reuses the soundpack helpers.
+
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use tokio::sync::watch;
+
+use crate::app_data::{game_config, migration_config};
+use crate::migration::{self, ModInfo};
+use crate::soundpack::{self, SoundpackError, SoundpackProgress};
+
+/// Errors that can occur while installing a mod
+#[derive(Error, Debug)]
+pub enum ModError {
+    #[error("No modinfo.json found in archive")]
+    NoModInfo,
+
+    #[error("Mod already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error(transparent)]
+    Archive(#[from] SoundpackError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// User mods directory inside the game folder
+pub fn user_mods_dir(game_dir: &Path) -> PathBuf {
+    game_dir.join("mods")
+}
+
+/// Find the mod directory (the one holding modinfo.json) in an extracted archive
+pub fn find_mod_dir(extract_dir: &Path) -> Option<PathBuf> {
+    let metadata = &game_config().metadata;
+    let migration_cfg = migration_config();
+    walkdir::WalkDir::new(extract_dir)
+        .min_depth(migration_cfg.soundpack.min_search_depth)
+        .max_depth(migration_cfg.soundpack.max_search_depth)
+        .into_iter()
+        .flatten()
+        .find(|entry| {
+            entry.file_name() == metadata.mod_info.as_str()
+                || entry.file_name() == metadata.mod_info_disabled.as_str()
+        })
+        .and_then(|entry| entry.path().parent().map(Path::to_path_buf))
+}
+
+/// Install a mod from a local archive file into the user mods directory
+pub async fn install_from_file(
+    archive_path: PathBuf,
+    game_dir: PathBuf,
+) -> Result<ModInfo, ModError> {
+    let temp_dir = tempfile::tempdir()?;
+    let extract_dir = temp_dir.path().join("extract");
+    std::fs::create_dir_all(&extract_dir)?;
+
+    let progress_tx = watch::channel(SoundpackProgress::default()).0;
+    soundpack::extract_archive(archive_path, extract_dir.clone(), progress_tx).await?;
+
+    let source = find_mod_dir(&extract_dir).ok_or(ModError::NoModInfo)?;
+    let info = migration::parse_mod_ident(&source).ok_or(ModError::NoModInfo)?;
+    let dir_name = source
+        .file_name()
+        .ok_or_else(|| SoundpackError::ExtractionFailed("Invalid path".to_string()))?;
+
+    let dest = user_mods_dir(&game_dir).join(dir_name);
+    if dest.exists() {
+        return Err(ModError::AlreadyExists(
+            dir_name.to_string_lossy().to_string(),
+        ));
+    }
+    tokio::fs::create_dir_all(user_mods_dir(&game_dir)).await?;
+
+    let dst = dest.clone();
+    tokio::task::spawn_blocking(move || soundpack::copy_dir_sync(&source, &dst))
+        .await
+        .map_err(|_| SoundpackError::Cancelled)??;
+
+    tracing::info!("Installed mod '{}' to {:?}", info.id, dest);
+    Ok(ModInfo { path: dest, ..info })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+
+    #[tokio::test]
+    async fn test_install_from_file_copies_nested_mod() {
+        let temp = TempDir::new().unwrap();
+        let archive_path = temp.path().join("my_mod.zip");
+        let game_dir = temp.path().join("game");
+        std::fs::create_dir_all(&game_dir).unwrap();
+
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&archive_path).unwrap());
+        writer
+            .start_file(
+                "my_mod-main/MyMod/modinfo.json",
+                SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer
+            .write_all(br#"[{"type": "MOD_INFO", "id": "my_mod"}]"#)
+            .unwrap();
+        writer
+            .start_file("my_mod-main/MyMod/items.json", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"[]").unwrap();
+        writer.finish().unwrap();
+
+        let info = install_from_file(archive_path.clone(), game_dir.clone())
+            .await
+            .unwrap();
+        assert_eq!(info.id, "my_mod");
+        assert_eq!(info.path, game_dir.join("mods/MyMod"));
+        assert!(game_dir.join("mods/MyMod/items.json").exists());
+
+        let again = install_from_file(archive_path, game_dir).await;
+        assert!(matches!(again, Err(ModError::AlreadyExists(_))));
+    }
+}
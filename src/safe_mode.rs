@@ -0,0 +1,158 @@
+//! Safe-mode launches with all mods disabled.
+//!
+//! CDDA only loads mods whose `modinfo.json` it can find, so renaming each
+//! file to `modinfo.json.disabled` (the same convention the migration code
+//! reads) turns a mod off without touching its content. Core mods like
+//! `dda` are left alone since the game can't start without them.
+//!
+//! The renamed files are listed in a marker file in the game directory, so
+//! [`restore`] only re-enables mods that safe mode disabled, even if the
+//! launcher was closed while the game was running.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::app_data::game_config;
+use crate::migration::parse_mod_ident;
+
+/// Marker listing the modinfo files disabled for a safe-mode launch
+const MARKER_FILE: &str = ".phoenix_safe_mode";
+
+/// Mod directories searched for modinfo files (bundled and user mods)
+fn mod_roots(game_dir: &Path) -> [PathBuf; 2] {
+    [
+        game_dir.join(&game_config().directories.data).join("mods"),
+        game_dir.join("mods"),
+    ]
+}
+
+/// Check whether mods are currently disabled by safe mode
+pub fn is_active(game_dir: &Path) -> bool {
+    game_dir.join(MARKER_FILE).exists()
+}
+
+/// Disable every non-core mod for a safe-mode launch.
+///
+/// Returns the number of mods disabled. Any mods left disabled by an earlier
+/// safe-mode launch are restored first.
+pub fn enable(game_dir: &Path) -> Result<usize> {
+    restore(game_dir)?;
+
+    let metadata = &game_config().metadata;
+    let mut disabled: Vec<PathBuf> = Vec::new();
+
+    for root in mod_roots(game_dir) {
+        let Ok(entries) = fs::read_dir(&root) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let mod_dir = entry.path();
+            let modinfo = mod_dir.join(&metadata.mod_info);
+            if !modinfo.is_file() || parse_mod_ident(&mod_dir).is_some_and(|info| info.core) {
+                continue;
+            }
+
+            let target = mod_dir.join(&metadata.mod_info_disabled);
+            if let Err(e) = fs::rename(&modinfo, &target) {
+                // Put back what was already renamed before giving up
+                for path in &disabled {
+                    let _ = fs::rename(disabled_path(path), path);
+                }
+                return Err(e).with_context(|| format!("Failed to disable {:?}", modinfo));
+            }
+            disabled.push(modinfo);
+        }
+    }
+
+    let listing: Vec<String> = disabled
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    fs::write(game_dir.join(MARKER_FILE), listing.join("\n"))
+        .context("Failed to write the safe mode marker")?;
+
+    tracing::info!("Safe mode: disabled {} mods", disabled.len());
+    Ok(disabled.len())
+}
+
+/// Re-enable the mods disabled by [`enable`].
+///
+/// Returns the number of mods restored; does nothing if safe mode isn't active.
+pub fn restore(game_dir: &Path) -> Result<usize> {
+    let marker = game_dir.join(MARKER_FILE);
+    let Ok(listing) = fs::read_to_string(&marker) else {
+        return Ok(0);
+    };
+
+    let mut restored = 0;
+    for line in listing.lines().filter(|line| !line.is_empty()) {
+        let modinfo = PathBuf::from(line);
+        let disabled = disabled_path(&modinfo);
+        // Skip mods the user re-enabled or removed in the meantime
+        if !disabled.exists() || modinfo.exists() {
+            continue;
+        }
+        fs::rename(&disabled, &modinfo)
+            .with_context(|| format!("Failed to re-enable {:?}", modinfo))?;
+        restored += 1;
+    }
+
+    fs::remove_file(&marker).context("Failed to remove the safe mode marker")?;
+    tracing::info!("Safe mode: re-enabled {} mods", restored);
+    Ok(restored)
+}
+
+/// Disabled counterpart of a modinfo file path
+fn disabled_path(modinfo: &Path) -> PathBuf {
+    modinfo.with_file_name(&game_config().metadata.mod_info_disabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_mod(dir: &Path, id: &str, core: bool) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("modinfo.json"),
+            format!(
+                r#"[{{"type": "MOD_INFO", "id": "{}", "core": {}}}]"#,
+                id, core
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_safe_mode_disables_and_restores_mods() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path();
+        write_mod(&game_dir.join("data/mods/dda"), "dda", true);
+        write_mod(&game_dir.join("data/mods/magiclysm"), "magiclysm", false);
+        write_mod(&game_dir.join("mods/my_mod"), "my_mod", false);
+        // Already disabled by the user: must stay disabled afterwards
+        let user_disabled = game_dir.join("mods/old_mod");
+        fs::create_dir_all(&user_disabled).unwrap();
+        fs::write(user_disabled.join("modinfo.json.disabled"), "[]").unwrap();
+
+        assert_eq!(enable(game_dir).unwrap(), 2);
+        assert!(is_active(game_dir));
+        assert!(game_dir.join("data/mods/dda/modinfo.json").exists());
+        assert!(
+            game_dir
+                .join("data/mods/magiclysm/modinfo.json.disabled")
+                .exists()
+        );
+        assert!(game_dir.join("mods/my_mod/modinfo.json.disabled").exists());
+
+        assert_eq!(restore(game_dir).unwrap(), 2);
+        assert!(!is_active(game_dir));
+        assert!(game_dir.join("data/mods/magiclysm/modinfo.json").exists());
+        assert!(game_dir.join("mods/my_mod/modinfo.json").exists());
+        assert!(user_disabled.join("modinfo.json.disabled").exists());
+        assert!(!user_disabled.join("modinfo.json").exists());
+    }
+}
@@ -0,0 +1,282 @@
+//! Self-update for the launcher.
+//!
+//! Checks Phoenix's own GitHub releases (separate from the game repository)
+//! against `CARGO_PKG_VERSION` and swaps in a newer executable. Windows can't
+//! overwrite a running executable but can rename it, so the current exe is
+//! renamed to `phoenix.exe.old`, the download moved into its place, and the
+//! new version runs on the next start. The leftover `.old` file is removed at
+//! startup by [`cleanup_previous`].
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::sync::watch;
+
+use crate::app_data::launcher_config;
+use crate::game::calculate_sha256;
+use crate::github::{GitHubClient, Release, ReleaseAsset};
+use crate::update::{self, UpdateProgress};
+
+/// A newer launcher release that can be installed
+#[derive(Debug, Clone, Serialize)]
+pub struct LauncherUpdate {
+    /// Version without the leading "v" (e.g. "0.6.0")
+    pub version: String,
+    /// Release page on GitHub
+    pub page_url: String,
+    /// Executable asset for this platform
+    #[serde(skip)]
+    pub asset: ReleaseAsset,
+}
+
+/// Version of the running launcher
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Parse a version tag like "v0.5.2" into its numeric components
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    // Ignore pre-release/build suffixes ("0.6.0-beta.1" compares as 0.6.0)
+    let core = version.split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Check whether `candidate` is a newer version than `current`
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+/// Find the launcher executable for this platform in a release.
+///
+/// Releases publish a bare `phoenix.exe` (Windows) or `phoenix` (Linux).
+pub fn find_launcher_asset(release: &Release) -> Option<&ReleaseAsset> {
+    let expected = format!("phoenix{}", std::env::consts::EXE_SUFFIX);
+    release
+        .assets
+        .iter()
+        .find(|asset| asset.name.eq_ignore_ascii_case(&expected))
+}
+
+/// Check the launcher's GitHub releases for a newer version
+pub async fn check(client: &GitHubClient) -> Result<Option<LauncherUpdate>> {
+    let repository = &launcher_config().github.launcher_repository;
+    let release = client.get_latest_release(repository).await?;
+
+    if !is_newer(&release.tag_name, current_version()) {
+        tracing::info!(
+            "Launcher is up to date ({} >= {})",
+            current_version(),
+            release.tag_name
+        );
+        return Ok(None);
+    }
+
+    let asset = find_launcher_asset(&release)
+        .with_context(|| {
+            format!(
+                "Launcher release {} has no executable for this platform",
+                release.tag_name
+            )
+        })?
+        .clone();
+
+    Ok(Some(LauncherUpdate {
+        version: release.tag_name.trim_start_matches(['v', 'V']).to_string(),
+        page_url: format!(
+            "https://github.com/{}/releases/tag/{}",
+            repository, release.tag_name
+        ),
+        asset,
+    }))
+}
+
+/// Path a file is moved to while being replaced (e.g. "phoenix.exe.old")
+fn sibling_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Download a launcher update and swap it in for the running executable.
+///
+/// Returns the path of the replaced executable. The new version runs the next
+/// time Phoenix starts.
+pub async fn install(
+    client: &GitHubClient,
+    launcher_update: &LauncherUpdate,
+    progress_tx: watch::Sender<UpdateProgress>,
+) -> Result<PathBuf> {
+    let current = std::env::current_exe().context("Could not locate the running launcher")?;
+    let staged = sibling_with_suffix(&current, ".new");
+
+    tracing::info!(
+        "Downloading launcher {} to {:?}",
+        launcher_update.version,
+        staged
+    );
+    // Download mirrors are for game releases only: the launcher replaces
+    // itself with this file, so it only comes from GitHub
+    update::download_asset(
+        client.client().clone(),
+        vec![launcher_update.asset.browser_download_url.clone()],
+        staged.clone(),
+        progress_tx,
+        // Launcher binaries are too small to be worth splitting
+        1,
+        None,
+    )
+    .await?;
+
+    let verified = {
+        let staged = staged.clone();
+        let asset = launcher_update.asset.clone();
+        tokio::task::spawn_blocking(move || verify_download(&staged, &asset)).await?
+    };
+    if let Err(e) = verified {
+        let _ = tokio::fs::remove_file(&staged).await;
+        return Err(e);
+    }
+
+    swap_executable(&current, &staged)?;
+    tracing::info!("Launcher updated to {}", launcher_update.version);
+    Ok(current)
+}
+
+/// Check a downloaded launcher against the size and SHA-256 digest GitHub
+/// published for its asset
+fn verify_download(path: &Path, asset: &ReleaseAsset) -> Result<()> {
+    let downloaded = std::fs::metadata(path)?.len();
+    if asset.size > 0 && downloaded != asset.size {
+        anyhow::bail!(
+            "Launcher download is incomplete ({} of {} bytes)",
+            downloaded,
+            asset.size
+        );
+    }
+
+    match asset
+        .digest
+        .as_deref()
+        .and_then(|d| d.strip_prefix("sha256:"))
+    {
+        Some(expected) => {
+            let actual = calculate_sha256(path)?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                anyhow::bail!(
+                    "Launcher download doesn't match its published checksum (expected {}, got {})",
+                    expected,
+                    actual
+                );
+            }
+        }
+        None => tracing::warn!(
+            "Launcher asset {} has no published checksum; only its size was checked",
+            asset.name
+        ),
+    }
+
+    Ok(())
+}
+
+/// Replace `current` with `staged`, keeping the old file as `<name>.old`
+fn swap_executable(current: &Path, staged: &Path) -> Result<()> {
+    let old = sibling_with_suffix(current, ".old");
+    if old.exists() {
+        std::fs::remove_file(&old)
+            .with_context(|| format!("Failed to remove previous launcher {:?}", old))?;
+    }
+
+    // Renaming a running executable is allowed, overwriting it is not
+    std::fs::rename(current, &old)
+        .with_context(|| format!("Failed to move the running launcher aside: {:?}", current))?;
+
+    if let Err(e) = std::fs::rename(staged, current) {
+        // Put the original back so the launcher still starts
+        let _ = std::fs::rename(&old, current);
+        return Err(e).context("Failed to move the new launcher into place");
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(current, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
+
+/// Remove the executable left behind by a previous self-update
+pub fn cleanup_previous() {
+    let Ok(current) = std::env::current_exe() else {
+        return;
+    };
+    let old = sibling_with_suffix(&current, ".old");
+    if old.exists() {
+        match std::fs::remove_file(&old) {
+            Ok(()) => tracing::info!("Removed previous launcher {:?}", old),
+            Err(e) => tracing::debug!("Could not remove previous launcher {:?}: {}", old, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_newer_compares_numerically() {
+        assert!(is_newer("v0.10.0", "0.9.3"));
+        assert!(is_newer("1.0.0", "0.99.99"));
+        assert!(is_newer("v0.5.1", "0.5.0"));
+        assert!(!is_newer("v0.5.0", "0.5.0"));
+        assert!(!is_newer("v0.4.9", "0.5.0"));
+        assert!(!is_newer("v0.6.0-beta.1", "0.6.0"));
+        assert!(!is_newer("nightly", "0.5.0"));
+    }
+
+    #[test]
+    fn test_verify_download_checks_digest() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("phoenix");
+        fs::write(&path, b"new launcher").unwrap();
+
+        let asset = |size, digest: Option<&str>| ReleaseAsset {
+            name: "phoenix".to_string(),
+            size,
+            browser_download_url: String::new(),
+            digest: digest.map(str::to_string),
+        };
+        let digest = format!("sha256:{}", calculate_sha256(&path).unwrap());
+
+        assert!(verify_download(&path, &asset(12, Some(&digest))).is_ok());
+        assert!(verify_download(&path, &asset(12, None)).is_ok());
+        assert!(verify_download(&path, &asset(13, Some(&digest))).is_err());
+        let wrong = format!("sha256:{}", "0".repeat(64));
+        assert!(verify_download(&path, &asset(12, Some(&wrong))).is_err());
+    }
+
+    #[test]
+    fn test_swap_executable_keeps_previous_version() {
+        let temp = TempDir::new().unwrap();
+        let current = temp.path().join("phoenix.exe");
+        let staged = temp.path().join("phoenix.exe.new");
+        fs::write(&current, "old build").unwrap();
+        fs::write(&staged, "new build").unwrap();
+
+        swap_executable(&current, &staged).unwrap();
+
+        assert_eq!(fs::read_to_string(&current).unwrap(), "new build");
+        assert_eq!(
+            fs::read_to_string(temp.path().join("phoenix.exe.old")).unwrap(),
+            "old build"
+        );
+        assert!(!staged.exists());
+    }
+}
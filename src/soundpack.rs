@@ -6,10 +6,12 @@
 //! - Downloading and installing soundpacks from the repository
 //! - Enabling/disabling soundpacks via file rename
 //! - Extracting archives (ZIP, RAR, 7z)
+//! - Previewing an installed soundpack by playing one of its samples
 //!
 //! Soundpack repository loaded via `app_data::soundpacks_repository()`.
 
 use crate::app_data::{RepoSoundpack, game_config, migration_config, soundpacks_repository};
+use crate::util::{decode_text, parse_info_field};
 use futures::StreamExt;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
@@ -125,6 +127,12 @@ pub enum SoundpackError {
 
     #[error("Task cancelled")]
     Cancelled,
+
+    #[error("No .ogg or .wav samples found in soundpack")]
+    NoPreviewSample,
+
+    #[error("Audio playback failed: {0}")]
+    Playback(String),
 }
 
 // ============================================================================
@@ -165,29 +173,14 @@ pub fn parse_soundpack_txt(soundpack_dir: &Path) -> Option<(String, String, bool
     };
 
     let content = std::fs::read(&file_path).ok()?;
-    // Use lossy UTF-8 conversion (original uses latin1)
-    let text = String::from_utf8_lossy(&content);
+    let text = decode_text(&content);
 
     let mut name = None;
     let mut view = None;
 
     for line in text.lines() {
-        let line = line.trim();
-        if line.starts_with("NAME") {
-            if let Some(rest) = line.strip_prefix("NAME") {
-                let value = rest.trim().replace(',', "");
-                if !value.is_empty() {
-                    name = Some(value);
-                }
-            }
-        } else if line.starts_with("VIEW")
-            && let Some(rest) = line.strip_prefix("VIEW")
-        {
-            let value = rest.trim().to_string();
-            if !value.is_empty() {
-                view = Some(value);
-            }
-        }
+        name = name.or_else(|| parse_info_field(line, "NAME", true));
+        view = view.or_else(|| parse_info_field(line, "VIEW", false));
 
         // Stop early if we found both
         if name.is_some() && view.is_some() {
@@ -202,7 +195,7 @@ pub fn parse_soundpack_txt(soundpack_dir: &Path) -> Option<(String, String, bool
 }
 
 /// Calculate directory size recursively
-fn calculate_dir_size(path: &Path) -> u64 {
+pub(crate) fn calculate_dir_size(path: &Path) -> u64 {
     let mut size = 0u64;
     if let Ok(entries) = std::fs::read_dir(path) {
         for entry in entries.flatten() {
@@ -283,6 +276,26 @@ pub async fn set_soundpack_enabled(
     Ok(())
 }
 
+/// Enable the soundpack at `soundpack_path` and disable every other one.
+///
+/// The game plays a single soundpack, so this is usually what enabling one
+/// means. Returns how many other soundpacks were disabled.
+pub async fn set_active_soundpack(
+    game_dir: &Path,
+    soundpack_path: &Path,
+) -> Result<usize, SoundpackError> {
+    set_soundpack_enabled(soundpack_path, true).await?;
+
+    let mut disabled = 0;
+    for other in list_installed_soundpacks(game_dir).await? {
+        if other.enabled && other.path != soundpack_path {
+            set_soundpack_enabled(&other.path, false).await?;
+            disabled += 1;
+        }
+    }
+    Ok(disabled)
+}
+
 /// Delete a soundpack directory
 pub async fn delete_soundpack(soundpack_path: PathBuf) -> Result<(), SoundpackError> {
     if !soundpack_path.exists() {
@@ -315,6 +328,53 @@ pub fn detect_archive_format(path: &Path) -> Option<ArchiveFormat> {
     }
 }
 
+/// Kind of game content an archive holds, in install priority order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ArchiveContent {
+    Soundpack,
+    Tileset,
+    Mod,
+}
+
+/// Identify the content of an archive from its metadata files, without
+/// extracting it.
+///
+/// Returns `None` if the archive has no soundpack.txt, tileset.txt or
+/// modinfo.json. Soundpacks win over tilesets, and tilesets over mods, when
+/// an archive holds more than one.
+pub fn detect_archive_content(path: &Path) -> Result<Option<ArchiveContent>, SoundpackError> {
+    if detect_archive_format(path).is_none() {
+        return Err(SoundpackError::InvalidArchiveFormat(
+            path.extension()
+                .map(|e| e.to_string_lossy().to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ));
+    }
+
+    let file = std::fs::File::open(path)?;
+    let archive =
+        zip::ZipArchive::new(file).map_err(|e| SoundpackError::ExtractionFailed(e.to_string()))?;
+
+    let metadata = &game_config().metadata;
+    let mut found = None;
+    for name in archive.file_names() {
+        let file_name = name.rsplit('/').next().unwrap_or(name);
+        let content = if file_name == metadata.soundpack_info
+            || file_name == metadata.soundpack_info_disabled
+        {
+            ArchiveContent::Soundpack
+        } else if file_name == metadata.tileset_info {
+            ArchiveContent::Tileset
+        } else if file_name == metadata.mod_info || file_name == metadata.mod_info_disabled {
+            ArchiveContent::Mod
+        } else {
+            continue;
+        };
+        found = Some(found.map_or(content, |prev: ArchiveContent| prev.min(content)));
+    }
+    Ok(found)
+}
+
 /// Extract archive to destination directory
 pub async fn extract_archive(
     archive_path: PathBuf,
@@ -413,7 +473,7 @@ pub fn find_soundpack_dir(extract_dir: &Path) -> Option<PathBuf> {
 }
 
 /// Extract filename from URL
-fn extract_filename_from_url(url: &str) -> String {
+pub(crate) fn extract_filename_from_url(url: &str) -> String {
     url.split('/')
         .next_back()
         .and_then(|s| s.split('?').next())
@@ -421,6 +481,38 @@ fn extract_filename_from_url(url: &str) -> String {
         .to_string()
 }
 
+/// Whether a repository download is large enough to confirm before starting
+pub fn is_large_download(size: u64) -> bool {
+    size >= migration_config().download.large_download_confirm_mb * 1024 * 1024
+}
+
+/// Ask the server for a download's size without fetching the body.
+///
+/// Returns `None` if the HEAD request fails or no `Content-Length` is sent.
+pub async fn probe_download_size(client: &reqwest::Client, url: &str) -> Option<u64> {
+    let response = match client.head(url).send().await {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            tracing::debug!("HEAD {} returned {}", url, response.status());
+            return None;
+        }
+        Err(e) => {
+            tracing::debug!("HEAD {} failed: {}", url, e);
+            return None;
+        }
+    };
+
+    // Read the header directly: a HEAD response has no body to size
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .filter(|&size| size > 0)
+}
+
 /// Download a file with progress tracking
 pub async fn download_file(
     client: &reqwest::Client,
@@ -564,7 +656,7 @@ pub async fn install_extracted_soundpack(
 }
 
 /// Synchronous directory copy
-fn copy_dir_sync(src: &Path, dst: &Path) -> Result<(), SoundpackError> {
+pub(crate) fn copy_dir_sync(src: &Path, dst: &Path) -> Result<(), SoundpackError> {
     std::fs::create_dir_all(dst)?;
 
     for entry in std::fs::read_dir(src)? {
@@ -596,9 +688,16 @@ pub async fn install_soundpack(
     let download_path = temp_dir.path().join(&filename);
 
     // Phase 1: Download
+    // Entries without a known size are probed first so the progress bar has
+    // a total from the start; if HEAD is unsupported, stream without one
+    let known_size = match repo_soundpack.size {
+        Some(size) => Some(size),
+        None => probe_download_size(&client, &repo_soundpack.url).await,
+    };
+
     let _ = progress_tx.send(SoundpackProgress {
         phase: SoundpackPhase::Downloading,
-        total_bytes: repo_soundpack.size.unwrap_or(0),
+        total_bytes: known_size.unwrap_or(0),
         ..Default::default()
     });
 
@@ -607,7 +706,7 @@ pub async fn install_soundpack(
         &repo_soundpack.url,
         &download_path,
         &progress_tx,
-        repo_soundpack.size,
+        known_size,
     )
     .await?;
 
@@ -674,6 +773,103 @@ pub fn is_soundpack_installed(installed: &[InstalledSoundpack], name: &str) -> b
     installed.iter().any(|s| s.name == name)
 }
 
+// ============================================================================
+// Preview
+// ============================================================================
+
+/// Audio formats the preview player can decode
+const PREVIEW_EXTENSIONS: [&str; 2] = ["ogg", "wav"];
+
+/// Pick a representative sample from a soundpack to preview.
+///
+/// Prefers the title music the game plays on the main menu, falling back to
+/// the first sample in name order so the choice is stable.
+pub fn find_preview_sample(soundpack_dir: &Path) -> Option<PathBuf> {
+    let samples: Vec<PathBuf> = walkdir::WalkDir::new(soundpack_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| PREVIEW_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        })
+        .collect();
+
+    let is_title = |path: &&PathBuf| {
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem.to_lowercase().contains("title"))
+    };
+    samples
+        .iter()
+        .find(is_title)
+        .or_else(|| samples.first())
+        .cloned()
+}
+
+/// A soundpack sample playing on the default audio device.
+///
+/// Playback stops when this is dropped.
+pub struct SoundPreview {
+    /// Soundpack directory the sample belongs to
+    pub soundpack: PathBuf,
+    /// Sample file being played
+    pub sample: PathBuf,
+    sink: rodio::Sink,
+    _stream: rodio::OutputStream,
+}
+
+impl SoundPreview {
+    /// Whether the sample has played to the end
+    pub fn is_finished(&self) -> bool {
+        self.sink.empty()
+    }
+}
+
+/// A soundpack sample opened for decoding, ready to play
+pub struct PreviewSample {
+    soundpack: PathBuf,
+    sample: PathBuf,
+    source: rodio::Decoder<std::io::BufReader<std::fs::File>>,
+}
+
+/// Find and open a representative sample from an installed soundpack.
+///
+/// Walks the soundpack directory; call from a blocking task.
+pub fn load_preview(soundpack_dir: &Path) -> Result<PreviewSample, SoundpackError> {
+    let sample = find_preview_sample(soundpack_dir).ok_or(SoundpackError::NoPreviewSample)?;
+    let file = std::fs::File::open(&sample)?;
+    let source =
+        rodio::Decoder::try_from(file).map_err(|e| SoundpackError::Playback(e.to_string()))?;
+
+    Ok(PreviewSample {
+        soundpack: soundpack_dir.to_path_buf(),
+        sample,
+        source,
+    })
+}
+
+/// Start playing a loaded sample on the default audio device
+pub fn play_preview(sample: PreviewSample) -> Result<SoundPreview, SoundpackError> {
+    let mut stream = rodio::OutputStreamBuilder::open_default_stream()
+        .map_err(|e| SoundpackError::Playback(e.to_string()))?;
+    stream.log_on_drop(false);
+
+    let sink = rodio::Sink::connect_new(stream.mixer());
+    sink.append(sample.source);
+
+    tracing::debug!("Previewing soundpack sample {:?}", sample.sample);
+    Ok(SoundPreview {
+        soundpack: sample.soundpack,
+        sample: sample.sample,
+        sink,
+        _stream: stream,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -769,4 +965,132 @@ mod tests {
         assert_eq!(progress.download_fraction(), 0.0);
         assert_eq!(progress.extract_fraction(), 0.0);
     }
+
+    #[test]
+    fn test_find_preview_sample_prefers_title_music() {
+        let temp = tempfile::tempdir().unwrap();
+        let pack = temp.path();
+        std::fs::create_dir_all(pack.join("music")).unwrap();
+        std::fs::create_dir_all(pack.join("sfx")).unwrap();
+        std::fs::write(pack.join("soundpack.txt"), "NAME: Test").unwrap();
+        std::fs::write(pack.join("sfx/alarm.wav"), b"").unwrap();
+        std::fs::write(pack.join("sfx/zombie.OGG"), b"").unwrap();
+
+        assert_eq!(find_preview_sample(pack), Some(pack.join("sfx/alarm.wav")));
+
+        std::fs::write(pack.join("music/Title_Theme.ogg"), b"").unwrap();
+        assert_eq!(
+            find_preview_sample(pack),
+            Some(pack.join("music/Title_Theme.ogg"))
+        );
+
+        let empty = temp.path().join("empty");
+        std::fs::create_dir_all(&empty).unwrap();
+        assert_eq!(find_preview_sample(&empty), None);
+    }
+
+    #[test]
+    fn test_detect_archive_content() {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        let temp = tempfile::tempdir().unwrap();
+        let write_zip = |name: &str, entries: &[&str]| {
+            let path = temp.path().join(name);
+            let mut writer = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+            for entry in entries {
+                writer
+                    .start_file(*entry, SimpleFileOptions::default())
+                    .unwrap();
+                writer.write_all(b"").unwrap();
+            }
+            writer.finish().unwrap();
+            path
+        };
+
+        let mod_zip = write_zip("mod.zip", &["MyMod/modinfo.json"]);
+        assert_eq!(
+            detect_archive_content(&mod_zip).unwrap(),
+            Some(ArchiveContent::Mod)
+        );
+
+        // A soundpack that also ships a mod is installed as a soundpack
+        let pack_zip = write_zip(
+            "pack.zip",
+            &["Pack/extras/modinfo.json", "Pack/soundpack.txt"],
+        );
+        assert_eq!(
+            detect_archive_content(&pack_zip).unwrap(),
+            Some(ArchiveContent::Soundpack)
+        );
+
+        let other_zip = write_zip("other.zip", &["readme.txt"]);
+        assert_eq!(detect_archive_content(&other_zip).unwrap(), None);
+
+        assert!(matches!(
+            detect_archive_content(Path::new("pack.7z")),
+            Err(SoundpackError::InvalidArchiveFormat(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_active_soundpack() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let game_dir = temp.path();
+        let sound_dir = soundpacks_dir(game_dir);
+        for name in ["alpha", "beta", "gamma"] {
+            std::fs::create_dir_all(sound_dir.join(name)).unwrap();
+            std::fs::write(
+                sound_dir.join(name).join("soundpack.txt"),
+                format!("NAME {}\nVIEW {}\n", name, name),
+            )
+            .unwrap();
+        }
+        set_soundpack_enabled(&sound_dir.join("beta"), false)
+            .await
+            .unwrap();
+
+        let disabled = set_active_soundpack(game_dir, &sound_dir.join("beta"))
+            .await
+            .unwrap();
+        assert_eq!(disabled, 2);
+
+        let enabled: Vec<String> = list_installed_soundpacks(game_dir)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.enabled)
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(enabled, vec!["beta"]);
+    }
+
+    #[test]
+    fn test_parse_soundpack_txt_bom_and_latin1() {
+        let temp = tempfile::TempDir::new().unwrap();
+
+        let bom = temp.path().join("bom");
+        std::fs::create_dir(&bom).unwrap();
+        std::fs::write(
+            bom.join("soundpack.txt"),
+            "\u{FEFF}NAME CC-Sounds\u{200B}\r\nVIEW CC Sounds\r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            parse_soundpack_txt(&bom),
+            Some(("CC-Sounds".to_string(), "CC Sounds".to_string(), true))
+        );
+
+        let latin1 = temp.path().join("latin1");
+        std::fs::create_dir(&latin1).unwrap();
+        std::fs::write(
+            latin1.join("soundpack.txt"),
+            b"NAME Bruits\nVIEW Bruits d\xE9fauts\n",
+        )
+        .unwrap();
+        assert_eq!(
+            parse_soundpack_txt(&latin1),
+            Some(("Bruits".to_string(), "Bruits défauts".to_string(), true))
+        );
+    }
 }
@@ -1,15 +1,36 @@
 //! Backup-related application state
 
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use eframe::egui;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
-use crate::backup::{self, BackupError, BackupInfo, BackupPhase, BackupProgress};
+use crate::backup::{
+    self, AutoBackupType, BackupDiff, BackupEntry, BackupError, BackupInfo, BackupNameVars,
+    BackupPhase, BackupProgress, RetentionPolicy,
+};
 use crate::state::StateEvent;
 use crate::task::{PollResult, poll_task};
 
+/// Backup name and the result of verifying its archive
+type VerifyOutcome = (String, Result<usize, BackupError>);
+
+/// A backup opened in the browser to pick files from
+#[derive(Debug, Default)]
+pub struct BackupBrowser {
+    /// Name of the backup being browsed
+    pub name: String,
+    /// Folder tree of the archive (empty while loading)
+    pub entries: Vec<BackupEntry>,
+    /// Whether the archive listing is still loading
+    pub loading: bool,
+    /// Path of the selected file or folder inside the archive
+    pub selected: Option<String>,
+}
+
 /// Backup-related state
 #[derive(Default)]
 pub struct BackupState {
@@ -17,12 +38,20 @@ pub struct BackupState {
     pub list: Vec<BackupInfo>,
     /// Whether backup list is being loaded
     pub list_loading: bool,
-    /// Index of selected backup in list
-    pub selected_idx: Option<usize>,
+    /// Indices of the selected backups in the list
+    pub selected: HashSet<usize>,
+    /// Last clicked backup, where a shift-click range starts
+    anchor: Option<usize>,
     /// Input field for manual backup name
     pub name_input: String,
-    /// Async task for backup operation
-    task: Option<JoinHandle<Result<(), BackupError>>>,
+    /// Note being edited for the selected backup
+    pub note_input: String,
+    /// Backup whose note is loaded into `note_input`
+    note_loaded_for: Option<String>,
+    /// Whether the name field has been prefilled from the name template
+    pub name_prefilled: bool,
+    /// Async task for backup operation (yields the safety backup taken by a restore)
+    task: Option<JoinHandle<Result<Option<String>, BackupError>>>,
     /// Async task for loading backup list
     list_task: Option<JoinHandle<Result<Vec<BackupInfo>, BackupError>>>,
     /// Channel receiver for backup progress
@@ -35,32 +64,40 @@ pub struct BackupState {
     pub confirm_delete: bool,
     /// Whether to show restore confirmation
     pub confirm_restore: bool,
+    /// Async task for comparing two backups
+    diff_task: Option<JoinHandle<Result<BackupDiff, BackupError>>>,
+    /// Result of the last backup comparison
+    pub diff: Option<BackupDiff>,
+    /// External backup archive awaiting restore confirmation
+    pub external_restore: Option<PathBuf>,
+    /// Whether to back up current saves before restoring the external archive
+    pub external_restore_backup_first: bool,
+    /// Async task verifying every backup archive in the background
+    verify_task: Option<JoinHandle<Result<Vec<VerifyOutcome>, BackupError>>>,
+    /// When the last background verification started
+    last_verify: Option<Instant>,
+    /// Backups that failed verification, with the reason
+    pub corrupt: HashMap<String, String>,
+    /// When the running game's last scheduled backup was due
+    last_scheduled: Option<Instant>,
+    /// Safety backup from the last restore, until the game has been played
+    pub restored_safety_backup: Option<String>,
+    /// Safety backup offered for deletion now that the game has been played
+    pub offer_safety_cleanup: Option<String>,
+    /// Async task deleting old safety backups
+    cleanup_task: Option<JoinHandle<Result<usize, BackupError>>>,
+    /// Backup open in the browser
+    pub browser: Option<BackupBrowser>,
+    /// Async task listing the browsed backup's contents
+    browse_task: Option<JoinHandle<Result<Vec<BackupEntry>, BackupError>>>,
+    /// Async task extracting a file or folder from the browsed backup
+    extract_task: Option<JoinHandle<Result<(usize, PathBuf), BackupError>>>,
 }
 
 impl BackupState {
     /// Check if a backup operation is in progress
     pub fn is_busy(&self) -> bool {
-        self.task.is_some() || self.list_loading
-    }
-
-    /// Validate a backup name
-    pub fn validate_name(&self, name: &str) -> Result<(), String> {
-        if name.is_empty() {
-            return Err("Name cannot be empty".to_string());
-        }
-        if name.len() > 100 {
-            return Err("Name too long (max 100 chars)".to_string());
-        }
-        for c in name.chars() {
-            if !c.is_alphanumeric() && c != '_' && c != '-' && c != ' ' {
-                return Err(format!("Invalid character: '{}'", c));
-            }
-        }
-        // Check if already exists
-        if self.list.iter().any(|b| b.name == name) {
-            return Err("A backup with this name already exists".to_string());
-        }
-        Ok(())
+        self.task.is_some() || self.cleanup_task.is_some() || self.list_loading
     }
 
     /// Start a manual backup
@@ -88,7 +125,7 @@ impl BackupState {
 
         self.task = Some(tokio::spawn(async move {
             backup::create_backup(&game_dir, &name, compression_level, progress_tx).await?;
-            Ok(())
+            Ok(None)
         }));
 
         // Clear input on success start
@@ -100,6 +137,187 @@ impl BackupState {
         )))
     }
 
+    /// The selected backup, when exactly one is selected.
+    ///
+    /// Restoring, browsing, comparing and notes work on a single backup.
+    pub fn selected_idx(&self) -> Option<usize> {
+        match self.selected.len() {
+            1 => self.selected.iter().next().copied(),
+            _ => None,
+        }
+    }
+
+    /// Select only the backup at `idx`
+    pub fn select_only(&mut self, idx: usize) {
+        self.selected = HashSet::from([idx]);
+        self.anchor = Some(idx);
+    }
+
+    /// Update the selection for a click on the backup at `idx`.
+    ///
+    /// `toggle` (ctrl/cmd) adds or removes the backup, `range` (shift)
+    /// selects everything from the last clicked backup; both together add
+    /// the range to the selection.
+    pub fn click_select(&mut self, idx: usize, toggle: bool, range: bool) {
+        match (range, self.anchor) {
+            (true, Some(anchor)) => {
+                if !toggle {
+                    self.selected.clear();
+                }
+                self.selected.extend(anchor.min(idx)..=anchor.max(idx));
+                // Keep the anchor so the range can be adjusted
+            }
+            _ if toggle => {
+                if !self.selected.remove(&idx) {
+                    self.selected.insert(idx);
+                }
+                self.anchor = Some(idx);
+            }
+            _ => self.select_only(idx),
+        }
+    }
+
+    /// Check if a backup comparison is running
+    pub fn is_comparing(&self) -> bool {
+        self.diff_task.is_some()
+    }
+
+    /// Compare the selected backup against another one in the list
+    pub fn start_diff(&mut self, other_idx: usize) {
+        let (Some(a), Some(b)) = (
+            self.selected_idx().and_then(|idx| self.list.get(idx)),
+            self.list.get(other_idx),
+        ) else {
+            return;
+        };
+
+        // Older backup first, so the diff reads as "what changed since"
+        let (a, b) = if a.modified <= b.modified {
+            (a.name.clone(), b.name.clone())
+        } else {
+            (b.name.clone(), a.name.clone())
+        };
+
+        self.diff = None;
+        self.diff_task = Some(tokio::spawn(
+            async move { backup::diff_backups(&a, &b).await },
+        ));
+    }
+
+    /// Start a background integrity check of all backups if one is due.
+    ///
+    /// Runs every `interval_hours` (0 disables it), starting with the first
+    /// call after launch.
+    pub fn verify_if_due(&mut self, ctx: &egui::Context, interval_hours: u32) {
+        if interval_hours == 0 || self.verify_task.is_some() {
+            return;
+        }
+
+        let interval = Duration::from_secs(u64::from(interval_hours) * 3600);
+        if let Some(last) = self.last_verify {
+            let elapsed = last.elapsed();
+            if elapsed < interval {
+                // Wake up when the next check is due even if the UI is idle
+                ctx.request_repaint_after(interval - elapsed);
+                return;
+            }
+        }
+
+        tracing::info!("Starting background backup verification");
+        self.last_verify = Some(Instant::now());
+        self.verify_task = Some(tokio::spawn(async move {
+            let mut results = Vec::new();
+            for info in backup::list_backups().await? {
+                let result = backup::verify_backup(&info.path).await;
+                results.push((info.name, result));
+            }
+            Ok(results)
+        }));
+    }
+
+    /// Whether a scheduled backup of the running game is due.
+    ///
+    /// The first one is due a full interval into the session, so a game that
+    /// was just launched isn't backed up straight away. A due backup waits
+    /// while another backup operation is running.
+    pub fn scheduled_backup_due(
+        &mut self,
+        ctx: &egui::Context,
+        game_running: bool,
+        interval_minutes: u32,
+    ) -> bool {
+        if !game_running || interval_minutes == 0 {
+            self.last_scheduled = None;
+            return false;
+        }
+
+        let interval = Duration::from_secs(u64::from(interval_minutes) * 60);
+        let Some(last) = self.last_scheduled else {
+            self.last_scheduled = Some(Instant::now());
+            ctx.request_repaint_after(interval);
+            return false;
+        };
+
+        let elapsed = last.elapsed();
+        if elapsed < interval {
+            // Wake up when the backup is due even if the UI is idle
+            ctx.request_repaint_after(interval - elapsed);
+            return false;
+        }
+        if self.is_busy() {
+            return false;
+        }
+
+        self.last_scheduled = Some(Instant::now());
+        true
+    }
+
+    /// Start a scheduled auto-backup of the running game's saves
+    pub fn start_scheduled_backup(
+        &mut self,
+        game_dir: &Path,
+        name_template: &str,
+        name_vars: BackupNameVars,
+        compression_level: u8,
+        retention: RetentionPolicy,
+    ) -> Option<StateEvent> {
+        if self.task.is_some() {
+            return None;
+        }
+
+        self.error = None;
+        self.progress = BackupProgress::default();
+
+        let (progress_tx, progress_rx) = watch::channel(BackupProgress::default());
+        self.progress_rx = Some(progress_rx);
+
+        let game_dir = game_dir.to_path_buf();
+        let name_template = name_template.to_string();
+
+        tracing::info!("Starting scheduled backup");
+
+        self.task = Some(tokio::spawn(async move {
+            let info = backup::create_auto_backup(
+                &game_dir,
+                AutoBackupType::Scheduled,
+                &name_template,
+                &name_vars,
+                compression_level,
+                retention,
+                progress_tx,
+            )
+            .await?;
+            if info.is_none() {
+                tracing::info!("No saves to back up, skipped scheduled backup");
+            }
+            Ok(None)
+        }));
+
+        Some(StateEvent::StatusMessage(
+            "Creating scheduled backup...".to_string(),
+        ))
+    }
+
     /// Refresh the backup list
     pub fn refresh_list(&mut self) {
         if self.list_loading || self.list_task.is_some() {
@@ -107,35 +325,182 @@ impl BackupState {
         }
 
         self.list_loading = true;
-        self.selected_idx = None;
+        self.selected.clear();
+        self.anchor = None;
         self.error = None;
 
         self.list_task = Some(tokio::spawn(async move { backup::list_backups().await }));
     }
 
-    /// Delete the selected backup
-    pub fn delete_selected(&mut self) -> Option<StateEvent> {
-        let idx = self.selected_idx?;
-        let backup = self.list.get(idx)?;
+    /// Names of the selected backups, in list order
+    pub fn selected_names(&self) -> Vec<String> {
+        let mut indices: Vec<usize> = self.selected.iter().copied().collect();
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .filter_map(|idx| self.list.get(idx))
+            .map(|backup| backup.name.clone())
+            .collect()
+    }
 
-        let backup_name = backup.name.clone();
-        let backup_name_for_status = backup_name.clone();
+    /// Delete the selected backups
+    pub fn delete_selected(&mut self) -> Option<StateEvent> {
+        let backup_names = self.selected_names();
+        let status = match backup_names.as_slice() {
+            [] => return None,
+            [name] => format!("Deleting backup: {}", name),
+            names => format!("Deleting {} backups", names.len()),
+        };
 
         self.error = None;
-        tracing::info!("Deleting backup: {}", backup_name);
+        self.progress = BackupProgress::default();
+
+        let (progress_tx, progress_rx) = watch::channel(BackupProgress::default());
+        self.progress_rx = Some(progress_rx);
+
+        tracing::info!("Deleting backups: {}", backup_names.join(", "));
 
         self.task = Some(tokio::spawn(async move {
-            backup::delete_backup(&backup_name).await
+            backup::delete_backups(&backup_names, progress_tx).await?;
+            Ok(None)
+        }));
+
+        self.selected.clear();
+        self.anchor = None;
+
+        Some(StateEvent::StatusMessage(status))
+    }
+
+    /// Load the selected backup's note into `note_input` when the selection changes
+    pub fn sync_note_input(&mut self) {
+        let selected = self.selected_idx().and_then(|idx| self.list.get(idx));
+        if self.note_loaded_for.as_ref() == selected.map(|b| &b.name) {
+            return;
+        }
+        self.note_input = selected.and_then(|b| b.note.clone()).unwrap_or_default();
+        self.note_loaded_for = selected.map(|b| b.name.clone());
+    }
+
+    /// Save `note_input` as the selected backup's note
+    pub fn save_selected_note(&mut self) -> Option<StateEvent> {
+        let backup = self.selected_idx().and_then(|idx| self.list.get_mut(idx))?;
+
+        match backup::set_backup_note(&backup.name, &self.note_input) {
+            Ok(()) => {
+                let note = self.note_input.trim();
+                backup.note = (!note.is_empty()).then(|| note.to_string());
+                self.note_input = backup.note.clone().unwrap_or_default();
+                Some(StateEvent::StatusMessage(format!(
+                    "Saved note for {}",
+                    backup.name
+                )))
+            }
+            Err(e) => {
+                let msg = format!("Failed to save note: {}", e);
+                self.error = Some(msg.clone());
+                Some(StateEvent::StatusMessage(msg))
+            }
+        }
+    }
+
+    /// Open the selected backup in the browser
+    pub fn browse_selected(&mut self) {
+        let Some(backup) = self.selected_idx().and_then(|idx| self.list.get(idx)) else {
+            return;
+        };
+
+        let name = backup.name.clone();
+        self.browser = Some(BackupBrowser {
+            name: name.clone(),
+            loading: true,
+            ..Default::default()
+        });
+        self.browse_task = Some(tokio::spawn(async move {
+            backup::list_backup_entries(&name).await
+        }));
+    }
+
+    /// Check if a file is being extracted from the browsed backup
+    pub fn is_extracting(&self) -> bool {
+        self.extract_task.is_some()
+    }
+
+    /// Extract the entry selected in the browser into `dest`
+    pub fn extract_selected_entry(&mut self, dest: PathBuf) -> Option<StateEvent> {
+        if self.extract_task.is_some() {
+            return None;
+        }
+        let browser = self.browser.as_ref()?;
+        let entry = browser.selected.clone()?;
+        let name = browser.name.clone();
+
+        self.error = None;
+        tracing::info!("Extracting '{}' from backup {} to {:?}", entry, name, dest);
+
+        let status = format!("Extracting {} from {}...", entry, name);
+        self.extract_task = Some(tokio::spawn(async move {
+            backup::extract_from_backup(&name, &entry, &dest).await
         }));
 
-        self.selected_idx = None;
+        Some(StateEvent::StatusMessage(status))
+    }
+
+    /// Offer the last restore's safety backup for deletion, once the game has run
+    pub fn on_session_ended(&mut self, offer_cleanup: bool) {
+        let safety_backup = self.restored_safety_backup.take();
+        if offer_cleanup && self.offer_safety_cleanup.is_none() {
+            self.offer_safety_cleanup = safety_backup;
+        }
+    }
+
+    /// Delete the safety backup offered after a restore
+    pub fn delete_offered_safety_backup(&mut self) -> Option<StateEvent> {
+        if self.task.is_some() {
+            return None;
+        }
+        let backup_name = self.offer_safety_cleanup.take()?;
+
+        self.error = None;
+        self.progress = BackupProgress::default();
+
+        let (progress_tx, progress_rx) = watch::channel(BackupProgress::default());
+        self.progress_rx = Some(progress_rx);
+
+        tracing::info!("Deleting safety backup: {}", backup_name);
+
+        let name_for_status = backup_name.clone();
+        self.task = Some(tokio::spawn(async move {
+            backup::delete_backup(&backup_name, progress_tx).await?;
+            Ok(None)
+        }));
 
         Some(StateEvent::StatusMessage(format!(
-            "Deleting backup: {}",
-            backup_name_for_status
+            "Deleting safety backup: {}",
+            name_for_status
         )))
     }
 
+    /// Delete safety backups older than `max_age_days`
+    pub fn clean_safety_backups(&mut self, max_age_days: u32) -> Option<StateEvent> {
+        if self.cleanup_task.is_some() {
+            return None;
+        }
+
+        self.error = None;
+        tracing::info!(
+            "Cleaning up safety backups older than {} days",
+            max_age_days
+        );
+
+        self.cleanup_task = Some(tokio::spawn(async move {
+            backup::clean_safety_backups(max_age_days).await
+        }));
+
+        Some(StateEvent::StatusMessage(
+            "Cleaning up old safety backups...".to_string(),
+        ))
+    }
+
     /// Restore the selected backup
     pub fn restore_selected(
         &mut self,
@@ -143,7 +508,7 @@ impl BackupState {
         skip_backup_before_restore: bool,
         compression_level: u8,
     ) -> Option<StateEvent> {
-        let idx = self.selected_idx?;
+        let idx = self.selected_idx()?;
         let backup = self.list.get(idx)?;
 
         let backup_name = backup.name.clone();
@@ -176,6 +541,57 @@ impl BackupState {
         )))
     }
 
+    /// Check a picked archive and queue it for restore confirmation
+    pub fn select_external_restore(&mut self, path: PathBuf, skip_backup_before_restore: bool) {
+        self.error = None;
+        match backup::validate_backup_archive(&path) {
+            Ok(_) => {
+                self.external_restore = Some(path);
+                self.external_restore_backup_first = !skip_backup_before_restore;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    /// Restore the confirmed external backup archive
+    pub fn restore_external(
+        &mut self,
+        game_dir: &Path,
+        compression_level: u8,
+    ) -> Option<StateEvent> {
+        let archive_path = self.external_restore.take()?;
+        let file_name = archive_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let game_dir = game_dir.to_path_buf();
+        let backup_first = self.external_restore_backup_first;
+
+        self.error = None;
+        self.progress = BackupProgress::default();
+
+        let (progress_tx, progress_rx) = watch::channel(BackupProgress::default());
+        self.progress_rx = Some(progress_rx);
+
+        tracing::info!("Restoring external backup: {:?}", archive_path);
+
+        self.task = Some(tokio::spawn(async move {
+            backup::restore_external_backup(
+                &game_dir,
+                &archive_path,
+                backup_first,
+                compression_level,
+                progress_tx,
+            )
+            .await
+        }));
+
+        Some(StateEvent::StatusMessage(format!(
+            "Restoring backup from file: {}",
+            file_name
+        )))
+    }
+
     /// Poll backup tasks for progress and completion
     pub fn poll(&mut self, ctx: &egui::Context) -> Vec<StateEvent> {
         let mut events = Vec::new();
@@ -192,7 +608,10 @@ impl BackupState {
 
         // Check if backup operation task is complete
         match poll_task(&mut self.task) {
-            PollResult::Complete(Ok(Ok(()))) => {
+            PollResult::Complete(Ok(Ok(safety_backup))) => {
+                if safety_backup.is_some() {
+                    self.restored_safety_backup = safety_backup;
+                }
                 self.progress_rx = None;
                 self.progress.phase = BackupPhase::Complete;
                 events.push(StateEvent::StatusMessage(
@@ -228,11 +647,150 @@ impl BackupState {
             PollResult::NoTask => {}
         }
 
+        // Check if safety backup cleanup is complete
+        match poll_task(&mut self.cleanup_task) {
+            PollResult::Complete(Ok(Ok(deleted))) => {
+                let msg = format!("Deleted {} old safety backups", deleted);
+                events.push(StateEvent::LogInfo(msg.clone()));
+                events.push(StateEvent::StatusMessage(msg));
+                self.refresh_list();
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                let msg = format!("Safety backup cleanup failed: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg.clone());
+                events.push(StateEvent::StatusMessage(msg));
+            }
+            PollResult::Complete(Err(e)) => {
+                events.push(StateEvent::LogError(format!(
+                    "Safety backup cleanup task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        // Check if the browsed backup's listing is ready
+        match poll_task(&mut self.browse_task) {
+            PollResult::Complete(Ok(Ok(entries))) => {
+                if let Some(browser) = &mut self.browser {
+                    browser.entries = entries;
+                    browser.loading = false;
+                }
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                self.browser = None;
+                let msg = format!("Failed to read backup: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg);
+            }
+            PollResult::Complete(Err(e)) => {
+                self.browser = None;
+                events.push(StateEvent::LogError(format!(
+                    "Backup browse task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        // Check if extraction from the browsed backup is complete
+        match poll_task(&mut self.extract_task) {
+            PollResult::Complete(Ok(Ok((count, target)))) => {
+                let msg = format!(
+                    "Extracted {} files from backup to {}",
+                    count,
+                    target.display()
+                );
+                events.push(StateEvent::LogInfo(msg.clone()));
+                events.push(StateEvent::StatusMessage(msg));
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                let msg = format!("Extraction failed: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg.clone());
+                events.push(StateEvent::StatusMessage(msg));
+            }
+            PollResult::Complete(Err(e)) => {
+                events.push(StateEvent::LogError(format!(
+                    "Backup extract task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        // Check if backup comparison is complete
+        match poll_task(&mut self.diff_task) {
+            PollResult::Complete(Ok(Ok(diff))) => {
+                self.diff = Some(diff);
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                events.push(StateEvent::LogError(format!(
+                    "Failed to compare backups: {}",
+                    e
+                )));
+                self.error = Some(format!("Failed to compare backups: {}", e));
+            }
+            PollResult::Complete(Err(e)) => {
+                events.push(StateEvent::LogError(format!(
+                    "Backup compare task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        // Check if background verification is complete
+        match poll_task(&mut self.verify_task) {
+            PollResult::Complete(Ok(Ok(results))) => {
+                let checked = results.len();
+                // Rebuilt from scratch so deleted backups drop out
+                let previous = std::mem::take(&mut self.corrupt);
+                for (name, result) in results {
+                    let Err(e) = result else {
+                        continue;
+                    };
+                    let reason = e.to_string();
+                    // Only report newly found problems
+                    if previous.get(&name) != Some(&reason) {
+                        events.push(StateEvent::LogError(format!(
+                            "Backup '{}' failed verification: {}",
+                            name, reason
+                        )));
+                    }
+                    self.corrupt.insert(name, reason);
+                }
+                tracing::info!(
+                    "Verified {} backups, {} corrupt",
+                    checked,
+                    self.corrupt.len()
+                );
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                tracing::warn!("Background backup verification failed: {}", e);
+            }
+            PollResult::Complete(Err(e)) => {
+                tracing::warn!("Backup verification task panicked: {}", e);
+            }
+            // Low-priority background work; check back occasionally
+            PollResult::Pending => ctx.request_repaint_after(Duration::from_secs(1)),
+            PollResult::NoTask => {}
+        }
+
         // Check if backup list loading task is complete
         match poll_task(&mut self.list_task) {
             PollResult::Complete(Ok(Ok(list))) => {
                 self.list_loading = false;
                 self.list = list;
+                // Offer a fresh templated name now that the list is current
+                if self.name_input.is_empty() {
+                    self.name_prefilled = false;
+                }
                 events.push(StateEvent::LogInfo(format!(
                     "Loaded {} backups",
                     self.list.len()
@@ -260,3 +818,46 @@ impl BackupState {
         events
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(state: &BackupState) -> Vec<usize> {
+        let mut selected: Vec<usize> = state.selected.iter().copied().collect();
+        selected.sort_unstable();
+        selected
+    }
+
+    #[test]
+    fn test_click_select() {
+        let mut state = BackupState::default();
+
+        state.click_select(2, false, false);
+        assert_eq!(selected(&state), vec![2]);
+        assert_eq!(state.selected_idx(), Some(2));
+
+        // Ctrl-click adds and removes single backups
+        state.click_select(5, true, false);
+        assert_eq!(selected(&state), vec![2, 5]);
+        assert_eq!(state.selected_idx(), None);
+        state.click_select(2, true, false);
+        assert_eq!(selected(&state), vec![5]);
+
+        // Shift-click selects from the last clicked backup, either direction
+        state.click_select(5, false, false);
+        state.click_select(3, false, true);
+        assert_eq!(selected(&state), vec![3, 4, 5]);
+        state.click_select(7, false, true);
+        assert_eq!(selected(&state), vec![5, 6, 7]);
+
+        // Ctrl+shift adds the range to the selection
+        state.click_select(0, true, false);
+        state.click_select(1, true, true);
+        assert_eq!(selected(&state), vec![0, 1, 5, 6, 7]);
+
+        // A plain click starts over
+        state.click_select(4, false, false);
+        assert_eq!(selected(&state), vec![4]);
+    }
+}
@@ -0,0 +1,175 @@
+//! Game launch state
+
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use eframe::egui;
+use tokio::task::JoinHandle;
+
+use crate::db::SessionStats;
+use crate::game::{self, LaunchDiagnostics};
+use crate::safe_mode;
+use crate::state::StateEvent;
+use crate::task::{PollResult, poll_task};
+use crate::util::format_duration;
+
+/// A running game launched with "keep launcher open" enabled
+struct GameSession {
+    child: Child,
+    started: Instant,
+    /// Wall-clock start time (Unix timestamp) for the session history
+    started_at: i64,
+    /// Game version being played
+    version: Option<String>,
+    /// Game directory whose mods were disabled for a safe-mode launch
+    safe_mode_dir: Option<PathBuf>,
+}
+
+/// Game launch state
+#[derive(Default)]
+pub struct LaunchState {
+    /// Game process being tracked for play time
+    session: Option<GameSession>,
+    /// Duration of the last tracked session
+    pub last_session: Option<Duration>,
+    /// Play time statistics from the database
+    pub stats: Option<SessionStats>,
+    /// Async task for a diagnostic launch
+    diagnostic_task: Option<JoinHandle<Result<LaunchDiagnostics>>>,
+    /// Result of the last diagnostic launch (shown until dismissed)
+    pub diagnostics: Option<LaunchDiagnostics>,
+}
+
+impl LaunchState {
+    /// Time the tracked game has been running, if any
+    pub fn session_elapsed(&self) -> Option<Duration> {
+        self.session.as_ref().map(|s| s.started.elapsed())
+    }
+
+    /// Launch the game and track it until it exits
+    pub fn start_session(
+        &mut self,
+        executable: &Path,
+        params: &str,
+        version: Option<String>,
+    ) -> Result<()> {
+        let child = game::spawn_game(executable, params)?;
+        self.session = Some(GameSession {
+            child,
+            started: Instant::now(),
+            started_at: chrono::Utc::now().timestamp(),
+            version,
+            safe_mode_dir: None,
+        });
+        Ok(())
+    }
+
+    /// Launch the game with all mods disabled and track it, re-enabling the
+    /// mods once it exits. Returns the number of mods disabled.
+    pub fn start_safe_mode_session(
+        &mut self,
+        game_dir: &Path,
+        executable: &Path,
+        params: &str,
+        version: Option<String>,
+    ) -> Result<usize> {
+        let disabled = safe_mode::enable(game_dir)?;
+        if let Err(e) = self.start_session(executable, params, version) {
+            if let Err(restore_err) = safe_mode::restore(game_dir) {
+                tracing::error!("Failed to re-enable mods: {}", restore_err);
+            }
+            return Err(e);
+        }
+        if let Some(session) = &mut self.session {
+            session.safe_mode_dir = Some(game_dir.to_path_buf());
+        }
+        Ok(disabled)
+    }
+
+    /// Check if a diagnostic launch is waiting for the game
+    pub fn is_diagnosing(&self) -> bool {
+        self.diagnostic_task.is_some()
+    }
+
+    /// Launch the game with its output captured
+    pub fn start_diagnostic(&mut self, executable: &Path, params: &str) -> Option<StateEvent> {
+        if self.diagnostic_task.is_some() {
+            return None;
+        }
+
+        self.diagnostics = None;
+        let executable = executable.to_path_buf();
+        let params = params.to_string();
+        self.diagnostic_task = Some(tokio::task::spawn_blocking(move || {
+            game::diagnostic_launch(&executable, &params)
+        }));
+
+        Some(StateEvent::StatusMessage(
+            "Diagnostic launch: capturing game output...".to_string(),
+        ))
+    }
+
+    /// Poll the diagnostic launch for completion
+    pub fn poll(&mut self, ctx: &egui::Context) -> Vec<StateEvent> {
+        let mut events = Vec::new();
+
+        // Check whether the tracked game has exited
+        if let Some(session) = &mut self.session {
+            match session.child.try_wait() {
+                Ok(None) => ctx.request_repaint_after(Duration::from_secs(1)),
+                result => {
+                    if let Err(e) = result {
+                        tracing::warn!("Lost track of the game process: {}", e);
+                    }
+                    let duration = session.started.elapsed();
+                    events.push(StateEvent::SessionEnded {
+                        started_at: session.started_at,
+                        ended_at: chrono::Utc::now().timestamp(),
+                        version: session.version.take(),
+                    });
+                    if let Some(game_dir) = session.safe_mode_dir.take() {
+                        match safe_mode::restore(&game_dir) {
+                            Ok(count) => events.push(StateEvent::LogInfo(format!(
+                                "Safe mode ended, re-enabled {} mods",
+                                count
+                            ))),
+                            Err(e) => events.push(StateEvent::LogError(format!(
+                                "Failed to re-enable mods after safe mode: {}",
+                                e
+                            ))),
+                        }
+                    }
+                    self.session = None;
+                    self.last_session = Some(duration);
+                    let msg = format!("Last session: {}", format_duration(duration));
+                    events.push(StateEvent::LogInfo(format!("Game exited. {}", msg)));
+                    events.push(StateEvent::StatusMessage(msg));
+                }
+            }
+        }
+
+        match poll_task(&mut self.diagnostic_task) {
+            PollResult::Complete(Ok(Ok(diagnostics))) => {
+                events.push(StateEvent::StatusMessage(diagnostics.summary()));
+                self.diagnostics = Some(diagnostics);
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                let msg = format!("Failed to launch: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                events.push(StateEvent::StatusMessage(msg));
+            }
+            PollResult::Complete(Err(e)) => {
+                events.push(StateEvent::LogError(format!(
+                    "Diagnostic launch task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        events
+    }
+}
@@ -0,0 +1,129 @@
+//! Launcher self-update state
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use eframe::egui;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::github::GitHubClient;
+use crate::self_update::{self, LauncherUpdate};
+use crate::state::StateEvent;
+use crate::task::{PollResult, poll_task};
+use crate::update::UpdateProgress;
+
+/// Launcher self-update state
+#[derive(Default)]
+pub struct LauncherUpdateState {
+    /// Async task checking for a newer launcher
+    check_task: Option<JoinHandle<Result<Option<LauncherUpdate>>>>,
+    /// Async task downloading and swapping in the new launcher
+    install_task: Option<JoinHandle<Result<PathBuf>>>,
+    /// Newer launcher release, if one was found
+    pub available: Option<LauncherUpdate>,
+    /// Whether the last check found the launcher up to date
+    pub up_to_date: bool,
+    /// Version installed this session (takes effect after a restart)
+    pub installed: Option<String>,
+    /// Error message from the last check or install
+    pub error: Option<String>,
+}
+
+impl LauncherUpdateState {
+    /// Check if a check or install is in progress
+    pub fn is_busy(&self) -> bool {
+        self.check_task.is_some() || self.install_task.is_some()
+    }
+
+    /// Check GitHub for a newer launcher release
+    pub fn check(&mut self, client: &GitHubClient) {
+        if self.is_busy() {
+            return;
+        }
+
+        self.error = None;
+        self.up_to_date = false;
+        let client = client.clone();
+        self.check_task = Some(tokio::spawn(
+            async move { self_update::check(&client).await },
+        ));
+    }
+
+    /// Download the available launcher release and swap it in
+    pub fn install(&mut self, client: &GitHubClient) -> Option<StateEvent> {
+        if self.is_busy() {
+            return None;
+        }
+        let launcher_update = self.available.clone()?;
+
+        self.error = None;
+        let client = client.clone();
+        let version = launcher_update.version.clone();
+        self.install_task = Some(tokio::spawn(async move {
+            let progress_tx = watch::channel(UpdateProgress::default()).0;
+            self_update::install(&client, &launcher_update, progress_tx).await
+        }));
+
+        Some(StateEvent::StatusMessage(format!(
+            "Downloading Phoenix {}...",
+            version
+        )))
+    }
+
+    /// Poll the check and install tasks
+    pub fn poll(&mut self, ctx: &egui::Context) -> Vec<StateEvent> {
+        let mut events = Vec::new();
+
+        match poll_task(&mut self.check_task) {
+            PollResult::Complete(Ok(Ok(available))) => {
+                self.up_to_date = available.is_none();
+                if let Some(ref update) = available {
+                    events.push(StateEvent::StatusMessage(format!(
+                        "Phoenix {} is available",
+                        update.version
+                    )));
+                }
+                self.available = available;
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                let msg = format!("Launcher update check failed: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg);
+            }
+            PollResult::Complete(Err(e)) => {
+                let msg = format!("Launcher update check panicked: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg);
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        match poll_task(&mut self.install_task) {
+            PollResult::Complete(Ok(Ok(_))) => {
+                if let Some(update) = self.available.take() {
+                    events.push(StateEvent::StatusMessage(format!(
+                        "Phoenix {} installed - restart to finish",
+                        update.version
+                    )));
+                    self.installed = Some(update.version);
+                }
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                let msg = format!("Launcher update failed: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg);
+            }
+            PollResult::Complete(Err(e)) => {
+                let msg = format!("Launcher update panicked: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg);
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        events
+    }
+}
@@ -0,0 +1,65 @@
+//! Library search state
+
+use std::path::Path;
+
+use eframe::egui;
+use tokio::task::JoinHandle;
+
+use crate::library::{self, LibraryItem};
+use crate::state::StateEvent;
+use crate::task::{PollResult, poll_task};
+
+/// Library search state
+#[derive(Default)]
+pub struct LibraryState {
+    /// Search box contents
+    pub query: String,
+    /// Indexed content, sorted by kind then name
+    pub items: Vec<LibraryItem>,
+    /// Whether the index has been built at least once
+    pub loaded: bool,
+    /// Result to select once its tab's list has loaded
+    pub pending_jump: Option<LibraryItem>,
+    /// Async task rebuilding the index
+    task: Option<JoinHandle<Vec<LibraryItem>>>,
+}
+
+impl LibraryState {
+    /// Check if the index is being rebuilt
+    pub fn is_loading(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// Rebuild the index from the game directory and backup folder
+    pub fn rebuild(&mut self, game_dir: &Path) {
+        if self.task.is_some() {
+            return;
+        }
+
+        let game_dir = game_dir.to_path_buf();
+        self.task = Some(tokio::spawn(library::build_index(game_dir)));
+    }
+
+    /// Poll the rebuild task
+    pub fn poll(&mut self, ctx: &egui::Context) -> Vec<StateEvent> {
+        let mut events = Vec::new();
+
+        match poll_task(&mut self.task) {
+            PollResult::Complete(Ok(items)) => {
+                tracing::info!("Library indexed {} items", items.len());
+                self.items = items;
+                self.loaded = true;
+            }
+            PollResult::Complete(Err(e)) => {
+                events.push(StateEvent::LogError(format!(
+                    "Library index task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        events
+    }
+}
@@ -4,16 +4,26 @@
 //! Each state struct owns its related fields and poll methods.
 
 mod backup;
+mod launch;
+mod launcher_update;
+mod library;
 mod releases;
 mod soundpack;
+mod tileset;
 mod ui;
 mod update;
+mod watch;
 
 pub use backup::BackupState;
-pub use releases::ReleasesState;
+pub use launch::LaunchState;
+pub use launcher_update::LauncherUpdateState;
+pub use library::LibraryState;
+pub use releases::{ReleasesState, is_installed_release};
 pub use soundpack::SoundpackState;
+pub use tileset::TilesetState;
 pub use ui::{Tab, UiState};
-pub use update::{UpdateParams, UpdateState};
+pub use update::{RollbackParams, UpdateParams, UpdateState};
+pub use watch::WatchState;
 
 /// Events that state poll methods can return.
 /// These communicate results back to PhoenixApp without direct mutation.
@@ -31,9 +41,25 @@ pub enum StateEvent {
     /// Log an info message
     LogInfo(String),
 
+    /// A release fetch finished for a branch (`ok` is false if it failed)
+    ReleasesFetched { branch: Option<String>, ok: bool },
+
     /// Changelog was fetched for a release (tag, body)
     ChangelogFetched { tag: String, body: String },
 
+    /// Retry the update (e.g. after closing the running game)
+    RetryUpdate,
+
     /// Game version was refined via hash lookup (may indicate stable release)
     GameVersionRefined(crate::game::GameInfo),
+
+    /// The game directory changed on disk; detection should be refreshed
+    GameDirChanged,
+
+    /// A tracked game session ended (Unix timestamps)
+    SessionEnded {
+        started_at: i64,
+        ended_at: i64,
+        version: Option<String>,
+    },
 }
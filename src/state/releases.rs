@@ -1,5 +1,7 @@
 //! Releases-related application state
 
+use std::collections::{HashMap, HashSet};
+
 use anyhow::Result;
 use eframe::egui;
 use tokio::task::JoinHandle;
@@ -9,8 +11,11 @@ use crate::github::{FetchResult, GitHubClient, RateLimitInfo, Release};
 use crate::state::StateEvent;
 use crate::task::{PollResult, poll_task};
 
-/// Result of a changelog fetch task: the changelog body and an optional title.
-type ChangelogResult = Result<(String, Option<String>)>;
+/// Result of a changelog fetch task: the changelog body, if the release has one.
+type ChangelogResult = Result<Option<String>>;
+
+/// Maximum number of builds combined into a "what's new" changelog
+const MAX_AGGREGATED_CHANGELOGS: usize = 25;
 
 /// Releases-related state
 #[derive(Default)]
@@ -33,8 +38,19 @@ pub struct ReleasesState {
     pub rate_limit: RateLimitInfo,
     /// Async task for fetching a changelog
     changelog_task: Option<JoinHandle<ChangelogResult>>,
+    /// Which release tag's changelog is being fetched
+    changelog_tag: Option<String>,
     /// Whether a changelog is being fetched
     pub changelog_loading: bool,
+    /// Changelog bodies by release tag.
+    ///
+    /// Bodies are moved out of fetched releases into this cache so they survive
+    /// list refreshes, and bodies fetched lazily per tag land here as well.
+    changelogs: HashMap<String, String>,
+    /// Tags whose changelog could not be fetched (avoids refetching every frame)
+    changelog_unavailable: HashSet<String>,
+    /// Last release checked for a platform build: tag, asset count, and result
+    asset_check: Option<(String, usize, bool)>,
 }
 
 impl ReleasesState {
@@ -58,7 +74,15 @@ impl ReleasesState {
 
     /// Simple check: is the selected release different from the installed version?
     /// Returns true if they are different (can update/switch), false if same or can't compare
-    pub fn is_selected_different(&self, branch: &str, game_info: Option<&GameInfo>) -> bool {
+    ///
+    /// `version_override` is a manually entered tag or build number, used only
+    /// when the installed version could not be detected.
+    pub fn is_selected_different(
+        &self,
+        branch: &str,
+        game_info: Option<&GameInfo>,
+        version_override: Option<&str>,
+    ) -> bool {
         let Some(game_info) = game_info else {
             return false; // No game installed
         };
@@ -67,26 +91,67 @@ impl ReleasesState {
             return false; // No release selected
         };
 
-        // Compare build numbers - this distinguishes multiple builds on the same day
-        // Installed: build_number like "2025-12-20-2147" stored in released_on
-        // Release tag: like "cdda-experimental-2025-12-20-2147"
-        if let Some(version_info) = &game_info.version_info
-            && let Some(ref installed_build) = version_info.released_on
+        !is_installed_release(&selected_release.tag_name, game_info, version_override)
+    }
+
+    /// Whether the selected release has a build for this platform.
+    ///
+    /// The check only reruns when the selection changes (or a refresh brings
+    /// new assets for it), so this is cheap to call every frame. Returns true
+    /// when nothing is selected.
+    pub fn selected_has_asset(&mut self, branch: &str) -> bool {
+        let Some(release) = self
+            .selected_idx
+            .and_then(|i| self.for_branch(branch).get(i))
+        else {
+            return true;
+        };
+
+        if let Some((tag, count, has_asset)) = &self.asset_check
+            && *tag == release.tag_name
+            && *count == release.assets.len()
         {
-            // Check if the release tag contains our build number
-            // e.g., "cdda-experimental-2025-12-20-2147" contains "2025-12-20-2147"
-            if selected_release.tag_name.contains(installed_build) {
-                return false; // Same version
-            }
-            return true; // Different version
+            return *has_asset;
         }
 
-        // Fallback: assume different (allow update)
-        true
+        let has_asset = GitHubClient::find_platform_asset(release).is_some();
+        self.asset_check = Some((release.tag_name.clone(), release.assets.len(), has_asset));
+        has_asset
+    }
+
+    /// Find the release matching the installed game in the given branch
+    pub fn installed_release(&self, branch: &str, game_info: &GameInfo) -> Option<&Release> {
+        game_info.version_info.as_ref()?;
+        self.for_branch(branch)
+            .iter()
+            .find(|r| is_installed_release(&r.tag_name, game_info, None))
+    }
+
+    /// Combined changelog of every experimental build newer than the installed one,
+    /// up to and including the selected release.
+    ///
+    /// Returns `None` unless at least two builds separate the installed version
+    /// from the selection (a single build is already shown by the normal view).
+    pub fn changelog_since_installed(&self, game_info: Option<&GameInfo>) -> Option<String> {
+        let installed_build = game_info?.version_info.as_ref()?.released_on.as_deref()?;
+        aggregate_changelog(
+            &self.experimental,
+            &self.changelogs,
+            self.selected_idx?,
+            installed_build,
+            MAX_AGGREGATED_CHANGELOGS,
+        )
     }
 
     /// Start fetching releases for a specific branch
-    pub fn fetch_for_branch(&mut self, branch: &str, client: &GitHubClient) -> Option<StateEvent> {
+    ///
+    /// `experimental_count` is how many experimental builds to fetch.
+    pub fn fetch_for_branch(
+        &mut self,
+        branch: &str,
+        client: &GitHubClient,
+        experimental_count: u32,
+    ) -> Option<StateEvent> {
         if self.loading {
             return None; // Already fetching
         }
@@ -102,7 +167,7 @@ impl ReleasesState {
             if is_stable {
                 client.get_stable_releases().await
             } else {
-                client.get_experimental_releases().await
+                client.get_experimental_releases(experimental_count).await
             }
         }));
 
@@ -122,12 +187,20 @@ impl ReleasesState {
                 let count = result.data.len();
                 self.rate_limit = result.rate_limit;
 
+                // Keep bodies in the per-tag cache; the release list stays lightweight
+                let mut data = result.data;
+                for release in &mut data {
+                    if let Some(body) = release.body.take() {
+                        self.changelogs.insert(release.tag_name.clone(), body);
+                    }
+                }
+
                 // Store in appropriate list based on which branch we fetched
                 let is_current_branch = branch.as_deref() == Some(current_branch);
                 if branch.as_deref() == Some("stable") {
-                    self.stable = result.data;
+                    self.stable = data;
                 } else {
-                    self.experimental = result.data;
+                    self.experimental = data;
                 }
                 // Auto-select latest release if this is for the current branch
                 if is_current_branch && count > 0 {
@@ -142,10 +215,12 @@ impl ReleasesState {
                     count,
                     branch.as_deref().unwrap_or("unknown")
                 )));
+                events.push(StateEvent::ReleasesFetched { branch, ok: true });
                 self.loading = false;
             }
             PollResult::Complete(Ok(Err(e))) => {
-                self.fetching_branch.take();
+                let branch = self.fetching_branch.take();
+                events.push(StateEvent::ReleasesFetched { branch, ok: false });
                 let msg = e.to_string();
                 events.push(StateEvent::LogError(format!(
                     "Failed to fetch releases: {}",
@@ -156,7 +231,8 @@ impl ReleasesState {
                 self.loading = false;
             }
             PollResult::Complete(Err(e)) => {
-                self.fetching_branch.take();
+                let branch = self.fetching_branch.take();
+                events.push(StateEvent::ReleasesFetched { branch, ok: false });
                 let msg = e.to_string();
                 events.push(StateEvent::LogError(format!("Task panicked: {}", msg)));
                 self.error = Some(msg);
@@ -169,19 +245,30 @@ impl ReleasesState {
         events
     }
 
+    /// Get the cached changelog body for a release tag
+    pub fn changelog(&self, tag: &str) -> Option<&str> {
+        self.changelogs.get(tag).map(String::as_str)
+    }
+
+    /// Whether a changelog for this tag is cached or known to be unavailable
+    pub fn has_changelog_result(&self, tag: &str) -> bool {
+        self.changelogs.contains_key(tag) || self.changelog_unavailable.contains(tag)
+    }
+
     /// Start fetching a changelog for a specific release tag
     pub fn fetch_changelog(&mut self, tag: &str, client: &GitHubClient) {
-        if self.changelog_loading {
-            return; // Already fetching
+        if self.changelog_loading || self.has_changelog_result(tag) {
+            return; // Already fetching or already known
         }
 
         self.changelog_loading = true;
+        self.changelog_tag = Some(tag.to_string());
         let client = client.clone();
         let tag = tag.to_string();
 
         self.changelog_task = Some(tokio::spawn(async move {
             let (release, _rate_limit) = client.get_release_by_tag(&tag).await;
-            Ok((tag, release.and_then(|r| r.body)))
+            Ok(release.and_then(|r| r.body))
         }));
     }
 
@@ -190,18 +277,19 @@ impl ReleasesState {
         let mut events = Vec::new();
 
         match poll_task(&mut self.changelog_task) {
-            PollResult::Complete(Ok(Ok((tag, body)))) => {
+            PollResult::Complete(Ok(Ok(body))) => {
                 self.changelog_loading = false;
+                let tag = self.changelog_tag.take().unwrap_or_default();
                 if let Some(body) = body {
-                    // Update the release in our stable list
-                    if let Some(release) = self.stable.iter_mut().find(|r| r.tag_name == tag) {
-                        release.body = Some(body.clone());
-                    }
+                    self.changelogs.insert(tag.clone(), body.clone());
                     events.push(StateEvent::ChangelogFetched { tag, body });
+                } else {
+                    self.changelog_unavailable.insert(tag);
                 }
             }
             PollResult::Complete(Ok(Err(e))) => {
                 self.changelog_loading = false;
+                self.changelog_unavailable.extend(self.changelog_tag.take());
                 events.push(StateEvent::LogError(format!(
                     "Failed to fetch changelog: {}",
                     e
@@ -209,6 +297,7 @@ impl ReleasesState {
             }
             PollResult::Complete(Err(e)) => {
                 self.changelog_loading = false;
+                self.changelog_unavailable.extend(self.changelog_tag.take());
                 events.push(StateEvent::LogError(format!(
                     "Changelog task panicked: {}",
                     e
@@ -221,10 +310,369 @@ impl ReleasesState {
         events
     }
 
-    /// Set the body of a stable release (used when loading from DB cache)
-    pub fn set_stable_release_body(&mut self, tag: &str, body: String) {
-        if let Some(release) = self.stable.iter_mut().find(|r| r.tag_name == tag) {
-            release.body = Some(body);
+    /// Cache the changelog body for a release (used when loading from DB cache)
+    pub fn set_changelog(&mut self, tag: &str, body: String) {
+        self.changelogs.insert(tag.to_string(), body);
+    }
+}
+
+/// A stable release version such as "0.H" or "0.F-3" (point release 3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct StableVersion {
+    /// Release letter ('H' in "0.H")
+    letter: char,
+    /// Point release number (0 for the initial release)
+    point: u32,
+}
+
+impl StableVersion {
+    /// Parse a stable version or release tag.
+    ///
+    /// Accepts "0.H", "0.H-RELEASE", "0.F-3" and "0.F-3-RELEASE"; returns
+    /// `None` for anything else, including experimental build tags.
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text
+            .strip_suffix("-RELEASE")
+            .or_else(|| text.strip_suffix("-release"))
+            .unwrap_or(text);
+        let rest = text.strip_prefix("0.")?;
+
+        let (letter, point) = match rest.split_once('-') {
+            Some((letter, point)) => (letter, point.parse().ok()?),
+            None => (rest, 0),
+        };
+        let mut chars = letter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) if letter.is_ascii_uppercase() => Some(Self { letter, point }),
+            _ => None,
         }
     }
 }
+
+/// Whether the release tagged `tag` is the installed game.
+///
+/// Stable releases are compared semantically, since "0.H", "0.H-RELEASE" and
+/// the installed version string need not match textually. Experimental builds
+/// are matched by build number (installed "2025-12-20-2147" in tag
+/// "cdda-experimental-2025-12-20-2147"), which distinguishes several builds
+/// on the same day. `version_override` is used only when the installed
+/// version could not be detected; an unknown version never matches.
+pub fn is_installed_release(
+    tag: &str,
+    game_info: &GameInfo,
+    version_override: Option<&str>,
+) -> bool {
+    let Some(version_info) = &game_info.version_info else {
+        return version_override.is_some_and(|version| tag_matches_override(tag, version));
+    };
+
+    if let Some(installed) = StableVersion::parse(&version_info.version)
+        && let Some(release) = StableVersion::parse(tag)
+    {
+        return installed == release;
+    }
+    match &version_info.released_on {
+        Some(installed_build) => tag.contains(installed_build.as_str()),
+        None => tag == version_info.version,
+    }
+}
+
+/// Whether a release tag names the manually entered installed version.
+///
+/// The override may be a full tag, an experimental build number such as
+/// "2025-12-20-2147", or a stable version such as "0.H".
+fn tag_matches_override(tag: &str, version: &str) -> bool {
+    let version = version.trim();
+    if version.is_empty() {
+        return false;
+    }
+    match StableVersion::parse(version) {
+        Some(stable) => StableVersion::parse(tag) == Some(stable),
+        None => tag.contains(version),
+    }
+}
+
+/// Concatenate release bodies from `selected_idx` down to (but excluding) the
+/// release matching `installed_build`. Releases are ordered newest first.
+///
+/// If the installed build is older than everything in the list, all releases
+/// from the selection onwards are used. At most `max` builds are included.
+fn aggregate_changelog(
+    releases: &[Release],
+    changelogs: &HashMap<String, String>,
+    selected_idx: usize,
+    installed_build: &str,
+    max: usize,
+) -> Option<String> {
+    let installed_idx = releases
+        .iter()
+        .position(|r| r.tag_name.contains(installed_build));
+    let end = installed_idx.unwrap_or(releases.len());
+    if selected_idx >= end {
+        return None; // Selected release is the installed one or older
+    }
+
+    let newer = &releases[selected_idx..end];
+    if newer.len() < 2 {
+        return None;
+    }
+
+    let mut combined = String::new();
+    for release in newer.iter().take(max) {
+        let date = release
+            .published_at
+            .get(..10)
+            .unwrap_or(&release.published_at);
+        combined.push_str(&format!("## {} ({})\n\n", release.name, date));
+        combined.push_str(
+            changelogs
+                .get(&release.tag_name)
+                .map(String::as_str)
+                .unwrap_or("No changelog available"),
+        );
+        combined.push_str("\n\n---\n\n");
+    }
+
+    let omitted = newer.len().saturating_sub(max);
+    if omitted > 0 {
+        combined.push_str(&format!("*...and {} older builds not shown.*\n", omitted));
+    } else if installed_idx.is_none() {
+        combined.push_str("*Installed build is older than the fetched releases.*\n");
+    }
+
+    Some(combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUILDS: [(&str, &str); 4] = [
+        ("cdda-experimental-2025-12-22-0100", "third"),
+        ("cdda-experimental-2025-12-21-0100", "second"),
+        ("cdda-experimental-2025-12-20-0100", "first"),
+        ("cdda-experimental-2025-12-19-0100", "installed"),
+    ];
+
+    fn releases() -> Vec<Release> {
+        BUILDS
+            .iter()
+            .map(|(tag, _)| Release {
+                tag_name: tag.to_string(),
+                name: tag.to_string(),
+                body: None,
+                published_at: "2025-12-20T00:00:00Z".to_string(),
+                assets: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn changelogs() -> HashMap<String, String> {
+        BUILDS
+            .iter()
+            .map(|(tag, body)| (tag.to_string(), body.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_aggregate_changelog_between_builds() {
+        let text =
+            aggregate_changelog(&releases(), &changelogs(), 0, "2025-12-19-0100", 25).unwrap();
+        assert!(text.contains("third"));
+        assert!(text.contains("second"));
+        assert!(text.contains("first"));
+        assert!(!text.contains("installed"));
+        // Newest first
+        assert!(text.find("third").unwrap() < text.find("first").unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_changelog_not_newer() {
+        // Selected is the installed build
+        assert!(
+            aggregate_changelog(&releases(), &changelogs(), 3, "2025-12-19-0100", 25).is_none()
+        );
+        // Only one build newer than installed
+        assert!(
+            aggregate_changelog(&releases(), &changelogs(), 2, "2025-12-19-0100", 25).is_none()
+        );
+    }
+
+    #[test]
+    fn test_stable_version_parse() {
+        let h = StableVersion::parse("0.H").unwrap();
+        assert_eq!(StableVersion::parse("0.H-RELEASE"), Some(h));
+        assert_eq!(h.point, 0);
+
+        let f3 = StableVersion::parse("0.F-3").unwrap();
+        assert_eq!(StableVersion::parse("0.F-3-RELEASE"), Some(f3));
+        assert_eq!((f3.letter, f3.point), ('F', 3));
+        assert_ne!(StableVersion::parse("0.F"), Some(f3));
+        assert!(StableVersion::parse("0.F").unwrap() < f3);
+        assert!(f3 < h);
+
+        assert_eq!(
+            StableVersion::parse("cdda-experimental-2025-12-20-2147"),
+            None
+        );
+        assert_eq!(StableVersion::parse("2025-12-13 (302bb35)"), None);
+        assert_eq!(StableVersion::parse("0.HH"), None);
+    }
+
+    #[test]
+    fn test_selected_has_asset() {
+        let asset = |name: &str| crate::github::ReleaseAsset {
+            name: name.to_string(),
+            size: 1,
+            browser_download_url: String::new(),
+            digest: None,
+        };
+        let mut state = ReleasesState {
+            experimental: releases(),
+            ..Default::default()
+        };
+        assert!(state.selected_has_asset("experimental"));
+
+        state.selected_idx = Some(0);
+        assert!(!state.selected_has_asset("experimental"));
+
+        // Assets uploaded after the release was first fetched are picked up
+        state.experimental[0].assets = vec![
+            asset("cdda-windows-with-graphics-x64-2025-12-22-0100.zip"),
+            asset("cdda-linux-with-graphics-x64-2025-12-22-0100.tar.gz"),
+        ];
+        assert!(state.selected_has_asset("experimental"));
+
+        state.selected_idx = Some(1);
+        assert!(!state.selected_has_asset("experimental"));
+    }
+
+    #[test]
+    fn test_stable_selection_compared_semantically() {
+        let tags = ["0.H-RELEASE", "0.G", "0.F-3", "0.F"];
+        let state = ReleasesState {
+            stable: tags
+                .iter()
+                .map(|tag| Release {
+                    tag_name: tag.to_string(),
+                    name: tag.to_string(),
+                    body: None,
+                    published_at: "2024-11-23T00:00:00Z".to_string(),
+                    assets: Vec::new(),
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let installed = |version: &str| GameInfo {
+            executable: "cataclysm-tiles".into(),
+            version_info: Some(crate::db::VersionInfo {
+                version: version.to_string(),
+                stable: true,
+                released_on: None,
+            }),
+            saves_size: 0,
+        };
+        let select = |idx: usize| ReleasesState {
+            selected_idx: Some(idx),
+            stable: state.stable.clone(),
+            ..Default::default()
+        };
+
+        let h = installed("0.H-RELEASE");
+        assert!(!select(0).is_selected_different("stable", Some(&h), None));
+        assert!(select(1).is_selected_different("stable", Some(&h), None));
+
+        // "0.F" must not be mistaken for the "0.F-3" point release
+        let f = installed("0.F");
+        assert!(!select(3).is_selected_different("stable", Some(&f), None));
+        assert!(select(2).is_selected_different("stable", Some(&f), None));
+        assert_eq!(
+            state
+                .installed_release("stable", &f)
+                .map(|r| r.tag_name.as_str()),
+            Some("0.F")
+        );
+        assert_eq!(
+            state
+                .installed_release("stable", &installed("0.H"))
+                .map(|r| r.tag_name.as_str()),
+            Some("0.H-RELEASE")
+        );
+    }
+
+    #[test]
+    fn test_is_installed_release_latest_build() {
+        // What detection reports for an experimental build
+        let installed = GameInfo {
+            executable: "cataclysm-tiles".into(),
+            version_info: Some(crate::db::VersionInfo {
+                version: "2025-12-19 (302bb35)".to_string(),
+                stable: false,
+                released_on: Some("2025-12-19-0100".to_string()),
+            }),
+            saves_size: 0,
+        };
+        let latest = "cdda-experimental-2025-12-19-0100";
+
+        // The displayed version never equals the tag; the build number does
+        assert_ne!(installed.version_display(), latest);
+        assert!(is_installed_release(latest, &installed, None));
+        assert!(!is_installed_release(
+            "cdda-experimental-2025-12-20-0100",
+            &installed,
+            None
+        ));
+
+        let undetected = GameInfo {
+            version_info: None,
+            ..installed
+        };
+        assert!(!is_installed_release(latest, &undetected, None));
+        assert!(is_installed_release(
+            latest,
+            &undetected,
+            Some("2025-12-19-0100")
+        ));
+    }
+
+    #[test]
+    fn test_version_override_used_when_undetected() {
+        let state = ReleasesState {
+            experimental: releases(),
+            selected_idx: Some(1),
+            ..Default::default()
+        };
+        let selected = state.experimental[1].tag_name.clone();
+        let build = selected.trim_start_matches("cdda-experimental-");
+        let undetected = GameInfo {
+            executable: "cataclysm-tiles".into(),
+            version_info: None,
+            saves_size: 0,
+        };
+
+        // Without an override an unknown install always offers an update
+        assert!(state.is_selected_different("experimental", Some(&undetected), None));
+        assert!(!state.is_selected_different("experimental", Some(&undetected), Some(build)));
+        assert!(!state.is_selected_different("experimental", Some(&undetected), Some(&selected)));
+        assert!(state.is_selected_different(
+            "experimental",
+            Some(&undetected),
+            Some("2020-01-01-0000")
+        ));
+
+        assert!(tag_matches_override("0.H-RELEASE", "0.H"));
+        assert!(!tag_matches_override("0.F-3", "0.F"));
+        assert!(!tag_matches_override("0.H-RELEASE", " "));
+    }
+
+    #[test]
+    fn test_aggregate_changelog_cap_and_unknown_install() {
+        let text =
+            aggregate_changelog(&releases(), &changelogs(), 0, "2020-01-01-0000", 2).unwrap();
+        assert!(text.contains("third"));
+        assert!(text.contains("second"));
+        assert!(!text.contains("first"));
+        assert!(text.contains("2 older builds not shown"));
+    }
+}
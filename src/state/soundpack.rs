@@ -1,5 +1,7 @@
 //! Soundpack-related application state
 
+use std::collections::HashSet;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 use eframe::egui;
@@ -8,11 +10,21 @@ use tokio::task::JoinHandle;
 
 use crate::app_data::RepoSoundpack;
 use crate::soundpack::{
-    self, InstalledSoundpack, SoundpackError, SoundpackPhase, SoundpackProgress,
+    self, InstalledSoundpack, PreviewSample, SoundPreview, SoundpackError, SoundpackPhase,
+    SoundpackProgress,
 };
 use crate::state::StateEvent;
 use crate::task::{PollResult, poll_task};
 
+/// Maximum number of repository size lookups in flight at once
+const MAX_SIZE_LOOKUPS: usize = 4;
+
+/// HEAD request resolving the size of one repository soundpack
+struct SizeLookup {
+    url: String,
+    task: Option<JoinHandle<Option<u64>>>,
+}
+
 /// Soundpack-related state
 pub struct SoundpackState {
     /// List of installed soundpacks
@@ -29,6 +41,9 @@ pub struct SoundpackState {
     pub task: Option<JoinHandle<Result<InstalledSoundpack, SoundpackError>>>,
     /// Async task for loading soundpack list
     list_task: Option<JoinHandle<Result<Vec<InstalledSoundpack>, SoundpackError>>>,
+    /// Async task enabling or disabling soundpacks (yields how many others
+    /// were disabled)
+    enable_task: Option<JoinHandle<Result<usize, SoundpackError>>>,
     /// Channel receiver for soundpack progress
     progress_rx: Option<watch::Receiver<SoundpackProgress>>,
     /// Current soundpack progress
@@ -41,6 +56,24 @@ pub struct SoundpackState {
     pub browser_download_url: Option<String>,
     /// Browser download repo soundpack
     pub browser_download_soundpack: Option<RepoSoundpack>,
+    /// Large download awaiting confirmation, with its size in bytes
+    pub confirm_download: Option<(RepoSoundpack, u64)>,
+    /// Soundpack whose download size is being probed
+    size_pending: Option<RepoSoundpack>,
+    /// Async task probing the download size
+    size_task: Option<JoinHandle<Option<u64>>>,
+    /// Size lookups for repository rows as they scroll into view
+    size_lookups: Vec<SizeLookup>,
+    /// Repository URLs whose size the server wouldn't report
+    size_unavailable: HashSet<String>,
+    /// HTTP client for downloads (carries the proxy settings)
+    pub client: reqwest::Client,
+    /// Sample currently playing for a soundpack preview
+    preview: Option<SoundPreview>,
+    /// Soundpack whose preview sample is being loaded
+    preview_pending: Option<PathBuf>,
+    /// Async task finding and opening the preview sample
+    preview_task: Option<JoinHandle<Result<PreviewSample, SoundpackError>>>,
 }
 
 impl Default for SoundpackState {
@@ -53,12 +86,22 @@ impl Default for SoundpackState {
             repository: soundpack::load_repository(),
             task: None,
             list_task: None,
+            enable_task: None,
             progress_rx: None,
             progress: SoundpackProgress::default(),
             error: None,
             confirm_delete: false,
             browser_download_url: None,
             browser_download_soundpack: None,
+            confirm_download: None,
+            size_pending: None,
+            size_task: None,
+            size_lookups: Vec::new(),
+            size_unavailable: HashSet::new(),
+            client: reqwest::Client::default(),
+            preview: None,
+            preview_pending: None,
+            preview_task: None,
         }
     }
 }
@@ -66,7 +109,129 @@ impl Default for SoundpackState {
 impl SoundpackState {
     /// Check if a soundpack operation is in progress
     pub fn is_busy(&self) -> bool {
-        self.task.is_some() || self.list_loading
+        self.task.is_some()
+            || self.list_loading
+            || self.size_task.is_some()
+            || self.enable_task.is_some()
+    }
+
+    /// Number of installed soundpacks that are enabled
+    pub fn enabled_count(&self) -> usize {
+        self.list.iter().filter(|s| s.enabled).count()
+    }
+
+    /// Enable or disable the soundpack at `path`; with `exclusive`, enabling
+    /// it disables every other soundpack. The list refreshes afterwards.
+    pub fn set_enabled(&mut self, path: PathBuf, enabled: bool, exclusive: bool, game_dir: &Path) {
+        if self.enable_task.is_some() {
+            return;
+        }
+
+        self.error = None;
+        let game_dir = game_dir.to_path_buf();
+        self.enable_task = Some(tokio::spawn(async move {
+            if enabled && exclusive {
+                soundpack::set_active_soundpack(&game_dir, &path).await
+            } else {
+                soundpack::set_soundpack_enabled(&path, enabled)
+                    .await
+                    .map(|()| 0)
+            }
+        }));
+    }
+
+    /// Check if a sample from the soundpack at `path` is playing (or about to)
+    pub fn is_previewing(&self, path: &Path) -> bool {
+        self.preview_pending.as_deref() == Some(path)
+            || self
+                .preview
+                .as_ref()
+                .is_some_and(|preview| preview.soundpack == path)
+    }
+
+    /// Sample playing for the soundpack at `path`, if any
+    pub fn preview_sample(&self, path: &Path) -> Option<&Path> {
+        self.preview
+            .as_ref()
+            .filter(|preview| preview.soundpack == path)
+            .map(|preview| preview.sample.as_path())
+    }
+
+    /// Play a sample from the soundpack at `path`, or stop it if it's
+    /// already playing. Starting a preview stops any other one.
+    pub fn toggle_preview(&mut self, path: &Path) {
+        let was_playing = self.is_previewing(path);
+        self.stop_preview();
+        if was_playing {
+            return;
+        }
+
+        self.preview_pending = Some(path.to_path_buf());
+        let path = path.to_path_buf();
+        self.preview_task = Some(tokio::task::spawn_blocking(move || {
+            soundpack::load_preview(&path)
+        }));
+    }
+
+    /// Stop any playing (or loading) preview
+    pub fn stop_preview(&mut self) {
+        if let Some(task) = self.preview_task.take() {
+            task.abort();
+        }
+        self.preview_pending = None;
+        self.preview = None;
+    }
+
+    /// Check if a download size is being probed before install
+    pub fn is_checking_size(&self) -> bool {
+        self.size_task.is_some()
+    }
+
+    /// Check if the size of a repository soundpack is being looked up
+    pub fn is_size_pending(&self, url: &str) -> bool {
+        self.size_lookups.iter().any(|lookup| lookup.url == url)
+    }
+
+    /// Start size lookups for the repository rows in `rows` that don't have
+    /// a size yet, a few at a time. Called each frame with the visible rows,
+    /// so sizes resolve lazily as the list scrolls.
+    pub fn request_repo_sizes(&mut self, rows: Range<usize>) {
+        for idx in rows {
+            if self.size_lookups.len() >= MAX_SIZE_LOOKUPS {
+                return;
+            }
+            let Some(repo_soundpack) = self.repository.get(idx) else {
+                return;
+            };
+            let url = &repo_soundpack.url;
+            if repo_soundpack.size.is_some()
+                || repo_soundpack.download_type == "browser_download"
+                || self.size_unavailable.contains(url)
+                || self.is_size_pending(url)
+            {
+                continue;
+            }
+
+            let client = self.client.clone();
+            let probe_url = url.clone();
+            self.size_lookups.push(SizeLookup {
+                url: url.clone(),
+                task: Some(tokio::spawn(async move {
+                    soundpack::probe_download_size(&client, &probe_url).await
+                })),
+            });
+        }
+    }
+
+    /// Abort outstanding repository size lookups (e.g. when leaving the tab).
+    ///
+    /// Rows that didn't resolve are looked up again when next shown.
+    pub fn cancel_size_lookups(&mut self) {
+        for lookup in self.size_lookups.drain(..) {
+            if let Some(task) = lookup.task {
+                task.abort();
+            }
+        }
     }
 
     /// Refresh the installed soundpack list
@@ -85,6 +250,37 @@ impl SoundpackState {
         self.list_task = Some(task);
     }
 
+    /// Install a soundpack from the repository, asking for confirmation
+    /// first if the download is large.
+    ///
+    /// Repository entries without a known size are probed with a HEAD request;
+    /// the install continues from `poll` once the size is known.
+    pub fn request_install(&mut self, repo_soundpack: RepoSoundpack, game_dir: &Path) {
+        if repo_soundpack.download_type == "browser_download" {
+            self.install(repo_soundpack, game_dir);
+            return;
+        }
+
+        match repo_soundpack.size {
+            Some(size) if soundpack::is_large_download(size) => {
+                self.confirm_download = Some((repo_soundpack, size));
+            }
+            Some(_) => self.install(repo_soundpack, game_dir),
+            None => {
+                if self.size_task.is_some() {
+                    return;
+                }
+                self.error = None;
+                let url = repo_soundpack.url.clone();
+                let client = self.client.clone();
+                self.size_pending = Some(repo_soundpack);
+                self.size_task = Some(tokio::spawn(async move {
+                    soundpack::probe_download_size(&client, &url).await
+                }));
+            }
+        }
+    }
+
     /// Install a soundpack from the repository
     pub fn install(&mut self, repo_soundpack: RepoSoundpack, game_dir: &Path) {
         // Check if it's a browser download
@@ -100,7 +296,7 @@ impl SoundpackState {
         let (progress_tx, progress_rx) = watch::channel(SoundpackProgress::default());
         self.progress_rx = Some(progress_rx);
 
-        let client = reqwest::Client::new();
+        let client = self.client.clone();
         let game_dir = game_dir.to_path_buf();
 
         let task = tokio::spawn(async move {
@@ -139,6 +335,39 @@ impl SoundpackState {
             ctx.request_repaint();
         }
 
+        // Start playing the preview sample once it's loaded
+        match poll_task(&mut self.preview_task) {
+            PollResult::Complete(Ok(Ok(sample))) => {
+                self.preview_pending = None;
+                match soundpack::play_preview(sample) {
+                    Ok(preview) => self.preview = Some(preview),
+                    Err(e) => self.error = Some(format!("Preview failed: {}", e)),
+                }
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                self.preview_pending = None;
+                self.error = Some(format!("Preview failed: {}", e));
+            }
+            PollResult::Complete(Err(e)) => {
+                self.preview_pending = None;
+                events.push(StateEvent::LogError(format!(
+                    "Soundpack preview task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        // Reset the Preview button once the sample has played out
+        if let Some(preview) = &self.preview {
+            if preview.is_finished() {
+                self.preview = None;
+            } else {
+                ctx.request_repaint_after(std::time::Duration::from_millis(250));
+            }
+        }
+
         // Check main soundpack task
         match poll_task(&mut self.task) {
             PollResult::Complete(Ok(Ok(installed))) => {
@@ -185,6 +414,95 @@ impl SoundpackState {
             PollResult::NoTask => {}
         }
 
+        // Check the download size probe, then confirm or install
+        let probed = match poll_task(&mut self.size_task) {
+            PollResult::Complete(Ok(size)) => Some(size),
+            PollResult::Complete(Err(e)) => {
+                tracing::warn!("Soundpack size probe panicked: {}", e);
+                Some(None)
+            }
+            PollResult::Pending => {
+                ctx.request_repaint();
+                None
+            }
+            PollResult::NoTask => None,
+        };
+        if let Some(size) = probed
+            && let Some(mut repo_soundpack) = self.size_pending.take()
+        {
+            repo_soundpack.size = size;
+            match size {
+                Some(size) if soundpack::is_large_download(size) => {
+                    self.confirm_download = Some((repo_soundpack, size));
+                }
+                _ => {
+                    if let Some(dir) = game_dir {
+                        self.install(repo_soundpack, dir);
+                    }
+                }
+            }
+        }
+
+        // Record repository sizes as their lookups finish
+        let mut resolved = Vec::new();
+        self.size_lookups
+            .retain_mut(|lookup| match poll_task(&mut lookup.task) {
+                PollResult::Complete(result) => {
+                    resolved.push((lookup.url.clone(), result.ok().flatten()));
+                    false
+                }
+                PollResult::Pending => true,
+                PollResult::NoTask => false,
+            });
+        for (url, size) in resolved {
+            match size {
+                Some(size) => {
+                    for repo_soundpack in self.repository.iter_mut().filter(|r| r.url == url) {
+                        repo_soundpack.size = Some(size);
+                    }
+                }
+                None => {
+                    self.size_unavailable.insert(url);
+                }
+            }
+        }
+        if !self.size_lookups.is_empty() {
+            ctx.request_repaint();
+        }
+
+        // Check the enable/disable task, then reload the list
+        match poll_task(&mut self.enable_task) {
+            PollResult::Complete(Ok(Ok(disabled))) => {
+                if disabled > 0 {
+                    events.push(StateEvent::StatusMessage(format!(
+                        "Disabled {} other soundpack(s)",
+                        disabled
+                    )));
+                }
+                if let Some(dir) = game_dir {
+                    self.refresh_list(dir);
+                }
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                events.push(StateEvent::LogError(format!(
+                    "Failed to toggle soundpack: {}",
+                    e
+                )));
+                self.error = Some(e.to_string());
+                if let Some(dir) = game_dir {
+                    self.refresh_list(dir);
+                }
+            }
+            PollResult::Complete(Err(e)) => {
+                events.push(StateEvent::LogError(format!(
+                    "Soundpack toggle task panicked: {}",
+                    e
+                )));
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
         // Check list loading task
         match poll_task(&mut self.list_task) {
             PollResult::Complete(Ok(Ok(list))) => {
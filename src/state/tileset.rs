@@ -0,0 +1,222 @@
+//! Tileset-related application state
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::app_data::RepoTileset;
+use crate::soundpack::{SoundpackPhase, SoundpackProgress};
+use crate::state::StateEvent;
+use crate::task::{PollResult, poll_task};
+use crate::tileset::{self, InstalledTileset, TilesetError};
+
+/// Tileset-related state
+pub struct TilesetState {
+    /// List of installed tilesets
+    pub list: Vec<InstalledTileset>,
+    /// Whether tileset list is being loaded
+    pub list_loading: bool,
+    /// Index of selected installed tileset
+    pub installed_idx: Option<usize>,
+    /// Index of selected repository tileset
+    pub repo_idx: Option<usize>,
+    /// Repository tilesets
+    pub repository: Vec<RepoTileset>,
+    /// Async task for install/delete operations (`None` result means a delete finished)
+    task: Option<JoinHandle<Result<Option<InstalledTileset>, TilesetError>>>,
+    /// Async task for loading tileset list
+    list_task: Option<JoinHandle<Result<Vec<InstalledTileset>, TilesetError>>>,
+    /// Channel receiver for tileset progress
+    progress_rx: Option<watch::Receiver<SoundpackProgress>>,
+    /// Current tileset progress
+    pub progress: SoundpackProgress,
+    /// Error message from last tileset operation
+    pub error: Option<String>,
+    /// Whether to show delete confirmation
+    pub confirm_delete: bool,
+    /// HTTP client for downloads (carries the proxy settings)
+    pub client: reqwest::Client,
+}
+
+impl Default for TilesetState {
+    fn default() -> Self {
+        Self {
+            list: Vec::new(),
+            list_loading: false,
+            installed_idx: None,
+            repo_idx: None,
+            repository: tileset::load_repository(),
+            task: None,
+            list_task: None,
+            progress_rx: None,
+            progress: SoundpackProgress::default(),
+            error: None,
+            confirm_delete: false,
+            client: reqwest::Client::default(),
+        }
+    }
+}
+
+impl TilesetState {
+    /// Check if a tileset operation is in progress
+    pub fn is_busy(&self) -> bool {
+        self.task.is_some() || self.list_loading
+    }
+
+    /// Refresh the installed tileset list
+    pub fn refresh_list(&mut self, game_dir: &Path) {
+        if self.list_loading {
+            return;
+        }
+
+        self.list_loading = true;
+        self.error = None;
+
+        let game_dir = game_dir.to_path_buf();
+        self.list_task = Some(tokio::spawn(async move {
+            tileset::list_installed_tilesets(&game_dir).await
+        }));
+    }
+
+    /// Install a tileset from the repository
+    pub fn install(&mut self, repo_tileset: RepoTileset, game_dir: &Path) {
+        let progress_tx = self.begin_operation();
+        let client = self.client.clone();
+        let game_dir = game_dir.to_path_buf();
+
+        self.task = Some(tokio::spawn(async move {
+            tileset::install_tileset(client, repo_tileset, game_dir, progress_tx)
+                .await
+                .map(Some)
+        }));
+    }
+
+    /// Install a tileset from a local archive
+    pub fn install_from_file(&mut self, archive_path: PathBuf, game_dir: &Path) {
+        let progress_tx = self.begin_operation();
+        let game_dir = game_dir.to_path_buf();
+
+        self.task = Some(tokio::spawn(async move {
+            tileset::install_from_file(archive_path, game_dir, progress_tx)
+                .await
+                .map(Some)
+        }));
+    }
+
+    /// Delete the selected installed tileset
+    pub fn delete_selected(&mut self) {
+        let Some(path) = self
+            .installed_idx
+            .and_then(|i| self.list.get(i))
+            .map(|t| t.path.clone())
+        else {
+            return;
+        };
+
+        self.error = None;
+        self.installed_idx = None;
+        self.progress_rx = None;
+        self.progress = SoundpackProgress {
+            phase: SoundpackPhase::Deleting,
+            ..Default::default()
+        };
+
+        self.task = Some(tokio::spawn(async move {
+            tileset::delete_tileset(path).await.map(|_| None)
+        }));
+    }
+
+    /// Reset progress and create a fresh progress channel
+    fn begin_operation(&mut self) -> watch::Sender<SoundpackProgress> {
+        self.error = None;
+        self.progress = SoundpackProgress::default();
+
+        let (progress_tx, progress_rx) = watch::channel(SoundpackProgress::default());
+        self.progress_rx = Some(progress_rx);
+        progress_tx
+    }
+
+    /// Poll tileset tasks for completion
+    pub fn poll(&mut self, ctx: &egui::Context, game_dir: Option<&Path>) -> Vec<StateEvent> {
+        let mut events = Vec::new();
+
+        if let Some(ref mut rx) = self.progress_rx
+            && rx.has_changed().unwrap_or(false)
+        {
+            self.progress = rx.borrow_and_update().clone();
+            ctx.request_repaint();
+        }
+
+        match poll_task(&mut self.task) {
+            PollResult::Complete(Ok(Ok(installed))) => {
+                self.progress_rx = None;
+                self.progress.phase = SoundpackPhase::Complete;
+                let message = match installed {
+                    Some(t) => format!("Tileset installed: {}", t.name),
+                    None => "Tileset deleted".to_string(),
+                };
+                events.push(StateEvent::LogInfo(message.clone()));
+                events.push(StateEvent::StatusMessage(message));
+
+                if let Some(dir) = game_dir {
+                    self.refresh_list(dir);
+                }
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                self.progress_rx = None;
+                events.push(StateEvent::LogError(format!(
+                    "Tileset operation failed: {}",
+                    e
+                )));
+                self.error = Some(e.to_string());
+                self.progress.phase = SoundpackPhase::Failed;
+                self.progress.error = Some(e.to_string());
+            }
+            PollResult::Complete(Err(e)) => {
+                self.progress_rx = None;
+                events.push(StateEvent::LogError(format!(
+                    "Tileset task panicked: {}",
+                    e
+                )));
+                self.error = Some("Task panicked".to_string());
+                self.progress.phase = SoundpackPhase::Failed;
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        match poll_task(&mut self.list_task) {
+            PollResult::Complete(Ok(Ok(list))) => {
+                self.list_loading = false;
+                self.list = list;
+                if let Some(idx) = self.installed_idx
+                    && idx >= self.list.len()
+                {
+                    self.installed_idx = None;
+                }
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                self.list_loading = false;
+                events.push(StateEvent::LogError(format!(
+                    "Failed to load tileset list: {}",
+                    e
+                )));
+                self.error = Some(e.to_string());
+            }
+            PollResult::Complete(Err(e)) => {
+                self.list_loading = false;
+                events.push(StateEvent::LogError(format!(
+                    "Tileset list task panicked: {}",
+                    e
+                )));
+                self.error = Some("Task panicked".to_string());
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        events
+    }
+}
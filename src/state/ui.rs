@@ -1,7 +1,13 @@
 //! UI-related application state
 
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+use eframe::egui;
 use egui_commonmark::CommonMarkCache;
 
+use crate::config::ConfigRecovery;
+use crate::logging::LogSnapshot;
 use crate::ui::theme::Theme;
 
 /// Application tabs representing the main navigation sections.
@@ -14,10 +20,24 @@ pub enum Tab {
     Backups,
     /// Soundpacks tab: install soundpacks from repository or local files
     Soundpacks,
+    /// Tilesets tab: install tilesets from repository or local files
+    Tilesets,
+    /// Library tab: search across mods, soundpacks, tilesets and backups
+    Library,
     /// Settings tab: theme selection, update preferences, backup options
     Settings,
 }
 
+/// Number of status messages kept in the status bar history
+pub const STATUS_HISTORY_LEN: usize = 20;
+
+/// A status bar message and when it was shown
+#[derive(Debug, Clone)]
+pub struct StatusEntry {
+    pub time: DateTime<Local>,
+    pub message: String,
+}
+
 /// UI-related state
 pub struct UiState {
     /// Cache for markdown rendering
@@ -28,8 +48,24 @@ pub struct UiState {
     pub active_tab: Tab,
     /// Whether theme needs to be applied
     pub theme_dirty: bool,
+    /// OS light/dark preference the Auto theme was last resolved against
+    pub system_theme: Option<egui::Theme>,
     /// Whether to show the About dialog
     pub show_about_dialog: bool,
+    /// Whether the changelog shows every build since the installed one
+    pub show_changelog_since_installed: bool,
+    /// Whether to show the confirmation for removing the installation archive
+    pub confirm_remove_archive: bool,
+    /// Save compatibility warning awaiting confirmation before launching
+    pub confirm_launch: Option<String>,
+    /// Notice that a corrupt config file was recovered at startup
+    pub config_recovery: Option<ConfigRecovery>,
+    /// Recent status bar messages, oldest first
+    pub status_history: VecDeque<StatusEntry>,
+    /// Whether the status history panel is expanded
+    pub show_status_history: bool,
+    /// Log lines shown in the Settings tab
+    pub logs: LogSnapshot,
 }
 
 impl UiState {
@@ -40,7 +76,58 @@ impl UiState {
             current_theme: theme,
             active_tab: Tab::default(),
             theme_dirty: true, // Apply theme on first frame
+            system_theme: None,
             show_about_dialog: false,
+            show_changelog_since_installed: false,
+            confirm_remove_archive: false,
+            confirm_launch: None,
+            config_recovery: None,
+            status_history: VecDeque::new(),
+            show_status_history: false,
+            logs: LogSnapshot::default(),
+        }
+    }
+
+    /// Record a status message in the history if it differs from the last one
+    pub fn record_status(&mut self, message: &str) {
+        if message.is_empty()
+            || self
+                .status_history
+                .back()
+                .is_some_and(|entry| entry.message == message)
+        {
+            return;
+        }
+        if self.status_history.len() == STATUS_HISTORY_LEN {
+            self.status_history.pop_front();
+        }
+        self.status_history.push_back(StatusEntry {
+            time: Local::now(),
+            message: message.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_status_skips_repeats_and_caps_length() {
+        let mut ui = UiState::new(Theme::amber());
+        ui.record_status("");
+        ui.record_status("Ready");
+        ui.record_status("Ready");
+        assert_eq!(ui.status_history.len(), 1);
+
+        for i in 0..STATUS_HISTORY_LEN + 5 {
+            ui.record_status(&format!("Message {}", i));
         }
+        assert_eq!(ui.status_history.len(), STATUS_HISTORY_LEN);
+        assert_eq!(ui.status_history[0].message, "Message 5");
+        assert_eq!(
+            ui.status_history.back().unwrap().message,
+            format!("Message {}", STATUS_HISTORY_LEN + 4)
+        );
     }
 }
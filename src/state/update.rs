@@ -1,17 +1,21 @@
 //! Update-related application state
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use anyhow::Result;
 use eframe::egui;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
-use crate::backup::{self, AutoBackupType, BackupProgress};
+use crate::backup::{self, AutoBackupType, BackupNameVars, BackupProgress, RetentionPolicy};
 use crate::github::{GitHubClient, Release, ReleaseAsset};
 use crate::state::StateEvent;
 use crate::task::{PollResult, poll_task};
-use crate::update::{self, UpdatePhase, UpdateProgress};
+use crate::update::{
+    self, ArchivedInstall, GameRunningError, InstallOptions, MigrationSummary, RunningProcess,
+    SpeedHistory, UpdatePhase, UpdateProgress,
+};
 
 /// Configuration for starting an update
 pub struct UpdateParams {
@@ -19,24 +23,84 @@ pub struct UpdateParams {
     pub asset: ReleaseAsset,
     pub game_dir: PathBuf,
     pub client: GitHubClient,
-    pub prevent_save_move: bool,
-    pub remove_previous_version: bool,
+    pub install: InstallOptions,
     pub backup_before_update: bool,
     pub compression_level: u8,
-    pub max_backups: u32,
+    pub retention: RetentionPolicy,
+    pub backup_name_template: String,
+    pub branch: String,
+    /// Re-install the current version, reusing its download if present
+    pub repair: bool,
+    /// Retry a failed install, reusing its download if it's still intact
+    pub retry: bool,
+    /// Parallel connections for the download
+    pub download_connections: u8,
+}
+
+/// Configuration for rolling back to an archived installation
+pub struct RollbackParams {
+    pub target: ArchivedInstall,
+    pub game_dir: PathBuf,
+    pub install: InstallOptions,
 }
 
 /// Update-related state
 #[derive(Default)]
 pub struct UpdateState {
     /// Async task for update operation
-    task: Option<JoinHandle<Result<()>>>,
+    task: Option<JoinHandle<Result<MigrationSummary>>>,
     /// Channel receiver for update progress
     progress_rx: Option<watch::Receiver<UpdateProgress>>,
     /// Current update progress
     pub progress: UpdateProgress,
+    /// Recent download speed samples for the progress sparkline
+    pub speed_history: SpeedHistory,
     /// Error message from last update attempt
     pub error: Option<String>,
+    /// Custom content preserved by the last successful update (until dismissed)
+    pub preserved: Option<MigrationSummary>,
+    /// Size of the installation archive in bytes (None until calculated)
+    pub archive_size: Option<u64>,
+    /// Async task for calculating the installation archive size
+    archive_size_task: Option<JoinHandle<u64>>,
+    /// Game processes that blocked the last update attempt
+    pub running_game: Vec<RunningProcess>,
+    /// Whether to show the confirmation for terminating the running game
+    pub confirm_terminate: bool,
+    /// Whether to show the confirmation for repairing the installation
+    pub confirm_repair: bool,
+    /// Whether the current (or last) operation is a repair
+    pub repairing: bool,
+    /// Whether to show the confirmation for a clean reinstall
+    pub confirm_clean: bool,
+    /// Whether a clean reinstall also deletes the archived installations
+    pub clean_delete_archives: bool,
+    /// Whether the current (or last) operation is a clean reinstall
+    pub cleaning: bool,
+    /// Archived installations available for rollback (None until listed)
+    pub archives: Option<Vec<ArchivedInstall>>,
+    /// Async task for listing the archived installations
+    archives_task: Option<JoinHandle<Vec<ArchivedInstall>>>,
+    /// Whether to show the rollback dialog
+    pub show_rollback: bool,
+    /// Archive selected in the rollback dialog
+    pub rollback_selected: Option<usize>,
+    /// Whether the current (or last) operation is a rollback
+    pub rolling_back: bool,
+    /// Whether to show the confirmation for switching to the latest stable
+    pub confirm_switch_stable: bool,
+    /// Whether the latest stable release installs once stable releases are fetched
+    pub pending_stable_install: bool,
+    /// Async task for terminating the running game
+    terminate_task: Option<JoinHandle<Result<()>>>,
+    /// Pause control for the running download (`true` = paused)
+    pause_tx: Option<watch::Sender<bool>>,
+    /// Whether the running download is paused
+    pub paused: bool,
+    /// Release of the last update, repair or clean reinstall started
+    pub last_release: Option<Release>,
+    /// Whether the last update made its pre-update backup (set by the task)
+    backed_up_rx: Option<watch::Receiver<bool>>,
 }
 
 impl UpdateState {
@@ -45,6 +109,75 @@ impl UpdateState {
         self.task.is_some()
     }
 
+    /// Check if the running update is downloading and can be paused or resumed
+    pub fn can_pause(&self) -> bool {
+        self.pause_tx.is_some() && self.progress.phase == UpdatePhase::Downloading
+    }
+
+    /// Pause or resume the running download, keeping what was downloaded
+    pub fn set_paused(&mut self, paused: bool) {
+        if let Some(tx) = &self.pause_tx {
+            let _ = tx.send(paused);
+            self.paused = paused;
+            tracing::info!("Download {}", if paused { "paused" } else { "resumed" });
+        }
+    }
+
+    /// Check if the last update failed and can be retried
+    pub fn can_retry(&self) -> bool {
+        self.task.is_none()
+            && self.progress.phase == UpdatePhase::Failed
+            && !self.rolling_back
+            && self.last_release.is_some()
+    }
+
+    /// Check if the installation archive size is being calculated
+    pub fn is_calculating_archive_size(&self) -> bool {
+        self.archive_size_task.is_some()
+    }
+
+    /// Calculate the installation archive size in the background
+    pub fn refresh_archive_size(&mut self, game_dir: &Path) {
+        if self.archive_size_task.is_some() {
+            return;
+        }
+
+        let game_dir = game_dir.to_path_buf();
+        self.archive_size_task = Some(tokio::spawn(async move {
+            update::installation_archive_size(&game_dir).await
+        }));
+    }
+
+    /// List the archived installations in the background
+    pub fn refresh_archives(&mut self, game_dir: &Path) {
+        if self.archives_task.is_some() {
+            return;
+        }
+
+        let game_dir = game_dir.to_path_buf();
+        self.archives_task = Some(tokio::task::spawn_blocking(move || {
+            update::list_archives(&game_dir)
+        }));
+    }
+
+    /// Check if the running game is being terminated
+    pub fn is_terminating(&self) -> bool {
+        self.terminate_task.is_some()
+    }
+
+    /// Terminate the game processes that blocked the last update.
+    /// The update is retried once they have exited.
+    pub fn terminate_running_game(&mut self) {
+        if self.terminate_task.is_some() || self.running_game.is_empty() {
+            return;
+        }
+
+        let processes = std::mem::take(&mut self.running_game);
+        self.terminate_task = Some(tokio::task::spawn_blocking(move || {
+            update::terminate_game(&processes)
+        }));
+    }
+
     /// Start the update process
     /// Returns a status message event if started successfully
     pub fn start(&mut self, params: UpdateParams) -> Option<StateEvent> {
@@ -65,11 +198,25 @@ impl UpdateState {
         let zip_path = download_dir.join(&params.asset.name);
         let download_url = params.asset.browser_download_url.clone();
         let release_name = params.release.name.clone();
+        let reuse_download = (params.repair || params.retry)
+            && update::is_complete_download(&zip_path, params.asset.size);
+        // Don't back up the saves again if the failed attempt already did
+        let backed_up = params.retry && self.backed_up_rx.as_ref().is_some_and(|rx| *rx.borrow());
+        let backup_before_update = params.backup_before_update && !backed_up;
+        let (backed_up_tx, backed_up_rx) = watch::channel(backed_up);
+        self.backed_up_rx = Some(backed_up_rx);
+        self.last_release = Some(params.release.clone());
+        self.repairing = params.repair;
+        self.cleaning = params.install.clean;
+        self.rolling_back = false;
 
         // Create progress channel
         let (progress_tx, progress_rx) = watch::channel(UpdateProgress::default());
         self.progress_rx = Some(progress_rx);
         self.error = None;
+        self.preserved = None;
+        self.running_game.clear();
+        self.speed_history.clear();
         self.progress = UpdateProgress {
             phase: UpdatePhase::Downloading,
             total_bytes: params.asset.size,
@@ -77,13 +224,19 @@ impl UpdateState {
         };
 
         let client = params.client;
-        let prevent_save_move = params.prevent_save_move;
-        let remove_previous_version = params.remove_previous_version;
-        let backup_before_update = params.backup_before_update;
+        let install = params.install;
         let compression_level = params.compression_level;
-        let max_backups = params.max_backups;
-        let version_tag = params.release.tag_name.clone();
+        let retention = params.retention;
+        let backup_name_template = params.backup_name_template;
+        let backup_name_vars = BackupNameVars {
+            version: Some(params.release.tag_name.clone()),
+            branch: Some(params.branch),
+        };
         let game_dir = params.game_dir;
+        let download_connections = params.download_connections;
+        let (pause_tx, pause_rx) = watch::channel(false);
+        self.pause_tx = Some(pause_tx);
+        self.paused = false;
 
         tracing::info!(
             "Starting update: {} from {}",
@@ -95,7 +248,7 @@ impl UpdateState {
         self.task = Some(tokio::spawn(async move {
             // Pre-flight check: verify we have write access before doing any work
             // This prevents wasting time on backup/download if the game is running
-            update::check_installation_access(&game_dir).await?;
+            update::check_installation_access(&game_dir, install.skip_access_check).await?;
 
             // Phase 0: Auto-backup before update (if enabled)
             if backup_before_update {
@@ -104,18 +257,21 @@ impl UpdateState {
                 match backup::create_auto_backup(
                     &game_dir,
                     AutoBackupType::BeforeUpdate,
-                    Some(&version_tag),
+                    &backup_name_template,
+                    &backup_name_vars,
                     compression_level,
-                    max_backups,
+                    retention,
                     backup_progress_tx,
                 )
                 .await
                 {
                     Ok(Some(info)) => {
                         tracing::info!("Pre-update backup created: {}", info.name);
+                        let _ = backed_up_tx.send(true);
                     }
                     Ok(None) => {
                         tracing::info!("No saves to backup before update");
+                        let _ = backed_up_tx.send(true);
                     }
                     Err(e) => {
                         tracing::warn!(
@@ -127,33 +283,76 @@ impl UpdateState {
                 }
             }
 
-            // Phase 1: Download
-            let result = update::download_asset(
-                client.client().clone(),
-                download_url,
-                zip_path.clone(),
-                progress_tx.clone(),
-            )
-            .await?;
-
-            tracing::info!("Download complete: {} bytes", result.bytes);
+            // Phase 1: Download (a repair or retry reuses the archive already
+            // on disk, unless it turns out to be damaged)
+            let reuse_download = reuse_download && {
+                let path = zip_path.clone();
+                tokio::task::spawn_blocking(move || update::is_intact_archive(&path))
+                    .await
+                    .unwrap_or(false)
+            };
+            let archive_path = if reuse_download {
+                tracing::info!("Reusing downloaded archive {:?}", zip_path);
+                zip_path
+            } else {
+                let result = update::download_asset(
+                    client.client().clone(),
+                    client.download_urls(&download_url),
+                    zip_path.clone(),
+                    progress_tx.clone(),
+                    download_connections,
+                    Some(pause_rx),
+                )
+                .await?;
+                tracing::info!("Download complete: {} bytes", result.bytes);
+                result.file_path
+            };
 
             // Phase 2: Install (backup, extract, restore with smart migration)
-            update::install_update(
-                result.file_path,
-                game_dir,
-                progress_tx,
-                prevent_save_move,
-                remove_previous_version,
-            )
-            .await?;
-
-            Ok(())
+            update::install_update(archive_path, game_dir, progress_tx, install).await
         }));
 
+        Some(StateEvent::StatusMessage(if params.retry {
+            format!("Retrying {}...", release_name)
+        } else if reuse_download {
+            format!("Repairing {}...", release_name)
+        } else {
+            format!("Downloading {}...", release_name)
+        }))
+    }
+
+    /// Start rolling back to an archived installation
+    /// Returns a status message event if started successfully
+    pub fn start_rollback(&mut self, params: RollbackParams) -> Option<StateEvent> {
+        if self.task.is_some() {
+            return None;
+        }
+
+        let (progress_tx, progress_rx) = watch::channel(UpdateProgress::default());
+        self.progress_rx = Some(progress_rx);
+        self.error = None;
+        self.preserved = None;
+        self.running_game.clear();
+        self.speed_history.clear();
+        self.progress = UpdateProgress {
+            phase: UpdatePhase::BackingUp,
+            ..Default::default()
+        };
+        self.repairing = false;
+        self.cleaning = false;
+        self.rolling_back = true;
+
+        let version = params.target.version_display().to_string();
+        self.task = Some(tokio::spawn(update::rollback_to_archive(
+            params.game_dir,
+            params.target,
+            progress_tx,
+            params.install,
+        )));
+
         Some(StateEvent::StatusMessage(format!(
-            "Downloading {}...",
-            release_name
+            "Rolling back to {}...",
+            version
         )))
     }
 
@@ -171,24 +370,65 @@ impl UpdateState {
             ));
         }
 
+        // Sample every frame so a stalled download registers as zero speed
+        if self.progress.phase == UpdatePhase::Downloading {
+            self.speed_history
+                .record(Instant::now(), self.progress.bytes_downloaded);
+        }
+
         // Check if task is complete
         match poll_task(&mut self.task) {
-            PollResult::Complete(Ok(Ok(()))) => {
+            PollResult::Complete(Ok(Ok(summary))) => {
                 self.progress_rx = None;
                 self.progress.phase = UpdatePhase::Complete;
-                events.push(StateEvent::StatusMessage(
-                    "Update complete! Refreshing game info...".to_string(),
-                ));
-                events.push(StateEvent::LogInfo(
-                    "Update completed successfully".to_string(),
-                ));
+                if !summary.is_empty() {
+                    events.push(StateEvent::LogInfo(summary.to_string()));
+                    self.preserved = Some(summary);
+                }
+                let action = if self.rolling_back {
+                    "Rollback"
+                } else if self.repairing {
+                    "Repair"
+                } else if self.cleaning {
+                    "Clean reinstall"
+                } else {
+                    "Update"
+                };
+                events.push(StateEvent::StatusMessage(format!(
+                    "{} complete! Refreshing game info...",
+                    action
+                )));
+                events.push(StateEvent::LogInfo(format!(
+                    "{} completed successfully",
+                    action
+                )));
                 events.push(StateEvent::RefreshGameInfo);
+                notify_if_unfocused(
+                    ctx,
+                    &format!("{} complete", action),
+                    "The game is ready to launch.",
+                );
+                // The archive now holds the previous version (or was removed)
+                self.archive_size = None;
+                self.archives = None;
             }
             PollResult::Complete(Ok(Err(e))) => {
                 self.progress_rx = None;
                 self.progress.phase = UpdatePhase::Failed;
+                // Retrying only applies to updates
+                if !self.rolling_back
+                    && let Some(blocked) = e.downcast_ref::<GameRunningError>()
+                {
+                    self.running_game = update::find_running_game(&blocked.game_dir);
+                }
                 let msg = e.to_string();
-                events.push(StateEvent::LogError(format!("Update failed: {}", msg)));
+                let action = if self.rolling_back {
+                    "Rollback"
+                } else {
+                    "Update"
+                };
+                events.push(StateEvent::LogError(format!("{} failed: {}", action, msg)));
+                notify_if_unfocused(ctx, &format!("{} failed", action), &msg);
                 self.error = Some(msg);
             }
             PollResult::Complete(Err(e)) => {
@@ -196,12 +436,94 @@ impl UpdateState {
                 self.progress.phase = UpdatePhase::Failed;
                 let msg = format!("Update task panicked: {}", e);
                 events.push(StateEvent::LogError(msg.clone()));
+                notify_if_unfocused(ctx, "Update failed", &msg);
                 self.error = Some(msg);
             }
             PollResult::Pending => ctx.request_repaint(),
             PollResult::NoTask => {}
         }
+        if self.task.is_none() {
+            self.pause_tx = None;
+            self.paused = false;
+        }
+
+        // Check if game termination is complete
+        match poll_task(&mut self.terminate_task) {
+            PollResult::Complete(Ok(Ok(()))) => {
+                events.push(StateEvent::LogInfo(
+                    "Game closed, retrying update".to_string(),
+                ));
+                events.push(StateEvent::RetryUpdate);
+            }
+            PollResult::Complete(Ok(Err(e))) => {
+                let msg = format!("Failed to close the game: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg);
+            }
+            PollResult::Complete(Err(e)) => {
+                let msg = format!("Terminate task panicked: {}", e);
+                events.push(StateEvent::LogError(msg.clone()));
+                self.error = Some(msg);
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        // Check if archive size calculation is complete
+        match poll_task(&mut self.archive_size_task) {
+            PollResult::Complete(Ok(size)) => self.archive_size = Some(size),
+            PollResult::Complete(Err(e)) => {
+                tracing::warn!("Archive size task panicked: {}", e);
+                self.archive_size = Some(0);
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
+
+        // Check if archive listing is complete
+        match poll_task(&mut self.archives_task) {
+            PollResult::Complete(Ok(archives)) => {
+                // Keep the selection only if it still points at an archive
+                if self.rollback_selected.is_some_and(|i| i >= archives.len()) {
+                    self.rollback_selected = None;
+                }
+                self.archives = Some(archives);
+            }
+            PollResult::Complete(Err(e)) => {
+                tracing::warn!("Archive listing task panicked: {}", e);
+                self.archives = Some(Vec::new());
+            }
+            PollResult::Pending => ctx.request_repaint(),
+            PollResult::NoTask => {}
+        }
 
         events
     }
 }
+
+/// Let the user know an update finished while they were in another window:
+/// flash the taskbar (bounce the dock on macOS) and show an OS notification
+fn notify_if_unfocused(ctx: &egui::Context, summary: &str, body: &str) {
+    // Treat an unknown focus state as focused rather than notify needlessly
+    if ctx.input(|i| i.viewport().focused).unwrap_or(true) {
+        return;
+    }
+
+    ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+        egui::UserAttentionType::Critical,
+    ));
+
+    let summary = summary.to_string();
+    let body = body.to_string();
+    // Showing a notification can block on the desktop's notification service
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = notify_rust::Notification::new()
+            .appname("Phoenix")
+            .summary(&summary)
+            .body(&body)
+            .show()
+        {
+            tracing::warn!("Failed to show notification: {}", e);
+        }
+    });
+}
@@ -0,0 +1,137 @@
+//! Game directory watching state
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::app_data::game_config;
+use crate::state::StateEvent;
+
+/// Quiet period after the last change before detection is refreshed, so a
+/// copy that touches several files triggers a single refresh
+const DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Watches the game directory and asks for re-detection when the executable,
+/// version file or save directory changes, or the directory itself goes away
+#[derive(Default)]
+pub struct WatchState {
+    /// Active watcher (dropping it stops watching)
+    watcher: Option<RecommendedWatcher>,
+    /// Directory currently watched
+    dir: Option<PathBuf>,
+    /// Channel receiver for filesystem events
+    rx: Option<mpsc::Receiver<notify::Result<notify::Event>>>,
+    /// When the last relevant change was seen, while a refresh is pending
+    pending: Option<Instant>,
+}
+
+impl WatchState {
+    /// Watch `dir` for changes, replacing any previous watch.
+    /// Does nothing if `dir` is already being watched.
+    pub fn watch(&mut self, ctx: &egui::Context, dir: Option<&Path>) {
+        if self.dir.as_deref() == dir {
+            return;
+        }
+
+        *self = Self::default();
+        let Some(dir) = dir else {
+            return;
+        };
+        self.dir = Some(dir.to_path_buf());
+
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+            ctx.request_repaint();
+        })
+        .and_then(|mut watcher| {
+            // Only the top level holds what detection looks at
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+
+        match watcher {
+            Ok(watcher) => {
+                tracing::debug!("Watching game directory {:?}", dir);
+                self.watcher = Some(watcher);
+                self.rx = Some(rx);
+            }
+            Err(e) => tracing::warn!("Failed to watch game directory {:?}: {}", dir, e),
+        }
+    }
+
+    /// Collect filesystem events and request a refresh once they settle.
+    ///
+    /// While `paused` (e.g. during an update, which refreshes detection
+    /// itself when done) events are discarded.
+    pub fn poll(&mut self, ctx: &egui::Context, paused: bool) -> Vec<StateEvent> {
+        let (Some(rx), Some(dir)) = (&self.rx, &self.dir) else {
+            return Vec::new();
+        };
+
+        let mut changed = false;
+        for event in rx.try_iter() {
+            match event {
+                Ok(event) => changed |= event.paths.iter().any(|path| is_relevant(dir, path)),
+                Err(e) => tracing::debug!("Game directory watch error: {}", e),
+            }
+        }
+
+        if paused {
+            self.pending = None;
+            return Vec::new();
+        }
+        if changed {
+            self.pending = Some(Instant::now());
+        }
+
+        match self.pending {
+            Some(since) if since.elapsed() >= DEBOUNCE => {
+                self.pending = None;
+                vec![StateEvent::GameDirChanged]
+            }
+            Some(since) => {
+                ctx.request_repaint_after(DEBOUNCE.saturating_sub(since.elapsed()));
+                Vec::new()
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Whether a change to `path` can affect game detection in `dir`
+fn is_relevant(dir: &Path, path: &Path) -> bool {
+    if path == dir {
+        return true;
+    }
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let config = game_config();
+    name == config.version.filename
+        || name == config.version.json_filename
+        || name == config.directories.save
+        || config.executables.names().iter().any(|exe| exe == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_relevant() {
+        let dir = Path::new("/games/cdda");
+        let exe = &game_config().executables.names()[0];
+
+        assert!(is_relevant(dir, dir));
+        assert!(is_relevant(dir, &dir.join("VERSION.txt")));
+        assert!(is_relevant(dir, &dir.join(exe)));
+        assert!(is_relevant(dir, &dir.join("save")));
+        assert!(!is_relevant(dir, &dir.join("config")));
+        assert!(!is_relevant(dir, &dir.join("debug.log")));
+    }
+}
@@ -0,0 +1,395 @@
+//! Tileset management for CDDA.
+//!
+//! This module handles:
+//! - Scanning installed tilesets from the game's `gfx/` directory
+//! - Loading the embedded tileset repository
+//! - Downloading and installing tilesets from the repository or a local archive
+//! - Deleting installed tilesets
+//!
+//! Downloading and extraction reuse the soundpack helpers, so progress is
+//! reported through [`SoundpackProgress`].
+//!
+//! Tileset repository loaded via `app_data::tilesets_repository()`.
+
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use tokio::sync::watch;
+
+use crate::app_data::{RepoTileset, game_config, migration_config, tilesets_repository};
+use crate::soundpack::{self, SoundpackError, SoundpackPhase, SoundpackProgress};
+use crate::util::{decode_text, parse_info_field};
+
+/// Information about an installed tileset
+#[derive(Debug, Clone)]
+pub struct InstalledTileset {
+    /// Internal name from tileset.txt NAME field
+    pub name: String,
+    /// Display name from tileset.txt VIEW field
+    pub view_name: String,
+    /// Path to the tileset directory
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// Errors that can occur during tileset operations
+#[derive(Error, Debug)]
+pub enum TilesetError {
+    #[error("Tileset not found: {0}")]
+    TilesetNotFound(String),
+
+    #[error("No tileset.txt with a NAME found in archive")]
+    NoTilesetTxt,
+
+    #[error("Tileset already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error(transparent)]
+    Archive(#[from] SoundpackError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Task cancelled")]
+    Cancelled,
+}
+
+// ============================================================================
+// Repository Loading
+// ============================================================================
+
+/// Load the tilesets repository from embedded JSON
+pub fn load_repository() -> Vec<RepoTileset> {
+    tilesets_repository().clone()
+}
+
+// ============================================================================
+// Tileset Scanning
+// ============================================================================
+
+/// Get the tilesets directory for a game installation
+pub fn tilesets_dir(game_dir: &Path) -> PathBuf {
+    game_dir.join(&game_config().directories.gfx)
+}
+
+/// Parse tileset.txt to extract NAME and VIEW fields
+///
+/// Accepts both `NAME: value` and `NAME value` forms; see
+/// [`parse_info_field`] for quoting and comments.
+/// Returns (name, view_name) if a NAME is present.
+pub fn parse_tileset_txt(tileset_dir: &Path) -> Option<(String, String)> {
+    let file_path = tileset_dir.join(&game_config().metadata.tileset_info);
+    let content = std::fs::read(&file_path).ok()?;
+    let text = decode_text(&content);
+
+    let mut name = None;
+    let mut view = None;
+
+    for line in text.lines() {
+        name = name.or_else(|| parse_info_field(line, "NAME", true));
+        view = view.or_else(|| parse_info_field(line, "VIEW", false));
+
+        if name.is_some() && view.is_some() {
+            break;
+        }
+    }
+
+    let name = name?;
+    let view = view.unwrap_or_else(|| name.clone());
+
+    Some((name, view))
+}
+
+/// Scan the gfx directory and return the list of installed tilesets
+pub async fn list_installed_tilesets(
+    game_dir: &Path,
+) -> Result<Vec<InstalledTileset>, TilesetError> {
+    let gfx_dir = tilesets_dir(game_dir);
+    if !gfx_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut tilesets = Vec::new();
+
+        for entry in std::fs::read_dir(&gfx_dir)?.flatten() {
+            if entry.path().is_dir()
+                && let Some((name, view_name)) = parse_tileset_txt(&entry.path())
+            {
+                let size = soundpack::calculate_dir_size(&entry.path());
+                tilesets.push(InstalledTileset {
+                    name,
+                    view_name,
+                    path: entry.path(),
+                    size,
+                });
+            }
+        }
+
+        tilesets.sort_by_key(|t| t.view_name.to_lowercase());
+
+        Ok(tilesets)
+    })
+    .await
+    .map_err(|_| TilesetError::Cancelled)?
+}
+
+/// Delete a tileset directory
+pub async fn delete_tileset(tileset_path: PathBuf) -> Result<(), TilesetError> {
+    if !tileset_path.exists() {
+        return Err(TilesetError::TilesetNotFound(
+            tileset_path.display().to_string(),
+        ));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        remove_dir_all::remove_dir_all(&tileset_path)
+            .map_err(|e| TilesetError::Io(std::io::Error::other(e)))
+    })
+    .await
+    .map_err(|_| TilesetError::Cancelled)??;
+
+    Ok(())
+}
+
+// ============================================================================
+// Download and Install
+// ============================================================================
+
+/// Find a directory containing a valid tileset.txt in an extracted archive (may be nested)
+pub fn find_tileset_dir(extract_dir: &Path) -> Option<PathBuf> {
+    let game_cfg = game_config();
+    let migration_cfg = migration_config();
+    walkdir::WalkDir::new(extract_dir)
+        .min_depth(migration_cfg.soundpack.min_search_depth)
+        .max_depth(migration_cfg.soundpack.max_search_depth)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() == game_cfg.metadata.tileset_info.as_str())
+        .filter_map(|e| e.path().parent().map(|p| p.to_path_buf()))
+        .find(|dir| parse_tileset_txt(dir).is_some())
+}
+
+/// Install a tileset from an extracted directory into the game's gfx folder
+pub async fn install_extracted_tileset(
+    extract_dir: &Path,
+    game_dir: &Path,
+) -> Result<InstalledTileset, TilesetError> {
+    let source = find_tileset_dir(extract_dir).ok_or(TilesetError::NoTilesetTxt)?;
+    let (name, view_name) = parse_tileset_txt(&source).ok_or(TilesetError::NoTilesetTxt)?;
+
+    let dir_name = source
+        .file_name()
+        .ok_or_else(|| SoundpackError::ExtractionFailed("Invalid path".to_string()))?;
+
+    let dest = tilesets_dir(game_dir).join(dir_name);
+
+    if dest.exists() {
+        return Err(TilesetError::AlreadyExists(
+            dir_name.to_string_lossy().to_string(),
+        ));
+    }
+
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    // Copy to final location (can't rename across filesystems)
+    let src = source.clone();
+    let dst = dest.clone();
+    tokio::task::spawn_blocking(move || soundpack::copy_dir_sync(&src, &dst))
+        .await
+        .map_err(|_| TilesetError::Cancelled)??;
+
+    let dest_for_size = dest.clone();
+    let size = tokio::task::spawn_blocking(move || soundpack::calculate_dir_size(&dest_for_size))
+        .await
+        .unwrap_or(0);
+
+    tracing::info!("Installed tileset '{}' to {:?}", name, dest);
+
+    Ok(InstalledTileset {
+        name,
+        view_name,
+        path: dest,
+        size,
+    })
+}
+
+/// Download and install a tileset from the repository
+pub async fn install_tileset(
+    client: reqwest::Client,
+    repo_tileset: RepoTileset,
+    game_dir: PathBuf,
+    progress_tx: watch::Sender<SoundpackProgress>,
+) -> Result<InstalledTileset, TilesetError> {
+    let temp_dir = tempfile::tempdir()?;
+
+    let filename = soundpack::extract_filename_from_url(&repo_tileset.url);
+    let download_path = temp_dir.path().join(&filename);
+
+    // Phase 1: Download
+    let _ = progress_tx.send(SoundpackProgress {
+        phase: SoundpackPhase::Downloading,
+        total_bytes: repo_tileset.size.unwrap_or(0),
+        ..Default::default()
+    });
+
+    soundpack::download_file(
+        &client,
+        &repo_tileset.url,
+        &download_path,
+        &progress_tx,
+        repo_tileset.size,
+    )
+    .await?;
+
+    // Phases 2 and 3: Extract and install
+    install_from_file(download_path, game_dir, progress_tx).await
+}
+
+/// Install a tileset from a local archive file
+pub async fn install_from_file(
+    archive_path: PathBuf,
+    game_dir: PathBuf,
+    progress_tx: watch::Sender<SoundpackProgress>,
+) -> Result<InstalledTileset, TilesetError> {
+    let temp_dir = tempfile::tempdir()?;
+    let extract_dir = temp_dir.path().join("extract");
+    std::fs::create_dir_all(&extract_dir)?;
+
+    soundpack::extract_archive(archive_path, extract_dir.clone(), progress_tx.clone()).await?;
+
+    let _ = progress_tx.send(SoundpackProgress {
+        phase: SoundpackPhase::Installing,
+        ..Default::default()
+    });
+
+    let installed = install_extracted_tileset(&extract_dir, &game_dir).await?;
+
+    let _ = progress_tx.send(SoundpackProgress {
+        phase: SoundpackPhase::Complete,
+        ..Default::default()
+    });
+
+    Ok(installed)
+}
+
+// ============================================================================
+// Utility Functions
+// ============================================================================
+
+/// Check if a tileset with the given name is installed
+pub fn is_tileset_installed(installed: &[InstalledTileset], name: &str) -> bool {
+    installed.iter().any(|t| t.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_tileset(dir: &Path, contents: impl AsRef<[u8]>) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("tileset.txt"), contents).unwrap();
+    }
+
+    #[test]
+    fn test_load_repository() {
+        let repo = load_repository();
+        assert!(!repo.is_empty(), "Repository should have tilesets");
+        assert!(!repo[0].name.is_empty());
+        assert!(!repo[0].url.is_empty());
+    }
+
+    #[test]
+    fn test_parse_tileset_txt() {
+        let temp = TempDir::new().unwrap();
+
+        let colon = temp.path().join("colon");
+        write_tileset(
+            &colon,
+            "#Name of the tileset\nNAME: UltimateCataclysm\n#Viewing (Option) name\nVIEW: Ultimate Cataclysm\n",
+        );
+        assert_eq!(
+            parse_tileset_txt(&colon),
+            Some((
+                "UltimateCataclysm".to_string(),
+                "Ultimate Cataclysm".to_string()
+            ))
+        );
+
+        let plain = temp.path().join("plain");
+        write_tileset(&plain, "NAME MyTiles\n");
+        assert_eq!(
+            parse_tileset_txt(&plain),
+            Some(("MyTiles".to_string(), "MyTiles".to_string()))
+        );
+
+        let nameless = temp.path().join("nameless");
+        write_tileset(&nameless, "VIEW: No Name\n");
+        assert_eq!(parse_tileset_txt(&nameless), None);
+    }
+
+    #[test]
+    fn test_parse_tileset_txt_bom_and_latin1() {
+        let temp = TempDir::new().unwrap();
+
+        let bom = temp.path().join("bom");
+        write_tileset(
+            &bom,
+            "\u{FEFF}NAME: BomTiles\r\nVIEW: \u{200B}Bom Tiles\r\n",
+        );
+        assert_eq!(
+            parse_tileset_txt(&bom),
+            Some(("BomTiles".to_string(), "Bom Tiles".to_string()))
+        );
+
+        let latin1 = temp.path().join("latin1");
+        write_tileset(&latin1, b"NAME: Tuiles\nVIEW: Tuiles \xE9t\xE9\n");
+        assert_eq!(
+            parse_tileset_txt(&latin1),
+            Some(("Tuiles".to_string(), "Tuiles été".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_find_tileset_dir_skips_invalid() {
+        let temp = TempDir::new().unwrap();
+        write_tileset(&temp.path().join("pack-master/a_broken"), "JSON: x.json\n");
+        write_tileset(&temp.path().join("pack-master/b_good"), "NAME: Good\n");
+
+        let found = find_tileset_dir(temp.path()).unwrap();
+        assert!(found.ends_with("b_good"));
+    }
+
+    #[tokio::test]
+    async fn test_install_extracted_tileset() {
+        let extract = TempDir::new().unwrap();
+        let game = TempDir::new().unwrap();
+        write_tileset(&extract.path().join("wrapper/MyTiles"), "NAME: MyTiles\n");
+
+        let installed = install_extracted_tileset(extract.path(), game.path())
+            .await
+            .unwrap();
+        assert_eq!(installed.name, "MyTiles");
+        assert!(game.path().join("gfx/MyTiles/tileset.txt").exists());
+
+        let list = list_installed_tilesets(game.path()).await.unwrap();
+        assert!(is_tileset_installed(&list, "MyTiles"));
+
+        // Installing the same tileset again is rejected
+        let again = install_extracted_tileset(extract.path(), game.path()).await;
+        assert!(matches!(again, Err(TilesetError::AlreadyExists(_))));
+
+        delete_tileset(installed.path).await.unwrap();
+        assert!(
+            list_installed_tilesets(game.path())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -1,12 +1,14 @@
 //! Backups tab UI rendering
 
+use chrono::Local;
 use eframe::egui::{self, RichText};
 use std::path::{Path, PathBuf};
 
 use super::theme::Theme;
 use crate::app::PhoenixApp;
-use crate::backup::BackupPhase;
+use crate::backup::{self, BackupEntry, BackupPhase, RetentionPolicy};
 use crate::ui::components::{progress_frame, render_current_file, render_file_progress};
+use crate::util::format_size;
 
 /// Render the backups tab
 pub fn render_backups_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
@@ -50,6 +52,11 @@ pub fn render_backups_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
             );
             ui.add_space(12.0);
 
+            if !app.backup.name_prefilled && !app.backup.list_loading {
+                app.backup.name_input = app.default_backup_name();
+                app.backup.name_prefilled = true;
+            }
+
             ui.horizontal(|ui| {
                 ui.label(RichText::new("Backup name:").color(theme.text_muted));
                 ui.add_sized(
@@ -113,14 +120,23 @@ pub fn render_backups_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                     ui.label(RichText::new("Loading backups...").color(theme.text_muted));
                 });
             } else if app.backup.list.is_empty() {
-                ui.label(RichText::new("No backups found.").color(theme.text_muted));
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("No backups found.").color(theme.text_muted));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        render_restore_from_file_button(app, ui, is_busy);
+                    });
+                });
             } else {
+                // Auto-backups the next rotation would delete
+                let expiring = RetentionPolicy::from_config(&app.config.backups)
+                    .expired_after_next(&app.backup.list, Local::now());
+
                 // Backup table
                 egui::ScrollArea::vertical()
                     .max_height(300.0)
                     .show(ui, |ui| {
                         egui::Grid::new("backup_list_grid")
-                            .num_columns(7)
+                            .num_columns(8)
                             .spacing([12.0, 8.0])
                             .striped(true)
                             .show(ui, |ui| {
@@ -167,34 +183,56 @@ pub fn render_backups_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                                         .strong()
                                         .size(11.0),
                                 );
+                                ui.label(
+                                    RichText::new("Note")
+                                        .color(theme.text_muted)
+                                        .strong()
+                                        .size(11.0),
+                                );
                                 ui.end_row();
 
                                 // Data rows
+                                let mut clicked = None;
                                 for (i, backup) in app.backup.list.iter().enumerate() {
-                                    let is_selected = app.backup.selected_idx == Some(i);
+                                    let is_selected = app.backup.selected.contains(&i);
+                                    let is_expiring = expiring.contains(&backup.name);
+                                    let corrupt = app.backup.corrupt.get(&backup.name);
                                     let text_color = if is_selected {
                                         theme.accent
+                                    } else if corrupt.is_some() {
+                                        theme.error
+                                    } else if is_expiring {
+                                        theme.warning
                                     } else {
                                         theme.text_primary
                                     };
 
                                     // Truncate long names
-                                    let display_name = if backup.name.len() > 25 {
+                                    let mut display_name = if backup.name.len() > 25 {
                                         format!("{}...", &backup.name[..22])
                                     } else {
                                         backup.name.clone()
                                     };
+                                    if corrupt.is_some() {
+                                        display_name = format!("\u{26a0} {}", display_name);
+                                    }
 
-                                    if ui
-                                        .selectable_label(
-                                            is_selected,
-                                            RichText::new(&display_name)
-                                                .color(text_color)
-                                                .size(12.0),
-                                        )
-                                        .clicked()
-                                    {
-                                        app.backup.selected_idx = Some(i);
+                                    let mut response = ui.selectable_label(
+                                        is_selected,
+                                        RichText::new(&display_name).color(text_color).size(12.0),
+                                    );
+                                    if let Some(reason) = corrupt {
+                                        response = response.on_hover_text(format!(
+                                            "Failed integrity check: {}",
+                                            reason
+                                        ));
+                                    } else if is_expiring {
+                                        response = response.on_hover_text(
+                                            "Will be deleted by the next auto-backup",
+                                        );
+                                    }
+                                    if response.clicked() {
+                                        clicked = Some(i);
                                     }
 
                                     ui.label(
@@ -232,39 +270,128 @@ pub fn render_backups_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                                         .color(text_color)
                                         .size(12.0),
                                     );
+                                    let note = backup.note.as_deref().unwrap_or_default();
+                                    let short_note = if note.chars().count() > 30 {
+                                        format!("{}...", note.chars().take(27).collect::<String>())
+                                    } else {
+                                        note.to_string()
+                                    };
+                                    ui.label(
+                                        RichText::new(short_note)
+                                            .color(theme.text_muted)
+                                            .italics()
+                                            .size(12.0),
+                                    )
+                                    .on_hover_text(note);
                                     ui.end_row();
                                 }
+
+                                if let Some(i) = clicked {
+                                    let modifiers = ui.input(|input| input.modifiers);
+                                    app.backup
+                                        .click_select(i, modifiers.command, modifiers.shift);
+                                }
                             });
                     });
 
+                if !app.backup.corrupt.is_empty() {
+                    ui.add_space(4.0);
+                    ui.label(
+                        RichText::new(format!(
+                            "{} backup(s) failed the integrity check and may not restore",
+                            app.backup.corrupt.len()
+                        ))
+                        .color(theme.error)
+                        .size(11.0),
+                    );
+                }
+
+                if !expiring.is_empty() {
+                    ui.add_space(4.0);
+                    ui.label(
+                        RichText::new(format!(
+                            "{} highlighted auto-backup(s) will be deleted by the next auto-backup",
+                            expiring.len()
+                        ))
+                        .color(theme.warning)
+                        .size(11.0),
+                    );
+                }
+
                 ui.add_space(12.0);
 
                 // Action buttons
                 ui.horizontal(|ui| {
-                    let has_selection = app.backup.selected_idx.is_some();
+                    let has_selection = app.backup.selected_idx().is_some();
+                    let multi_hint = "Select a single backup";
 
                     // Restore button
                     if ui
                         .add_enabled(has_selection && !is_busy, egui::Button::new("Restore"))
+                        .on_disabled_hover_text(multi_hint)
                         .clicked()
                     {
                         app.backup.confirm_restore = true;
                     }
 
-                    // Delete button
+                    // Delete button (all selected backups)
+                    let delete_label = match app.backup.selected.len() {
+                        0 | 1 => "Delete".to_string(),
+                        n => format!("Delete {}", n),
+                    };
                     if ui
-                        .add_enabled(has_selection && !is_busy, egui::Button::new("Delete"))
+                        .add_enabled(
+                            !app.backup.selected.is_empty() && !is_busy,
+                            egui::Button::new(delete_label),
+                        )
+                        .on_hover_text("Ctrl-click or shift-click to select several backups")
                         .clicked()
                     {
                         app.backup.confirm_delete = true;
                     }
+
+                    // Browse button
+                    if ui
+                        .add_enabled(has_selection && !is_busy, egui::Button::new("Browse..."))
+                        .on_hover_text("Pick individual files or folders out of this backup")
+                        .on_disabled_hover_text(multi_hint)
+                        .clicked()
+                    {
+                        app.backup.browse_selected();
+                    }
+
+                    ui.add_space(16.0);
+                    render_compare_select(app, ui, has_selection && !is_busy);
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        render_restore_from_file_button(app, ui, is_busy);
+                    });
                 });
+
+                if app.backup.selected_idx().is_some() {
+                    ui.add_space(8.0);
+                    render_backup_details(app, ui, &theme);
+                }
+
+                if app.backup.list.iter().any(|b| b.is_safety_backup()) {
+                    ui.add_space(8.0);
+                    render_safety_cleanup(app, ui, &theme, is_busy);
+                }
             }
         });
 
+    // Backup comparison results
+    if app.backup.diff.is_some() {
+        ui.add_space(12.0);
+        render_backup_diff(app, ui, &theme);
+    }
+
     // Confirmation dialogs
     render_backup_confirm_dialogs(app, ui, &theme, &game_dir);
 
+    // Backup browser
+    render_backup_browser(app, ui, &theme);
+
     // Progress section
     if is_busy
         || app.backup.progress.phase == BackupPhase::Complete
@@ -281,6 +408,401 @@ pub fn render_backups_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
     }
 }
 
+/// Render the selected backup's note editor and checksum
+fn render_backup_details(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    app.backup.sync_note_input();
+    let Some(backup) = app
+        .backup
+        .selected_idx()
+        .and_then(|idx| app.backup.list.get(idx))
+    else {
+        return;
+    };
+    let saved_note = backup.note.clone().unwrap_or_default();
+    let checksum = backup.checksum.clone();
+
+    let mut save = false;
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Note:").color(theme.text_muted));
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut app.backup.note_input)
+                .hint_text("e.g. before the big raid")
+                .desired_width(300.0),
+        );
+        save |= response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        let changed = app.backup.note_input.trim() != saved_note;
+        save |= ui
+            .add_enabled(changed, egui::Button::new("Save Note"))
+            .clicked();
+    });
+    if save {
+        app.save_backup_note();
+    }
+
+    let checksum_text = match &checksum {
+        Some(sum) => format!("SHA-256: {}...", &sum[..sum.len().min(16)]),
+        None => "No checksum recorded (created by an older version)".to_string(),
+    };
+    let label = ui.label(
+        RichText::new(checksum_text)
+            .color(theme.text_muted)
+            .size(11.0),
+    );
+    if let Some(sum) = checksum {
+        label.on_hover_text(sum);
+    }
+}
+
+/// Render the "Restore from File..." button, which picks an external archive
+fn render_restore_from_file_button(app: &mut PhoenixApp, ui: &mut egui::Ui, is_busy: bool) {
+    if ui
+        .add_enabled(!is_busy, egui::Button::new("Restore from File..."))
+        .on_hover_text("Restore a backup archive from another location or machine")
+        .clicked()
+        && let Some(path) = rfd::FileDialog::new()
+            .add_filter("Backups", &["zip"])
+            .set_title("Select Backup Archive")
+            .pick_file()
+    {
+        app.backup
+            .select_external_restore(path, app.config.backups.skip_backup_before_restore);
+    }
+}
+
+/// Render the maintenance row that deletes old pre-restore safety backups
+fn render_safety_cleanup(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme, is_busy: bool) {
+    let expired = backup::expired_safety_backups(
+        &app.backup.list,
+        app.config.backups.safety_max_age_days,
+        Local::now(),
+    );
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(
+                !is_busy && !expired.is_empty(),
+                egui::Button::new("Clean Up Safety Backups"),
+            )
+            .on_hover_text("Delete the backups taken automatically before each restore")
+            .clicked()
+        {
+            app.clean_safety_backups();
+        }
+        ui.label(RichText::new("older than").color(theme.text_muted));
+        if ui
+            .add(
+                egui::DragValue::new(&mut app.config.backups.safety_max_age_days)
+                    .range(1..=365)
+                    .speed(1.0)
+                    .suffix(" days"),
+            )
+            .changed()
+        {
+            app.save_config();
+        }
+        ui.label(
+            RichText::new(format!("({} to delete)", expired.len()))
+                .color(theme.text_muted)
+                .size(11.0),
+        );
+    });
+}
+
+/// Offer to delete the last restore's safety backup after the game has run
+pub fn render_safety_cleanup_dialog(app: &mut PhoenixApp, ctx: &egui::Context) {
+    let Some(name) = app.backup.offer_safety_cleanup.clone() else {
+        return;
+    };
+
+    let theme = &app.ui.current_theme;
+    let mut keep = false;
+    let mut delete = false;
+
+    egui::Window::new("Restore Worked?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_width(380.0)
+        .show(ctx, |ui| {
+            ui.label("You've played since restoring a backup.");
+            ui.label(format!(
+                "If everything looks right, the safety backup \"{}\" is no longer needed.",
+                name
+            ));
+            ui.add_space(8.0);
+            ui.label(RichText::new("Deleting it cannot be undone.").color(theme.warning));
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Keep").clicked() {
+                    keep = true;
+                }
+                if ui.button("Delete Safety Backup").clicked() {
+                    delete = true;
+                }
+            });
+        });
+
+    if delete {
+        app.delete_offered_safety_backup();
+    } else if keep {
+        app.backup.offer_safety_cleanup = None;
+    }
+}
+
+/// Render the backup browser window for extracting single files or folders
+fn render_backup_browser(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let extracting = app.backup.is_extracting();
+    let Some(browser) = &mut app.backup.browser else {
+        return;
+    };
+
+    let mut open = true;
+    let mut extract_to = None;
+
+    egui::Window::new(format!("Browse Backup: {}", browser.name))
+        .open(&mut open)
+        .collapsible(false)
+        .default_width(460.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            if browser.loading {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(RichText::new("Reading backup...").color(theme.text_muted));
+                });
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(360.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for entry in &browser.entries {
+                        render_backup_entry(ui, entry, &mut browser.selected, theme);
+                    }
+                });
+
+            ui.add_space(8.0);
+            ui.separator();
+
+            match &browser.selected {
+                Some(path) => ui.label(RichText::new(path).color(theme.text_primary)),
+                None => ui.label(
+                    RichText::new("Select a file or folder to extract").color(theme.text_muted),
+                ),
+            };
+            ui.label(
+                RichText::new(
+                    "Extracting copies it out of the backup; current saves are untouched",
+                )
+                .color(theme.text_muted)
+                .size(11.0),
+            );
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                let can_extract = browser.selected.is_some() && !extracting;
+                if ui
+                    .add_enabled(can_extract, egui::Button::new("Extract To..."))
+                    .clicked()
+                    && let Some(dest) = rfd::FileDialog::new().set_title("Extract To").pick_folder()
+                {
+                    extract_to = Some(dest);
+                }
+                if extracting {
+                    ui.spinner();
+                }
+            });
+        });
+
+    if let Some(dest) = extract_to {
+        app.extract_backup_entry(dest);
+    }
+    if !open {
+        app.backup.browser = None;
+    }
+}
+
+/// Render one entry of the backup tree, recursing into folders
+fn render_backup_entry(
+    ui: &mut egui::Ui,
+    entry: &BackupEntry,
+    selected: &mut Option<String>,
+    theme: &Theme,
+) {
+    let is_selected = selected.as_deref() == Some(entry.path.as_str());
+
+    if entry.is_dir {
+        let id = ui.make_persistent_id(("backup_entry", &entry.path));
+        egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+            .show_header(ui, |ui| {
+                if ui
+                    .selectable_label(is_selected, format!("{}/", entry.name))
+                    .clicked()
+                {
+                    *selected = Some(entry.path.clone());
+                }
+                ui.label(
+                    RichText::new(format!(
+                        "{} files, {}",
+                        entry.file_count(),
+                        format_size(entry.size)
+                    ))
+                    .color(theme.text_muted)
+                    .size(11.0),
+                );
+            })
+            .body(|ui| {
+                for child in &entry.children {
+                    render_backup_entry(ui, child, selected, theme);
+                }
+            });
+    } else {
+        ui.horizontal(|ui| {
+            if ui.selectable_label(is_selected, &entry.name).clicked() {
+                *selected = Some(entry.path.clone());
+            }
+            ui.label(
+                RichText::new(format_size(entry.size))
+                    .color(theme.text_muted)
+                    .size(11.0),
+            );
+        });
+    }
+}
+
+/// Render the "Compare with" selector for the selected backup
+fn render_compare_select(app: &mut PhoenixApp, ui: &mut egui::Ui, enabled: bool) {
+    if app.backup.is_comparing() {
+        ui.spinner();
+        ui.label("Comparing...");
+        return;
+    }
+
+    let mut compare_with = None;
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt("backup_compare_with")
+            .selected_text("Compare with...")
+            .show_ui(ui, |ui| {
+                for (i, backup) in app.backup.list.iter().enumerate() {
+                    if Some(i) != app.backup.selected_idx()
+                        && ui.selectable_label(false, &backup.name).clicked()
+                    {
+                        compare_with = Some(i);
+                    }
+                }
+            });
+    });
+
+    if let Some(idx) = compare_with {
+        app.backup.start_diff(idx);
+    }
+}
+
+/// Render the differences between two backups
+fn render_backup_diff(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let Some(diff) = &app.backup.diff else {
+        return;
+    };
+    let mut close = false;
+
+    egui::Frame::new()
+        .fill(theme.bg_medium)
+        .corner_radius(8.0)
+        .inner_margin(16.0)
+        .stroke(egui::Stroke::new(1.0, theme.border))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new("Backup Comparison")
+                        .color(theme.accent)
+                        .size(13.0)
+                        .strong(),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+            ui.label(
+                RichText::new(format!("A: {}   B: {}", diff.backup_a, diff.backup_b))
+                    .color(theme.text_muted)
+                    .size(11.0),
+            );
+            ui.add_space(8.0);
+
+            if diff.is_empty() {
+                ui.label(
+                    RichText::new("No differences in worlds, characters, or file sizes.")
+                        .color(theme.success),
+                );
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .id_salt("backup_diff_scroll")
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    let sections = [
+                        ("Worlds only in A", &diff.worlds_only_in_a, theme.warning),
+                        ("Worlds only in B", &diff.worlds_only_in_b, theme.success),
+                        (
+                            "Characters only in A",
+                            &diff.characters_only_in_a,
+                            theme.warning,
+                        ),
+                        (
+                            "Characters only in B",
+                            &diff.characters_only_in_b,
+                            theme.success,
+                        ),
+                    ];
+                    for (label, items, color) in sections {
+                        if items.is_empty() {
+                            continue;
+                        }
+                        ui.label(
+                            RichText::new(format!("{} ({})", label, items.len()))
+                                .color(theme.text_primary)
+                                .strong(),
+                        );
+                        for item in items {
+                            ui.label(RichText::new(format!("  {}", item)).color(color).size(12.0));
+                        }
+                        ui.add_space(6.0);
+                    }
+
+                    if !diff.size_changes.is_empty() {
+                        ui.label(
+                            RichText::new(format!("Size changes ({})", diff.size_changes.len()))
+                                .color(theme.text_primary)
+                                .strong(),
+                        );
+                        for change in &diff.size_changes {
+                            ui.label(
+                                RichText::new(format!(
+                                    "  {}: {} -> {}",
+                                    change.path,
+                                    format_size(change.size_a),
+                                    format_size(change.size_b)
+                                ))
+                                .color(theme.text_muted)
+                                .size(12.0),
+                            );
+                        }
+                    }
+                });
+        });
+
+    if close {
+        app.backup.diff = None;
+    }
+}
+
 /// Render backup confirmation dialogs
 fn render_backup_confirm_dialogs(
     app: &mut PhoenixApp,
@@ -295,26 +817,42 @@ fn render_backup_confirm_dialogs(
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ui.ctx(), |ui| {
-                if let Some(idx) = app.backup.selected_idx {
-                    if let Some(backup) = app.backup.list.get(idx) {
-                        ui.label(format!("Delete backup \"{}\"?", backup.name));
-                        ui.add_space(8.0);
-                        ui.label(RichText::new("This cannot be undone.").color(theme.warning));
-                        ui.add_space(12.0);
-
-                        ui.horizontal(|ui| {
-                            if ui.button("Cancel").clicked() {
-                                app.backup.confirm_delete = false;
-                            }
-                            if ui.button("Delete").clicked() {
-                                app.delete_selected_backup();
-                                app.backup.confirm_delete = false;
-                            }
-                        });
+                let names = app.backup.selected_names();
+                match names.as_slice() {
+                    [] => {
+                        app.backup.confirm_delete = false;
+                        return;
+                    }
+                    [name] => {
+                        ui.label(format!("Delete backup \"{}\"?", name));
+                    }
+                    names => {
+                        ui.label(format!("Delete {} backups?", names.len()));
+                        ui.add_space(4.0);
+                        egui::ScrollArea::vertical()
+                            .max_height(150.0)
+                            .show(ui, |ui| {
+                                for name in names {
+                                    ui.label(
+                                        RichText::new(name).color(theme.text_muted).size(12.0),
+                                    );
+                                }
+                            });
                     }
-                } else {
-                    app.backup.confirm_delete = false;
                 }
+                ui.add_space(8.0);
+                ui.label(RichText::new("This cannot be undone.").color(theme.warning));
+                ui.add_space(12.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Cancel").clicked() {
+                        app.backup.confirm_delete = false;
+                    }
+                    if ui.button("Delete").clicked() {
+                        app.delete_selected_backup();
+                        app.backup.confirm_delete = false;
+                    }
+                });
             });
     }
 
@@ -325,7 +863,7 @@ fn render_backup_confirm_dialogs(
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ui.ctx(), |ui| {
-                if let Some(idx) = app.backup.selected_idx {
+                if let Some(idx) = app.backup.selected_idx() {
                     if let Some(backup) = app.backup.list.get(idx) {
                         ui.label(format!("Restore backup \"{}\"?", backup.name));
                         ui.add_space(8.0);
@@ -358,6 +896,48 @@ fn render_backup_confirm_dialogs(
                 }
             });
     }
+
+    // External restore confirmation
+    if let Some(path) = app.backup.external_restore.clone() {
+        let mut open = true;
+        egui::Window::new("Restore from File")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label("Restore saves from this archive?");
+                ui.label(
+                    RichText::new(path.display().to_string())
+                        .color(theme.text_muted)
+                        .size(11.0),
+                );
+                ui.add_space(8.0);
+
+                ui.checkbox(
+                    &mut app.backup.external_restore_backup_first,
+                    "Back up current saves first",
+                );
+                if !app.backup.external_restore_backup_first {
+                    ui.label(
+                        RichText::new("Warning: Current saves will be replaced!")
+                            .color(theme.warning),
+                    );
+                }
+                ui.add_space(12.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Cancel").clicked() {
+                        open = false;
+                    }
+                    if ui.button("Restore").clicked() {
+                        app.restore_external_backup(game_dir);
+                    }
+                });
+            });
+        if !open {
+            app.backup.external_restore = None;
+        }
+    }
 }
 
 /// Render backup progress
@@ -373,6 +953,7 @@ fn render_backup_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
             BackupPhase::Compressing => ("Compressing saves...", theme.accent),
             BackupPhase::Extracting => ("Extracting backup...", theme.accent),
             BackupPhase::Cleaning => ("Cleaning up...", theme.warning),
+            BackupPhase::Deleting => ("Deleting backup...", theme.warning),
             BackupPhase::Complete => ("Backup operation complete!", theme.success),
             BackupPhase::Failed => ("Operation failed", theme.error),
             BackupPhase::Idle => ("Ready", theme.text_muted),
@@ -386,9 +967,22 @@ fn render_backup_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
         );
         ui.add_space(8.0);
 
-        // Progress bar for compress/extract phases
+        // Progress bar for scan/compress/extract phases
         match progress.phase {
-            BackupPhase::Compressing | BackupPhase::Extracting => {
+            BackupPhase::Scanning if progress.total_files > 0 => {
+                ui.add(egui::ProgressBar::new(progress.fraction()).show_percentage());
+
+                ui.add_space(4.0);
+                ui.label(
+                    RichText::new(format!(
+                        "Scanning {} / {} files",
+                        progress.files_processed, progress.total_files
+                    ))
+                    .color(theme.text_muted)
+                    .size(11.0),
+                );
+            }
+            BackupPhase::Compressing | BackupPhase::Extracting | BackupPhase::Deleting => {
                 let fraction = progress.fraction();
                 ui.add(egui::ProgressBar::new(fraction).show_percentage());
 
@@ -1,13 +1,13 @@
 //! Shared UI components for Phoenix launcher
 
 use eframe::egui::{self, Color32, CornerRadius, RichText, Vec2};
-use std::path::PathBuf;
 
 use super::theme::Theme;
 use crate::app::PhoenixApp;
 use crate::app_data::launcher_config;
 use crate::config::Config;
 use crate::state::Tab;
+use crate::{diagnostics, logging};
 
 /// Render a tab button
 pub fn render_tab(app: &mut PhoenixApp, ui: &mut egui::Ui, tab: Tab, label: &str) {
@@ -31,27 +31,7 @@ pub fn render_tab(app: &mut PhoenixApp, ui: &mut egui::Ui, tab: Tab, label: &str
         .min_size(Vec2::new(80.0, 32.0));
 
     if ui.add(button).clicked() {
-        let previous_tab = app.ui.active_tab;
-        app.ui.active_tab = tab;
-
-        // Load backup list when switching to Backups tab
-        if tab == Tab::Backups
-            && previous_tab != Tab::Backups
-            && app.backup.list.is_empty()
-            && !app.backup.list_loading
-        {
-            app.refresh_backup_list();
-        }
-
-        // Load soundpack list when switching to Soundpacks tab
-        if tab == Tab::Soundpacks
-            && previous_tab != Tab::Soundpacks
-            && let Some(ref dir) = app.config.game.directory
-            && app.soundpack.list.is_empty()
-            && !app.soundpack.list_loading
-        {
-            app.refresh_soundpack_list(&PathBuf::from(dir));
-        }
+        app.switch_tab(tab);
     }
 }
 
@@ -61,7 +41,7 @@ pub fn render_about_dialog(app: &mut PhoenixApp, ctx: &egui::Context) {
         return;
     }
 
-    let theme = &app.ui.current_theme;
+    let theme = app.ui.current_theme.clone();
 
     egui::Window::new("About Phoenix")
         .collapsible(false)
@@ -133,6 +113,28 @@ pub fn render_about_dialog(app: &mut PhoenixApp, ctx: &egui::Context) {
                 {
                     let _ = open::that(&path);
                 }
+                if let Some(path) = logging::logs_dir()
+                    && ui.link("Logs").clicked()
+                {
+                    let _ = open::that(path);
+                }
+
+                ui.add_space(8.0);
+
+                if ui
+                    .button("Copy Diagnostics")
+                    .on_hover_text("Copy a report for bug reports (home folder redacted)")
+                    .clicked()
+                {
+                    let report = diagnostics::build_report(
+                        &app.config,
+                        app.game_info.as_ref(),
+                        &app.releases.rate_limit,
+                        &logging::recent_lines(),
+                    );
+                    ui.ctx().copy_text(report);
+                    app.set_status("Diagnostics copied to clipboard");
+                }
 
                 ui.add_space(8.0);
 
@@ -155,6 +157,90 @@ pub fn render_about_dialog(app: &mut PhoenixApp, ctx: &egui::Context) {
         });
 }
 
+/// Render the one-time notice that a corrupt config file was recovered
+pub fn render_config_recovery_dialog(app: &mut PhoenixApp, ctx: &egui::Context) {
+    let Some(recovery) = &app.ui.config_recovery else {
+        return;
+    };
+
+    let theme = &app.ui.current_theme;
+    let mut close = false;
+
+    egui::Window::new("Settings Reset")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            ui.label("Your settings file could not be read:");
+            ui.label(RichText::new(&recovery.error).color(theme.error).size(11.0));
+            ui.add_space(8.0);
+
+            if recovery.dropped_keys.is_empty() {
+                ui.label("All settings were reset to their defaults.");
+            } else {
+                ui.label("Valid settings were kept. These were reset to their defaults:");
+                for key in &recovery.dropped_keys {
+                    ui.label(RichText::new(format!("  {}", key)).color(theme.warning));
+                }
+            }
+
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new(format!(
+                    "The original file was saved to:\n{}",
+                    recovery.backup_path.display()
+                ))
+                .color(theme.text_muted)
+                .size(11.0),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if let Some(dir) = recovery.backup_path.parent()
+                    && ui.button("Open Folder").clicked()
+                {
+                    let _ = open::that(dir);
+                }
+                if ui.button("OK").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+    if close {
+        app.ui.config_recovery = None;
+    }
+}
+
+/// Dim the window and explain what happens while files are dragged over it
+pub fn render_drop_overlay(app: &PhoenixApp, ctx: &egui::Context) {
+    if ctx.input(|i| i.raw.hovered_files.is_empty()) {
+        return;
+    }
+
+    let theme = &app.ui.current_theme;
+    let text = if app.ui.active_tab == Tab::Soundpacks {
+        "Drop a ZIP archive to install it as a soundpack"
+    } else {
+        "Drop a ZIP archive to install a soundpack, tileset or mod"
+    };
+
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("drop_overlay"),
+    ));
+    let screen = ctx.content_rect();
+    painter.rect_filled(screen, 0.0, Color32::from_black_alpha(192));
+    painter.text(
+        screen.center(),
+        egui::Align2::CENTER_CENTER,
+        text,
+        egui::FontId::proportional(18.0),
+        theme.accent,
+    );
+}
+
 // ============================================================================
 // Progress display helpers
 // ============================================================================
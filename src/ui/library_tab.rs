@@ -0,0 +1,132 @@
+//! Library tab UI rendering
+
+use eframe::egui::{self, RichText};
+use std::path::PathBuf;
+
+use crate::app::PhoenixApp;
+use crate::library::{self, LibraryKind};
+
+/// Render the library tab
+pub fn render_library_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+
+    ui.label(
+        RichText::new("Library")
+            .color(theme.text_primary)
+            .size(20.0)
+            .strong(),
+    );
+    ui.add_space(16.0);
+
+    // Check if game directory is set
+    let Some(game_dir) = app.config.game.directory.as_ref().map(PathBuf::from) else {
+        ui.label(
+            RichText::new("Set a game directory in the Main tab to search your content.")
+                .color(theme.text_muted),
+        );
+        return;
+    };
+
+    let mut open = None;
+
+    egui::Frame::new()
+        .fill(theme.bg_medium)
+        .corner_radius(8.0)
+        .inner_margin(16.0)
+        .stroke(egui::Stroke::new(1.0, theme.border))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Search:").color(theme.text_muted));
+                ui.add(
+                    egui::TextEdit::singleline(&mut app.library.query)
+                        .hint_text("Mods, soundpacks, tilesets, backups...")
+                        .desired_width(300.0),
+                );
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add_enabled(!app.library.is_loading(), egui::Button::new("Rebuild"))
+                        .on_hover_text("Rescan the game folder and backups")
+                        .clicked()
+                    {
+                        app.library.rebuild(&game_dir);
+                    }
+                    if app.library.is_loading() {
+                        ui.spinner();
+                    }
+                });
+            });
+            ui.add_space(12.0);
+
+            if !app.library.loaded {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(RichText::new("Indexing content...").color(theme.text_muted));
+                });
+                return;
+            }
+
+            let results = library::search(&app.library.items, &app.library.query);
+            ui.label(
+                RichText::new(format!(
+                    "{} of {} items",
+                    results.len(),
+                    app.library.items.len()
+                ))
+                .color(theme.text_muted)
+                .size(11.0),
+            );
+            ui.add_space(8.0);
+
+            if results.is_empty() {
+                ui.label(RichText::new("Nothing matches.").color(theme.text_muted));
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(400.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("library_grid")
+                        .num_columns(3)
+                        .spacing([12.0, 6.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for item in results {
+                                ui.label(
+                                    RichText::new(item.kind.label())
+                                        .color(theme.text_muted)
+                                        .size(11.0),
+                                );
+                                let hover = match item.kind {
+                                    LibraryKind::Mod => "Open the mod's folder",
+                                    _ => "Show in its tab",
+                                };
+                                if ui
+                                    .selectable_label(
+                                        false,
+                                        RichText::new(&item.name)
+                                            .color(theme.text_primary)
+                                            .size(12.0),
+                                    )
+                                    .on_hover_text(hover)
+                                    .clicked()
+                                {
+                                    open = Some(item.clone());
+                                }
+                                ui.label(
+                                    RichText::new(&item.detail)
+                                        .color(theme.text_muted)
+                                        .size(12.0),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+
+    if let Some(item) = open {
+        app.open_library_item(&item);
+    }
+}
@@ -5,9 +5,17 @@ use egui_commonmark::CommonMarkViewer;
 
 use super::theme::Theme;
 use crate::app::PhoenixApp;
+use crate::db::SessionStats;
 use crate::ui::components::{progress_frame, render_current_file, render_file_progress};
-use crate::update::UpdatePhase;
-use crate::util::format_size;
+use crate::update::{MigrationSummary, SpeedHistory, UpdatePhase};
+use crate::util::{format_duration, format_size};
+
+/// Tooltip for install buttons when the selected release has no usable build
+const NO_PLATFORM_BUILD: &str = if cfg!(target_os = "windows") {
+    "This release has no Windows build to install"
+} else {
+    "This release has no Linux build to install"
+};
 
 /// Render the main tab content
 pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
@@ -32,6 +40,51 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                 if ui.button("Browse...").clicked() {
                     app.browse_for_directory();
                 }
+                if app.game_info.is_some() {
+                    let can_repair = app.installed_release().is_some() && !app.is_updating();
+                    if ui
+                        .add_enabled(can_repair, egui::Button::new("Repair..."))
+                        .on_hover_text("Re-install the current version, keeping saves and mods")
+                        .on_disabled_hover_text(if app.is_updating() {
+                            "Wait for the update to finish"
+                        } else {
+                            "The installed version is not in the release list"
+                        })
+                        .clicked()
+                    {
+                        app.update.confirm_repair = true;
+                    }
+                    let has_selection = app.releases.selected_idx.is_some();
+                    let has_asset = app.selected_release_has_asset();
+                    if ui
+                        .add_enabled(
+                            has_selection && has_asset && !app.is_updating(),
+                            egui::Button::new("Clean Reinstall..."),
+                        )
+                        .on_hover_text(
+                            "Install the selected release from scratch, keeping only saves and config",
+                        )
+                        .on_disabled_hover_text(if app.is_updating() {
+                            "Wait for the update to finish"
+                        } else if !has_asset {
+                            NO_PLATFORM_BUILD
+                        } else {
+                            "Select a release to install"
+                        })
+                        .clicked()
+                    {
+                        app.update.clean_delete_archives = false;
+                        app.update.confirm_clean = true;
+                    }
+                    if ui
+                        .add_enabled(!app.is_updating(), egui::Button::new("Roll Back..."))
+                        .on_hover_text("Restore an installation archived by an earlier update")
+                        .on_disabled_hover_text("Wait for the update to finish")
+                        .clicked()
+                    {
+                        app.open_rollback();
+                    }
+                }
             });
         });
 
@@ -49,7 +102,12 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                         // Left column - version info
                         ui.vertical(|ui| {
                             ui.label(RichText::new("Version").color(theme.text_muted).size(11.0));
-                            let version_text = info.version_display();
+                            let version_text = match app.config.game.version_override() {
+                                Some(version) if info.version_info.is_none() => {
+                                    format!("{} (manual)", version)
+                                }
+                                _ => info.version_display().to_string(),
+                            };
                             ui.label(
                                 RichText::new(version_text)
                                     .color(theme.text_primary)
@@ -96,6 +154,13 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                     });
                 });
         }
+
+        if let Some(ref stats) = app.launch.stats
+            && stats.total_secs > 0
+        {
+            ui.add_space(8.0);
+            render_play_stats(ui, stats, &theme);
+        }
     });
 
     ui.add_space(12.0);
@@ -176,6 +241,18 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                     let branch = app.config.game.branch.clone();
                     app.fetch_releases_for_branch(&branch);
                 }
+                let on_experimental = app.game_info.as_ref().is_some_and(|g| !g.is_stable());
+                if on_experimental {
+                    let busy = app.is_updating() || app.update.pending_stable_install;
+                    if ui
+                        .add_enabled(!busy, egui::Button::new("Switch to Latest Stable..."))
+                        .on_hover_text("Switch to the stable branch and install its newest release")
+                        .on_disabled_hover_text("Wait for the update to finish")
+                        .clicked()
+                    {
+                        app.update.confirm_switch_stable = true;
+                    }
+                }
             });
         });
 
@@ -259,13 +336,90 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
             render_update_progress(app, ui, &theme);
         }
 
+        // Pausing keeps the partial download; resuming continues from there
+        if app.update.can_pause() {
+            ui.add_space(4.0);
+            let (label, hover) = if app.update.paused {
+                (
+                    "Resume Download",
+                    "Continue the download from where it stopped",
+                )
+            } else {
+                (
+                    "Pause Download",
+                    "Stop downloading for now, keeping what was downloaded",
+                )
+            };
+            if ui.button(label).on_hover_text(hover).clicked() {
+                let paused = !app.update.paused;
+                app.update.set_paused(paused);
+            }
+        }
+
+        // Summarize custom content carried over by the update
+        if let Some(summary) = app.update.preserved.clone() {
+            ui.add_space(8.0);
+            render_preserved_content(app, ui, &summary, &theme);
+        }
+
         // Show update error
         if let Some(ref err) = app.update.error {
             ui.add_space(8.0);
             ui.label(RichText::new(format!("Error: {}", err)).color(theme.error));
         }
+
+        // Offer to close the game if it blocked the update
+        if app.update.is_terminating() {
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(RichText::new("Closing the game...").color(theme.text_muted));
+            });
+        } else if !app.update.running_game.is_empty() && !app.is_updating() {
+            ui.add_space(8.0);
+            if ui.button("Close Game and Retry").clicked() {
+                app.update.confirm_terminate = true;
+            }
+        } else if app.update.can_retry() {
+            ui.add_space(8.0);
+            if ui
+                .button("Retry")
+                .on_hover_text("Install again, reusing the downloaded file if it's intact")
+                .clicked()
+            {
+                app.retry_failed_update();
+            }
+        }
     });
 
+    if app.update.confirm_terminate {
+        render_terminate_dialog(app, ui, &theme);
+    }
+
+    if app.update.confirm_repair {
+        render_repair_dialog(app, ui, &theme);
+    }
+
+    if app.update.confirm_clean {
+        render_clean_reinstall_dialog(app, ui, &theme);
+    }
+
+    if app.update.confirm_switch_stable {
+        render_switch_stable_dialog(app, ui, &theme);
+    }
+
+    if app.update.show_rollback {
+        render_rollback_dialog(app, ui, &theme);
+    }
+
+    if let Some(warning) = app.ui.confirm_launch.clone() {
+        render_launch_confirm_dialog(app, ui, &warning, &theme);
+    }
+
+    if app.launch.diagnostics.is_some() {
+        render_launch_diagnostics_dialog(app, ui, &theme);
+    }
+
     ui.add_space(12.0);
 
     // Changelog section - use remaining vertical space
@@ -282,23 +436,64 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
             .show(ui, |ui| {
                 ui.set_width(ui.available_width());
 
-                ui.label(
-                    RichText::new("Changelog")
-                        .color(theme.accent)
-                        .size(13.0)
-                        .strong(),
-                );
+                // Combined changelog since the installed build (experimental only)
+                let since_installed = if app.config.game.branch == "experimental" {
+                    app.releases
+                        .changelog_since_installed(app.game_info.as_ref())
+                } else {
+                    None
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new("Changelog")
+                            .color(theme.accent)
+                            .size(13.0)
+                            .strong(),
+                    );
+                    let page_url = app
+                        .releases
+                        .selected_idx
+                        .and_then(|idx| app.current_releases().get(idx))
+                        .map(|release| release.page_url());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if let Some(url) = page_url
+                            && ui
+                                .link(RichText::new("View on GitHub").size(11.0))
+                                .on_hover_text(&url)
+                                .clicked()
+                        {
+                            let _ = open::that(&url);
+                        }
+                        if since_installed.is_some() {
+                            ui.add_space(12.0);
+                            ui.checkbox(
+                                &mut app.ui.show_changelog_since_installed,
+                                "All changes since installed build",
+                            );
+                        }
+                    });
+                });
                 ui.add_space(12.0);
 
                 if let Some(idx) = app.releases.selected_idx {
                     let releases = app.current_releases();
                     if let Some(release) = releases.get(idx) {
-                        // Release date header
-                        let date = &release.published_at[..10];
-                        ui.label(RichText::new(date).color(theme.accent).size(14.0).strong());
-                        ui.add_space(8.0);
+                        let aggregated =
+                            since_installed.filter(|_| app.ui.show_changelog_since_installed);
+
+                        // Release date header (aggregated view carries per-build headers)
+                        if aggregated.is_none() {
+                            let date = &release.published_at[..10];
+                            ui.label(RichText::new(date).color(theme.accent).size(14.0).strong());
+                            ui.add_space(8.0);
+                        }
 
-                        let body = release.body.clone();
+                        let body = aggregated.or_else(|| {
+                            app.releases
+                                .changelog(&release.tag_name)
+                                .map(str::to_string)
+                        });
                         let scroll_height = (available_height - 80.0).max(100.0);
                         egui::ScrollArea::vertical()
                             .max_height(scroll_height)
@@ -337,7 +532,10 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
     let is_updating = app.is_updating();
 
     ui.horizontal(|ui| {
-        let button_width = (ui.available_width() - 16.0) / 2.0;
+        // Leave room for the launch profile dropdown when profiles exist
+        let has_profiles = !app.config.game.launch_profiles.is_empty();
+        let profile_width = if has_profiles { 140.0 } else { 0.0 };
+        let button_width = (ui.available_width() - 16.0 - profile_width) / 2.0;
 
         // Simple logic:
         // - No game installed + directory + release selected → Install
@@ -349,16 +547,21 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
 
         // Check if selected release is different from installed version
         let is_different_version = app.is_selected_release_different();
-
-        let can_install = !has_game && has_directory && has_release && !is_updating;
-        let can_update = has_game && has_release && is_different_version && !is_updating;
+        // Some builds are published without an asset for this platform
+        let has_asset = app.selected_release_has_asset();
+
+        let wants_install = !has_game && has_directory && has_release;
+        let wants_update = has_game && has_release && is_different_version;
+        let missing_asset = (wants_install || wants_update) && !has_asset;
+        let can_install = wants_install && has_asset && !is_updating;
+        let can_update = wants_update && has_asset && !is_updating;
         let can_click = can_install || can_update;
 
         let update_label = if is_updating {
             "Updating..."
-        } else if can_install {
+        } else if wants_install {
             "Install Game"
-        } else if can_update {
+        } else if wants_update {
             "Update Game"
         } else {
             "Up to Date"
@@ -382,14 +585,22 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
         .min_size(Vec2::new(button_width, 44.0))
         .corner_radius(6.0);
 
-        if ui.add_enabled(can_click, update_btn).clicked() {
+        let update_response = ui.add_enabled(can_click, update_btn);
+        let update_response = if missing_asset && !is_updating {
+            update_response.on_disabled_hover_text(NO_PLATFORM_BUILD)
+        } else {
+            update_response
+        };
+        if update_response.clicked() {
             app.start_update();
         }
 
         ui.add_space(16.0);
 
         // Launch button - right side, prominent (disabled during update)
-        let can_launch = app.game_info.is_some() && !is_updating;
+        let game_running = app.launch.session_elapsed().is_some();
+        let can_launch =
+            app.game_info.is_some() && !is_updating && !app.launch.is_diagnosing() && !game_running;
         let launch_btn = egui::Button::new(
             RichText::new("Launch Game")
                 .color(if can_launch {
@@ -408,9 +619,101 @@ pub fn render_main_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
         .min_size(Vec2::new(button_width, 44.0))
         .corner_radius(6.0);
 
-        if ui.add_enabled(can_launch, launch_btn).clicked() {
+        let launch_response = ui
+            .add_enabled(can_launch, launch_btn)
+            .on_hover_text("Right-click for a safe-mode or diagnostic launch")
+            .on_disabled_hover_text(if game_running {
+                "The game is already running"
+            } else if is_updating {
+                "Wait for the update to finish"
+            } else if app.launch.is_diagnosing() {
+                "A diagnostic launch is in progress"
+            } else {
+                "No game detected"
+            });
+        if launch_response.clicked() {
             app.launch_game();
         }
+        launch_response.context_menu(|ui| {
+            if ui
+                .button("Launch (Safe Mode)")
+                .on_hover_text(
+                    "Launch with all mods disabled; they are re-enabled when the game exits",
+                )
+                .clicked()
+            {
+                app.launch_game_safe_mode();
+                ui.close();
+            }
+            if ui
+                .button("Diagnostic Launch")
+                .on_hover_text("Capture the game's output to see why it fails to start")
+                .clicked()
+            {
+                app.launch_game_diagnostic();
+                ui.close();
+            }
+        });
+
+        if has_profiles {
+            ui.add_space(8.0);
+            render_profile_select(app, ui, profile_width - 8.0);
+        }
+    });
+}
+
+/// Render the launch profile dropdown
+fn render_profile_select(app: &mut PhoenixApp, ui: &mut egui::Ui, width: f32) {
+    let selected_text = app
+        .config
+        .game
+        .selected_profile
+        .clone()
+        .unwrap_or_else(|| "Default".to_string());
+
+    let mut changed = false;
+    egui::ComboBox::from_id_salt("launch_profile_select")
+        .selected_text(selected_text)
+        .width(width)
+        .show_ui(ui, |ui| {
+            changed |= ui
+                .selectable_value(&mut app.config.game.selected_profile, None, "Default")
+                .changed();
+            for profile in &app.config.game.launch_profiles {
+                changed |= ui
+                    .selectable_value(
+                        &mut app.config.game.selected_profile,
+                        Some(profile.name.clone()),
+                        &profile.name,
+                    )
+                    .on_hover_text(&profile.params)
+                    .changed();
+            }
+        });
+
+    if changed {
+        app.save_config();
+    }
+}
+
+/// Render the play time statistics row
+fn render_play_stats(ui: &mut egui::Ui, stats: &SessionStats, theme: &Theme) {
+    ui.horizontal(|ui| {
+        let stat = |ui: &mut egui::Ui, label: &str, value: String| {
+            ui.label(RichText::new(label).color(theme.text_muted).size(11.0));
+            ui.label(RichText::new(value).color(theme.text_primary).size(11.0));
+        };
+        stat(ui, "Played:", format!("{:.1} h", stats.total_hours()));
+        ui.add_space(16.0);
+        stat(
+            ui,
+            "This week:",
+            format!("{} sessions", stats.sessions_this_week),
+        );
+        if let Some(ref version) = stats.last_version {
+            ui.add_space(16.0);
+            stat(ui, "Last played:", version.clone());
+        }
     });
 }
 
@@ -443,10 +746,12 @@ fn render_update_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
 
         // Phase label with icon
         let (phase_text, phase_color) = match progress.phase {
+            UpdatePhase::Downloading if app.update.paused => ("Download paused", theme.warning),
             UpdatePhase::Downloading => ("Downloading...", theme.accent),
             UpdatePhase::BackingUp => ("Backing up current installation...", theme.warning),
             UpdatePhase::Extracting => ("Extracting files...", theme.accent),
             UpdatePhase::Restoring => ("Restoring saves and settings...", theme.accent),
+            UpdatePhase::Cleaning => ("Removing previous version...", theme.accent),
             UpdatePhase::Complete => ("Update complete!", theme.success),
             UpdatePhase::Failed => ("Update failed", theme.error),
             UpdatePhase::Idle => ("Ready", theme.text_muted),
@@ -460,6 +765,20 @@ fn render_update_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
         );
         ui.add_space(8.0);
 
+        // Overall progress across all phases; the bars below are per-phase detail
+        if matches!(
+            progress.phase,
+            UpdatePhase::Downloading
+                | UpdatePhase::BackingUp
+                | UpdatePhase::Extracting
+                | UpdatePhase::Restoring
+                | UpdatePhase::Cleaning
+        ) {
+            ui.label(RichText::new("Overall").color(theme.text_muted).size(11.0));
+            ui.add(egui::ProgressBar::new(progress.overall_fraction()).show_percentage());
+            ui.add_space(8.0);
+        }
+
         // Progress bar for download/extract phases
         match progress.phase {
             UpdatePhase::Downloading => {
@@ -488,7 +807,27 @@ fn render_update_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
                                 .size(11.0),
                         );
                     }
+
+                    // Time remaining, based on the average recent speed
+                    let history = &app.update.speed_history;
+                    let speed = match history.average() {
+                        0 => progress.speed,
+                        average => average,
+                    };
+                    if let Some(eta) = progress.eta(speed) {
+                        ui.add_space(16.0);
+                        ui.label(
+                            RichText::new(format!("{} remaining", format_duration(eta)))
+                                .color(theme.text_muted)
+                                .size(11.0),
+                        );
+                    }
                 });
+
+                if app.update.speed_history.samples().len() >= 2 {
+                    ui.add_space(6.0);
+                    render_speed_sparkline(ui, &app.update.speed_history, theme);
+                }
             }
             UpdatePhase::Extracting => {
                 let fraction = progress.extract_fraction();
@@ -498,7 +837,13 @@ fn render_update_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
                 render_file_progress(ui, progress.files_extracted, progress.total_files, theme);
                 render_current_file(ui, &progress.current_file, theme);
             }
-            UpdatePhase::BackingUp | UpdatePhase::Restoring => {
+            UpdatePhase::Cleaning if progress.total_to_remove > 0 => {
+                ui.add(egui::ProgressBar::new(progress.cleanup_fraction()).show_percentage());
+
+                ui.add_space(4.0);
+                render_file_progress(ui, progress.files_removed, progress.total_to_remove, theme);
+            }
+            UpdatePhase::BackingUp | UpdatePhase::Restoring | UpdatePhase::Cleaning => {
                 // Indeterminate progress (spinner-like)
                 ui.add(egui::ProgressBar::new(0.0).animate(true));
             }
@@ -507,6 +852,120 @@ fn render_update_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
     });
 }
 
+/// Render the dismissible summary of custom content kept by the last update
+fn render_preserved_content(
+    app: &mut PhoenixApp,
+    ui: &mut egui::Ui,
+    summary: &MigrationSummary,
+    theme: &Theme,
+) {
+    egui::Frame::new()
+        .fill(theme.success.gamma_multiply(0.15))
+        .corner_radius(4.0)
+        .inner_margin(egui::vec2(12.0, 8.0))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(summary.to_string()).color(theme.success));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .small_button("Dismiss")
+                        .on_hover_text("Hide this summary")
+                        .clicked()
+                    {
+                        app.update.preserved = None;
+                    }
+                });
+            });
+
+            if !summary.mods.is_empty() {
+                ui.label(
+                    RichText::new(format!("Custom mods: {}", summary.mods.join(", ")))
+                        .color(theme.text_muted)
+                        .size(11.0),
+                );
+            }
+            if !summary.replaced.is_empty() {
+                ui.label(
+                    RichText::new(format!(
+                        "Replaced with your previous copy: {}",
+                        summary.replaced.join(", ")
+                    ))
+                    .color(theme.text_muted)
+                    .size(11.0),
+                );
+            }
+            if !summary.overwritten_mods.is_empty() {
+                let hint = if summary.archive_removed {
+                    "You edited these official mods. The previous installation was removed, \
+                     so those edits are gone."
+                } else {
+                    "You edited these official mods. \
+                     Your edited copies are still in the archived installation."
+                };
+                ui.label(
+                    RichText::new(format!(
+                        "Overwritten by the new version: {}",
+                        summary.overwritten_mods.join(", ")
+                    ))
+                    .color(theme.warning)
+                    .size(11.0),
+                )
+                .on_hover_text(hint);
+            }
+            for warning in &summary.mod_warnings {
+                ui.label(
+                    RichText::new(format!("Warning: {}", warning))
+                        .color(theme.warning)
+                        .size(11.0),
+                )
+                .on_hover_text("Check this mod still works with the new version");
+            }
+            for error in &summary.restore_errors {
+                ui.label(
+                    RichText::new(format!("Not restored: {}", error))
+                        .color(theme.error)
+                        .size(11.0),
+                )
+                .on_hover_text("This file is still in the installation archive");
+            }
+        });
+}
+
+/// Draw a small line chart of recent download speed samples
+fn render_speed_sparkline(ui: &mut egui::Ui, history: &SpeedHistory, theme: &Theme) {
+    let samples = history.samples();
+    let (rect, _) =
+        ui.allocate_exact_size(Vec2::new(ui.available_width(), 32.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+
+    painter.rect_filled(rect, 4.0, theme.bg_dark);
+
+    let peak = samples.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let step = rect.width() / (samples.len() - 1) as f32;
+    let points: Vec<egui::Pos2> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &speed)| {
+            let x = rect.left() + i as f32 * step;
+            let y = rect.bottom() - 2.0 - (speed as f32 / peak) * (rect.height() - 4.0);
+            egui::pos2(x, y)
+        })
+        .collect();
+
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.5, theme.accent),
+    ));
+    painter.text(
+        rect.right_top() + Vec2::new(-4.0, 2.0),
+        egui::Align2::RIGHT_TOP,
+        format!("peak {}/s", format_size(peak as u64)),
+        egui::FontId::proportional(10.0),
+        theme.text_muted,
+    );
+}
+
 /// Convert raw URLs in text to markdown links
 /// This makes URLs clickable in the markdown renderer
 fn convert_urls_to_links(text: &str) -> String {
@@ -557,3 +1016,372 @@ fn convert_urls_to_links(text: &str) -> String {
 
     result
 }
+
+/// Render the confirmation dialog for terminating the running game
+fn render_terminate_dialog(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let names: Vec<String> = app
+        .update
+        .running_game
+        .iter()
+        .map(|p| format!("{} (pid {})", p.exe_name, p.pid))
+        .collect();
+
+    egui::Window::new("Close Game")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label("The game is running and must be closed before updating:");
+            for name in &names {
+                ui.label(RichText::new(format!("  {}", name)).color(theme.text_muted));
+            }
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new("Any unsaved progress will be lost.")
+                    .color(theme.warning)
+                    .small(),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Close Game").color(theme.error))
+                    .clicked()
+                {
+                    app.update.terminate_running_game();
+                    app.update.confirm_terminate = false;
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.update.confirm_terminate = false;
+                }
+            });
+        });
+}
+
+/// Render the confirmation dialog for repairing the installation
+fn render_repair_dialog(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let release_name = app
+        .installed_release()
+        .map(|r| r.name.clone())
+        .unwrap_or_default();
+
+    egui::Window::new("Repair Installation")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label(format!("Re-install {} over the current installation?", release_name));
+            ui.label(
+                RichText::new(
+                    "The downloaded archive is reused if present. Saves, mods, and other custom content are restored as after an update.",
+                )
+                .color(theme.text_muted)
+                .small(),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Repair").color(theme.warning))
+                    .clicked()
+                {
+                    app.update.confirm_repair = false;
+                    app.start_repair();
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.update.confirm_repair = false;
+                }
+            });
+        });
+}
+
+/// Render the confirmation dialog for switching to the latest stable release
+fn render_switch_stable_dialog(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let target = app
+        .releases
+        .stable
+        .first()
+        .map(|r| r.name.clone())
+        .unwrap_or_else(|| "the latest stable release".to_string());
+
+    egui::Window::new("Switch to Stable")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label(format!("Switch to the stable branch and install {}?", target));
+            ui.label(
+                RichText::new(
+                    "Stable releases are fetched first if needed. Saves, mods, and other custom content are restored as after an update. Saves made by a newer experimental build may not load in an older stable release.",
+                )
+                .color(theme.text_muted)
+                .small(),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Switch and Install").color(theme.warning))
+                    .clicked()
+                {
+                    app.update.confirm_switch_stable = false;
+                    app.switch_to_latest_stable();
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.update.confirm_switch_stable = false;
+                }
+            });
+        });
+}
+
+/// Render the confirmation dialog for launching a build that may not match the saves
+fn render_launch_confirm_dialog(
+    app: &mut PhoenixApp,
+    ui: &mut egui::Ui,
+    warning: &str,
+    theme: &Theme,
+) {
+    egui::Window::new("Launch Game?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label(warning);
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new("Consider backing up your saves before continuing.")
+                    .color(theme.warning)
+                    .small(),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Launch Anyway").color(theme.warning))
+                    .clicked()
+                {
+                    app.ui.confirm_launch = None;
+                    app.launch_game_confirmed();
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.ui.confirm_launch = None;
+                }
+            });
+        });
+}
+
+/// Render the confirmation dialog for a clean reinstall
+fn render_clean_reinstall_dialog(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let release_name = app
+        .releases
+        .selected_idx
+        .and_then(|i| app.current_releases().get(i))
+        .map(|r| r.name.clone())
+        .unwrap_or_default();
+
+    egui::Window::new("Clean Reinstall")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label(format!("Install {} from scratch?", release_name));
+            ui.label(
+                RichText::new(
+                    "Only saves, templates, memorial, graveyard and config are kept. Custom mods, tilesets, soundpacks and fonts are not carried over.",
+                )
+                .color(theme.text_muted)
+                .small(),
+            );
+            ui.add_space(8.0);
+            ui.checkbox(
+                &mut app.update.clean_delete_archives,
+                "Also delete archived installations",
+            )
+            .on_hover_text(
+                "Without this, the replaced installation is archived as usual and can be rolled back",
+            );
+            if app.update.clean_delete_archives {
+                ui.label(
+                    RichText::new("Your custom content will be permanently deleted.")
+                        .color(theme.warning)
+                        .small(),
+                );
+            }
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Clean Reinstall").color(theme.error))
+                    .clicked()
+                {
+                    app.update.confirm_clean = false;
+                    app.start_clean_reinstall();
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.update.confirm_clean = false;
+                }
+            });
+        });
+}
+
+/// Render the dialog for rolling back to an archived installation
+fn render_rollback_dialog(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    egui::Window::new("Roll Back Installation")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label("Choose an archived installation to restore:");
+            ui.add_space(8.0);
+
+            match &app.update.archives {
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(RichText::new("Looking for archives...").color(theme.text_muted));
+                    });
+                }
+                Some(archives) if archives.is_empty() => {
+                    ui.label(
+                        RichText::new("No archived installations. One is kept after each update.")
+                            .color(theme.text_muted),
+                    );
+                }
+                Some(archives) => {
+                    for (i, archive) in archives.iter().enumerate() {
+                        let archived_at = archive
+                            .archived_at
+                            .as_deref()
+                            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_else(|| "unknown date".to_string());
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut app.update.rollback_selected,
+                                Some(i),
+                                archive.version_display(),
+                            );
+                            ui.label(
+                                RichText::new(format!(
+                                    "archived {} - {}",
+                                    archived_at,
+                                    format_size(archive.size)
+                                ))
+                                .color(theme.text_muted)
+                                .size(11.0),
+                            );
+                        });
+                    }
+                    let total: u64 = archives.iter().map(|a| a.size).sum();
+                    ui.add_space(4.0);
+                    ui.label(
+                        RichText::new(format!("Archives use {} in total", format_size(total)))
+                            .color(theme.text_muted)
+                            .size(11.0),
+                    );
+                }
+            }
+
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new(
+                    "The current version is archived in its place. Saves, mods, and other custom content are carried over.",
+                )
+                .color(theme.text_muted)
+                .small(),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                let can_roll_back =
+                    app.update.rollback_selected.is_some() && !app.is_updating();
+                if ui
+                    .add_enabled(
+                        can_roll_back,
+                        egui::Button::new(RichText::new("Roll Back").color(theme.warning)),
+                    )
+                    .clicked()
+                {
+                    app.update.show_rollback = false;
+                    app.start_rollback();
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.update.show_rollback = false;
+                }
+            });
+        });
+}
+
+/// Render the captured output of a diagnostic launch
+fn render_launch_diagnostics_dialog(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let Some(diagnostics) = &app.launch.diagnostics else {
+        return;
+    };
+    let mut close = false;
+
+    egui::Window::new("Launch Diagnostics")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_width(520.0)
+        .show(ui.ctx(), |ui| {
+            let color = if diagnostics.still_running || diagnostics.exit_code == Some(0) {
+                theme.text_primary
+            } else {
+                theme.error
+            };
+            ui.label(RichText::new(diagnostics.summary()).color(color).strong());
+            ui.add_space(8.0);
+
+            if diagnostics.output.is_empty() {
+                ui.label(
+                    RichText::new("The game produced no output.")
+                        .color(theme.text_muted)
+                        .size(11.0),
+                );
+            } else {
+                egui::Frame::new()
+                    .fill(theme.bg_darkest)
+                    .corner_radius(4.0)
+                    .inner_margin(8.0)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(240.0)
+                            .auto_shrink([false, true])
+                            .show(ui, |ui| {
+                                for line in &diagnostics.output {
+                                    ui.label(
+                                        RichText::new(line)
+                                            .monospace()
+                                            .size(11.0)
+                                            .color(theme.text_secondary),
+                                    );
+                                }
+                            });
+                    });
+            }
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if !diagnostics.output.is_empty() && ui.button("Copy Output").clicked() {
+                    let mut text = diagnostics.summary();
+                    text.push('\n');
+                    text.push_str(&diagnostics.output.join("\n"));
+                    ui.ctx().copy_text(text);
+                }
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+    if close {
+        app.launch.diagnostics = None;
+    }
+}
@@ -4,13 +4,19 @@
 
 mod backups_tab;
 mod components;
+mod library_tab;
 mod main_tab;
 mod settings_tab;
 mod soundpacks_tab;
 pub mod theme;
+mod tilesets_tab;
 
-pub use backups_tab::render_backups_tab;
-pub use components::{render_about_dialog, render_tab};
+pub use backups_tab::{render_backups_tab, render_safety_cleanup_dialog};
+pub use components::{
+    render_about_dialog, render_config_recovery_dialog, render_drop_overlay, render_tab,
+};
+pub use library_tab::render_library_tab;
 pub use main_tab::render_main_tab;
 pub use settings_tab::render_settings_tab;
 pub use soundpacks_tab::render_soundpacks_tab;
+pub use tilesets_tab::render_tilesets_tab;
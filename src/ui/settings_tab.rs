@@ -1,14 +1,39 @@
 //! Settings tab UI rendering
 
+use std::path::Path;
+
 use eframe::egui::{self, RichText, Vec2};
 
 use super::theme::ThemePreset;
 use crate::app::PhoenixApp;
+use crate::app_data::{game_config, migration_config};
+use crate::config::{
+    DOWNLOAD_CONNECTIONS_RANGE, EXPERIMENTAL_FETCH_RANGE, LaunchProfile, MAX_KEEP_ARCHIVES,
+};
+use crate::github::DEFAULT_USER_AGENT;
+use crate::logging;
+use crate::migration::MigrationMode;
+use crate::self_update;
+use crate::update;
+use crate::util::format_size;
 
 /// Render the settings tab
 pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
     let theme = app.ui.current_theme.clone();
 
+    // Lazily calculate the installation archive size
+    if app.update.archive_size.is_none()
+        && !app.update.is_calculating_archive_size()
+        && !app.is_updating()
+        && let Some(game_dir) = app.config.game.directory.clone()
+    {
+        app.update.refresh_archive_size(Path::new(&game_dir));
+    }
+
+    if app.ui.confirm_remove_archive {
+        render_remove_archive_dialog(app, ui);
+    }
+
     egui::ScrollArea::vertical()
         .id_salt("settings_scroll")
         .show(ui, |ui| {
@@ -55,7 +80,9 @@ pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                                         .clicked()
                                     {
                                         app.config.launcher.theme = *preset;
-                                        app.ui.current_theme = preset.theme();
+                                        app.ui.current_theme =
+                                            preset.resolve(ui.ctx().system_theme()).theme();
+                                        app.ui.system_theme = ui.ctx().system_theme();
                                         app.ui.theme_dirty = true;
                                         app.save_config();
                                     }
@@ -111,6 +138,7 @@ pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                             &mut app.config.launcher.keep_open,
                             "Keep launcher open after game exits",
                         )
+                        .on_hover_text("Track the running game and show session play time")
                         .changed()
                     {
                         app.save_config();
@@ -169,13 +197,141 @@ pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                         )
                         .changed()
                     {
-                        app.save_config();
+                        if app.config.updates.remove_previous_version {
+                            // Don't enable until the user confirms
+                            app.config.updates.remove_previous_version = false;
+                            app.ui.confirm_remove_archive = true;
+                        } else {
+                            app.save_config();
+                        }
                     }
                     ui.label(
                         RichText::new("  Not recommended - removes rollback capability")
                             .color(theme.warning)
                             .size(11.0),
                     );
+
+                    ui.add_space(8.0);
+
+                    ui.add_enabled_ui(!app.config.updates.remove_previous_version, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                RichText::new("Installation archives to keep:")
+                                    .color(theme.text_muted),
+                            );
+                            if ui
+                                .add(
+                                    egui::DragValue::new(&mut app.config.updates.keep_archives)
+                                        .range(1..=MAX_KEEP_ARCHIVES)
+                                        .speed(0.1),
+                                )
+                                .changed()
+                            {
+                                app.save_config();
+                            }
+                        });
+                    });
+                    ui.label(
+                        RichText::new(
+                            "  Each archive is a full copy of the game; older ones are removed at the next update",
+                        )
+                        .color(theme.text_muted)
+                        .size(11.0),
+                    );
+                    ui.label(
+                        RichText::new(format!(
+                            "  Archives currently use {}",
+                            archive_size_text(app)
+                        ))
+                        .color(theme.text_muted)
+                        .size(11.0),
+                    );
+
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Experimental builds to list:").color(theme.text_muted),
+                        );
+                        if ui
+                            .add(
+                                egui::DragValue::new(
+                                    &mut app.config.updates.experimental_fetch_count,
+                                )
+                                .range(EXPERIMENTAL_FETCH_RANGE)
+                                .speed(1.0),
+                            )
+                            .changed()
+                        {
+                            app.save_config();
+                        }
+                    });
+                    ui.label(
+                        RichText::new("  Applies the next time the release list is refreshed")
+                            .color(theme.text_muted)
+                            .size(11.0),
+                    );
+
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Download connections:").color(theme.text_muted),
+                        );
+                        if ui
+                            .add(
+                                egui::DragValue::new(
+                                    &mut app.config.updates.download_connections,
+                                )
+                                .range(DOWNLOAD_CONNECTIONS_RANGE)
+                                .speed(0.1),
+                            )
+                            .changed()
+                        {
+                            app.save_config();
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "  Above 1, large builds are fetched in parallel ranges when the server allows it",
+                        )
+                        .color(theme.text_muted)
+                        .size(11.0),
+                    );
+
+                    ui.add_space(8.0);
+                    render_minimal_install(app, ui);
+
+                    ui.add_space(8.0);
+                    render_restore_dirs(app, ui);
+
+                    ui.add_space(8.0);
+                    render_reset_config_files(app, ui);
+
+                    ui.add_space(8.0);
+                    render_migration_mode(app, ui);
+
+                    ui.add_space(12.0);
+                    render_launcher_update(app, ui);
+
+                    ui.add_space(8.0);
+
+                    if ui
+                        .checkbox(
+                            &mut app.config.updates.skip_access_check,
+                            "Skip game lock check before updating (advanced)",
+                        )
+                        .changed()
+                    {
+                        app.save_config();
+                    }
+                    ui.label(
+                        RichText::new(
+                            "  Only for antivirus false positives - updates may fail mid-extract if the game is running",
+                        )
+                        .color(theme.warning)
+                        .size(11.0),
+                    );
                 });
 
             ui.add_space(12.0);
@@ -226,6 +382,38 @@ pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
 
                     ui.add_space(8.0);
 
+                    ui.horizontal(|ui| {
+                        let mut changed = ui
+                            .checkbox(
+                                &mut app.config.backups.backup_on_interval,
+                                "Backup saves while playing, every",
+                            )
+                            .changed();
+                        changed |= ui
+                            .add_enabled(
+                                app.config.backups.backup_on_interval,
+                                egui::DragValue::new(
+                                    &mut app.config.backups.backup_interval_minutes,
+                                )
+                                .range(5..=720)
+                                .speed(1.0)
+                                .suffix(" min"),
+                            )
+                            .changed();
+                        if changed {
+                            app.save_config();
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "  Only while a game launched from Phoenix is running; counts toward the auto-backup limit",
+                        )
+                        .color(theme.text_muted)
+                        .size(11.0),
+                    );
+
+                    ui.add_space(8.0);
+
                     if ui
                         .checkbox(
                             &mut app.config.backups.skip_backup_before_restore,
@@ -241,6 +429,23 @@ pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                             .size(11.0),
                     );
 
+                    ui.add_space(8.0);
+
+                    if ui
+                        .checkbox(
+                            &mut app.config.backups.offer_safety_cleanup,
+                            "Offer to delete the safety backup after a restore",
+                        )
+                        .changed()
+                    {
+                        app.save_config();
+                    }
+                    ui.label(
+                        RichText::new("  Asked once the game has been played after the restore")
+                            .color(theme.text_muted)
+                            .size(11.0),
+                    );
+
                     ui.add_space(12.0);
 
                     // Max backups
@@ -262,6 +467,34 @@ pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
 
                     ui.add_space(8.0);
 
+                    // Max age
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Also keep auto-backups newer than:")
+                                .color(theme.text_muted),
+                        );
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut app.config.backups.max_age_days)
+                                    .range(0..=365)
+                                    .speed(1.0)
+                                    .suffix(" days"),
+                            )
+                            .changed()
+                        {
+                            app.save_config();
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "  0 = off. Older auto-backups beyond the count above are deleted",
+                        )
+                        .color(theme.text_muted)
+                        .size(11.0),
+                    );
+
+                    ui.add_space(8.0);
+
                     // Compression level
                     ui.horizontal(|ui| {
                         ui.label(RichText::new("Compression level:").color(theme.text_muted));
@@ -280,6 +513,54 @@ pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                             .color(theme.text_muted)
                             .size(11.0),
                     );
+
+                    ui.add_space(8.0);
+
+                    // Name template
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Backup name template:").color(theme.text_muted));
+                        if ui
+                            .text_edit_singleline(&mut app.config.backups.name_template)
+                            .changed()
+                        {
+                            app.save_config();
+                        }
+                    });
+                    ui.label(
+                        RichText::new(format!(
+                            "  {{date}}, {{time}}, {{version}}, {{branch}} - e.g. {}",
+                            app.default_backup_name()
+                        ))
+                        .color(theme.text_muted)
+                        .size(11.0),
+                    );
+
+                    ui.add_space(8.0);
+
+                    // Background verification
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Verify backups every:").color(theme.text_muted));
+                        if ui
+                            .add(
+                                egui::DragValue::new(
+                                    &mut app.config.backups.verify_interval_hours,
+                                )
+                                .range(0..=168)
+                                .speed(1.0)
+                                .suffix(" hours"),
+                            )
+                            .changed()
+                        {
+                            app.save_config();
+                        }
+                    });
+                    ui.label(
+                        RichText::new(
+                            "  0 = off. Reads every archive in the background to catch corruption early",
+                        )
+                        .color(theme.text_muted)
+                        .size(11.0),
+                    );
                 });
 
             ui.add_space(12.0);
@@ -310,6 +591,640 @@ pub fn render_settings_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
                     {
                         app.save_config();
                     }
+                    ui.label(
+                        RichText::new("  Used by the Default launch profile")
+                            .color(theme.text_muted)
+                            .size(11.0),
+                    );
+                    ui.add_space(4.0);
+                    if ui
+                        .add_enabled(
+                            app.game_info.is_some(),
+                            egui::Button::new("Copy Launch Command"),
+                        )
+                        .on_hover_text(
+                            "Copy the executable and the selected profile's parameters, \
+                             for launching from another frontend (run it from the game folder)",
+                        )
+                        .on_disabled_hover_text("No game detected")
+                        .clicked()
+                    {
+                        app.copy_launch_command(ui.ctx());
+                    }
+
+                    ui.add_space(12.0);
+                    render_launch_profiles(app, ui);
+
+                    ui.add_space(12.0);
+                    if ui
+                        .checkbox(
+                            &mut app.config.game.single_soundpack,
+                            "Enabling a soundpack disables the others",
+                        )
+                        .on_hover_text(
+                            "The game plays only one soundpack; without this, \
+                             the Soundpacks tab warns when several are enabled",
+                        )
+                        .changed()
+                    {
+                        app.save_config();
+                    }
+
+                    if app.config.game.directory.is_some() {
+                        ui.add_space(12.0);
+                        ui.label(
+                            RichText::new("Override installed version:").color(theme.text_muted),
+                        );
+                        ui.add_space(4.0);
+                        let mut version = app.config.game.version_override().map(str::to_string);
+                        let committed = optional_text_edit(
+                            ui,
+                            &mut version,
+                            "e.g. 0.H or 2025-12-20-2147",
+                            false,
+                        );
+                        if version.as_deref() != app.config.game.version_override() {
+                            app.config.game.set_version_override(version.as_deref());
+                        }
+                        if committed {
+                            app.save_config();
+                        }
+                        ui.label(
+                            RichText::new(
+                                "  Release tag or build number of this game folder, used for \
+                                 update checks when the version can't be detected",
+                            )
+                            .color(theme.text_muted)
+                            .size(11.0),
+                        );
+                    }
+                });
+
+            ui.add_space(12.0);
+
+            // Network section
+            egui::Frame::new()
+                .fill(theme.bg_medium)
+                .corner_radius(8.0)
+                .inner_margin(16.0)
+                .stroke(egui::Stroke::new(1.0, theme.border))
+                .show(ui, |ui| {
+                    ui.set_width(available_width - 32.0);
+                    ui.label(
+                        RichText::new("Network")
+                            .color(theme.accent)
+                            .size(13.0)
+                            .strong(),
+                    );
+                    ui.add_space(12.0);
+
+                    let mut committed = false;
+                    egui::Grid::new("network_settings")
+                        .num_columns(2)
+                        .spacing([8.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Proxy:").color(theme.text_muted));
+                            committed |= optional_text_edit(
+                                ui,
+                                &mut app.config.network.proxy,
+                                "http://proxy.example.com:8080",
+                                false,
+                            );
+                            ui.end_row();
+
+                            ui.label(RichText::new("Username:").color(theme.text_muted));
+                            committed |= optional_text_edit(
+                                ui,
+                                &mut app.config.network.proxy_username,
+                                "optional",
+                                false,
+                            );
+                            ui.end_row();
+
+                            ui.label(RichText::new("Password:").color(theme.text_muted));
+                            committed |= optional_text_edit(
+                                ui,
+                                &mut app.config.network.proxy_password,
+                                "optional",
+                                true,
+                            );
+                            ui.end_row();
+
+                            ui.label(RichText::new("User agent:").color(theme.text_muted));
+                            committed |= optional_text_edit(
+                                ui,
+                                &mut app.config.network.user_agent,
+                                DEFAULT_USER_AGENT,
+                                false,
+                            );
+                            ui.end_row();
+                        });
+                    if committed {
+                        app.apply_network_settings();
+                    }
+                    ui.label(
+                        RichText::new(
+                            "  Leave empty to use the HTTP_PROXY / HTTPS_PROXY environment variables",
+                        )
+                        .color(theme.text_muted)
+                        .size(11.0),
+                    );
+                    ui.add_space(8.0);
+                    render_download_mirrors(app, ui);
+                });
+
+            ui.add_space(12.0);
+
+            // Logs section
+            egui::Frame::new()
+                .fill(theme.bg_medium)
+                .corner_radius(8.0)
+                .inner_margin(16.0)
+                .stroke(egui::Stroke::new(1.0, theme.border))
+                .show(ui, |ui| {
+                    ui.set_width(available_width - 32.0);
+                    render_logs(app, ui);
                 });
         }); // ScrollArea
 }
+
+/// Render the launcher self-update row
+fn render_launcher_update(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!("Phoenix v{}", self_update::current_version()))
+                .color(theme.text_muted),
+        );
+        if app.launcher_update.is_busy() {
+            ui.spinner();
+        } else if let Some(version) = app
+            .launcher_update
+            .available
+            .as_ref()
+            .map(|u| u.version.clone())
+        {
+            if ui
+                .button(RichText::new(format!("Install v{}", version)).color(theme.success))
+                .clicked()
+            {
+                app.install_launcher_update();
+            }
+        } else if app.launcher_update.installed.is_none()
+            && ui.button("Check for Launcher Update").clicked()
+        {
+            app.check_launcher_update();
+        }
+    });
+
+    let (text, color) = if let Some(ref version) = app.launcher_update.installed {
+        (
+            format!(
+                "  Phoenix v{} installed - restart Phoenix to use it",
+                version
+            ),
+            theme.success,
+        )
+    } else if let Some(ref error) = app.launcher_update.error {
+        (format!("  {}", error), theme.error)
+    } else if let Some(ref update) = app.launcher_update.available {
+        (
+            format!(
+                "  A new launcher version is available ({})",
+                update.page_url
+            ),
+            theme.text_muted,
+        )
+    } else if app.launcher_update.up_to_date {
+        ("  The launcher is up to date".to_string(), theme.text_muted)
+    } else {
+        return;
+    };
+    ui.label(RichText::new(text).color(color).size(11.0));
+}
+
+/// Single-line editor for an optional setting (empty = unset).
+///
+/// Returns true when editing finishes, so the caller can apply the value.
+fn optional_text_edit(
+    ui: &mut egui::Ui,
+    value: &mut Option<String>,
+    hint: &str,
+    password: bool,
+) -> bool {
+    let mut text = value.clone().unwrap_or_default();
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut text)
+            .hint_text(hint)
+            .password(password)
+            .desired_width(260.0),
+    );
+    if response.changed() {
+        *value = (!text.trim().is_empty()).then_some(text);
+    }
+    response.lost_focus()
+}
+
+/// Render the recent log viewer
+fn render_logs(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+    let lines = app.ui.logs.lines();
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new("Logs")
+                .color(theme.accent)
+                .size(13.0)
+                .strong(),
+        );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("Clear").clicked() {
+                logging::clear();
+            }
+            if let Some(dir) = logging::logs_dir()
+                && ui.button("Open Log Folder").clicked()
+            {
+                let _ = open::that(dir);
+            }
+            if ui.button("Copy to Clipboard").clicked() {
+                ui.ctx().copy_text(lines.join("\n"));
+            }
+        });
+    });
+    ui.add_space(8.0);
+
+    egui::Frame::new()
+        .fill(theme.bg_dark)
+        .corner_radius(4.0)
+        .inner_margin(8.0)
+        .show(ui, |ui| {
+            egui::ScrollArea::both()
+                .id_salt("logs_scroll")
+                .max_height(200.0)
+                .stick_to_bottom(true)
+                .auto_shrink([false, true])
+                .show_rows(ui, row_height, lines.len().max(1), |ui, rows| {
+                    if lines.is_empty() {
+                        ui.label(RichText::new("No log output yet").color(theme.text_muted));
+                    }
+                    // Only lay out the visible rows
+                    for line in lines.get(rows).unwrap_or_default() {
+                        ui.add(
+                            egui::Label::new(
+                                RichText::new(line)
+                                    .monospace()
+                                    .size(11.0)
+                                    .color(theme.text_secondary),
+                            )
+                            .extend(),
+                        );
+                    }
+                });
+        });
+}
+
+/// Render the minimal install toggle and its list of left-out paths
+fn render_minimal_install(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+    let updates = &mut app.config.updates;
+
+    let mut changed = ui
+        .checkbox(
+            &mut updates.minimal_install,
+            "Minimal install (leave out optional content)",
+        )
+        .changed();
+    ui.label(
+        RichText::new(
+            "  Paths below are skipped when extracting updates, relative to the game folder",
+        )
+        .color(theme.text_muted)
+        .size(11.0),
+    );
+
+    ui.add_enabled_ui(updates.minimal_install, |ui| {
+        let mut remove_idx = None;
+        for (i, path) in updates.minimal_install_exclude.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                changed |= ui
+                    .add(
+                        egui::TextEdit::singleline(path)
+                            .hint_text("gfx/TilesetName")
+                            .desired_width(260.0),
+                    )
+                    .changed();
+                if ui.button("Remove").clicked() {
+                    remove_idx = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove_idx {
+            updates.minimal_install_exclude.remove(i);
+            changed = true;
+        }
+        if ui.button("Add Path").clicked() {
+            updates.minimal_install_exclude.push(String::new());
+            changed = true;
+        }
+    });
+
+    // Paths covering required game data aren't saved
+    let required = updates
+        .minimal_install_exclude
+        .iter()
+        .find_map(|path| update::excluded_required_dir(path).map(|dir| (path, dir)));
+    if let Some((path, dir)) = required {
+        ui.label(
+            RichText::new(format!(
+                "  Can't leave out {}: the game needs {}",
+                path, dir
+            ))
+            .color(theme.error)
+            .size(11.0),
+        );
+    } else if changed {
+        app.save_config();
+    }
+}
+
+/// Render the editable list of download mirrors
+fn render_download_mirrors(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+
+    ui.label(RichText::new("Download mirrors:").color(theme.text_muted));
+    ui.label(
+        RichText::new(
+            "  Tried in order when a download from GitHub fails; the URL's host is replaced",
+        )
+        .color(theme.text_muted)
+        .size(11.0),
+    );
+
+    let mut committed = false;
+    let mut remove_idx = None;
+    let mirrors = &mut app.config.network.download_mirrors;
+    for (i, mirror) in mirrors.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(mirror)
+                    .hint_text("https://mirror.example.com")
+                    .desired_width(260.0),
+            );
+            committed |= response.lost_focus();
+            if ui.button("Remove").clicked() {
+                remove_idx = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove_idx {
+        mirrors.remove(i);
+        committed = true;
+    }
+    if ui.button("Add Mirror").clicked() {
+        mirrors.push(String::new());
+    }
+
+    let invalid = mirrors
+        .iter()
+        .filter(|mirror| !mirror.trim().is_empty())
+        .find(|mirror| !crate::github::is_valid_mirror(mirror));
+    if let Some(invalid) = invalid {
+        ui.label(
+            RichText::new(format!("  Invalid mirror URL: {}", invalid))
+                .color(theme.error)
+                .size(11.0),
+        );
+    } else if committed {
+        app.apply_network_settings();
+    }
+}
+
+/// Render the checkboxes choosing which user data directories updates restore
+fn render_restore_dirs(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+
+    ui.label(RichText::new("Carry over during updates:").color(theme.text_muted));
+    ui.add_space(4.0);
+
+    let mut changed = false;
+    let skipped = &mut app.config.updates.skip_restore_dirs;
+    let save_dir = &game_config().directories.save;
+    ui.horizontal_wrapped(|ui| {
+        // Saves have their own "Do not copy saves during updates" option
+        for dir in migration_config()
+            .restore
+            .simple_dirs
+            .iter()
+            .filter(|dir| *dir != save_dir)
+        {
+            let mut restore = !skipped.contains(dir);
+            if ui.checkbox(&mut restore, dir.as_str()).changed() {
+                if restore {
+                    skipped.retain(|d| d != dir);
+                } else {
+                    skipped.push(dir.clone());
+                }
+                changed = true;
+            }
+        }
+    });
+    ui.label(
+        RichText::new("  Unchecked folders stay behind in the archived installation")
+            .color(theme.text_muted)
+            .size(11.0),
+    );
+
+    if changed {
+        app.save_config();
+    }
+}
+
+/// Render the editable list of config files that keep new defaults on update
+fn render_reset_config_files(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+
+    ui.label(RichText::new("Reset these config files on update:").color(theme.text_muted));
+    ui.label(
+        RichText::new("  The new version's copy is kept instead of restoring yours")
+            .color(theme.text_muted)
+            .size(11.0),
+    );
+
+    let mut changed = false;
+    let mut remove_idx = None;
+    let files = &mut app.config.updates.reset_config_files;
+    for (i, name) in files.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            changed |= ui
+                .add(
+                    egui::TextEdit::singleline(name)
+                        .hint_text("keybindings.json")
+                        .desired_width(260.0),
+                )
+                .changed();
+            if ui.button("Remove").clicked() {
+                remove_idx = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove_idx {
+        files.remove(i);
+        changed = true;
+    }
+    if ui.button("Add File").clicked() {
+        files.push(String::new());
+        changed = true;
+    }
+
+    if changed {
+        app.save_config();
+    }
+}
+
+/// Render the choice of how much old content updates carry forward
+fn render_migration_mode(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("Restore mods and packs:").color(theme.text_muted));
+        let mode = &mut app.config.updates.migration_mode;
+        egui::ComboBox::from_id_salt("migration_mode_select")
+            .selected_text(mode.name())
+            .show_ui(ui, |ui| {
+                for option in MigrationMode::all() {
+                    changed |= ui.selectable_value(mode, *option, option.name()).changed();
+                }
+            });
+    });
+    let hint = match app.config.updates.migration_mode {
+        MigrationMode::CustomOnly => "  Only content the new version doesn't ship is restored",
+        MigrationMode::CustomAndModified => {
+            "  Official mods, tilesets and soundpacks you changed are restored too, which can undo the update's fixes to them"
+        }
+        MigrationMode::Everything => {
+            "  Your previous copy of every mod, tileset and soundpack replaces the new one, undoing the update's changes to them"
+        }
+    };
+    ui.label(RichText::new(hint).color(theme.text_muted).size(11.0));
+
+    if changed {
+        app.save_config();
+    }
+}
+
+/// Render the launch profile editor
+fn render_launch_profiles(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+
+    ui.label(RichText::new("Launch profiles:").color(theme.text_muted));
+    ui.add_space(4.0);
+
+    let mut changed = false;
+    let mut remove_idx = None;
+    let game = &mut app.config.game;
+
+    for (i, profile) in game.launch_profiles.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let old_name = profile.name.clone();
+            if ui
+                .add(egui::TextEdit::singleline(&mut profile.name).desired_width(120.0))
+                .changed()
+            {
+                // Keep the selection pointing at the renamed profile
+                if game.selected_profile.as_deref() == Some(old_name.as_str()) {
+                    game.selected_profile = Some(profile.name.clone());
+                }
+                changed = true;
+            }
+            changed |= ui
+                .add(
+                    egui::TextEdit::singleline(&mut profile.params)
+                        .hint_text("Parameters")
+                        .desired_width(ui.available_width() - 80.0),
+                )
+                .changed();
+            if ui.button("Remove").clicked() {
+                remove_idx = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = remove_idx {
+        let removed = game.launch_profiles.remove(i);
+        if game.selected_profile.as_deref() == Some(removed.name.as_str()) {
+            game.selected_profile = None;
+        }
+        changed = true;
+    }
+
+    if ui.button("Add Profile").clicked() {
+        let name = (1..)
+            .map(|n| format!("Profile {}", n))
+            .find(|name| !game.launch_profiles.iter().any(|p| &p.name == name))
+            .unwrap_or_default();
+        game.launch_profiles.push(LaunchProfile {
+            name,
+            params: String::new(),
+        });
+        changed = true;
+    }
+
+    ui.label(
+        RichText::new("  Select a profile next to the Launch button. Quote arguments with spaces.")
+            .color(theme.text_muted)
+            .size(11.0),
+    );
+
+    if changed {
+        app.save_config();
+    }
+}
+
+/// Human-readable size of the installation archive for display
+fn archive_size_text(app: &PhoenixApp) -> String {
+    match app.update.archive_size {
+        Some(size) => format_size(size),
+        None if app.update.is_calculating_archive_size() => "...".to_string(),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Render the confirmation dialog for enabling archive removal
+fn render_remove_archive_dialog(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+    let size_text = archive_size_text(app);
+
+    egui::Window::new("Remove Installation Archive")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label("After each update, the previous installation will be deleted.");
+            ui.label(format!("Archives currently use {}.", size_text));
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new("You will no longer be able to roll back to the previous version.")
+                    .color(theme.warning)
+                    .small(),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Enable").color(theme.warning))
+                    .clicked()
+                {
+                    app.config.updates.remove_previous_version = true;
+                    app.save_config();
+                    app.ui.confirm_remove_archive = false;
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.ui.confirm_remove_archive = false;
+                }
+            });
+        });
+}
@@ -63,6 +63,11 @@ pub fn render_soundpacks_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
         render_soundpack_delete_dialog(app, ui, &theme, &game_dir);
     }
 
+    // Large download confirmation dialog
+    if app.soundpack.confirm_download.is_some() {
+        render_download_confirm_dialog(app, ui, &theme, &game_dir);
+    }
+
     // Browser download dialog
     if app.soundpack.browser_download_url.is_some() {
         render_browser_download_dialog(app, ui, &theme, &game_dir);
@@ -155,6 +160,20 @@ fn render_installed_soundpacks_panel(
 
             ui.add_space(8.0);
 
+            let enabled_count = app.soundpack.enabled_count();
+            if enabled_count > 1 {
+                ui.label(
+                    RichText::new(format!(
+                        "{} soundpacks are enabled, but the game plays only one. \
+                         Use Set as Active to choose it.",
+                        enabled_count
+                    ))
+                    .color(theme.warning)
+                    .size(11.0),
+                );
+                ui.add_space(4.0);
+            }
+
             // Action buttons
             ui.horizontal(|ui| {
                 let has_selection = app.soundpack.installed_idx.is_some();
@@ -171,27 +190,37 @@ fn render_installed_soundpacks_panel(
                     "Enable"
                 };
 
+                let selected_path = app
+                    .soundpack
+                    .installed_idx
+                    .and_then(|i| app.soundpack.list.get(i))
+                    .map(|s| s.path.clone());
+
                 if ui
                     .add_enabled(
                         has_selection && !is_busy,
                         egui::Button::new(RichText::new(toggle_text).color(theme.text_primary)),
                     )
                     .clicked()
-                    && let Some(idx) = app.soundpack.installed_idx
-                    && let Some(soundpack) = app.soundpack.list.get(idx)
+                    && let Some(path) = selected_path.clone()
                 {
-                    let path = soundpack.path.clone();
-                    let new_enabled = !soundpack.enabled;
-                    let game_dir = game_dir.to_path_buf();
-
-                    tokio::spawn(async move {
-                        if let Err(e) = soundpack::set_soundpack_enabled(&path, new_enabled).await {
-                            tracing::error!("Failed to toggle soundpack: {}", e);
-                        }
-                    });
+                    let single = app.config.game.single_soundpack;
+                    app.soundpack
+                        .set_enabled(path, !selected_enabled, single, game_dir);
+                }
 
-                    // Refresh the list after a short delay
-                    app.refresh_soundpack_list(&game_dir);
+                // Only useful when it would change something
+                let others_enabled = app.soundpack.enabled_count() > usize::from(selected_enabled);
+                if ui
+                    .add_enabled(
+                        has_selection && !is_busy && (!selected_enabled || others_enabled),
+                        egui::Button::new(RichText::new("Set as Active").color(theme.text_primary)),
+                    )
+                    .on_hover_text("Enable this soundpack and disable all others")
+                    .clicked()
+                    && let Some(path) = selected_path.clone()
+                {
+                    app.soundpack.set_enabled(path, true, true, game_dir);
                 }
 
                 if ui
@@ -203,6 +232,23 @@ fn render_installed_soundpacks_panel(
                 {
                     app.soundpack.confirm_delete = true;
                 }
+
+                let previewing = selected_path
+                    .as_deref()
+                    .is_some_and(|path| app.soundpack.is_previewing(path));
+                let preview_text = if previewing { "Stop" } else { "Preview" };
+
+                if ui
+                    .add_enabled(
+                        selected_path.is_some(),
+                        egui::Button::new(RichText::new(preview_text).color(theme.text_primary)),
+                    )
+                    .on_hover_text("Play a sample sound from this soundpack")
+                    .clicked()
+                    && let Some(path) = selected_path
+                {
+                    app.soundpack.toggle_preview(&path);
+                }
             });
         });
 }
@@ -229,12 +275,20 @@ fn render_repository_soundpacks_panel(
             );
             ui.add_space(8.0);
 
-            // Repository list
+            // Repository list; only visible rows are laid out, and their
+            // download sizes are looked up as they scroll into view
+            let row_height = ui.spacing().interact_size.y;
+            let total_rows = app.soundpack.repository.len();
             egui::ScrollArea::vertical()
                 .id_salt("repository_soundpacks")
                 .max_height(200.0)
-                .show(ui, |ui| {
-                    for (idx, repo_soundpack) in app.soundpack.repository.iter().enumerate() {
+                .show_rows(ui, row_height, total_rows, |ui, rows| {
+                    app.soundpack.request_repo_sizes(rows.clone());
+
+                    for idx in rows {
+                        let Some(repo_soundpack) = app.soundpack.repository.get(idx) else {
+                            break;
+                        };
                         let is_selected = app.soundpack.repo_idx == Some(idx);
                         let is_installed = soundpack::is_soundpack_installed(
                             &app.soundpack.list,
@@ -253,10 +307,29 @@ fn render_repository_soundpacks_panel(
                             theme.text_primary
                         };
 
-                        let response = ui.selectable_label(
-                            is_selected,
-                            RichText::new(&display_name).color(text_color),
-                        );
+                        let response = ui
+                            .horizontal(|ui| {
+                                let response = ui.selectable_label(
+                                    is_selected,
+                                    RichText::new(&display_name).color(text_color),
+                                );
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if app.soundpack.is_size_pending(&repo_soundpack.url) {
+                                            ui.add(egui::Spinner::new().size(12.0));
+                                        } else if let Some(size) = repo_soundpack.size {
+                                            ui.label(
+                                                RichText::new(format_size(size))
+                                                    .color(theme.text_muted)
+                                                    .size(11.0),
+                                            );
+                                        }
+                                    },
+                                );
+                                response
+                            })
+                            .inner;
 
                         if response.clicked() {
                             app.soundpack.repo_idx = Some(idx);
@@ -348,6 +421,16 @@ fn render_soundpack_details_panel(app: &PhoenixApp, ui: &mut egui::Ui, theme: &T
                         };
                         ui.label(RichText::new(status).color(color));
                     });
+                    if let Some(sample) = app.soundpack.preview_sample(&soundpack.path) {
+                        let sample = sample.strip_prefix(&soundpack.path).unwrap_or(sample);
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("Playing:").color(theme.text_muted));
+                            ui.label(
+                                RichText::new(sample.display().to_string())
+                                    .color(theme.text_secondary),
+                            );
+                        });
+                    }
                 }
             } else if let Some(idx) = app.soundpack.repo_idx {
                 if let Some(repo) = app.soundpack.repository.get(idx) {
@@ -391,6 +474,14 @@ fn render_soundpack_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme)
     let progress = &app.soundpack.progress;
 
     progress_frame(theme).show(ui, |ui| {
+        if app.soundpack.is_checking_size() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(RichText::new("Checking download size...").color(theme.text_primary));
+            });
+            return;
+        }
+
         let status_color = match progress.phase {
             SoundpackPhase::Complete => theme.success,
             SoundpackPhase::Failed => theme.error,
@@ -496,6 +587,8 @@ fn render_soundpack_delete_dialog(
                         && let Some(soundpack) = app.soundpack.list.get(idx)
                     {
                         let path = soundpack.path.clone();
+                        // The sample file must be closed before it can be deleted
+                        app.soundpack.stop_preview();
 
                         let task = tokio::spawn(async move {
                             soundpack::delete_soundpack(path).await?;
@@ -522,6 +615,51 @@ fn render_soundpack_delete_dialog(
         });
 }
 
+/// Render confirmation dialog for a large soundpack download
+fn render_download_confirm_dialog(
+    app: &mut PhoenixApp,
+    ui: &mut egui::Ui,
+    theme: &Theme,
+    game_dir: &Path,
+) {
+    let Some((repo_soundpack, size)) = app.soundpack.confirm_download.clone() else {
+        return;
+    };
+
+    egui::Window::new("Large Download")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label(format!(
+                "'{}' is a {} download.",
+                repo_soundpack.viewname,
+                format_size(size)
+            ));
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new("This may take a while on slower connections.")
+                    .color(theme.warning)
+                    .small(),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Download").color(theme.accent))
+                    .clicked()
+                {
+                    app.soundpack.confirm_download = None;
+                    app.soundpack.install(repo_soundpack, game_dir);
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.soundpack.confirm_download = None;
+                }
+            });
+        });
+}
+
 /// Render browser download dialog
 fn render_browser_download_dialog(
     app: &mut PhoenixApp,
@@ -5,45 +5,66 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ThemePreset {
+    /// Follow the OS light/dark preference, resolved at runtime
     #[default]
+    Auto,
     Amber,
     Purple,
     Cyan,
     Green,
     Catppuccin,
+    Latte,
 }
 
 impl ThemePreset {
     /// Get all available presets
     pub fn all() -> &'static [ThemePreset] {
         &[
+            ThemePreset::Auto,
             ThemePreset::Amber,
             ThemePreset::Purple,
             ThemePreset::Cyan,
             ThemePreset::Green,
             ThemePreset::Catppuccin,
+            ThemePreset::Latte,
         ]
     }
 
     /// Get display name for the preset
     pub fn name(&self) -> &'static str {
         match self {
+            ThemePreset::Auto => "Auto (System)",
             ThemePreset::Amber => "Amber",
             ThemePreset::Purple => "Purple",
             ThemePreset::Cyan => "Cyan",
             ThemePreset::Green => "Green",
             ThemePreset::Catppuccin => "Catppuccin Mocha",
+            ThemePreset::Latte => "Catppuccin Latte",
         }
     }
 
-    /// Get the theme colors for this preset
+    /// Resolve `Auto` to a concrete preset for the OS theme.
+    ///
+    /// Dark (or unknown) systems get Amber and light systems get Latte;
+    /// explicitly chosen presets are returned unchanged.
+    pub fn resolve(self, system: Option<egui::Theme>) -> ThemePreset {
+        match (self, system) {
+            (ThemePreset::Auto, Some(egui::Theme::Light)) => ThemePreset::Latte,
+            (ThemePreset::Auto, _) => ThemePreset::Amber,
+            (preset, _) => preset,
+        }
+    }
+
+    /// Get the theme colors for this preset (`Auto` assumes a dark system)
     pub fn theme(&self) -> Theme {
         match self {
+            ThemePreset::Auto => self.resolve(None).theme(),
             ThemePreset::Amber => Theme::amber(),
             ThemePreset::Purple => Theme::purple(),
             ThemePreset::Cyan => Theme::cyan(),
             ThemePreset::Green => Theme::green(),
             ThemePreset::Catppuccin => Theme::catppuccin(),
+            ThemePreset::Latte => Theme::latte(),
         }
     }
 }
@@ -75,6 +96,9 @@ pub struct Theme {
     // UI element colors
     pub border: Color32,
     pub selection: Color32,
+
+    /// Whether this is a dark theme (selects egui's base visuals)
+    pub dark: bool,
 }
 
 impl Theme {
@@ -100,6 +124,7 @@ impl Theme {
 
             border: Color32::from_rgb(63, 63, 70),
             selection: Color32::from_rgb(245, 158, 11).gamma_multiply(0.3),
+            dark: true,
         }
     }
 
@@ -125,6 +150,7 @@ impl Theme {
 
             border: Color32::from_rgb(75, 65, 100),
             selection: Color32::from_rgb(168, 85, 247).gamma_multiply(0.3),
+            dark: true,
         }
     }
 
@@ -150,6 +176,7 @@ impl Theme {
 
             border: Color32::from_rgb(51, 65, 85),
             selection: Color32::from_rgb(6, 182, 212).gamma_multiply(0.3),
+            dark: true,
         }
     }
 
@@ -175,6 +202,7 @@ impl Theme {
 
             border: Color32::from_rgb(50, 70, 55),
             selection: Color32::from_rgb(34, 197, 94).gamma_multiply(0.3),
+            dark: true,
         }
     }
 
@@ -200,12 +228,45 @@ impl Theme {
 
             border: Color32::from_rgb(69, 71, 90), // Surface1
             selection: Color32::from_rgb(137, 180, 250).gamma_multiply(0.3),
+            dark: true,
+        }
+    }
+
+    /// Catppuccin Latte theme - light counterpart to Mocha
+    pub fn latte() -> Self {
+        Self {
+            bg_darkest: Color32::from_rgb(220, 224, 232), // Crust
+            bg_dark: Color32::from_rgb(230, 233, 239),    // Mantle
+            bg_medium: Color32::from_rgb(239, 241, 245),  // Base
+            bg_light: Color32::from_rgb(204, 208, 218),   // Surface0
+
+            text_primary: Color32::from_rgb(76, 79, 105), // Text
+            text_secondary: Color32::from_rgb(92, 95, 119), // Subtext1
+            text_muted: Color32::from_rgb(140, 143, 161), // Overlay1
+
+            accent: Color32::from_rgb(30, 102, 245), // Blue
+            accent_hover: Color32::from_rgb(114, 135, 253), // Lavender
+            accent_muted: Color32::from_rgb(136, 170, 245), // Lighter blue
+
+            success: Color32::from_rgb(64, 160, 43),  // Green
+            warning: Color32::from_rgb(223, 142, 29), // Yellow
+            error: Color32::from_rgb(210, 15, 57),    // Red
+
+            border: Color32::from_rgb(188, 192, 204), // Surface1
+            selection: Color32::from_rgb(30, 102, 245).gamma_multiply(0.3),
+            dark: false,
         }
     }
 
     /// Apply this theme to egui's visuals
     pub fn apply(&self, ctx: &egui::Context) {
-        let mut visuals = Visuals::dark();
+        // Pin egui's own theme so it doesn't swap visuals when the OS does
+        let (egui_theme, mut visuals) = if self.dark {
+            (egui::Theme::Dark, Visuals::dark())
+        } else {
+            (egui::Theme::Light, Visuals::light())
+        };
+        ctx.set_theme(egui_theme);
 
         // Window and panel backgrounds
         visuals.window_fill = self.bg_dark;
@@ -0,0 +1,432 @@
+//! Tilesets tab UI rendering
+
+use eframe::egui::{self, RichText};
+use std::path::{Path, PathBuf};
+
+use super::theme::Theme;
+use crate::app::PhoenixApp;
+use crate::soundpack::SoundpackPhase;
+use crate::tileset;
+use crate::ui::components::{progress_frame, render_current_file};
+use crate::util::format_size;
+
+/// Render the tilesets tab
+pub fn render_tilesets_tab(app: &mut PhoenixApp, ui: &mut egui::Ui) {
+    let theme = app.ui.current_theme.clone();
+
+    ui.label(
+        RichText::new("Tilesets")
+            .color(theme.text_primary)
+            .size(20.0)
+            .strong(),
+    );
+    ui.add_space(16.0);
+
+    // Check if game directory is set
+    let game_dir = match &app.config.game.directory {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            ui.label(
+                RichText::new("Set a game directory in the Main tab to manage tilesets.")
+                    .color(theme.text_muted),
+            );
+            return;
+        }
+    };
+
+    let is_busy = app.is_tileset_busy();
+
+    ui.columns(2, |columns| {
+        render_installed_tilesets_panel(app, &mut columns[0], &theme, &game_dir, is_busy);
+        render_repository_tilesets_panel(app, &mut columns[1], &theme, &game_dir, is_busy);
+    });
+
+    ui.add_space(12.0);
+
+    render_tileset_details_panel(app, ui, &theme);
+
+    // Progress section
+    if is_busy
+        || app.tileset.progress.phase == SoundpackPhase::Complete
+        || app.tileset.progress.phase == SoundpackPhase::Failed
+    {
+        ui.add_space(12.0);
+        render_tileset_progress(app, ui, &theme);
+    }
+
+    // Delete confirmation dialog
+    if app.tileset.confirm_delete {
+        render_tileset_delete_dialog(app, ui, &theme);
+    }
+
+    // Error display
+    if let Some(ref err) = app.tileset.error {
+        ui.add_space(8.0);
+        ui.label(RichText::new(format!("Error: {}", err)).color(theme.error));
+    }
+}
+
+/// Render the installed tilesets panel
+fn render_installed_tilesets_panel(
+    app: &mut PhoenixApp,
+    ui: &mut egui::Ui,
+    theme: &Theme,
+    game_dir: &Path,
+    is_busy: bool,
+) {
+    egui::Frame::new()
+        .fill(theme.bg_medium)
+        .corner_radius(8.0)
+        .inner_margin(12.0)
+        .stroke(egui::Stroke::new(1.0, theme.border))
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new("Installed")
+                        .color(theme.accent)
+                        .size(13.0)
+                        .strong(),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add_enabled(
+                            !is_busy,
+                            egui::Button::new(
+                                RichText::new("⟳").color(theme.text_secondary).size(14.0),
+                            ),
+                        )
+                        .on_hover_text("Refresh list")
+                        .clicked()
+                    {
+                        app.refresh_tileset_list(game_dir);
+                    }
+                });
+            });
+
+            ui.add_space(8.0);
+
+            egui::ScrollArea::vertical()
+                .id_salt("installed_tilesets")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    if app.tileset.list.is_empty() && !app.tileset.list_loading {
+                        ui.label(
+                            RichText::new("No tilesets installed")
+                                .color(theme.text_muted)
+                                .italics(),
+                        );
+                    } else {
+                        for (idx, tileset) in app.tileset.list.iter().enumerate() {
+                            let is_selected = app.tileset.installed_idx == Some(idx);
+                            if ui
+                                .selectable_label(
+                                    is_selected,
+                                    RichText::new(&tileset.view_name).color(theme.text_primary),
+                                )
+                                .clicked()
+                            {
+                                app.tileset.installed_idx = Some(idx);
+                                app.tileset.repo_idx = None;
+                            }
+                        }
+                    }
+                });
+
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                let has_selection = app.tileset.installed_idx.is_some();
+
+                if ui
+                    .add_enabled(
+                        has_selection && !is_busy,
+                        egui::Button::new(RichText::new("Delete").color(theme.error)),
+                    )
+                    .clicked()
+                {
+                    app.tileset.confirm_delete = true;
+                }
+
+                if ui
+                    .add_enabled(
+                        !is_busy,
+                        egui::Button::new(
+                            RichText::new("Install from File...").color(theme.text_primary),
+                        ),
+                    )
+                    .clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Archives", &["zip"])
+                        .set_title("Select Tileset Archive")
+                        .pick_file()
+                {
+                    app.install_tileset_from_file(path, game_dir);
+                }
+            });
+        });
+}
+
+/// Render the repository tilesets panel
+fn render_repository_tilesets_panel(
+    app: &mut PhoenixApp,
+    ui: &mut egui::Ui,
+    theme: &Theme,
+    game_dir: &Path,
+    is_busy: bool,
+) {
+    egui::Frame::new()
+        .fill(theme.bg_medium)
+        .corner_radius(8.0)
+        .inner_margin(12.0)
+        .stroke(egui::Stroke::new(1.0, theme.border))
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new("Repository")
+                    .color(theme.accent)
+                    .size(13.0)
+                    .strong(),
+            );
+            ui.add_space(8.0);
+
+            egui::ScrollArea::vertical()
+                .id_salt("repository_tilesets")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for (idx, repo_tileset) in app.tileset.repository.iter().enumerate() {
+                        let is_selected = app.tileset.repo_idx == Some(idx);
+                        let is_installed =
+                            tileset::is_tileset_installed(&app.tileset.list, &repo_tileset.name);
+
+                        let display_name = if is_installed {
+                            format!("{} ✓", repo_tileset.viewname)
+                        } else {
+                            repo_tileset.viewname.clone()
+                        };
+
+                        let text_color = if is_installed {
+                            theme.success
+                        } else {
+                            theme.text_primary
+                        };
+
+                        if ui
+                            .selectable_label(
+                                is_selected,
+                                RichText::new(&display_name).color(text_color),
+                            )
+                            .clicked()
+                        {
+                            app.tileset.repo_idx = Some(idx);
+                            app.tileset.installed_idx = None;
+                        }
+                    }
+                });
+
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                let has_selection = app.tileset.repo_idx.is_some();
+                let selected_installed = app
+                    .tileset
+                    .repo_idx
+                    .and_then(|i| app.tileset.repository.get(i))
+                    .map(|r| tileset::is_tileset_installed(&app.tileset.list, &r.name))
+                    .unwrap_or(false);
+
+                if ui
+                    .add_enabled(
+                        has_selection && !is_busy && !selected_installed,
+                        egui::Button::new(
+                            RichText::new("Install Selected").color(theme.text_primary),
+                        ),
+                    )
+                    .clicked()
+                    && let Some(idx) = app.tileset.repo_idx
+                    && let Some(repo_tileset) = app.tileset.repository.get(idx)
+                {
+                    app.install_tileset(repo_tileset.clone(), game_dir);
+                }
+            });
+        });
+}
+
+/// Render the tileset details panel
+fn render_tileset_details_panel(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    egui::Frame::new()
+        .fill(theme.bg_medium)
+        .corner_radius(8.0)
+        .inner_margin(12.0)
+        .stroke(egui::Stroke::new(1.0, theme.border))
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new("Details")
+                    .color(theme.accent)
+                    .size(13.0)
+                    .strong(),
+            );
+            ui.add_space(8.0);
+
+            if let Some(tileset) = app
+                .tileset
+                .installed_idx
+                .and_then(|i| app.tileset.list.get(i))
+            {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("View name:").color(theme.text_muted));
+                    ui.label(RichText::new(&tileset.view_name).color(theme.text_primary));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Name:").color(theme.text_muted));
+                    ui.label(RichText::new(&tileset.name).color(theme.text_primary));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Path:").color(theme.text_muted));
+                    ui.label(
+                        RichText::new(tileset.path.display().to_string())
+                            .color(theme.text_secondary),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Size:").color(theme.text_muted));
+                    ui.label(RichText::new(format_size(tileset.size)).color(theme.text_primary));
+                });
+            } else if let Some(repo) = app
+                .tileset
+                .repo_idx
+                .and_then(|i| app.tileset.repository.get(i))
+            {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("View name:").color(theme.text_muted));
+                    ui.label(RichText::new(&repo.viewname).color(theme.text_primary));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Name:").color(theme.text_muted));
+                    ui.label(RichText::new(&repo.name).color(theme.text_primary));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("URL:").color(theme.text_muted));
+                    ui.label(RichText::new(&repo.url).color(theme.text_secondary));
+                });
+                if let Some(size) = repo.size {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Size:").color(theme.text_muted));
+                        ui.label(RichText::new(format_size(size)).color(theme.text_primary));
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Homepage:").color(theme.text_muted));
+                    if ui.link(&repo.homepage).clicked() {
+                        let _ = open::that(&repo.homepage);
+                    }
+                });
+            } else {
+                ui.label(
+                    RichText::new("Select a tileset to view details")
+                        .color(theme.text_muted)
+                        .italics(),
+                );
+            }
+        });
+}
+
+/// Render tileset progress
+fn render_tileset_progress(app: &PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let progress = &app.tileset.progress;
+
+    progress_frame(theme).show(ui, |ui| {
+        let (status, status_color) = match progress.phase {
+            SoundpackPhase::Downloading => ("Downloading tileset...", theme.text_primary),
+            SoundpackPhase::Installing => ("Installing tileset...", theme.text_primary),
+            SoundpackPhase::Deleting => ("Deleting tileset...", theme.text_primary),
+            SoundpackPhase::Complete => (progress.phase.description(), theme.success),
+            SoundpackPhase::Failed => (progress.phase.description(), theme.error),
+            _ => (progress.phase.description(), theme.text_primary),
+        };
+
+        ui.label(RichText::new(status).color(status_color));
+
+        match progress.phase {
+            SoundpackPhase::Downloading => {
+                ui.add_space(4.0);
+                ui.add(
+                    egui::ProgressBar::new(progress.download_fraction())
+                        .text(format!(
+                            "{} / {} ({}/s)",
+                            format_size(progress.bytes_downloaded),
+                            format_size(progress.total_bytes),
+                            format_size(progress.speed)
+                        ))
+                        .fill(theme.accent),
+                );
+            }
+            SoundpackPhase::Extracting => {
+                ui.add_space(4.0);
+                ui.add(
+                    egui::ProgressBar::new(progress.extract_fraction())
+                        .text(format!(
+                            "{} / {} files",
+                            progress.files_extracted, progress.total_files
+                        ))
+                        .fill(theme.accent),
+                );
+                render_current_file(ui, &progress.current_file, theme);
+            }
+            SoundpackPhase::Installing | SoundpackPhase::Deleting => {
+                ui.add_space(4.0);
+                ui.spinner();
+            }
+            SoundpackPhase::Complete => {
+                ui.add_space(4.0);
+                ui.add(egui::ProgressBar::new(1.0).fill(theme.success));
+            }
+            SoundpackPhase::Failed => {
+                if let Some(ref err) = progress.error {
+                    ui.label(RichText::new(err).color(theme.error));
+                }
+            }
+            _ => {}
+        }
+    });
+}
+
+/// Render delete confirmation dialog
+fn render_tileset_delete_dialog(app: &mut PhoenixApp, ui: &mut egui::Ui, theme: &Theme) {
+    let selected_name = app
+        .tileset
+        .installed_idx
+        .and_then(|i| app.tileset.list.get(i))
+        .map(|t| t.view_name.clone())
+        .unwrap_or_default();
+
+    egui::Window::new("Confirm Delete")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label(format!(
+                "Are you sure you want to delete '{}'?",
+                selected_name
+            ));
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new("This action cannot be undone.")
+                    .color(theme.warning)
+                    .small(),
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Delete").color(theme.error))
+                    .clicked()
+                {
+                    app.tileset.delete_selected();
+                    app.tileset.confirm_delete = false;
+                }
+
+                if ui.button("Cancel").clicked() {
+                    app.tileset.confirm_delete = false;
+                }
+            });
+        });
+}
@@ -1,10 +1,39 @@
 //! Pre-flight access checks for game updates.
 
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use thiserror::Error;
 
 use crate::app_data::game_config;
 
+/// How long to wait for a terminated game process to exit
+const TERMINATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The game executable is locked because the game is running.
+///
+/// Returned (wrapped in `anyhow::Error`) by [`check_installation_access`] so
+/// callers can offer to close the game and retry.
+#[derive(Error, Debug)]
+#[error(
+    "Cannot update: {exe_name} is locked.\n\nThe game appears to be running. Please close it before updating.\n\nError: {source}"
+)]
+pub struct GameRunningError {
+    pub exe_name: String,
+    /// Installation whose executable is locked
+    pub game_dir: PathBuf,
+    source: std::io::Error,
+}
+
+/// A running game process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningProcess {
+    pub pid: u32,
+    pub exe_name: String,
+    /// Full path of the process's executable
+    pub exe_path: PathBuf,
+}
+
 /// Check if we have write access to the game installation before updating.
 ///
 /// This prevents update failures when:
@@ -12,10 +41,23 @@ use crate::app_data::game_config;
 /// - Files are open in another program (e.g., JSON file open in editor)
 /// - Antivirus is scanning files
 ///
+/// With `skip_exe_check`, the executable lock probe is skipped (some antivirus
+/// software makes it report false positives) and only the directory write test
+/// runs.
+///
 /// Returns Ok(()) if we can proceed, or an error explaining why not.
-pub async fn check_installation_access(game_dir: &Path) -> Result<()> {
+pub async fn check_installation_access(game_dir: &Path, skip_exe_check: bool) -> Result<()> {
+    if skip_exe_check {
+        tracing::warn!("Skipping executable lock check (updates.skip_access_check)");
+    }
+
     // Check if game executables can be renamed (indicates they're not in use)
-    for exe_name in game_config().executables.names() {
+    let exe_names = if skip_exe_check {
+        &[]
+    } else {
+        game_config().executables.names()
+    };
+    for exe_name in exe_names {
         let exe_path = game_dir.join(exe_name);
         if exe_path.exists() {
             // Try to open with exclusive write access
@@ -29,9 +71,15 @@ pub async fn check_installation_access(game_dir: &Path) -> Result<()> {
                     // Common Windows error codes:
                     // - 32: ERROR_SHARING_VIOLATION (file in use)
                     // - 5: ERROR_ACCESS_DENIED
-                    let hint = if e.raw_os_error() == Some(32) {
-                        "The game appears to be running. Please close it before updating."
-                    } else if e.raw_os_error() == Some(5) {
+                    if e.raw_os_error() == Some(32) {
+                        return Err(GameRunningError {
+                            exe_name: exe_name.clone(),
+                            game_dir: game_dir.to_path_buf(),
+                            source: e,
+                        }
+                        .into());
+                    }
+                    let hint = if e.raw_os_error() == Some(5) {
                         "Access denied. Try running the launcher as administrator, or check if antivirus is blocking access."
                     } else {
                         "The file may be in use by another program."
@@ -63,3 +111,185 @@ pub async fn check_installation_access(game_dir: &Path) -> Result<()> {
 
     Ok(())
 }
+
+/// Whether `exe` is directly inside `game_dir`, compared case-insensitively
+/// like Windows paths
+#[cfg(any(windows, test))]
+fn is_in_game_dir(exe: &Path, game_dir: &Path) -> bool {
+    let normalize = |path: &Path| {
+        path.to_string_lossy()
+            .replace('/', "\\")
+            .trim_end_matches('\\')
+            .to_lowercase()
+    };
+    normalize(exe)
+        .rsplit_once('\\')
+        .is_some_and(|(dir, _)| dir == normalize(game_dir))
+}
+
+/// Full path of a process's executable, if it can be queried
+#[cfg(windows)]
+fn process_exe_path(pid: u32) -> Option<PathBuf> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+        QueryFullProcessImageNameW,
+    };
+    use windows::core::PWSTR;
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut len,
+        );
+        let _ = CloseHandle(handle);
+        result.ok()?;
+        Some(PathBuf::from(String::from_utf16_lossy(
+            &buffer[..len as usize],
+        )))
+    }
+}
+
+#[cfg(not(windows))]
+fn process_exe_path(_pid: u32) -> Option<PathBuf> {
+    None
+}
+
+/// Find running processes of a game executable in `game_dir`.
+///
+/// Processes are matched by executable name first and then by full path, so
+/// the same game running from another installation is left alone. Only
+/// supported on Windows; returns an empty list elsewhere.
+#[cfg(windows)]
+pub fn find_running_game(game_dir: &Path) -> Vec<RunningProcess> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW,
+        TH32CS_SNAPPROCESS,
+    };
+
+    let names = game_config().executables.names();
+    let game_dir = std::path::absolute(game_dir).unwrap_or_else(|_| game_dir.to_path_buf());
+    let mut found = Vec::new();
+
+    unsafe {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+            return found;
+        };
+
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+
+        if Process32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                let len = entry
+                    .szExeFile
+                    .iter()
+                    .position(|&c| c == 0)
+                    .unwrap_or(entry.szExeFile.len());
+                let exe_name = String::from_utf16_lossy(&entry.szExeFile[..len]);
+                if names.iter().any(|n| n.eq_ignore_ascii_case(&exe_name))
+                    && let Some(exe_path) = process_exe_path(entry.th32ProcessID)
+                    && is_in_game_dir(&exe_path, &game_dir)
+                {
+                    found.push(RunningProcess {
+                        pid: entry.th32ProcessID,
+                        exe_name,
+                        exe_path,
+                    });
+                }
+                if Process32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+
+        let _ = CloseHandle(snapshot);
+    }
+
+    found
+}
+
+#[cfg(not(windows))]
+pub fn find_running_game(_game_dir: &Path) -> Vec<RunningProcess> {
+    Vec::new()
+}
+
+/// Forcefully terminate a process by PID.
+#[cfg(windows)]
+fn terminate_process(pid: u32) -> Result<()> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{OpenProcess, PROCESS_TERMINATE, TerminateProcess};
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, false, pid)?;
+        let result = TerminateProcess(handle, 1);
+        let _ = CloseHandle(handle);
+        result?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn terminate_process(_pid: u32) -> Result<()> {
+    anyhow::bail!("Terminating the game is only supported on Windows")
+}
+
+/// Terminate the given game processes and wait for them to exit.
+///
+/// Blocks for up to a few seconds; call from a blocking task.
+pub fn terminate_game(processes: &[RunningProcess]) -> Result<()> {
+    for process in processes {
+        tracing::info!("Terminating {} (pid {})", process.exe_name, process.pid);
+        terminate_process(process.pid)?;
+    }
+
+    // Wait for the processes to go away so their files are unlocked
+    let start = Instant::now();
+    while processes
+        .iter()
+        .any(|p| process_exe_path(p.pid).as_ref() == Some(&p.exe_path))
+    {
+        if start.elapsed() > TERMINATE_TIMEOUT {
+            anyhow::bail!("The game did not exit after being terminated");
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_in_game_dir() {
+        let game_dir = Path::new("C:\\Games\\CDDA");
+        assert!(is_in_game_dir(
+            Path::new("C:\\Games\\CDDA\\cataclysm-tiles.exe"),
+            game_dir
+        ));
+        assert!(is_in_game_dir(
+            Path::new("c:\\games\\cdda\\Cataclysm-Tiles.exe"),
+            Path::new("C:/Games/CDDA/")
+        ));
+        // The same game in another installation is left alone
+        assert!(!is_in_game_dir(
+            Path::new("C:\\Games\\CDDA-old\\cataclysm-tiles.exe"),
+            game_dir
+        ));
+        assert!(!is_in_game_dir(
+            Path::new("C:\\Games\\CDDA\\bin\\cataclysm-tiles.exe"),
+            game_dir
+        ));
+    }
+}
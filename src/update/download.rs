@@ -2,12 +2,15 @@
 
 use anyhow::{Context, Result};
 use futures::StreamExt;
-use std::path::PathBuf;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 use tokio::sync::watch;
 
 use crate::app_data::migration_config;
+use crate::config::DOWNLOAD_CONNECTIONS_RANGE;
 
 use super::{UpdatePhase, UpdateProgress};
 
@@ -17,14 +20,32 @@ pub struct DownloadResult {
     pub bytes: u64,
 }
 
+/// How many times a stalled download is resumed before giving up
+const MAX_STALL_RETRIES: u32 = 3;
+
+/// Smallest byte range worth its own connection in a chunked download
+const MIN_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
 /// Download a release asset with progress tracking.
 ///
-/// Downloads to a `.part` temporary file, then renames on success.
+/// Downloads to a `.part` temporary file, then renames on success. If the
+/// connection stalls (read timeout), the request is retried, resuming with a
+/// `Range` header when the server supports it. `urls` are tried in order
+/// (the original URL first, then mirrors) until one succeeds.
+///
+/// With more than one `connections`, a large file is split into byte ranges
+/// downloaded concurrently, if the server supports range requests.
+///
+/// While `pause` holds `true` the connections are dropped (the `.part` file
+/// is kept), and the download continues with range requests once it is
+/// set back to `false`.
 pub async fn download_asset(
     client: reqwest::Client,
-    url: String,
+    urls: Vec<String>,
     dest_path: PathBuf,
     progress_tx: watch::Sender<UpdateProgress>,
+    connections: u8,
+    mut pause: Option<watch::Receiver<bool>>,
 ) -> Result<DownloadResult> {
     let download_start = Instant::now();
 
@@ -34,26 +55,6 @@ pub async fn download_asset(
         ..Default::default()
     });
 
-    // Start the download request
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .context("Failed to connect to download server")?;
-
-    if !response.status().is_success() {
-        anyhow::bail!(
-            "Download failed with status: {} - {}",
-            response.status(),
-            response
-                .status()
-                .canonical_reason()
-                .unwrap_or("Unknown error")
-        );
-    }
-
-    let total_size = response.content_length().unwrap_or(0);
-
     // Create parent directory if needed
     if let Some(parent) = dest_path.parent() {
         tokio::fs::create_dir_all(parent)
@@ -68,41 +69,26 @@ pub async fn download_asset(
         .await
         .context("Failed to create temporary download file")?;
 
-    // Stream the response body to disk
-    let mut stream = response.bytes_stream();
-    let mut downloaded: u64 = 0;
-    let mut last_progress_time = Instant::now();
-    let mut last_downloaded: u64 = 0;
-
-    while let Some(chunk_result) = stream.next().await {
-        let chunk = chunk_result.context("Error reading download stream")?;
-
-        file.write_all(&chunk)
-            .await
-            .context("Failed to write to download file")?;
-
-        downloaded += chunk.len() as u64;
-
-        // Update progress at configured interval
-        let now = Instant::now();
-        let elapsed = now.duration_since(last_progress_time);
-        if elapsed >= Duration::from_millis(migration_config().download.progress_interval_ms) {
-            // Calculate speed
-            let bytes_since_last = downloaded - last_downloaded;
-            let current_speed = (bytes_since_last as f64 / elapsed.as_secs_f64()) as u64;
-
-            let _ = progress_tx.send(UpdateProgress {
-                phase: UpdatePhase::Downloading,
-                bytes_downloaded: downloaded,
-                total_bytes: total_size,
-                speed: current_speed,
-                ..Default::default()
-            });
-
-            last_downloaded = downloaded;
-            last_progress_time = now;
+    let mut result = Err(anyhow::anyhow!("No download URL"));
+    for (i, url) in urls.iter().enumerate() {
+        if i > 0 {
+            tracing::warn!("Retrying download from mirror {}", url);
+            file.set_len(0).await?;
+            file.seek(SeekFrom::Start(0)).await?;
+        }
+        result = match chunked_ranges(&client, url, connections).await {
+            Some(ranges) => {
+                download_chunked(&client, url, &temp_path, ranges, &progress_tx, &pause).await
+            }
+            None => download_to_file(&client, url, &mut file, &progress_tx, &mut pause).await,
+        };
+        match &result {
+            Ok(_) => break,
+            Err(e) if i + 1 < urls.len() => tracing::warn!("Download from {} failed: {:#}", url, e),
+            Err(_) => {}
         }
     }
+    let downloaded = result?;
 
     // Ensure all data is written
     file.sync_all()
@@ -130,12 +116,416 @@ pub async fn download_asset(
     })
 }
 
+/// Stream `url` into `file`, returning the number of bytes written.
+///
+/// Stalled reads are resumed up to [`MAX_STALL_RETRIES`] times.
+async fn download_to_file(
+    client: &reqwest::Client,
+    url: &str,
+    file: &mut tokio::fs::File,
+    progress_tx: &watch::Sender<UpdateProgress>,
+    pause: &mut Option<watch::Receiver<bool>>,
+) -> Result<u64> {
+    let mut downloaded: u64 = 0;
+    let mut total_size: u64 = 0;
+    let mut stall_retries = 0;
+
+    'request: loop {
+        until_resumed(pause).await;
+
+        // Start (or resume) the download request
+        let mut request = client.get(url);
+        if downloaded > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", downloaded));
+        }
+        let response = request
+            .send()
+            .await
+            .context("Failed to connect to download server")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Download failed with status: {} - {}",
+                response.status(),
+                response
+                    .status()
+                    .canonical_reason()
+                    .unwrap_or("Unknown error")
+            );
+        }
+
+        if downloaded == 0 {
+            total_size = response.content_length().unwrap_or(0);
+        } else if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            // The server ignored the range request: start over
+            tracing::warn!("Server does not support resuming, restarting download");
+            file.set_len(0).await?;
+            file.seek(SeekFrom::Start(0)).await?;
+            downloaded = 0;
+            total_size = response.content_length().unwrap_or(0);
+        }
+
+        // Stream the response body to disk
+        let mut stream = response.bytes_stream();
+        let mut last_progress_time = Instant::now();
+        let mut last_downloaded = downloaded;
+
+        loop {
+            let next = tokio::select! {
+                next = stream.next() => next,
+                () = until_paused(pause) => {
+                    tracing::info!("Download paused at {} bytes", downloaded);
+                    let _ = progress_tx.send(UpdateProgress {
+                        phase: UpdatePhase::Downloading,
+                        bytes_downloaded: downloaded,
+                        total_bytes: total_size,
+                        ..Default::default()
+                    });
+                    continue 'request;
+                }
+            };
+            let Some(chunk_result) = next else {
+                break;
+            };
+            let chunk = match chunk_result {
+                Ok(chunk) => chunk,
+                Err(e) if e.is_timeout() && stall_retries < MAX_STALL_RETRIES => {
+                    stall_retries += 1;
+                    tracing::warn!(
+                        "Download stalled at {} bytes, retrying ({}/{})",
+                        downloaded,
+                        stall_retries,
+                        MAX_STALL_RETRIES
+                    );
+                    continue 'request;
+                }
+                Err(e) if e.is_timeout() => {
+                    return Err(e).context("Download stalled: no data received from the server");
+                }
+                Err(e) => return Err(e).context("Error reading download stream"),
+            };
+
+            file.write_all(&chunk)
+                .await
+                .context("Failed to write to download file")?;
+
+            downloaded += chunk.len() as u64;
+
+            // Update progress at configured interval
+            let now = Instant::now();
+            let elapsed = now.duration_since(last_progress_time);
+            if elapsed >= Duration::from_millis(migration_config().download.progress_interval_ms) {
+                // Calculate speed
+                let bytes_since_last = downloaded - last_downloaded;
+                let current_speed = (bytes_since_last as f64 / elapsed.as_secs_f64()) as u64;
+
+                let _ = progress_tx.send(UpdateProgress {
+                    phase: UpdatePhase::Downloading,
+                    bytes_downloaded: downloaded,
+                    total_bytes: total_size,
+                    speed: current_speed,
+                    ..Default::default()
+                });
+
+                last_downloaded = downloaded;
+                last_progress_time = now;
+            }
+        }
+
+        break;
+    }
+
+    Ok(downloaded)
+}
+
+/// Byte ranges for a chunked download of `url`, or `None` to use a single
+/// stream (one connection, no range support, or a file too small to split)
+async fn chunked_ranges(
+    client: &reqwest::Client,
+    url: &str,
+    connections: u8,
+) -> Option<Vec<(u64, u64)>> {
+    let connections = connections.clamp(
+        *DOWNLOAD_CONNECTIONS_RANGE.start(),
+        *DOWNLOAD_CONNECTIONS_RANGE.end(),
+    );
+    if connections <= 1 {
+        return None;
+    }
+
+    // Ask for the first byte: a 206 with the total size means ranges work
+    let response = client
+        .get(url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
+        .send()
+        .await
+        .ok()?;
+    if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        tracing::debug!("Server does not support range requests, using a single stream");
+        return None;
+    }
+    let total = response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)?
+        .to_str()
+        .ok()
+        .and_then(content_range_total)?;
+
+    let ranges = split_ranges(total, connections.into());
+    (ranges.len() > 1).then_some(ranges)
+}
+
+/// Total size from a `Content-Range` header value (`bytes 0-0/12345`)
+fn content_range_total(value: &str) -> Option<u64> {
+    value
+        .strip_prefix("bytes ")?
+        .split_once('/')?
+        .1
+        .parse()
+        .ok()
+}
+
+/// Split `total` bytes into at most `connections` inclusive ranges of at
+/// least [`MIN_CHUNK_SIZE`] each (a file smaller than that gets one range)
+fn split_ranges(total: u64, connections: u64) -> Vec<(u64, u64)> {
+    if total == 0 {
+        return Vec::new();
+    }
+    let count = (total / MIN_CHUNK_SIZE).clamp(1, connections.max(1));
+    let chunk = total.div_ceil(count);
+    (0..count)
+        .map(|i| (i * chunk, ((i + 1) * chunk).min(total) - 1))
+        .filter(|(start, end)| start <= end)
+        .collect()
+}
+
+/// Download `ranges` of `url` concurrently into the file at `temp_path`,
+/// returning the total number of bytes written
+async fn download_chunked(
+    client: &reqwest::Client,
+    url: &str,
+    temp_path: &Path,
+    ranges: Vec<(u64, u64)>,
+    progress_tx: &watch::Sender<UpdateProgress>,
+    pause: &Option<watch::Receiver<bool>>,
+) -> Result<u64> {
+    let total_size = ranges.last().map_or(0, |(_, end)| end + 1);
+    tracing::info!(
+        "Downloading {} bytes over {} connections",
+        total_size,
+        ranges.len()
+    );
+
+    let downloaded = AtomicU64::new(0);
+    let chunks = futures::future::try_join_all(ranges.into_iter().map(|(start, end)| {
+        download_range(
+            client,
+            url,
+            temp_path,
+            (start, end),
+            &downloaded,
+            pause.clone(),
+        )
+    }));
+    tokio::pin!(chunks);
+
+    // Report the combined progress of all streams at the configured interval
+    let interval = Duration::from_millis(migration_config().download.progress_interval_ms);
+    let mut ticker = tokio::time::interval(interval);
+    let mut last_progress_time = Instant::now();
+    let mut last_downloaded = 0;
+    loop {
+        tokio::select! {
+            result = &mut chunks => {
+                result?;
+                // The last tick may predate the final bytes
+                let _ = progress_tx.send(UpdateProgress {
+                    phase: UpdatePhase::Downloading,
+                    bytes_downloaded: downloaded.load(Ordering::Relaxed),
+                    total_bytes: total_size,
+                    ..Default::default()
+                });
+                break;
+            }
+            _ = ticker.tick() => {
+                let now = Instant::now();
+                let elapsed = now.duration_since(last_progress_time).as_secs_f64();
+                let current = downloaded.load(Ordering::Relaxed);
+                let speed = if elapsed > 0.0 {
+                    ((current - last_downloaded) as f64 / elapsed) as u64
+                } else {
+                    0
+                };
+                let _ = progress_tx.send(UpdateProgress {
+                    phase: UpdatePhase::Downloading,
+                    bytes_downloaded: current,
+                    total_bytes: total_size,
+                    speed,
+                    ..Default::default()
+                });
+                last_downloaded = current;
+                last_progress_time = now;
+            }
+        }
+    }
+
+    Ok(downloaded.load(Ordering::Relaxed))
+}
+
+/// Download bytes `start..=end` of `url` into the same offsets of the file
+/// at `temp_path`, resuming stalled reads like [`download_to_file`]
+async fn download_range(
+    client: &reqwest::Client,
+    url: &str,
+    temp_path: &Path,
+    (start, end): (u64, u64),
+    downloaded: &AtomicU64,
+    mut pause: Option<watch::Receiver<bool>>,
+) -> Result<()> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .open(temp_path)
+        .await
+        .context("Failed to open download file")?;
+    file.seek(SeekFrom::Start(start)).await?;
+
+    let mut position = start;
+    let mut stall_retries = 0;
+
+    'request: while position <= end {
+        until_resumed(&mut pause).await;
+
+        let response = client
+            .get(url)
+            .header(
+                reqwest::header::RANGE,
+                format!("bytes={}-{}", position, end),
+            )
+            .send()
+            .await
+            .context("Failed to connect to download server")?;
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            anyhow::bail!(
+                "Download failed with status: {} (expected a partial response)",
+                response.status()
+            );
+        }
+
+        let mut stream = response.bytes_stream();
+        loop {
+            let next = tokio::select! {
+                next = stream.next() => next,
+                () = until_paused(&mut pause) => continue 'request,
+            };
+            let Some(chunk_result) = next else {
+                break;
+            };
+            let chunk = match chunk_result {
+                Ok(chunk) => chunk,
+                Err(e) if e.is_timeout() && stall_retries < MAX_STALL_RETRIES => {
+                    stall_retries += 1;
+                    tracing::warn!(
+                        "Download range stalled at byte {}, retrying ({}/{})",
+                        position,
+                        stall_retries,
+                        MAX_STALL_RETRIES
+                    );
+                    continue 'request;
+                }
+                Err(e) if e.is_timeout() => {
+                    return Err(e).context("Download stalled: no data received from the server");
+                }
+                Err(e) => return Err(e).context("Error reading download stream"),
+            };
+
+            // Never write past the range, even if the server sends extra
+            let remaining = end + 1 - position;
+            let chunk = &chunk[..chunk.len().min(remaining as usize)];
+            file.write_all(chunk)
+                .await
+                .context("Failed to write to download file")?;
+            position += chunk.len() as u64;
+            downloaded.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        }
+
+        if position <= end {
+            anyhow::bail!("Download range ended early at byte {} of {}", position, end);
+        }
+    }
+
+    file.flush()
+        .await
+        .context("Failed to write to download file")?;
+    Ok(())
+}
+
+/// Resolves once the download is paused (never without a live pause control)
+async fn until_paused(pause: &mut Option<watch::Receiver<bool>>) {
+    if let Some(rx) = pause
+        && rx.wait_for(|paused| *paused).await.is_ok()
+        && rx.has_changed().is_ok()
+    {
+        return;
+    }
+    std::future::pending().await
+}
+
+/// Wait until a paused download is resumed
+async fn until_resumed(pause: &mut Option<watch::Receiver<bool>>) {
+    if let Some(rx) = pause {
+        // A dropped sender can't resume us, so carry on
+        let _ = rx.wait_for(|paused| !*paused).await;
+    }
+}
+
+/// Check whether a previously downloaded asset is complete and can be reused.
+///
+/// Only a finished download (renamed from its `.part` file) of the expected
+/// size counts; an unknown expected size (0) never matches.
+pub fn is_complete_download(path: &Path, expected_size: u64) -> bool {
+    expected_size > 0
+        && std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.len() == expected_size)
+}
+
+/// Read a downloaded release archive end to end to check it isn't damaged.
+///
+/// ZIP entries are checked against their stored CRCs and a `.tar.gz` is
+/// decompressed in full, so truncation or corruption shows up without
+/// extracting anything. Blocking; call it from `spawn_blocking`.
+pub fn is_intact_archive(path: &Path) -> bool {
+    let result = if path.to_string_lossy().ends_with(".tar.gz") {
+        verify_tar_gz(path)
+    } else {
+        verify_zip(path)
+    };
+    if let Err(e) = &result {
+        tracing::warn!("Downloaded archive {:?} is damaged: {}", path, e);
+    }
+    result.is_ok()
+}
+
+fn verify_zip(path: &Path) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        std::io::copy(&mut entry, &mut std::io::sink())
+            .with_context(|| format!("'{}' is damaged", entry.name()))?;
+    }
+    Ok(())
+}
+
+fn verify_tar_gz(path: &Path) -> Result<()> {
+    let decoder = flate2::read::GzDecoder::new(std::fs::File::open(path)?);
+    let mut archive = tar::Archive::new(decoder);
+    for entry in archive.entries()? {
+        std::io::copy(&mut entry?, &mut std::io::sink())?;
+    }
+    Ok(())
+}
+
 /// Get the download cache directory.
 pub fn download_dir() -> Result<PathBuf> {
-    let dirs = directories::ProjectDirs::from("com", "phoenix", "Phoenix")
-        .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
-
-    let download_dir = dirs.data_dir().join("downloads");
+    let download_dir = crate::config::Config::data_dir()?.join("downloads");
     std::fs::create_dir_all(&download_dir)?;
 
     Ok(download_dir)
@@ -155,4 +545,96 @@ mod tests {
         assert!(dir.exists(), "download directory should be created");
         assert!(dir.is_dir(), "download path should be a directory");
     }
+
+    #[test]
+    fn test_is_complete_download() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("cdda.zip");
+        assert!(!is_complete_download(&path, 4));
+
+        std::fs::write(&path, b"data").unwrap();
+        assert!(is_complete_download(&path, 4));
+        assert!(!is_complete_download(&path, 5), "truncated download");
+        assert!(!is_complete_download(&path, 0), "unknown size");
+    }
+
+    #[test]
+    fn test_content_range_total() {
+        assert_eq!(content_range_total("bytes 0-0/12345"), Some(12345));
+        assert_eq!(content_range_total("bytes 0-0/*"), None);
+        assert_eq!(content_range_total("0-0/12345"), None);
+    }
+
+    #[test]
+    fn test_split_ranges() {
+        const MB: u64 = 1024 * 1024;
+
+        // Small files aren't split
+        assert_eq!(split_ranges(0, 4), vec![]);
+        assert_eq!(split_ranges(MB, 4), vec![(0, MB - 1)]);
+
+        // Ranges cover the file exactly, without gaps or overlap
+        let ranges = split_ranges(100 * MB + 3, 4);
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(ranges[0].0, 0);
+        assert_eq!(ranges[3].1, 100 * MB + 2);
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].1 + 1, pair[1].0);
+        }
+
+        // Never more ranges than MIN_CHUNK_SIZE allows
+        assert_eq!(split_ranges(10 * MB, 16).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pause_control() {
+        let (tx, rx) = watch::channel(false);
+        let mut pause = Some(rx);
+
+        // Running: resuming returns at once, pausing waits for the flag
+        until_resumed(&mut pause).await;
+        let paused = tokio::time::timeout(Duration::from_millis(20), until_paused(&mut pause));
+        assert!(paused.await.is_err());
+
+        tx.send(true).unwrap();
+        until_paused(&mut pause).await;
+        let resumed = tokio::time::timeout(Duration::from_millis(20), until_resumed(&mut pause));
+        assert!(resumed.await.is_err());
+
+        // A dropped control never pauses and never blocks
+        drop(tx);
+        until_resumed(&mut pause).await;
+        let paused = tokio::time::timeout(Duration::from_millis(20), until_paused(&mut pause));
+        assert!(paused.await.is_err());
+        let mut none = None;
+        until_resumed(&mut none).await;
+        let paused = tokio::time::timeout(Duration::from_millis(20), until_paused(&mut none));
+        assert!(paused.await.is_err());
+    }
+
+    #[test]
+    fn test_is_intact_archive() {
+        use std::io::Write;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("cdda-windows-with-graphics-x64.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        zip.start_file(
+            "cataclysm-tiles.exe",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .unwrap();
+        zip.write_all(&[7u8; 4096]).unwrap();
+        zip.finish().unwrap();
+        assert!(is_intact_archive(&path));
+
+        // A download cut short no longer reads back
+        let len = std::fs::metadata(&path).unwrap().len();
+        let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(len / 2).unwrap();
+        assert!(!is_intact_archive(&path));
+
+        let missing = temp.path().join("missing.tar.gz");
+        assert!(!is_intact_archive(&missing));
+    }
 }
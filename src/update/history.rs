@@ -0,0 +1,327 @@
+//! Numbered archive history for multi-step rollback.
+//!
+//! `.phoenix_archive/` always holds the installation replaced by the last
+//! update. When more than one archive is kept (`updates.keep_archives`), the
+//! archive an update would otherwise discard is moved into a numbered
+//! directory under `.phoenix_archive_history/` instead, and `index.json`
+//! records the version and time of each one. The oldest entries are pruned
+//! after every update so the history never holds more than the configured
+//! number of archives.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::app_data::{game_config, migration_config};
+use crate::game;
+
+/// Index file listing the archives in the history directory
+const INDEX_FILE: &str = "index.json";
+
+/// Directory an archive is moved to while it is being rolled back to
+const STAGING_DIR: &str = "staging";
+
+/// One numbered archive in the history directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryEntry {
+    id: u32,
+    version: Option<String>,
+    archived_at: Option<String>,
+}
+
+/// Contents of `index.json`, oldest archive first
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct HistoryIndex {
+    /// Number for the next archive; never reused, so an entry staged for
+    /// rollback can't collide with a new one
+    next_id: u32,
+    entries: Vec<HistoryEntry>,
+}
+
+/// An archived installation that can be rolled back to
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchivedInstall {
+    /// History entry number (`None` for the latest archive in `.phoenix_archive/`)
+    pub id: Option<u32>,
+    /// Game version read from the archive's VERSION.txt
+    pub version: Option<String>,
+    /// RFC 3339 time the installation was archived
+    pub archived_at: Option<String>,
+    /// Directory holding the archived installation
+    pub path: PathBuf,
+    /// Size of the archive on disk in bytes
+    pub size: u64,
+}
+
+impl ArchivedInstall {
+    /// Display-friendly version string
+    pub fn version_display(&self) -> &str {
+        self.version.as_deref().unwrap_or("Unknown version")
+    }
+}
+
+/// Directory holding the numbered archive history
+pub fn history_dir(game_dir: &Path) -> PathBuf {
+    game_dir.join(&migration_config().archive.history)
+}
+
+fn load_index(history: &Path) -> HistoryIndex {
+    fs::read_to_string(history.join(INDEX_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(history: &Path, index: &HistoryIndex) -> Result<()> {
+    let content = serde_json::to_string_pretty(index)?;
+    fs::write(history.join(INDEX_FILE), content).context("Failed to write archive history index")
+}
+
+/// Version and archive time of an archived installation
+fn describe(path: &Path) -> (Option<String>, Option<String>) {
+    let version = game::read_version_file(path, game_config()).map(|info| info.version);
+    // Files are moved into a fresh archive directory, so its modification
+    // time is when the installation was archived
+    let archived_at = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|time| DateTime::<Local>::from(time).to_rfc3339_opts(SecondsFormat::Secs, false));
+    (version, archived_at)
+}
+
+/// Move the archive at `archive_dir` into the history as the newest entry
+pub(super) fn push(game_dir: &Path, archive_dir: &Path) -> Result<()> {
+    let history = history_dir(game_dir);
+    fs::create_dir_all(&history).context("Failed to create archive history directory")?;
+
+    let mut index = load_index(&history);
+    let id = index.next_id.max(1);
+    index.next_id = id + 1;
+    let (version, archived_at) = describe(archive_dir);
+
+    fs::rename(archive_dir, history.join(id.to_string()))
+        .context("Failed to move the installation archive into the history")?;
+    tracing::debug!("Moved installation archive into history as #{}", id);
+
+    index.entries.push(HistoryEntry {
+        id,
+        version,
+        archived_at,
+    });
+    save_index(&history, &index)
+}
+
+/// Drop all but the newest `keep` history entries.
+///
+/// Returns the directories to delete; removing them is left to the caller so
+/// it can happen in the background.
+pub(super) fn prune(game_dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let history = history_dir(game_dir);
+    if !history.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut index = load_index(&history);
+    let excess = index.entries.len().saturating_sub(keep);
+    let removed: Vec<PathBuf> = index
+        .entries
+        .drain(..excess)
+        .map(|entry| history.join(entry.id.to_string()))
+        .collect();
+
+    if !removed.is_empty() {
+        save_index(&history, &index)?;
+        tracing::info!("Pruning {} old installation archives", removed.len());
+    }
+    Ok(removed)
+}
+
+/// Move an archive out of the way so it can be restored into the game directory.
+///
+/// `id` selects a history entry; `None` stages the latest archive in
+/// `.phoenix_archive/`. Returns the directory now holding the archive.
+pub(super) fn stage(game_dir: &Path, id: Option<u32>) -> Result<PathBuf> {
+    let history = history_dir(game_dir);
+
+    let Some(id) = id else {
+        let archive_dir = game_dir.join(&migration_config().archive.directory);
+        let staging = history.join(STAGING_DIR);
+        if staging.exists() {
+            fs::remove_dir_all(&staging).context("Failed to remove stale rollback staging")?;
+        }
+        fs::create_dir_all(&history).context("Failed to create archive history directory")?;
+        fs::rename(&archive_dir, &staging)
+            .context("Failed to move the installation archive for rollback")?;
+        return Ok(staging);
+    };
+
+    let mut index = load_index(&history);
+    let path = history.join(id.to_string());
+    if !path.is_dir() {
+        anyhow::bail!("Archive #{} no longer exists", id);
+    }
+    index.entries.retain(|entry| entry.id != id);
+    save_index(&history, &index)?;
+    Ok(path)
+}
+
+/// Put back an archive staged by [`stage`] when the rollback failed before
+/// any of its files were moved.
+///
+/// A history entry is listed in the index again. The latest archive returns
+/// to `.phoenix_archive/`, or joins the history if a new archive already
+/// took its place.
+pub(super) fn unstage(game_dir: &Path, id: Option<u32>, staged: &Path) -> Result<()> {
+    let Some(id) = id else {
+        let archive_dir = game_dir.join(&migration_config().archive.directory);
+        if archive_dir.exists() {
+            return push(game_dir, staged);
+        }
+        return fs::rename(staged, &archive_dir)
+            .context("Failed to move the installation archive back");
+    };
+
+    let history = history_dir(game_dir);
+    let mut index = load_index(&history);
+    let (version, archived_at) = describe(staged);
+    let position = index.entries.partition_point(|entry| entry.id < id);
+    index.entries.insert(
+        position,
+        HistoryEntry {
+            id,
+            version,
+            archived_at,
+        },
+    );
+    save_index(&history, &index)
+}
+
+/// List the archived installations, newest first, with their sizes.
+///
+/// Walks every archive to size it, so call it from a blocking context.
+pub fn list_archives(game_dir: &Path) -> Vec<ArchivedInstall> {
+    let mut archives = Vec::new();
+
+    let archive_dir = game_dir.join(&migration_config().archive.directory);
+    if archive_dir.is_dir() {
+        let (version, archived_at) = describe(&archive_dir);
+        archives.push(ArchivedInstall {
+            id: None,
+            version,
+            archived_at,
+            size: game::calculate_dir_size(&archive_dir).unwrap_or(0),
+            path: archive_dir,
+        });
+    }
+
+    let history = history_dir(game_dir);
+    for entry in load_index(&history).entries.into_iter().rev() {
+        let path = history.join(entry.id.to_string());
+        if !path.is_dir() {
+            continue;
+        }
+        archives.push(ArchivedInstall {
+            id: Some(entry.id),
+            version: entry.version,
+            archived_at: entry.archived_at,
+            size: game::calculate_dir_size(&path).unwrap_or(0),
+            path,
+        });
+    }
+
+    archives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_archive(game_dir: &Path, version: &str) -> PathBuf {
+        let archive_dir = game_dir.join(".phoenix_archive");
+        fs::create_dir_all(&archive_dir).unwrap();
+        fs::write(
+            archive_dir.join("VERSION.txt"),
+            format!("build number: {}\n", version),
+        )
+        .unwrap();
+        archive_dir
+    }
+
+    #[test]
+    fn test_push_and_prune_keep_newest_archives() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path();
+
+        for version in ["2024-01-01-0001", "2024-02-01-0001", "2024-03-01-0001"] {
+            let archive_dir = write_archive(game_dir, version);
+            push(game_dir, &archive_dir).unwrap();
+            assert!(!archive_dir.exists());
+        }
+
+        let removed = prune(game_dir, 2).unwrap();
+        assert_eq!(removed, vec![history_dir(game_dir).join("1")]);
+
+        let ids: Vec<_> = list_archives(game_dir).iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![Some(3), Some(2)]);
+    }
+
+    #[test]
+    fn test_stage_removes_entry_from_index() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path();
+
+        let archive_dir = write_archive(game_dir, "2024-01-01-0001");
+        push(game_dir, &archive_dir).unwrap();
+        write_archive(game_dir, "2024-02-01-0001");
+
+        let staged = stage(game_dir, Some(1)).unwrap();
+        assert!(staged.join("VERSION.txt").exists());
+        let remaining = list_archives(game_dir);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, None);
+
+        let staged = stage(game_dir, None).unwrap();
+        assert!(staged.join("VERSION.txt").exists());
+        assert!(list_archives(game_dir).is_empty());
+    }
+
+    #[test]
+    fn test_unstage_restores_archives() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path();
+
+        for version in ["2024-01-01-0001", "2024-02-01-0001"] {
+            let archive_dir = write_archive(game_dir, version);
+            push(game_dir, &archive_dir).unwrap();
+        }
+        write_archive(game_dir, "2024-03-01-0001");
+        let sizes = |game_dir: &Path| -> Vec<_> {
+            list_archives(game_dir)
+                .into_iter()
+                .map(|a| (a.id, a.size))
+                .collect()
+        };
+        let before = sizes(game_dir);
+
+        let staged = stage(game_dir, Some(1)).unwrap();
+        unstage(game_dir, Some(1), &staged).unwrap();
+        assert_eq!(sizes(game_dir), before);
+
+        let staged = stage(game_dir, None).unwrap();
+        unstage(game_dir, None, &staged).unwrap();
+        assert!(game_dir.join(".phoenix_archive/VERSION.txt").exists());
+
+        // A new archive in .phoenix_archive/ sends the staged one to the history
+        let staged = stage(game_dir, None).unwrap();
+        write_archive(game_dir, "2024-04-01-0001");
+        unstage(game_dir, None, &staged).unwrap();
+        let ids: Vec<_> = list_archives(game_dir).iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![None, Some(3), Some(2), Some(1)]);
+        assert!(!staged.exists());
+    }
+}
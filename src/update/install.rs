@@ -3,33 +3,243 @@
 //! Handles archiving, extraction, restoration, and rollback.
 
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::path::{Component, Path, PathBuf};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::watch;
 
 use crate::app_data::{game_config, migration_config};
-use crate::migration::{self, MigrationPlan, config_skip_files};
+use crate::config::UpdateConfig;
+use crate::db::Database;
+use crate::game;
+use crate::integrity::{self, FileHashes};
+use crate::migration::{self, MigrationMode, MigrationPlan, ModWarning, config_skip_files};
+use crate::util::{format_size, remove_all_with_progress};
 
 use super::access::check_installation_access;
+use super::history::{self, ArchivedInstall};
 use super::{UpdatePhase, UpdateProgress};
 
+/// Rate limiter for extraction progress updates.
+///
+/// Archives mix thousands of tiny JSON files with a few large assets, so a
+/// fixed file-count batch makes the bar jump. Throttling by time (like
+/// `download_asset`) keeps updates evenly spaced.
+struct ProgressThrottle {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl ProgressThrottle {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Create a throttle using the configured progress interval
+    fn from_config() -> Self {
+        Self::new(Duration::from_millis(
+            migration_config().download.progress_interval_ms,
+        ))
+    }
+
+    /// Returns true if enough time has passed to send another update
+    fn ready(&mut self) -> bool {
+        let now = Instant::now();
+        if self
+            .last
+            .is_some_and(|last| now.duration_since(last) < self.interval)
+        {
+            return false;
+        }
+        self.last = Some(now);
+        true
+    }
+}
+
+/// Custom content carried over from the previous version during an update
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationSummary {
+    /// Ids of custom mods restored to data/mods/ and mods/
+    pub mods: Vec<String>,
+    /// Custom tilesets restored to gfx/
+    pub tilesets: usize,
+    /// Custom soundpacks restored to data/sound/
+    pub soundpacks: usize,
+    /// Custom files merged into official soundpacks
+    pub soundpack_files: usize,
+    /// Custom fonts restored to font/ and data/font/
+    pub fonts: usize,
+    /// Whether user-default-mods.json was restored
+    pub user_default_mods: bool,
+    /// Restored mods that may be incompatible with the new build
+    pub mod_warnings: Vec<ModWarning>,
+    /// Official mods, tilesets and soundpacks replaced with the previous
+    /// version's copy (per the migration mode)
+    pub replaced: Vec<String>,
+    /// Official mods the user edited that were overwritten by the new
+    /// build's copy
+    pub overwritten_mods: Vec<String>,
+    /// Files that couldn't be restored (path and reason); the rest of the
+    /// restore carried on past them
+    pub restore_errors: Vec<String>,
+    /// Whether the previous installation was deleted afterwards, taking
+    /// any edits to overwritten mods with it
+    pub archive_removed: bool,
+}
+
+impl MigrationSummary {
+    /// True if no custom content was restored
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::fmt::Display for MigrationSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn count(n: usize, singular: &str, plural: &str) -> Option<String> {
+            match n {
+                0 => None,
+                1 => Some(format!("1 {}", singular)),
+                n => Some(format!("{} {}", n, plural)),
+            }
+        }
+
+        let mut parts: Vec<String> = [
+            count(self.mods.len(), "mod", "mods"),
+            count(self.tilesets, "tileset", "tilesets"),
+            count(self.soundpacks, "soundpack", "soundpacks"),
+            count(self.soundpack_files, "soundpack file", "soundpack files"),
+            count(self.fonts, "font", "fonts"),
+            count(
+                self.replaced.len(),
+                "official item replaced",
+                "official items replaced",
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if self.user_default_mods {
+            parts.push("default mod list".to_string());
+        }
+
+        if parts.is_empty() {
+            write!(f, "No custom content to preserve")?;
+        } else {
+            write!(f, "Preserved: {}", parts.join(", "))?;
+        }
+        if let Some(failed) = count(self.restore_errors.len(), "file", "files") {
+            write!(f, " ({} could not be restored)", failed)?;
+        }
+        Ok(())
+    }
+}
+
+/// Settings that control how an installation is replaced
+#[derive(Debug, Clone, Default)]
+pub struct InstallOptions {
+    /// Leave the save directory in place instead of archiving it
+    pub prevent_save_move: bool,
+    /// Delete the previous installation once the update succeeds
+    pub remove_previous_version: bool,
+    /// Number of previous installations to keep for rollback; archives
+    /// beyond the newest one go into the archive history
+    pub keep_archives: usize,
+    /// Skip the executable lock probe before making changes
+    pub skip_access_check: bool,
+    /// Paths (relative to the game directory) left out when extracting
+    pub exclude: Vec<PathBuf>,
+    /// Clean reinstall: restore only saves and config, leaving custom mods,
+    /// tilesets, soundpacks and fonts behind in the archive
+    pub clean: bool,
+    /// User data directories not restored from the previous version
+    pub skip_restore_dirs: Vec<String>,
+    /// Config files that keep the freshly extracted version
+    pub reset_config_files: Vec<String>,
+    /// Use the database when planning the migration: cached mod, tileset and
+    /// soundpack scans, and the previous install's file snapshot for telling
+    /// user edits from upstream changes. A snapshot of the new build is
+    /// recorded for the next update.
+    pub use_database: bool,
+    /// How much of the previous version's content the migration carries forward
+    pub migration_mode: MigrationMode,
+}
+
+impl InstallOptions {
+    /// Build install options from the update settings
+    pub fn from_config(config: &UpdateConfig) -> Self {
+        let exclude = if config.minimal_install {
+            config
+                .minimal_install_exclude
+                .iter()
+                .map(|path| path.trim())
+                .filter(|path| !path.is_empty())
+                .filter(|path| match excluded_required_dir(path) {
+                    Some(dir) => {
+                        tracing::warn!("Not leaving out {}: it contains {}", path, dir);
+                        false
+                    }
+                    None => true,
+                })
+                .map(PathBuf::from)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            prevent_save_move: config.prevent_save_move,
+            remove_previous_version: config.remove_previous_version,
+            keep_archives: config.keep_archives.into(),
+            skip_access_check: config.skip_access_check,
+            exclude,
+            clean: false,
+            skip_restore_dirs: config.skip_restore_dirs.clone(),
+            reset_config_files: config
+                .reset_config_files
+                .iter()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect(),
+            use_database: true,
+            migration_mode: config.migration_mode,
+        }
+    }
+}
+
 /// Perform the full update process: backup, extract, restore.
 ///
 /// If extraction or restore fails after archiving, automatically rolls back
-/// to the previous installation.
+/// to the previous installation. Returns a summary of the custom content
+/// carried over from the previous version.
 pub async fn install_update(
     archive_path: PathBuf,
     game_dir: PathBuf,
     progress_tx: watch::Sender<UpdateProgress>,
-    prevent_save_move: bool,
-    remove_previous_version: bool,
-) -> Result<()> {
+    options: InstallOptions,
+) -> Result<MigrationSummary> {
+    let InstallOptions {
+        prevent_save_move,
+        remove_previous_version,
+        keep_archives,
+        skip_access_check,
+        ref exclude,
+        clean,
+        ref skip_restore_dirs,
+        ref reset_config_files,
+        use_database,
+        ..
+    } = options;
     let update_start = Instant::now();
     let archive_dir = game_dir.join(&migration_config().archive.directory);
     let old_archive_dir = game_dir.join(&migration_config().archive.directory_old);
 
     // Pre-flight check: verify we have write access before making any changes
-    check_installation_access(&game_dir).await?;
+    check_installation_access(&game_dir, skip_access_check).await?;
 
     // Phase 1: Archive current installation (fast - uses rename, defers deletion)
     let _ = progress_tx.send(UpdateProgress {
@@ -38,8 +248,14 @@ pub async fn install_update(
     });
 
     let phase_start = Instant::now();
-    archive_current_installation(&game_dir, &archive_dir, &old_archive_dir, prevent_save_move)
-        .await?;
+    archive_current_installation(
+        &game_dir,
+        &archive_dir,
+        &old_archive_dir,
+        prevent_save_move,
+        keep_archives,
+    )
+    .await?;
     tracing::info!(
         "Archive complete in {:.1}s",
         phase_start.elapsed().as_secs_f32()
@@ -53,10 +269,18 @@ pub async fn install_update(
     });
 
     let phase_start = Instant::now();
-    let extract_result = extract_archive(&archive_path, &game_dir, progress_tx.clone()).await;
+    // Verification failures (e.g. a truncated archive) are treated like
+    // extraction failures so the previous version is restored.
+    let extract_result =
+        match extract_archive(&archive_path, &game_dir, progress_tx.clone(), exclude).await {
+            Ok(extraction) => verify_extraction(&game_dir, &extraction)
+                .await
+                .map(|()| extraction),
+            Err(e) => Err(e),
+        };
 
-    let total_files = match extract_result {
-        Ok(count) => count,
+    let extraction = match extract_result {
+        Ok(extraction) => extraction,
         Err(e) => {
             tracing::error!("Extraction failed, rolling back: {}", e);
             if let Err(rollback_err) = rollback_from_archive(&game_dir, &archive_dir).await {
@@ -76,14 +300,18 @@ pub async fn install_update(
             );
         }
     };
+    let total_files = extraction.count;
     tracing::info!(
         "Extracted {} files in {:.1}s",
         total_files,
         phase_start.elapsed().as_secs_f32()
     );
-
-    // Verify extraction succeeded
-    verify_extraction(&game_dir).await;
+    if !extraction.skipped.is_empty() {
+        tracing::info!(
+            "Minimal install: left out {} archive entries",
+            extraction.skipped.len()
+        );
+    }
 
     // Phase 3: Smart restore user data
     // If this fails, we also need to rollback
@@ -92,65 +320,94 @@ pub async fn install_update(
         ..Default::default()
     });
 
+    // Hash the pristine build before user data is restored into it
+    let pristine = if use_database {
+        hash_new_installation(&game_dir).await
+    } else {
+        None
+    };
+
     let phase_start = Instant::now();
-    let restore_result =
-        restore_user_directories_smart(&archive_dir, &game_dir, prevent_save_move).await;
+    let restore_result = if clean {
+        let mut errors = Vec::new();
+        restore_user_data(
+            &archive_dir,
+            &game_dir,
+            prevent_save_move,
+            skip_restore_dirs,
+            reset_config_files,
+            &mut errors,
+        )
+        .await
+        .map(|()| MigrationSummary {
+            restore_errors: errors,
+            ..Default::default()
+        })
+    } else {
+        restore_user_directories_smart(&archive_dir, &game_dir, &options, &extraction.skipped).await
+    };
 
-    if let Err(e) = restore_result {
-        tracing::error!("Restore failed, rolling back: {}", e);
-        if let Err(rollback_err) = rollback_from_archive(&game_dir, &archive_dir).await {
-            tracing::error!("Rollback also failed: {}", rollback_err);
-            anyhow::bail!(
-                "Update failed during restore AND rollback failed.\n\n\
+    let mut summary = match restore_result {
+        Ok(summary) => summary,
+        Err(e) => {
+            tracing::error!("Restore failed, rolling back: {}", e);
+            if let Err(rollback_err) = rollback_from_archive(&game_dir, &archive_dir).await {
+                tracing::error!("Rollback also failed: {}", rollback_err);
+                anyhow::bail!(
+                    "Update failed during restore AND rollback failed.\n\n\
                  Restore error: {}\n\
                  Rollback error: {}\n\n\
                  Your installation may be corrupted. Please reinstall the game.",
-                e,
-                rollback_err
+                    e,
+                    rollback_err
+                );
+            }
+            anyhow::bail!(
+                "Update failed during restore. Previous version has been restored.\n\nError: {}",
+                e
             );
         }
-        anyhow::bail!(
-            "Update failed during restore. Previous version has been restored.\n\nError: {}",
-            e
-        );
-    }
+    };
     tracing::info!(
         "Restore complete in {:.1}s",
         phase_start.elapsed().as_secs_f32()
     );
+    if let Some(files) = pristine {
+        record_install_snapshot(&game_dir, files).await;
+    }
 
     // Phase 4: Cleanup
-    // Always delete old_archive_dir (the stale archive from last update)
-    // Delete in background to not block completion
-    // Uses remove_dir_all crate which is faster than std::fs::remove_dir_all on Windows
-    let old_archive_for_cleanup = old_archive_dir.clone();
-    tokio::spawn(async move {
-        if old_archive_for_cleanup.exists() {
-            let start = Instant::now();
-            let path = old_archive_for_cleanup.clone();
-            let result =
-                tokio::task::spawn_blocking(move || remove_dir_all::remove_dir_all(&path)).await;
-
-            match result {
-                Ok(Ok(())) => {
-                    tracing::info!(
-                        "Background cleanup complete in {:.1}s",
-                        start.elapsed().as_secs_f32()
-                    );
-                }
-                Ok(Err(e)) => {
-                    tracing::warn!("Failed to remove old archive: {}", e);
-                }
-                Err(e) => {
-                    tracing::warn!("Cleanup task panicked: {}", e);
-                }
+    // Removing the previous version also drops the archive history
+    let keep_history = if remove_previous_version {
+        0
+    } else {
+        keep_archives.saturating_sub(1)
+    };
+    cleanup_old_archives(&game_dir, &old_archive_dir, keep_history);
+
+    // Optional cleanup of current archive directory, kept if it still
+    // holds files that couldn't be restored
+    if remove_previous_version && !summary.restore_errors.is_empty() {
+        tracing::warn!(
+            "Keeping installation archive: {} files could not be restored from it",
+            summary.restore_errors.len()
+        );
+    } else if remove_previous_version {
+        let _ = progress_tx.send(UpdateProgress {
+            phase: UpdatePhase::Cleaning,
+            ..Default::default()
+        });
+        let size = installation_archive_size(&game_dir).await;
+        match remove_installation_archive(archive_dir, progress_tx.clone()).await {
+            Ok(()) => {
+                tracing::info!(
+                    "Removed installation archive, reclaimed {}",
+                    format_size(size)
+                );
+                summary.archive_removed = true;
             }
+            Err(e) => tracing::warn!("Failed to remove installation archive: {}", e),
         }
-    });
-
-    // Optional cleanup of current archive directory
-    if remove_previous_version && let Err(e) = tokio::fs::remove_dir_all(&archive_dir).await {
-        tracing::warn!("Failed to remove installation archive: {}", e);
     }
 
     // Complete
@@ -165,9 +422,246 @@ pub async fn install_update(
         "Update complete in {:.1}s total",
         update_start.elapsed().as_secs_f32()
     );
+    Ok(summary)
+}
+
+/// Delete the installation archive, reporting files removed as `Cleaning` progress
+async fn remove_installation_archive(
+    archive_dir: PathBuf,
+    progress_tx: watch::Sender<UpdateProgress>,
+) -> Result<()> {
+    tokio::task::spawn_blocking(move || {
+        let mut throttle = ProgressThrottle::from_config();
+        remove_all_with_progress(&archive_dir, |files_removed, total_to_remove| {
+            if files_removed == total_to_remove || throttle.ready() {
+                let _ = progress_tx.send(UpdateProgress {
+                    phase: UpdatePhase::Cleaning,
+                    files_removed,
+                    total_to_remove,
+                    ..Default::default()
+                });
+            }
+        })
+    })
+    .await
+    .context("Archive removal task panicked")??;
     Ok(())
 }
 
+/// Delete the stale archive and any history beyond `keep_history` entries.
+///
+/// Deletion runs in the background so it doesn't block completion, using the
+/// remove_dir_all crate which is faster than std::fs::remove_dir_all on Windows.
+fn cleanup_old_archives(game_dir: &Path, old_archive_dir: &Path, keep_history: usize) {
+    let mut stale = match history::prune(game_dir, keep_history) {
+        Ok(pruned) => pruned,
+        Err(e) => {
+            tracing::warn!("Failed to prune archive history: {}", e);
+            Vec::new()
+        }
+    };
+    // Always delete old_archive_dir (the stale archive from last update)
+    stale.push(old_archive_dir.to_path_buf());
+
+    tokio::spawn(async move {
+        let start = Instant::now();
+        let result = tokio::task::spawn_blocking(move || {
+            for path in stale.iter().filter(|path| path.exists()) {
+                remove_dir_all::remove_dir_all(path)?;
+            }
+            std::io::Result::Ok(())
+        })
+        .await;
+
+        match result {
+            Ok(Ok(())) => {
+                tracing::info!(
+                    "Background cleanup complete in {:.1}s",
+                    start.elapsed().as_secs_f32()
+                );
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("Failed to remove old archive: {}", e);
+            }
+            Err(e) => {
+                tracing::warn!("Cleanup task panicked: {}", e);
+            }
+        }
+    });
+}
+
+/// Total size in bytes of the installation archives (`.phoenix_archive/`
+/// plus any archive history).
+///
+/// Returns 0 if there are no archives or they can't be read.
+pub async fn installation_archive_size(game_dir: &Path) -> u64 {
+    let archive_dir = game_dir.join(&migration_config().archive.directory);
+    let history_dir = history::history_dir(game_dir);
+    tokio::task::spawn_blocking(move || {
+        [archive_dir, history_dir]
+            .iter()
+            .map(|dir| game::calculate_dir_size(dir).unwrap_or(0))
+            .sum()
+    })
+    .await
+    .unwrap_or(0)
+}
+
+/// Roll the installation back to an archived version.
+///
+/// The current installation is archived like an update would, the chosen
+/// archive is moved into the game directory, and saves and custom content
+/// are carried over from the version being replaced. If anything fails
+/// after archiving, the current installation is put back.
+pub async fn rollback_to_archive(
+    game_dir: PathBuf,
+    target: ArchivedInstall,
+    progress_tx: watch::Sender<UpdateProgress>,
+    options: InstallOptions,
+) -> Result<MigrationSummary> {
+    let InstallOptions {
+        prevent_save_move,
+        keep_archives,
+        skip_access_check,
+        ..
+    } = options;
+    let archive_dir = game_dir.join(&migration_config().archive.directory);
+    let old_archive_dir = game_dir.join(&migration_config().archive.directory_old);
+
+    check_installation_access(&game_dir, skip_access_check).await?;
+    tracing::info!("Rolling back to {}", target.version_display());
+
+    // Phase 1: Set the target aside, then archive the current installation
+    let _ = progress_tx.send(UpdateProgress {
+        phase: UpdatePhase::BackingUp,
+        ..Default::default()
+    });
+    let staged = history::stage(&game_dir, target.id)?;
+    if let Err(e) = archive_current_installation(
+        &game_dir,
+        &archive_dir,
+        &old_archive_dir,
+        prevent_save_move,
+        keep_archives,
+    )
+    .await
+    {
+        // Nothing was taken from the target yet, so it stays available
+        if let Err(unstage_err) = history::unstage(&game_dir, target.id, &staged) {
+            tracing::warn!(
+                "Failed to return the archive to the history: {}",
+                unstage_err
+            );
+        }
+        return Err(e);
+    }
+
+    // Phase 2: Move the archived version into place
+    let _ = progress_tx.send(UpdateProgress {
+        phase: UpdatePhase::Extracting,
+        ..Default::default()
+    });
+    if let Err(e) = move_staged_installation(&staged, &game_dir).await {
+        remove_staged(&staged).await;
+        return Err(rollback_failure(&game_dir, &archive_dir, "moving files", e).await);
+    }
+
+    // Phase 3: Carry saves and custom content over from the replaced version
+    let _ = progress_tx.send(UpdateProgress {
+        phase: UpdatePhase::Restoring,
+        ..Default::default()
+    });
+    // Config resets and the migration mode only apply to new versions; the
+    // rolled back version's content is what the user last ran anyway
+    let restore_options = InstallOptions {
+        reset_config_files: Vec::new(),
+        migration_mode: MigrationMode::CustomOnly,
+        ..options
+    };
+    let summary = match restore_user_directories_smart(
+        &archive_dir,
+        &game_dir,
+        &restore_options,
+        &[],
+    )
+    .await
+    {
+        Ok(summary) => summary,
+        Err(e) => {
+            remove_staged(&staged).await;
+            return Err(rollback_failure(&game_dir, &archive_dir, "restore", e).await);
+        }
+    };
+
+    // Phase 4: Cleanup (the staged directory only holds stale user data now)
+    cleanup_old_archives(&game_dir, &old_archive_dir, keep_archives.saturating_sub(1));
+    remove_staged(&staged).await;
+
+    let _ = progress_tx.send(UpdateProgress {
+        phase: UpdatePhase::Complete,
+        ..Default::default()
+    });
+    tracing::info!("Rolled back to {}", target.version_display());
+    Ok(summary)
+}
+
+/// Delete a staged archive once its files have been moved out of it.
+///
+/// After a failure part of the archive is already gone, so it can't be
+/// listed for rollback again.
+async fn remove_staged(staged: &Path) {
+    if let Err(e) = tokio::fs::remove_dir_all(staged).await {
+        tracing::warn!("Failed to remove rollback staging directory: {}", e);
+    }
+}
+
+/// Move a staged archive's files into the game directory.
+///
+/// Entries already present (saves left in place by `prevent_save_move`) are
+/// kept; the restore step brings user data over from the replaced version.
+async fn move_staged_installation(staged: &Path, game_dir: &Path) -> Result<()> {
+    let mut entries = tokio::fs::read_dir(staged)
+        .await
+        .context("Failed to read the archived installation")?;
+
+    while let Some(entry) = entries.next_entry().await? {
+        let dst = game_dir.join(entry.file_name());
+        if dst.exists() {
+            continue;
+        }
+        tokio::fs::rename(entry.path(), &dst)
+            .await
+            .with_context(|| format!("Failed to move {:?} into the game directory", dst))?;
+    }
+    Ok(())
+}
+
+/// Restore the archived installation after a failed rollback and build the error
+async fn rollback_failure(
+    game_dir: &Path,
+    archive_dir: &Path,
+    step: &str,
+    error: anyhow::Error,
+) -> anyhow::Error {
+    tracing::error!("Rollback failed during {}, restoring: {}", step, error);
+    match rollback_from_archive(game_dir, archive_dir).await {
+        Ok(()) => anyhow::anyhow!(
+            "Rollback failed during {}. The current version has been restored.\n\nError: {}",
+            step,
+            error
+        ),
+        Err(restore_err) => anyhow::anyhow!(
+            "Rollback failed during {} AND restoring the current version failed.\n\n\
+             Error: {}\n\
+             Restore error: {}\n\n\
+             Your installation may be corrupted. Please reinstall the game.",
+            step,
+            error,
+            restore_err
+        ),
+    }
+}
+
 /// Move current installation to archive directory for rollback.
 ///
 /// This preserves the current game installation in `.phoenix_archive/` so users
@@ -176,7 +670,8 @@ pub async fn install_update(
 ///
 /// Uses fast rename operations to avoid blocking on deletion:
 /// 1. If old_archive_dir exists, delete it (from a previous failed update)
-/// 2. If archive_dir exists, rename it to old_archive_dir (instant)
+/// 2. If archive_dir exists, move it into the archive history when more than
+///    one archive is kept, otherwise rename it to old_archive_dir (instant)
 /// 3. Create new archive_dir and move files into it
 ///
 /// The old_archive_dir will be cleaned up in the background after the update completes.
@@ -185,6 +680,7 @@ async fn archive_current_installation(
     archive_dir: &Path,
     old_archive_dir: &Path,
     prevent_save_move: bool,
+    keep_archives: usize,
 ) -> Result<()> {
     // If old_archive_dir exists from a previous failed update, remove it first
     // This should be rare, so blocking here is acceptable
@@ -196,7 +692,9 @@ async fn archive_current_installation(
 
     // If archive_dir exists, rename it to old_archive_dir (instant operation)
     // This is the key optimization - we defer the expensive deletion
-    if archive_dir.exists() {
+    if archive_dir.exists() && keep_archives > 1 {
+        history::push(game_dir, archive_dir)?;
+    } else if archive_dir.exists() {
         tokio::fs::rename(archive_dir, old_archive_dir)
             .await
             .context("Failed to rename .phoenix_archive to old archive")?;
@@ -216,17 +714,8 @@ async fn archive_current_installation(
     let mut items_moved = 0u32;
     while let Some(entry) = entries.next_entry().await? {
         let name = entry.file_name();
-        let name_str = name.to_string_lossy();
-
-        // Skip archive directories and any temp download files
-        // Also skip save directory if prevent_save_move is enabled (leave saves in place)
-        let config = migration_config();
-        let is_archive_dir =
-            name_str == config.archive.directory || name_str == config.archive.directory_old;
-        let is_temp_download = name_str.ends_with(&config.download.temp_extension);
-        let is_save_protected = name_str == game_config().directories.save && prevent_save_move;
-
-        if is_archive_dir || is_temp_download || is_save_protected {
+        if classify_archive_entry(&name.to_string_lossy(), prevent_save_move) != ArchiveAction::Move
+        {
             continue;
         }
 
@@ -243,14 +732,93 @@ async fn archive_current_installation(
     Ok(())
 }
 
+/// What archiving the current installation does with a top-level entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveAction {
+    /// Moved into the archive and replaced by the new version
+    Move,
+    /// One of Phoenix's own archive directories
+    SkipArchive,
+    /// A partially downloaded file
+    SkipDownload,
+    /// The save directory, left in place by `prevent_save_move`
+    SkipSaves,
+}
+
+/// Classify a top-level game directory entry the way
+/// `archive_current_installation` treats it.
+pub fn classify_archive_entry(name: &str, prevent_save_move: bool) -> ArchiveAction {
+    if is_archive_dir_name(name) {
+        ArchiveAction::SkipArchive
+    } else if name.ends_with(&migration_config().download.temp_extension) {
+        ArchiveAction::SkipDownload
+    } else if prevent_save_move && name == game_config().directories.save {
+        ArchiveAction::SkipSaves
+    } else {
+        ArchiveAction::Move
+    }
+}
+
+/// A top-level game directory entry and what the next update will do with it
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchivePreviewEntry {
+    pub name: String,
+    pub is_dir: bool,
+    /// Total size in bytes (recursive for directories)
+    pub size: u64,
+    pub action: ArchiveAction,
+}
+
+/// List every top-level entry of the game directory with the action the next
+/// update's archive step would take, sorted by name.
+pub async fn preview_archive(
+    game_dir: &Path,
+    prevent_save_move: bool,
+) -> Result<Vec<ArchivePreviewEntry>> {
+    let game_dir = game_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut preview = Vec::new();
+        for entry in std::fs::read_dir(&game_dir).context("Failed to read game directory")? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            let size = if is_dir {
+                game::calculate_dir_size(&path).unwrap_or(0)
+            } else {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            };
+            preview.push(ArchivePreviewEntry {
+                action: classify_archive_entry(&name, prevent_save_move),
+                name,
+                is_dir,
+                size,
+            });
+        }
+        preview.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(preview)
+    })
+    .await
+    .context("Archive preview task panicked")?
+}
+
+/// Whether a top-level game directory entry is one of Phoenix's archive directories
+fn is_archive_dir_name(name: &str) -> bool {
+    let archive = &migration_config().archive;
+    name == archive.directory || name == archive.directory_old || name == archive.history
+}
+
 /// Extract a downloaded release archive, dispatching on its format.
 ///
 /// Windows releases ship as `.zip`; Linux releases ship as `.tar.gz`.
+/// Entries under any of the `exclude` paths are left out (minimal install).
 async fn extract_archive(
     archive_path: &Path,
     destination: &Path,
     progress_tx: watch::Sender<UpdateProgress>,
-) -> Result<usize> {
+    exclude: &[PathBuf],
+) -> Result<Extraction> {
     let name = archive_path
         .file_name()
         .and_then(|n| n.to_str())
@@ -258,10 +826,42 @@ async fn extract_archive(
         .to_lowercase();
 
     if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
-        extract_tar_gz(archive_path, destination, progress_tx).await
+        extract_tar_gz(archive_path, destination, progress_tx, exclude.to_vec()).await
     } else {
-        extract_zip(archive_path, destination, progress_tx).await
+        extract_zip(archive_path, destination, progress_tx, exclude.to_vec()).await
+    }
+}
+
+/// Result of extracting an update archive.
+#[derive(Debug, Default)]
+struct Extraction {
+    /// Number of archive entries extracted (used for progress)
+    count: usize,
+    /// Relative path and size of each regular file the archive lists (from
+    /// the ZIP central directory or the tar headers), minus left-out entries
+    entries: Vec<(PathBuf, u64)>,
+    /// Relative paths of the entries left out by a minimal install
+    skipped: Vec<PathBuf>,
+}
+
+/// The required game data directory (see the migration config's
+/// `verify.required_dirs`) that leaving out `path` would remove, if any
+pub fn excluded_required_dir(path: &str) -> Option<&'static str> {
+    let path = Path::new(path.trim());
+    if path.as_os_str().is_empty() {
+        return None;
     }
+    migration_config()
+        .verify
+        .required_dirs
+        .iter()
+        .find(|dir| Path::new(dir).starts_with(path))
+        .map(String::as_str)
+}
+
+/// Whether an archive entry falls under one of the excluded paths
+fn is_excluded(relative: &Path, exclude: &[PathBuf]) -> bool {
+    exclude.iter().any(|path| relative.starts_with(path))
 }
 
 /// Whether a path is safe to join onto an extraction destination.
@@ -274,6 +874,17 @@ fn is_safe_relative(path: &Path) -> bool {
         .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
 }
 
+/// Whether `path` resolves inside `root` (which must already be canonical).
+///
+/// `path` may not exist yet, so its deepest existing ancestor is canonicalized
+/// instead; this follows any symlinks along the way.
+fn resolves_within(root: &Path, path: &Path) -> bool {
+    path.ancestors()
+        .find(|p| p.exists())
+        .and_then(|p| p.canonicalize().ok())
+        .is_some_and(|resolved| resolved.starts_with(root))
+}
+
 /// Extract a gzip-compressed tarball to the destination directory.
 ///
 /// CDDA's Linux releases wrap everything in a single top-level directory
@@ -285,7 +896,8 @@ async fn extract_tar_gz(
     archive_path: &Path,
     destination: &Path,
     progress_tx: watch::Sender<UpdateProgress>,
-) -> Result<usize> {
+    exclude: Vec<PathBuf>,
+) -> Result<Extraction> {
     use flate2::read::GzDecoder;
     use std::ffi::OsString;
 
@@ -294,13 +906,14 @@ async fn extract_tar_gz(
 
     // Decompression + extraction is blocking, run in spawn_blocking
     tokio::task::spawn_blocking(move || {
-        // Pass 1: count entries and detect a wrapper directory shared by all
-        // entries (so we only strip it when it genuinely wraps everything).
-        let (total, strip_prefix) = {
+        // Pass 1: collect entry paths and detect a wrapper directory shared by
+        // all entries (so we only strip it when it genuinely wraps everything).
+        let (total, strip_prefix, entries) = {
             let file = std::fs::File::open(&archive_path).context("Failed to open tar.gz file")?;
             let mut archive = tar::Archive::new(GzDecoder::new(file));
 
-            let mut total = 0usize;
+            let mut paths = Vec::new();
+            let mut sizes = Vec::new();
             let mut prefix: Option<OsString> = None;
             let mut shared = true;
 
@@ -319,11 +932,31 @@ async fn extract_tar_gz(
                     _ => shared = false,
                 }
 
-                total += 1;
+                let size = entry.header().entry_type().is_file().then(|| entry.size());
+                paths.push(path.into_owned());
+                sizes.push(size);
             }
 
             let strip_prefix = if shared { prefix } else { None };
-            (total, strip_prefix)
+            // Only entries that will actually be extracted count toward progress
+            let total = paths
+                .iter()
+                .map(|path| strip_wrapper(path, strip_prefix.as_deref()))
+                .filter(|relative| {
+                    !relative.as_os_str().is_empty() && !is_excluded(relative, &exclude)
+                })
+                .count();
+            // Regular files that pass 2 will write
+            let entries = paths
+                .iter()
+                .zip(sizes)
+                .filter_map(|(path, size)| {
+                    let relative = strip_wrapper(path, strip_prefix.as_deref());
+                    (!is_excluded(&relative, &exclude) && is_safe_relative(&relative))
+                        .then_some((relative, size?))
+                })
+                .collect();
+            (total, strip_prefix, entries)
         };
 
         if let Some(prefix) = &strip_prefix {
@@ -344,14 +977,14 @@ async fn extract_tar_gz(
         archive.set_preserve_permissions(true);
         archive.set_overwrite(true);
 
-        let batch_size = migration_config().download.extraction_batch_size;
-        let mut extracted = 0usize;
+        let mut throttle = ProgressThrottle::from_config();
+        let mut extracted = Extraction {
+            entries,
+            ..Default::default()
+        };
+        let mut processed = 0usize;
 
-        for (i, entry) in archive
-            .entries()
-            .context("Failed to read tar.gz archive")?
-            .enumerate()
-        {
+        for entry in archive.entries().context("Failed to read tar.gz archive")? {
             let mut entry = entry.context("Failed to read tar entry")?;
             let entry_path = entry
                 .path()
@@ -359,19 +992,19 @@ async fn extract_tar_gz(
                 .into_owned();
 
             // Strip the common wrapper directory, if any.
-            let relative = match &strip_prefix {
-                Some(prefix) => entry_path
-                    .strip_prefix(prefix)
-                    .unwrap_or(&entry_path)
-                    .to_path_buf(),
-                None => entry_path.clone(),
-            };
+            let relative = strip_wrapper(&entry_path, strip_prefix.as_deref());
 
             // Skip the wrapper directory entry itself.
             if relative.as_os_str().is_empty() {
                 continue;
             }
 
+            if is_excluded(&relative, &exclude) {
+                extracted.skipped.push(relative);
+                continue;
+            }
+            processed += 1;
+
             // Guard against path traversal. Reject entries that could write
             // outside `destination`: parent-dir components ("..") or an
             // absolute/rooted path (which would make `destination.join` discard
@@ -412,14 +1045,14 @@ async fn extract_tar_gz(
             entry
                 .unpack(&outpath)
                 .with_context(|| format!("Failed to extract {:?}", outpath))?;
-            extracted += 1;
+            extracted.count += 1;
 
             // Update progress periodically
-            if i % batch_size == 0 || i == total.saturating_sub(1) {
+            if throttle.ready() || processed == total {
                 let current_file = relative.to_string_lossy().to_string();
                 let _ = progress_tx.send(UpdateProgress {
                     phase: UpdatePhase::Extracting,
-                    files_extracted: i + 1,
+                    files_extracted: processed,
                     total_files: total,
                     current_file,
                     ..Default::default()
@@ -433,12 +1066,54 @@ async fn extract_tar_gz(
     .context("tar.gz extraction task panicked")?
 }
 
+/// Path of a tar entry relative to the wrapper directory being stripped
+fn strip_wrapper(path: &Path, prefix: Option<&std::ffi::OsStr>) -> PathBuf {
+    match prefix {
+        Some(prefix) => path.strip_prefix(prefix).unwrap_or(path).to_path_buf(),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Top-level folder wrapping every entry of a ZIP, if it should be stripped.
+///
+/// Only a folder that holds the game itself (a `data` directory) counts, so an
+/// archive whose entries merely all live under e.g. `data/` is left alone.
+fn zip_wrapper_dir<'a>(names: impl Iterator<Item = &'a str>) -> Option<std::ffi::OsString> {
+    let mut wrapper: Option<&std::ffi::OsStr> = None;
+    let mut has_data = false;
+
+    for name in names {
+        let mut components = Path::new(name).components();
+        let Some(Component::Normal(first)) = components.next() else {
+            return None;
+        };
+        match wrapper {
+            None => wrapper = Some(first),
+            Some(w) if w == first => {}
+            Some(_) => return None,
+        }
+        // A file at the top level is not a wrapper directory
+        match components.next() {
+            Some(Component::Normal(second)) => has_data |= second == "data",
+            Some(_) => return None,
+            None if !name.ends_with('/') => return None,
+            None => {}
+        }
+    }
+
+    wrapper.filter(|_| has_data).map(|w| w.to_os_string())
+}
+
 /// Extract a ZIP archive to the destination directory.
+///
+/// A single top-level folder wrapping the whole game is stripped, so files
+/// land directly in the destination.
 async fn extract_zip(
     zip_path: &Path,
     destination: &Path,
     progress_tx: watch::Sender<UpdateProgress>,
-) -> Result<usize> {
+    exclude: Vec<PathBuf>,
+) -> Result<Extraction> {
     let zip_path = zip_path.to_path_buf();
     let destination = destination.to_path_buf();
 
@@ -447,7 +1122,41 @@ async fn extract_zip(
         let file = std::fs::File::open(&zip_path).context("Failed to open ZIP file")?;
         let mut archive = zip::ZipArchive::new(file).context("Failed to read ZIP archive")?;
 
-        let total = archive.len();
+        // Some archives wrap the whole game in one top-level folder
+        let wrapper = zip_wrapper_dir(archive.file_names());
+        if let Some(wrapper) = &wrapper {
+            tracing::debug!("Stripping ZIP wrapper directory: {:?}", wrapper);
+        }
+
+        // Only entries that will actually be extracted count toward progress
+        let total = archive
+            .file_names()
+            .map(|name| strip_wrapper(Path::new(name), wrapper.as_deref()))
+            .filter(|relative| !relative.as_os_str().is_empty() && !is_excluded(relative, &exclude))
+            .count();
+        let mut extracted = Extraction::default();
+
+        // Regular files and their sizes as the central directory lists them
+        for i in 0..archive.len() {
+            let file = archive
+                .by_index_raw(i)
+                .context("Failed to read ZIP entry")?;
+            if file.name().ends_with('/') {
+                continue;
+            }
+            let Some(enclosed) = file.enclosed_name() else {
+                continue;
+            };
+            let relative = strip_wrapper(&enclosed, wrapper.as_deref());
+            if !relative.as_os_str().is_empty() && !is_excluded(&relative, &exclude) {
+                extracted.entries.push((relative, file.size()));
+            }
+        }
+        let mut processed = 0usize;
+
+        let root = destination
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {:?}", destination))?;
 
         // Send initial extraction progress
         let _ = progress_tx.send(UpdateProgress {
@@ -457,14 +1166,34 @@ async fn extract_zip(
             ..Default::default()
         });
 
-        for i in 0..total {
+        let mut throttle = ProgressThrottle::from_config();
+        for i in 0..archive.len() {
             let mut file = archive.by_index(i).context("Failed to read ZIP entry")?;
 
-            // Get the output path - extract directly without modifying paths
-            let outpath = match file.enclosed_name() {
-                Some(path) => destination.join(path),
-                None => continue, // Skip entries with unsafe paths
+            // Get the output path, minus any wrapper directory.
+            // An unsafe entry means the archive is malformed or malicious, so
+            // abort (the caller rolls back) rather than install a partial build.
+            let Some(enclosed) = file.enclosed_name() else {
+                anyhow::bail!("Unsafe path in ZIP entry: {:?}", file.name());
             };
+            let relative = strip_wrapper(&enclosed, wrapper.as_deref());
+
+            // Skip the wrapper directory entry itself.
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+            if is_excluded(&relative, &exclude) {
+                extracted.skipped.push(relative);
+                continue;
+            }
+            processed += 1;
+            let outpath = destination.join(&relative);
+
+            // Defense in depth: the entry must also resolve inside the
+            // destination once symlinks in existing directories are followed.
+            if !resolves_within(&root, &outpath) {
+                anyhow::bail!("ZIP entry escapes the game directory: {:?}", file.name());
+            }
 
             // Handle directory or file
             if file.name().ends_with('/') {
@@ -488,12 +1217,11 @@ async fn extract_zip(
             }
 
             // Update progress periodically
-            let batch_size = migration_config().download.extraction_batch_size;
-            if i % batch_size == 0 || i == total - 1 {
+            if throttle.ready() || processed == total {
                 let current_file = file.name().to_string();
                 let _ = progress_tx.send(UpdateProgress {
                     phase: UpdatePhase::Extracting,
-                    files_extracted: i + 1,
+                    files_extracted: processed,
                     total_files: total,
                     current_file,
                     ..Default::default()
@@ -501,7 +1229,8 @@ async fn extract_zip(
             }
         }
 
-        Ok::<_, anyhow::Error>(total)
+        extracted.count = processed;
+        Ok::<_, anyhow::Error>(extracted)
     })
     .await
     .context("ZIP extraction task panicked")?
@@ -513,10 +1242,133 @@ async fn extract_zip(
 /// - Simple dirs (save, templates, memorial, graveyard) are copied completely
 /// - Config is copied with debug.log files filtered out
 /// - Mods, tilesets, soundpacks, fonts use identity-based detection to only restore custom content
+///
+/// `options` supplies the directories and config files to leave behind,
+/// the migration mode and whether the database is used. `skipped` lists
+/// the archive entries a minimal install left out; official content among
+/// them is not carried over from the previous version.
 async fn restore_user_directories_smart(
+    previous_dir: &Path,
+    game_dir: &Path,
+    options: &InstallOptions,
+    skipped: &[PathBuf],
+) -> Result<MigrationSummary> {
+    // Phases 1-2: Saves and other user data, then config
+    let mut errors = Vec::new();
+    restore_user_data(
+        previous_dir,
+        game_dir,
+        options.prevent_save_move,
+        &options.skip_restore_dirs,
+        &options.reset_config_files,
+        &mut errors,
+    )
+    .await?;
+
+    // Phase 3: Smart migration for mods, tilesets, soundpacks, fonts
+    let previous_dir_owned = previous_dir.to_path_buf();
+    let game_dir_owned = game_dir.to_path_buf();
+    let (use_database, mode) = (options.use_database, options.migration_mode);
+
+    let mut plan = tokio::task::spawn_blocking(move || {
+        let db = if use_database {
+            Database::open()
+                .inspect_err(|e| tracing::debug!("Scan cache unavailable: {}", e))
+                .ok()
+        } else {
+            None
+        };
+        let baseline = db
+            .as_ref()
+            .and_then(|db| previous_install_baseline(db, &game_dir_owned, &previous_dir_owned));
+        migration::create_migration_plan(
+            &previous_dir_owned,
+            &game_dir_owned,
+            db.as_ref(),
+            mode,
+            baseline.as_ref(),
+        )
+    })
+    .await
+    .context("Migration plan task panicked")?;
+    drop_left_out_content(&mut plan, previous_dir, skipped);
+
+    // Execute the migration plan
+    let mut summary = execute_migration_plan(&plan, game_dir, previous_dir, &mut errors).await?;
+    summary.restore_errors = errors;
+    Ok(summary)
+}
+
+/// File hashes recorded for the game directory, if they were taken of the
+/// version now archived in `previous_dir`
+fn previous_install_baseline(
+    db: &Database,
+    game_dir: &Path,
+    previous_dir: &Path,
+) -> Option<FileHashes> {
+    let snapshot = db
+        .get_install_snapshot(&game_dir.to_string_lossy())
+        .inspect_err(|e| tracing::debug!("Install snapshot unavailable: {}", e))
+        .ok()??;
+    let previous = game::detect_game_with_db(previous_dir, Some(db)).ok()??;
+    if snapshot.version.as_deref() != Some(previous.version_display()) {
+        tracing::info!(
+            "Install snapshot is of {}, not {}; official content edits can't be detected",
+            snapshot.version.as_deref().unwrap_or("an unknown version"),
+            previous.version_display()
+        );
+        return None;
+    }
+    Some(snapshot.files)
+}
+
+/// Hash the freshly extracted build, or `None` if it can't be read
+async fn hash_new_installation(game_dir: &Path) -> Option<FileHashes> {
+    let dir = game_dir.to_path_buf();
+    match tokio::task::spawn_blocking(move || integrity::hash_installation(&dir)).await {
+        Ok(Ok(files)) => Some(files),
+        Ok(Err(e)) => {
+            tracing::warn!("Failed to hash the new installation: {}", e);
+            None
+        }
+        Err(e) => {
+            tracing::warn!("Hashing task panicked: {}", e);
+            None
+        }
+    }
+}
+
+/// Store the new build's pristine hashes as the game directory's install
+/// snapshot, so the next update can tell user edits from upstream changes
+async fn record_install_snapshot(game_dir: &Path, files: FileHashes) {
+    let dir = game_dir.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || -> Result<()> {
+        let db = Database::open()?;
+        let version = game::detect_game_with_db(&dir, Some(&db))?
+            .map(|info| info.version_display().to_string());
+        db.store_install_snapshot(&dir.to_string_lossy(), version.as_deref(), &files)
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => tracing::info!("Recorded install snapshot"),
+        Ok(Err(e)) => tracing::warn!("Failed to record install snapshot: {}", e),
+        Err(e) => tracing::warn!("Install snapshot task panicked: {}", e),
+    }
+}
+
+/// Restore saves, the other simple user data directories and config.
+///
+/// This is all a clean reinstall carries over from the previous version.
+/// Directories in `skip_dirs` other than saves are left behind in the
+/// archive, as are the config files in `reset_config`. Files that fail to
+/// copy are recorded in `errors`.
+async fn restore_user_data(
     previous_dir: &Path,
     game_dir: &Path,
     prevent_save_move: bool,
+    skip_dirs: &[String],
+    reset_config: &[String],
+    errors: &mut Vec<String>,
 ) -> Result<()> {
     // Phase 1: Simple directory restoration
     let mut restored_dirs = Vec::new();
@@ -526,6 +1378,11 @@ async fn restore_user_directories_smart(
         if dir_name == save_dir && prevent_save_move {
             continue;
         }
+        // Saves are never skipped: a removed archive would take them along
+        if skip_dirs.contains(dir_name) && dir_name != save_dir {
+            tracing::info!("Not restoring {} (disabled in settings)", dir_name);
+            continue;
+        }
 
         let src = previous_dir.join(dir_name);
         let dst = game_dir.join(dir_name);
@@ -539,7 +1396,7 @@ async fn restore_user_directories_smart(
             }
 
             // Copy from backup
-            copy_dir_recursive(&src, &dst).await?;
+            copy_dir_recursive(&src, &dst, errors).await?;
             restored_dirs.push(dir_name.clone());
         }
     }
@@ -553,36 +1410,75 @@ async fn restore_user_directories_smart(
     }
 
     // Phase 2: Config directory with file filtering
-    restore_config_directory(previous_dir, game_dir).await?;
-
-    // Phase 3: Smart migration for mods, tilesets, soundpacks, fonts
-    let previous_dir_owned = previous_dir.to_path_buf();
-    let game_dir_owned = game_dir.to_path_buf();
+    restore_config_directory(previous_dir, game_dir, reset_config, errors).await
+}
 
-    let plan = tokio::task::spawn_blocking(move || {
-        migration::create_migration_plan(&previous_dir_owned, &game_dir_owned)
-    })
-    .await
-    .context("Migration plan task panicked")?;
+/// Remove tilesets and soundpacks the new release ships but a minimal install
+/// left out.
+///
+/// The migration plan treats anything missing from the new version as custom,
+/// so without this the excluded official content would be copied back from
+/// the previous installation.
+fn drop_left_out_content(plan: &mut MigrationPlan, previous_dir: &Path, skipped: &[PathBuf]) {
+    if skipped.is_empty() {
+        return;
+    }
 
-    // Execute the migration plan
-    execute_migration_plan(&plan, game_dir, previous_dir).await?;
+    let shipped: HashSet<&Path> = skipped.iter().flat_map(|path| path.ancestors()).collect();
+    let is_shipped = |path: &Path| {
+        path.strip_prefix(previous_dir)
+            .is_ok_and(|relative| shipped.contains(relative))
+    };
 
-    Ok(())
+    let before = plan.custom_tilesets.len() + plan.custom_soundpacks.len();
+    plan.custom_tilesets
+        .retain(|tileset| !is_shipped(&tileset.path));
+    plan.custom_soundpacks
+        .retain(|soundpack| !is_shipped(&soundpack.path));
+    let dropped = before - plan.custom_tilesets.len() - plan.custom_soundpacks.len();
+    if dropped > 0 {
+        tracing::info!(
+            "Minimal install: not restoring {} left-out tilesets/soundpacks",
+            dropped
+        );
+    }
 }
 
-/// Restore config directory, skipping debug.log files
-async fn restore_config_directory(previous_dir: &Path, game_dir: &Path) -> Result<()> {
+/// Restore config directory, skipping debug.log files.
+///
+/// Files in `reset_config` are never restored: the new version's copy is
+/// kept if it ships one, otherwise the game recreates them with defaults.
+async fn restore_config_directory(
+    previous_dir: &Path,
+    game_dir: &Path,
+    reset_config: &[String],
+    errors: &mut Vec<String>,
+) -> Result<()> {
     let src = previous_dir.join("config");
     let dst = game_dir.join("config");
 
     if !src.exists() {
         return Ok(());
     }
+    let is_reset = |file_name: &std::ffi::OsStr| {
+        reset_config
+            .iter()
+            .any(|name| file_name.to_string_lossy() == *name)
+    };
 
-    // Remove any config that was extracted
+    // Remove any config that was extracted, except files reset to the new defaults
     if dst.exists() {
-        tokio::fs::remove_dir_all(&dst).await?;
+        let mut extracted = tokio::fs::read_dir(&dst).await?;
+        while let Some(entry) = extracted.next_entry().await? {
+            if is_reset(&entry.file_name()) {
+                continue;
+            }
+            if entry.file_type().await?.is_dir() {
+                tokio::fs::remove_dir_all(entry.path()).await?;
+            } else {
+                tokio::fs::remove_file(entry.path()).await?;
+            }
+        }
     }
 
     // Create destination
@@ -601,14 +1497,22 @@ async fn restore_config_directory(previous_dir: &Path, game_dir: &Path) -> Resul
             continue;
         }
 
+        // Keep the new version's default
+        if is_reset(&file_name) {
+            tracing::info!("Not restoring reset config: {}", name_str);
+            continue;
+        }
+
         let src_path = entry.path();
         let dst_path = dst.join(&file_name);
 
         let file_type = entry.file_type().await?;
         if file_type.is_dir() {
-            Box::pin(copy_dir_recursive(&src_path, &dst_path)).await?;
+            if let Err(e) = Box::pin(copy_dir_recursive(&src_path, &dst_path, errors)).await {
+                record_restore_error(errors, &src_path, e);
+            }
         } else {
-            tokio::fs::copy(&src_path, &dst_path).await?;
+            copy_file(&src_path, &dst_path, errors).await;
         }
     }
 
@@ -619,30 +1523,30 @@ async fn restore_config_directory(previous_dir: &Path, game_dir: &Path) -> Resul
     Ok(())
 }
 
-/// Execute a migration plan by copying only custom content
+/// Execute a migration plan by copying only custom content.
+///
+/// Files that fail to copy are recorded in `errors`; only failing to create
+/// a target directory aborts the restore.
 async fn execute_migration_plan(
     plan: &MigrationPlan,
     game_dir: &Path,
     previous_dir: &Path,
-) -> Result<()> {
-    let mut restored_counts: Vec<String> = Vec::new();
+    errors: &mut Vec<String>,
+) -> Result<MigrationSummary> {
+    let mut summary = MigrationSummary::default();
 
     // Restore custom mods to data/mods/
     if !plan.custom_mods.is_empty() {
         let mods_dir = game_dir.join("data").join("mods");
-        let mut count = 0;
         for mod_info in &plan.custom_mods {
             if let Some(dir_name) = mod_info.path.file_name() {
                 let target = mods_dir.join(dir_name);
                 if !target.exists() {
-                    copy_dir_recursive(&mod_info.path, &target).await?;
-                    count += 1;
+                    copy_dir_recursive(&mod_info.path, &target, errors).await?;
+                    summary.mods.push(mod_info.id.clone());
                 }
             }
         }
-        if count > 0 {
-            restored_counts.push(format!("{} custom mods", count));
-        }
     }
 
     // Restore custom user mods to mods/
@@ -650,19 +1554,15 @@ async fn execute_migration_plan(
         let user_mods_dir = game_dir.join("mods");
         tokio::fs::create_dir_all(&user_mods_dir).await?;
 
-        let mut count = 0;
         for mod_info in &plan.custom_user_mods {
             if let Some(dir_name) = mod_info.path.file_name() {
                 let target = user_mods_dir.join(dir_name);
                 if !target.exists() {
-                    copy_dir_recursive(&mod_info.path, &target).await?;
-                    count += 1;
+                    copy_dir_recursive(&mod_info.path, &target, errors).await?;
+                    summary.mods.push(mod_info.id.clone());
                 }
             }
         }
-        if count > 0 {
-            restored_counts.push(format!("{} user mods", count));
-        }
     }
 
     // Restore custom tilesets to gfx/
@@ -673,13 +1573,13 @@ async fn execute_migration_plan(
             if let Some(dir_name) = tileset_info.path.file_name() {
                 let target = gfx_dir.join(dir_name);
                 if !target.exists() {
-                    copy_dir_recursive(&tileset_info.path, &target).await?;
+                    copy_dir_recursive(&tileset_info.path, &target, errors).await?;
                     count += 1;
                 }
             }
         }
         if count > 0 {
-            restored_counts.push(format!("{} tilesets", count));
+            summary.tilesets = count;
         }
     }
 
@@ -691,13 +1591,13 @@ async fn execute_migration_plan(
             if let Some(dir_name) = soundpack_info.path.file_name() {
                 let target = sound_dir.join(dir_name);
                 if !target.exists() {
-                    copy_dir_recursive(&soundpack_info.path, &target).await?;
+                    copy_dir_recursive(&soundpack_info.path, &target, errors).await?;
                     count += 1;
                 }
             }
         }
         if count > 0 {
-            restored_counts.push(format!("{} soundpacks", count));
+            summary.soundpacks = count;
         }
     }
 
@@ -715,13 +1615,14 @@ async fn execute_migration_plan(
                     if let Some(parent) = dst.parent() {
                         tokio::fs::create_dir_all(parent).await?;
                     }
-                    tokio::fs::copy(&src, &dst).await?;
-                    file_count += 1;
+                    if copy_file(&src, &dst, errors).await {
+                        file_count += 1;
+                    }
                 }
             }
         }
         if file_count > 0 {
-            restored_counts.push(format!("{} soundpack files", file_count));
+            summary.soundpack_files = file_count;
         }
     }
 
@@ -735,16 +1636,17 @@ async fn execute_migration_plan(
             if let Some(file_name) = font_path.file_name() {
                 let target = font_dir.join(file_name);
                 if font_path.is_file() {
-                    tokio::fs::copy(font_path, &target).await?;
-                    count += 1;
+                    if copy_file(font_path, &target, errors).await {
+                        count += 1;
+                    }
                 } else if font_path.is_dir() {
-                    copy_dir_recursive(font_path, &target).await?;
+                    copy_dir_recursive(font_path, &target, errors).await?;
                     count += 1;
                 }
             }
         }
         if count > 0 {
-            restored_counts.push(format!("{} fonts", count));
+            summary.fonts += count;
         }
     }
 
@@ -757,14 +1659,28 @@ async fn execute_migration_plan(
             if let Some(file_name) = font_path.file_name() {
                 let target = data_font_dir.join(file_name);
                 if font_path.is_file() {
-                    tokio::fs::copy(font_path, &target).await?;
+                    if copy_file(font_path, &target, errors).await {
+                        summary.fonts += 1;
+                    }
                 } else if font_path.is_dir() {
-                    copy_dir_recursive(font_path, &target).await?;
+                    copy_dir_recursive(font_path, &target, errors).await?;
+                    summary.fonts += 1;
                 }
             }
         }
     }
 
+    // Replace official content with the previous version's copy
+    for replaced in &plan.replaced {
+        if replaced.new_path.exists() {
+            tokio::fs::remove_dir_all(&replaced.new_path)
+                .await
+                .with_context(|| format!("Failed to remove new {}", replaced.name))?;
+        }
+        copy_dir_recursive(&replaced.old_path, &replaced.new_path, errors).await?;
+        summary.replaced.push(replaced.name.clone());
+    }
+
     // Restore user-default-mods.json if needed
     if plan.restore_user_default_mods {
         let src = previous_dir
@@ -776,21 +1692,58 @@ async fn execute_migration_plan(
             .join("mods")
             .join("user-default-mods.json");
         if src.exists() && !dst.exists() {
-            tokio::fs::copy(&src, &dst).await?;
-            restored_counts.push("user-default-mods.json".to_string());
+            summary.user_default_mods = copy_file(&src, &dst, errors).await;
         }
     }
 
+    summary.overwritten_mods = plan.overwritten_mods.clone();
+
+    // Only warn about mods that were actually restored
+    summary.mods.sort();
+    summary.mod_warnings = plan
+        .mod_warnings
+        .iter()
+        .filter(|warning| summary.mods.contains(&warning.id))
+        .cloned()
+        .collect();
+
     // Log summary of restored custom content
-    if !restored_counts.is_empty() {
-        tracing::info!("Restored custom content: {}", restored_counts.join(", "));
+    if !summary.is_empty() {
+        tracing::info!("{}", summary);
     }
 
-    Ok(())
+    Ok(summary)
+}
+
+/// Record a file or directory that couldn't be restored
+fn record_restore_error(errors: &mut Vec<String>, path: &Path, error: impl std::fmt::Display) {
+    tracing::warn!("Failed to restore {:?}: {}", path, error);
+    errors.push(format!("{}: {}", path.display(), error));
+}
+
+/// Copy a single file, recording a failure in `errors` instead of aborting.
+///
+/// Returns whether the file was copied.
+async fn copy_file(src: &Path, dst: &Path, errors: &mut Vec<String>) -> bool {
+    match tokio::fs::copy(src, dst).await {
+        Ok(_) => true,
+        Err(e) => {
+            record_restore_error(errors, src, e);
+            false
+        }
+    }
 }
 
 /// Recursively copy a directory.
-pub(crate) async fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
+///
+/// Entries that fail to copy (e.g. a file locked by another program) are
+/// recorded in `errors` and skipped. Fails only if `dst` can't be created or
+/// `src` can't be read at all.
+pub(crate) async fn copy_dir_recursive(
+    src: &Path,
+    dst: &Path,
+    errors: &mut Vec<String>,
+) -> Result<()> {
     tokio::fs::create_dir_all(dst)
         .await
         .with_context(|| format!("Failed to create directory {:?}", dst))?;
@@ -799,37 +1752,91 @@ pub(crate) async fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
         .await
         .with_context(|| format!("Failed to read directory {:?}", src))?;
 
-    while let Some(entry) = entries.next_entry().await? {
+    loop {
+        let entry = match entries.next_entry().await {
+            Ok(Some(entry)) => entry,
+            Ok(None) => break,
+            Err(e) => {
+                record_restore_error(errors, src, e);
+                break;
+            }
+        };
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
-        let file_type = entry.file_type().await?;
-        if file_type.is_dir() {
-            Box::pin(copy_dir_recursive(&src_path, &dst_path)).await?;
-        } else {
-            tokio::fs::copy(&src_path, &dst_path)
-                .await
-                .with_context(|| format!("Failed to copy {:?}", src_path))?;
+        match entry.file_type().await {
+            Ok(file_type) if file_type.is_dir() => {
+                // A subdirectory that can't be copied doesn't stop its siblings
+                if let Err(e) = Box::pin(copy_dir_recursive(&src_path, &dst_path, errors)).await {
+                    record_restore_error(errors, &src_path, e);
+                }
+            }
+            Ok(_) => {
+                copy_file(&src_path, &dst_path, errors).await;
+            }
+            Err(e) => record_restore_error(errors, &src_path, e),
         }
     }
 
     Ok(())
 }
 
-/// Verify critical files exist after extraction
-async fn verify_extraction(game_dir: &Path) -> bool {
-    // Just check for the executable - the most critical file
-    let exe_exists = game_config()
-        .executables
-        .names()
-        .iter()
-        .any(|exe| game_dir.join(exe).exists());
+/// Verify that extraction produced a complete installation.
+///
+/// Catches truncated or corrupt archives that still extract the executable:
+/// every file the archive lists must exist on disk with the size the archive
+/// records for it, and the key data directories must be non-empty.
+async fn verify_extraction(game_dir: &Path, extraction: &Extraction) -> Result<()> {
+    let game_dir = game_dir.to_path_buf();
+    let entries = extraction.entries.clone();
 
-    if !exe_exists {
-        tracing::warn!("Game executable not found after extraction");
-    }
+    tokio::task::spawn_blocking(move || {
+        let exe_exists = game_config()
+            .executables
+            .names()
+            .iter()
+            .any(|exe| game_dir.join(exe).exists());
+        if !exe_exists {
+            anyhow::bail!("Game executable not found after extraction");
+        }
+
+        // Archives may list the same file twice; the last entry is the one
+        // left on disk
+        let expected: HashMap<&PathBuf, u64> =
+            entries.iter().map(|(path, size)| (path, *size)).collect();
+        let damaged: Vec<&PathBuf> = expected
+            .iter()
+            .filter(|(path, size)| {
+                std::fs::metadata(game_dir.join(path))
+                    .map_or(true, |meta| !meta.is_file() || meta.len() != **size)
+            })
+            .map(|(path, _)| *path)
+            .collect();
+        if let Some(first) = damaged.first() {
+            anyhow::bail!(
+                "Extraction incomplete: {} of {} files from the archive are missing or \
+                 the wrong size (e.g. {}). The download may be corrupt.",
+                damaged.len(),
+                expected.len(),
+                first.display()
+            );
+        }
+        let present = expected.len();
+
+        for dir in &migration_config().verify.required_dirs {
+            let non_empty = std::fs::read_dir(game_dir.join(dir))
+                .map(|mut entries| entries.next().is_some())
+                .unwrap_or(false);
+            if !non_empty {
+                anyhow::bail!("Extraction incomplete: {} is missing or empty", dir);
+            }
+        }
 
-    exe_exists
+        tracing::debug!("Verified {} extracted files", present);
+        Ok(())
+    })
+    .await
+    .context("Verification task panicked")?
 }
 
 /// Rollback to the previous installation from archive.
@@ -841,7 +1848,6 @@ async fn rollback_from_archive(game_dir: &Path, archive_dir: &Path) -> Result<()
 
     // First, clear any partially extracted files from game_dir
     // (except the archive directories themselves)
-    let config = migration_config();
     let mut entries = tokio::fs::read_dir(game_dir)
         .await
         .context("Failed to read game directory during rollback")?;
@@ -851,7 +1857,7 @@ async fn rollback_from_archive(game_dir: &Path, archive_dir: &Path) -> Result<()
         let name_str = name.to_string_lossy();
 
         // Keep archive directories
-        if name_str == config.archive.directory || name_str == config.archive.directory_old {
+        if is_archive_dir_name(&name_str) {
             continue;
         }
 
@@ -900,6 +1906,17 @@ mod tests {
 
     use crate::app_data::{game_config, migration_config};
 
+    #[test]
+    fn test_progress_throttle_limits_update_rate() {
+        let mut throttle = ProgressThrottle::new(Duration::from_secs(3600));
+        assert!(throttle.ready(), "first update is always sent");
+        assert!(!throttle.ready());
+
+        let mut unthrottled = ProgressThrottle::new(Duration::ZERO);
+        assert!(unthrottled.ready());
+        assert!(unthrottled.ready());
+    }
+
     #[test]
     fn test_simple_restore_dirs_contains_critical_directories() {
         // Ensure save directory is always in simple restore config
@@ -946,7 +1963,7 @@ mod tests {
         // Archive the installation (prevent_save_move = false, so saves are archived)
         let archive_dir = game_dir.join(".phoenix_archive");
         let old_archive_dir = game_dir.join(".phoenix_archive_old");
-        archive_current_installation(&game_dir, &archive_dir, &old_archive_dir, false)
+        archive_current_installation(&game_dir, &archive_dir, &old_archive_dir, false, 1)
             .await
             .unwrap();
 
@@ -979,9 +1996,15 @@ mod tests {
         .unwrap();
 
         // Restore user directories with smart migration
-        restore_user_directories_smart(&archive_dir, &game_dir, false)
-            .await
-            .unwrap();
+        let summary = restore_user_directories_smart(
+            &archive_dir,
+            &game_dir,
+            &InstallOptions::default(),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(summary.mods, vec!["my_custom_mod"]);
 
         // Verify saves are restored
         assert!(game_dir.join("save").join("test_world.sav").exists());
@@ -1008,6 +2031,157 @@ mod tests {
         assert!(archive_dir.join("save").join("test_world.sav").exists());
     }
 
+    #[tokio::test]
+    async fn test_clean_install_restores_only_user_data() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().join("game");
+        let mod_dir = game_dir.join("data").join("mods").join("my_custom_mod");
+        fs::create_dir_all(&mod_dir).unwrap();
+        fs::write(
+            mod_dir.join("modinfo.json"),
+            r#"{"type": "MOD_INFO", "id": "my_custom_mod", "name": "My Custom Mod"}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(game_dir.join("save")).unwrap();
+        fs::write(game_dir.join("save").join("world.sav"), b"save data").unwrap();
+        fs::create_dir_all(game_dir.join("config")).unwrap();
+        fs::write(game_dir.join("config").join("options.json"), b"{}").unwrap();
+        fs::write(game_dir.join("stray.txt"), b"left behind").unwrap();
+
+        let archive_path = temp_dir.path().join("release.zip");
+        let exe = &game_config().executables.names()[0];
+        write_zip(
+            &archive_path,
+            &[
+                (exe.as_str(), b"new exe"),
+                ("data/json/items.json", b"[]"),
+                ("gfx/tiles.png", b"png"),
+            ],
+        );
+
+        let progress_tx = watch::channel(UpdateProgress::default()).0;
+        let summary = install_update(
+            archive_path,
+            game_dir.clone(),
+            progress_tx,
+            InstallOptions {
+                keep_archives: 1,
+                skip_access_check: true,
+                clean: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(summary.is_empty());
+        assert!(game_dir.join(exe).exists());
+        assert_eq!(
+            fs::read_to_string(game_dir.join("save").join("world.sav")).unwrap(),
+            "save data"
+        );
+        assert!(game_dir.join("config").join("options.json").exists());
+        assert!(!mod_dir.exists());
+        assert!(!game_dir.join("stray.txt").exists());
+        // Everything else stays in the archive for rollback
+        assert!(game_dir.join(".phoenix_archive").join("stray.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_restore_user_data_skips_disabled_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let previous_dir = temp_dir.path().join("previous");
+        let game_dir = temp_dir.path().join("game");
+        for dir in ["save", "templates", "graveyard"] {
+            fs::create_dir_all(previous_dir.join(dir)).unwrap();
+            fs::write(previous_dir.join(dir).join("file.txt"), dir).unwrap();
+        }
+        fs::create_dir_all(&game_dir).unwrap();
+
+        let mut errors = Vec::new();
+        restore_user_data(
+            &previous_dir,
+            &game_dir,
+            false,
+            &["graveyard".to_string()],
+            &[],
+            &mut errors,
+        )
+        .await
+        .unwrap();
+
+        assert!(errors.is_empty());
+        assert!(game_dir.join("save").join("file.txt").exists());
+        assert!(game_dir.join("templates").join("file.txt").exists());
+        assert!(!game_dir.join("graveyard").exists());
+    }
+
+    #[tokio::test]
+    async fn test_restore_config_keeps_reset_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let previous_dir = temp_dir.path().join("previous");
+        let game_dir = temp_dir.path().join("game");
+        fs::create_dir_all(previous_dir.join("config")).unwrap();
+        fs::create_dir_all(game_dir.join("config")).unwrap();
+        for name in [
+            "options.json",
+            "keybindings.json",
+            "user-only.json",
+            "fonts.json",
+        ] {
+            fs::write(previous_dir.join("config").join(name), "old").unwrap();
+        }
+        for name in ["options.json", "keybindings.json", "shipped.json"] {
+            fs::write(game_dir.join("config").join(name), "new").unwrap();
+        }
+
+        let mut errors = Vec::new();
+        restore_config_directory(
+            &previous_dir,
+            &game_dir,
+            &["keybindings.json".to_string(), "fonts.json".to_string()],
+            &mut errors,
+        )
+        .await
+        .unwrap();
+
+        let read = |name: &str| fs::read_to_string(game_dir.join("config").join(name)).ok();
+        assert!(errors.is_empty());
+        assert_eq!(read("options.json").as_deref(), Some("old"));
+        assert_eq!(read("keybindings.json").as_deref(), Some("new"));
+        assert_eq!(read("user-only.json").as_deref(), Some("old"));
+        // A reset file the new version doesn't ship is left for the game to recreate
+        assert_eq!(read("fonts.json"), None);
+        // Other extracted config is still replaced wholesale
+        assert_eq!(read("shipped.json"), None);
+    }
+
+    #[test]
+    fn test_migration_summary_display() {
+        let summary = MigrationSummary {
+            mods: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            tilesets: 1,
+            soundpacks: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            summary.to_string(),
+            "Preserved: 3 mods, 1 tileset, 2 soundpacks"
+        );
+        assert!(!summary.is_empty());
+        assert!(MigrationSummary::default().is_empty());
+
+        let summary = MigrationSummary {
+            restore_errors: vec!["save/world.sav: locked".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            summary.to_string(),
+            "No custom content to preserve (1 file could not be restored)"
+        );
+        assert!(!summary.is_empty());
+    }
+
     #[tokio::test]
     async fn test_restore_with_prevent_save_move() {
         let temp_dir = TempDir::new().unwrap();
@@ -1023,9 +2197,17 @@ mod tests {
         fs::create_dir_all(&game_dir).unwrap();
 
         // Restore with prevent_save_move = true
-        restore_user_directories_smart(&previous_dir, &game_dir, true)
-            .await
-            .unwrap();
+        restore_user_directories_smart(
+            &previous_dir,
+            &game_dir,
+            &InstallOptions {
+                prevent_save_move: true,
+                ..Default::default()
+            },
+            &[],
+        )
+        .await
+        .unwrap();
 
         // Save should NOT be restored when prevent_save_move is true
         assert!(!game_dir.join("save").exists());
@@ -1046,7 +2228,7 @@ mod tests {
         fs::write(game_dir.join("game.exe"), b"game").unwrap();
 
         // Archive should rename old .phoenix_archive to old_archive_dir
-        archive_current_installation(&game_dir, &archive_dir, &old_archive_dir, false)
+        archive_current_installation(&game_dir, &archive_dir, &old_archive_dir, false, 1)
             .await
             .unwrap();
 
@@ -1058,6 +2240,65 @@ mod tests {
         assert!(!archive_dir.join("old_file.txt").exists());
     }
 
+    #[tokio::test]
+    async fn test_rollback_to_history_archive_keeps_current_saves() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().to_path_buf();
+        let archive_dir = game_dir.join(".phoenix_archive");
+        let old_archive_dir = game_dir.join(".phoenix_archive_old");
+
+        // Two updates in a row, keeping up to three archives
+        // VERSION.txt needs a commit SHA; the version is its first 7 characters
+        let write_version = |sha: &str| {
+            fs::write(
+                game_dir.join("VERSION.txt"),
+                format!("commit sha: {}", sha.repeat(40)),
+            )
+            .unwrap()
+        };
+        write_version("a");
+        for sha in ["b", "c"] {
+            archive_current_installation(&game_dir, &archive_dir, &old_archive_dir, false, 3)
+                .await
+                .unwrap();
+            write_version(sha);
+        }
+        fs::create_dir_all(game_dir.join("save")).unwrap();
+        fs::write(game_dir.join("save").join("world.sav"), b"latest").unwrap();
+
+        let archives = history::list_archives(&game_dir);
+        let versions: Vec<_> = archives.iter().map(|a| a.version_display()).collect();
+        assert_eq!(versions, vec!["bbbbbbb", "aaaaaaa"]);
+
+        let progress_tx = watch::channel(UpdateProgress::default()).0;
+        rollback_to_archive(
+            game_dir.clone(),
+            archives[1].clone(),
+            progress_tx,
+            InstallOptions {
+                keep_archives: 3,
+                skip_access_check: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            fs::read_to_string(game_dir.join("VERSION.txt"))
+                .unwrap()
+                .starts_with("commit sha: aaaa")
+        );
+        assert_eq!(
+            fs::read_to_string(game_dir.join("save").join("world.sav")).unwrap(),
+            "latest"
+        );
+        // The replaced version is now the latest archive
+        let archives = history::list_archives(&game_dir);
+        let versions: Vec<_> = archives.iter().map(|a| a.version_display()).collect();
+        assert_eq!(versions, vec!["ccccccc", "bbbbbbb"]);
+    }
+
     #[tokio::test]
     async fn test_extract_tar_gz_strips_wrapper_and_preserves_mode() {
         use flate2::Compression;
@@ -1107,10 +2348,11 @@ mod tests {
         }
 
         let (progress_tx, _progress_rx) = watch::channel(UpdateProgress::default());
-        let extracted = extract_tar_gz(&archive_path, &dest, progress_tx)
+        let extracted = extract_tar_gz(&archive_path, &dest, progress_tx, Vec::new())
             .await
             .unwrap();
-        assert_eq!(extracted, 2);
+        assert_eq!(extracted.count, 2);
+        assert_eq!(extracted.entries.len(), 2);
 
         // Wrapper directory should be stripped: files land directly in dest.
         let exe_path = dest.join("cataclysm-tiles");
@@ -1181,12 +2423,12 @@ mod tests {
         }
 
         let (progress_tx, _progress_rx) = watch::channel(UpdateProgress::default());
-        let extracted = extract_tar_gz(&archive_path, &dest, progress_tx)
+        let extracted = extract_tar_gz(&archive_path, &dest, progress_tx, Vec::new())
             .await
             .unwrap();
 
         // Only the safe file should be written.
-        assert_eq!(extracted, 1, "unsafe entries must be skipped");
+        assert_eq!(extracted.count, 1, "unsafe entries must be skipped");
         assert!(dest.join("safe.txt").exists());
         assert!(
             !temp_dir.path().join("escape.txt").exists(),
@@ -1198,6 +2440,325 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_verify_extraction_detects_missing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("release.zip");
+        let game_dir = temp_dir.path().join("game");
+        fs::create_dir_all(&game_dir).unwrap();
+
+        let exe = game_config().executables.names()[0].clone();
+        write_zip(
+            &archive_path,
+            &[
+                (exe.as_str(), b"exe"),
+                ("data/json/items.json", b"[1, 2, 3]"),
+                ("gfx/tiles.png", b"png"),
+            ],
+        );
+        let progress_tx = watch::channel(UpdateProgress::default()).0;
+        let extraction = extract_zip(&archive_path, &game_dir, progress_tx, Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(extraction.entries.len(), 3);
+        assert!(verify_extraction(&game_dir, &extraction).await.is_ok());
+
+        // A file cut short on disk fails verification
+        let json = game_dir.join("data/json/items.json");
+        fs::write(&json, b"[1").unwrap();
+        assert!(verify_extraction(&game_dir, &extraction).await.is_err());
+
+        // So does a file listed in the archive but missing on disk
+        fs::write(&json, b"[1, 2, 3]").unwrap();
+        fs::remove_file(game_dir.join("gfx/tiles.png")).unwrap();
+        assert!(verify_extraction(&game_dir, &extraction).await.is_err());
+
+        // An empty key data directory fails verification
+        let no_json = Extraction {
+            entries: extraction.entries[..1].to_vec(),
+            ..Default::default()
+        };
+        fs::remove_file(&json).unwrap();
+        assert!(verify_extraction(&game_dir, &no_json).await.is_err());
+    }
+
+    /// Build a ZIP at `path` with the given (name, contents) entries, writing
+    /// names verbatim so unsafe paths can be simulated.
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_rejects_path_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("evil.zip");
+        let dest = temp_dir.path().join("game");
+        fs::create_dir_all(&dest).unwrap();
+
+        write_zip(
+            &archive_path,
+            &[("safe.txt", b"ok"), ("../evil.txt", b"pwned")],
+        );
+
+        let (progress_tx, _progress_rx) = watch::channel(UpdateProgress::default());
+        let result = extract_zip(&archive_path, &dest, progress_tx, Vec::new()).await;
+
+        assert!(result.is_err(), "traversal entry must abort extraction");
+        assert!(
+            !temp_dir.path().join("evil.txt").exists(),
+            "parent-dir traversal must not escape destination"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_skips_excluded_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("release.zip");
+        let dest = temp_dir.path().join("game");
+        fs::create_dir_all(&dest).unwrap();
+
+        write_zip(
+            &archive_path,
+            &[
+                ("data/json/items.json", b"[]"),
+                ("data/sound/CC-Sounds/soundpack.txt", b"NAME: CC-Sounds"),
+                ("data/soundtrack.txt", b"not a soundpack"),
+                ("gfx/Big/tile_config.json", b"{}"),
+            ],
+        );
+
+        let (progress_tx, progress_rx) = watch::channel(UpdateProgress::default());
+        let exclude = vec![PathBuf::from("data/sound"), PathBuf::from("gfx/Big")];
+        let extracted = extract_zip(&archive_path, &dest, progress_tx, exclude)
+            .await
+            .unwrap();
+
+        assert_eq!(extracted.count, 2);
+        assert_eq!(extracted.skipped.len(), 2);
+        assert!(dest.join("data/json/items.json").exists());
+        assert!(dest.join("data/soundtrack.txt").exists());
+        assert!(!dest.join("data/sound").exists());
+        assert!(!dest.join("gfx/Big").exists());
+
+        // Progress totals only count the entries actually extracted
+        let progress = progress_rx.borrow();
+        assert_eq!(progress.files_extracted, 2);
+        assert_eq!(progress.total_files, 2);
+    }
+
+    #[test]
+    fn test_drop_left_out_content_keeps_custom_packs() {
+        use crate::migration::SoundpackInfo;
+
+        let previous = Path::new("/old");
+        let mut plan = MigrationPlan {
+            custom_soundpacks: vec![
+                SoundpackInfo {
+                    name: "CC-Sounds".to_string(),
+                    path: previous.join("data/sound/CC-Sounds"),
+                },
+                SoundpackInfo {
+                    name: "MyPack".to_string(),
+                    path: previous.join("data/sound/MyPack"),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let skipped = vec![PathBuf::from("data/sound/CC-Sounds/soundpack.txt")];
+        drop_left_out_content(&mut plan, previous, &skipped);
+
+        let names: Vec<_> = plan.custom_soundpacks.iter().map(|s| &s.name).collect();
+        assert_eq!(names, vec!["MyPack"]);
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_strips_single_root_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("release.zip");
+        let dest = temp_dir.path().join("game");
+        fs::create_dir_all(&dest).unwrap();
+
+        write_zip(
+            &archive_path,
+            &[
+                ("cdda-0.H/", b""),
+                ("cdda-0.H/cataclysm-tiles.exe", b"exe"),
+                ("cdda-0.H/data/json/items.json", b"[]"),
+                (
+                    "cdda-0.H/data/sound/CC-Sounds/soundpack.txt",
+                    b"NAME: CC-Sounds",
+                ),
+            ],
+        );
+
+        let (progress_tx, progress_rx) = watch::channel(UpdateProgress::default());
+        let exclude = vec![PathBuf::from("data/sound")];
+        let extracted = extract_zip(&archive_path, &dest, progress_tx, exclude)
+            .await
+            .unwrap();
+
+        assert!(dest.join("cataclysm-tiles.exe").exists());
+        assert!(dest.join("data/json/items.json").exists());
+        assert!(!dest.join("cdda-0.H").exists());
+        // Exclusions apply to the stripped paths
+        assert!(!dest.join("data/sound").exists());
+        assert_eq!(
+            extracted.skipped,
+            vec![PathBuf::from("data/sound/CC-Sounds/soundpack.txt")]
+        );
+        assert_eq!(extracted.count, 2);
+        assert_eq!(progress_rx.borrow().total_files, 2);
+    }
+
+    #[test]
+    fn test_zip_wrapper_dir() {
+        let wrapper = |names: &[&str]| zip_wrapper_dir(names.iter().copied());
+
+        assert_eq!(
+            wrapper(&["cdda/", "cdda/data/json/a.json", "cdda/game.exe"]),
+            Some("cdda".into())
+        );
+        // Flat release layout
+        assert_eq!(wrapper(&["game.exe", "data/json/a.json"]), None);
+        // Everything under data/ is game content, not a wrapper
+        assert_eq!(wrapper(&["data/json/a.json", "data/sound/b.txt"]), None);
+        // A lone top-level file is not a directory
+        assert_eq!(wrapper(&["cdda", "cdda/data/a.json"]), None);
+        assert_eq!(wrapper(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_zip64() {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("large.zip");
+        let dest = temp_dir.path().join("game");
+        fs::create_dir_all(&dest).unwrap();
+
+        // More entries than the classic format's 16-bit count allows forces a
+        // zip64 end-of-central-directory record. One entry also carries a
+        // zip64 extra field, as writers emit for files over 4GB.
+        const ENTRIES: usize = u16::MAX as usize + 10;
+        {
+            let mut writer = zip::ZipWriter::new(fs::File::create(&archive_path).unwrap());
+            let stored =
+                SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+            writer
+                .start_file("data/large.bin", stored.large_file(true))
+                .unwrap();
+            writer.write_all(b"large").unwrap();
+            for i in 1..ENTRIES {
+                writer
+                    .start_file(format!("data/{}/{}.json", i % 100, i), stored)
+                    .unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let (progress_tx, _progress_rx) = watch::channel(UpdateProgress::default());
+        let extracted = extract_zip(&archive_path, &dest, progress_tx, Vec::new())
+            .await
+            .unwrap();
+
+        assert_eq!(extracted.count, ENTRIES);
+        assert_eq!(extracted.entries.len(), ENTRIES);
+        assert_eq!(fs::read(dest.join("data/large.bin")).unwrap(), b"large");
+        let last = ENTRIES - 1;
+        assert!(
+            dest.join(format!("data/{}/{}.json", last % 100, last))
+                .exists()
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_extract_zip_rejects_symlink_escape() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("evil.zip");
+        let dest = temp_dir.path().join("game");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir_all(&dest).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+
+        // A directory symlink already in the game dir that points elsewhere
+        std::os::unix::fs::symlink(&outside, dest.join("link")).unwrap();
+        write_zip(&archive_path, &[("link/evil.txt", b"pwned")]);
+
+        let (progress_tx, _progress_rx) = watch::channel(UpdateProgress::default());
+        let result = extract_zip(&archive_path, &dest, progress_tx, Vec::new()).await;
+
+        assert!(result.is_err(), "symlinked escape must abort extraction");
+        assert!(!outside.join("evil.txt").exists());
+    }
+
+    #[test]
+    fn test_classify_archive_entry() {
+        use ArchiveAction::*;
+
+        assert_eq!(classify_archive_entry("data", false), Move);
+        assert_eq!(
+            classify_archive_entry(".phoenix_archive", false),
+            SkipArchive
+        );
+        assert_eq!(
+            classify_archive_entry("cdda-windows.zip.part", false),
+            SkipDownload
+        );
+        assert_eq!(classify_archive_entry("save", false), Move);
+        assert_eq!(classify_archive_entry("save", true), SkipSaves);
+    }
+
+    #[tokio::test]
+    async fn test_preview_archive_matches_archiving() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+        fs::create_dir_all(game_dir.join("data")).unwrap();
+        fs::write(game_dir.join("data").join("a.json"), b"12345").unwrap();
+        fs::create_dir_all(game_dir.join("save")).unwrap();
+        fs::create_dir_all(game_dir.join(".phoenix_archive")).unwrap();
+        fs::write(game_dir.join("release.zip.part"), b"partial").unwrap();
+
+        let preview = preview_archive(game_dir, true).await.unwrap();
+        let actions: Vec<_> = preview
+            .iter()
+            .map(|e| (e.name.as_str(), e.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (".phoenix_archive", ArchiveAction::SkipArchive),
+                ("data", ArchiveAction::Move),
+                ("release.zip.part", ArchiveAction::SkipDownload),
+                ("save", ArchiveAction::SkipSaves),
+            ]
+        );
+        assert_eq!(preview[1].size, 5);
+        assert!(preview[1].is_dir);
+    }
+
+    #[test]
+    fn test_excluded_required_dir() {
+        assert_eq!(excluded_required_dir("data/json"), Some("data/json"));
+        assert_eq!(excluded_required_dir("data/"), Some("data/json"));
+        assert_eq!(excluded_required_dir(" gfx "), Some("gfx"));
+        assert_eq!(excluded_required_dir("data/json_extra"), None);
+        assert_eq!(excluded_required_dir("gfx/UltimateCataclysm"), None);
+        assert_eq!(excluded_required_dir(""), None);
+    }
+
     #[test]
     fn test_is_safe_relative() {
         assert!(is_safe_relative(Path::new("foo/bar.txt")));
@@ -1219,7 +2780,9 @@ mod tests {
         fs::write(src.join("subdir").join("file2.txt"), b"content2").unwrap();
 
         // Copy recursively
-        copy_dir_recursive(&src, &dst).await.unwrap();
+        let mut errors = Vec::new();
+        copy_dir_recursive(&src, &dst, &mut errors).await.unwrap();
+        assert!(errors.is_empty());
 
         // Verify structure is copied
         assert!(dst.join("file1.txt").exists());
@@ -1238,4 +2801,25 @@ mod tests {
         // Verify source still exists
         assert!(src.join("file1.txt").exists());
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_copy_dir_recursive_continues_past_failed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        let dst = temp_dir.path().join("dst");
+        fs::create_dir_all(src.join("subdir")).unwrap();
+        fs::write(src.join("a.txt"), b"a").unwrap();
+        fs::write(src.join("subdir").join("b.txt"), b"b").unwrap();
+        // A dangling symlink can't be copied, like a locked file on Windows
+        std::os::unix::fs::symlink(temp_dir.path().join("missing"), src.join("broken")).unwrap();
+
+        let mut errors = Vec::new();
+        copy_dir_recursive(&src, &dst, &mut errors).await.unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("broken"));
+        assert!(dst.join("a.txt").exists());
+        assert!(dst.join("subdir").join("b.txt").exists());
+    }
 }
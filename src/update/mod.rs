@@ -5,14 +5,25 @@
 //! - Backing up the current installation
 //! - Extracting new versions while preserving user data
 //! - Smart migration to only restore custom mods/tilesets/soundpacks/fonts
+//! - Keeping older installations for multi-step rollback
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 mod access;
 mod download;
+mod history;
 mod install;
 
-pub use access::check_installation_access;
-pub use download::{download_asset, download_dir};
-pub use install::install_update;
+pub use access::{
+    GameRunningError, RunningProcess, check_installation_access, find_running_game, terminate_game,
+};
+pub use download::{download_asset, download_dir, is_complete_download, is_intact_archive};
+pub use history::{ArchivedInstall, list_archives};
+pub use install::{
+    ArchiveAction, ArchivePreviewEntry, InstallOptions, MigrationSummary, excluded_required_dir,
+    install_update, installation_archive_size, preview_archive, rollback_to_archive,
+};
 
 /// Current phase of the update process
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -23,6 +34,7 @@ pub enum UpdatePhase {
     BackingUp,
     Extracting,
     Restoring,
+    Cleaning,
     Complete,
     Failed,
 }
@@ -36,12 +48,20 @@ impl UpdatePhase {
             UpdatePhase::BackingUp => "Backing up current installation...",
             UpdatePhase::Extracting => "Extracting new version...",
             UpdatePhase::Restoring => "Restoring saves and settings...",
+            UpdatePhase::Cleaning => "Removing previous version...",
             UpdatePhase::Complete => "Update complete!",
             UpdatePhase::Failed => "Update failed",
         }
     }
 }
 
+/// Share of overall update progress given to the download, by rough cost
+const DOWNLOAD_WEIGHT: f32 = 0.6;
+
+/// Share of overall update progress given to extraction; the remaining 10%
+/// covers restoring saves and settings, then removing the previous version
+const EXTRACT_WEIGHT: f32 = 0.3;
+
 /// Progress information for the update process
 #[derive(Debug, Clone, Default)]
 pub struct UpdateProgress {
@@ -52,6 +72,8 @@ pub struct UpdateProgress {
     pub files_extracted: usize,
     pub total_files: usize,
     pub current_file: String,
+    pub files_removed: usize,
+    pub total_to_remove: usize,
 }
 
 impl UpdateProgress {
@@ -72,6 +94,103 @@ impl UpdateProgress {
             self.files_extracted as f32 / self.total_files as f32
         }
     }
+
+    /// Calculate previous-version removal progress as a fraction (0.0 - 1.0)
+    pub fn cleanup_fraction(&self) -> f32 {
+        if self.total_to_remove == 0 {
+            0.0
+        } else {
+            self.files_removed as f32 / self.total_to_remove as f32
+        }
+    }
+
+    /// Combined progress across all update phases as a fraction (0.0 - 1.0)
+    ///
+    /// Unlike the per-phase fractions this never resets, so it can drive a
+    /// single bar for the whole update. Backing up and restoring report no
+    /// progress of their own and hold the bar at their phase's start.
+    pub fn overall_fraction(&self) -> f32 {
+        match self.phase {
+            UpdatePhase::Idle | UpdatePhase::Failed => 0.0,
+            UpdatePhase::Downloading => DOWNLOAD_WEIGHT * self.download_fraction(),
+            UpdatePhase::BackingUp => DOWNLOAD_WEIGHT,
+            UpdatePhase::Extracting => DOWNLOAD_WEIGHT + EXTRACT_WEIGHT * self.extract_fraction(),
+            UpdatePhase::Restoring => DOWNLOAD_WEIGHT + EXTRACT_WEIGHT,
+            UpdatePhase::Cleaning => {
+                let rest = 1.0 - DOWNLOAD_WEIGHT - EXTRACT_WEIGHT;
+                DOWNLOAD_WEIGHT + EXTRACT_WEIGHT + rest * self.cleanup_fraction()
+            }
+            UpdatePhase::Complete => 1.0,
+        }
+    }
+
+    /// Estimate the remaining download time at the given speed (bytes/sec)
+    pub fn eta(&self, speed: u64) -> Option<Duration> {
+        if speed == 0 || self.total_bytes == 0 {
+            return None;
+        }
+        let remaining = self.total_bytes.saturating_sub(self.bytes_downloaded);
+        Some(Duration::from_secs(remaining.div_ceil(speed)))
+    }
+}
+
+/// Interval between download speed samples
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of speed samples kept (about 30 seconds of history)
+const SPEED_HISTORY_LEN: usize = 30;
+
+/// Recent download speed samples, oldest first.
+///
+/// Samples are derived from the downloaded byte count rather than the reported
+/// speed, so a stalled connection shows up as zero instead of a stale figure.
+#[derive(Debug, Default)]
+pub struct SpeedHistory {
+    samples: VecDeque<u64>,
+    last: Option<(Instant, u64)>,
+}
+
+impl SpeedHistory {
+    /// Record the current download total, adding a sample once per interval
+    pub fn record(&mut self, now: Instant, bytes_downloaded: u64) {
+        let Some((last_time, last_bytes)) = self.last else {
+            self.last = Some((now, bytes_downloaded));
+            return;
+        };
+
+        let elapsed = now.duration_since(last_time);
+        if elapsed < SPEED_SAMPLE_INTERVAL {
+            return;
+        }
+
+        let bytes = bytes_downloaded.saturating_sub(last_bytes);
+        let speed = (bytes as f64 / elapsed.as_secs_f64()) as u64;
+        if self.samples.len() == SPEED_HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(speed);
+        self.last = Some((now, bytes_downloaded));
+    }
+
+    /// Speed samples in bytes/sec, oldest first
+    pub fn samples(&self) -> &VecDeque<u64> {
+        &self.samples
+    }
+
+    /// Average speed over the recorded window (0 if no samples yet)
+    pub fn average(&self) -> u64 {
+        if self.samples.is_empty() {
+            0
+        } else {
+            self.samples.iter().sum::<u64>() / self.samples.len() as u64
+        }
+    }
+
+    /// Discard all samples
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.last = None;
+    }
 }
 
 #[cfg(test)]
@@ -125,6 +244,32 @@ mod tests {
         assert_eq!(progress.extract_fraction(), 0.0);
     }
 
+    #[test]
+    fn test_overall_fraction_spans_phases() {
+        let mut progress = UpdateProgress {
+            phase: UpdatePhase::Downloading,
+            bytes_downloaded: 50,
+            total_bytes: 100,
+            ..Default::default()
+        };
+        assert!((progress.overall_fraction() - 0.3).abs() < 1e-6);
+
+        progress.phase = UpdatePhase::BackingUp;
+        assert!((progress.overall_fraction() - 0.6).abs() < 1e-6);
+
+        // Extraction starts from where the download left off, not from zero
+        progress.phase = UpdatePhase::Extracting;
+        progress.files_extracted = 50;
+        progress.total_files = 100;
+        assert!((progress.overall_fraction() - 0.75).abs() < 1e-6);
+
+        progress.phase = UpdatePhase::Restoring;
+        assert!((progress.overall_fraction() - 0.9).abs() < 1e-6);
+
+        progress.phase = UpdatePhase::Complete;
+        assert_eq!(progress.overall_fraction(), 1.0);
+    }
+
     #[test]
     fn test_update_progress_default() {
         let progress = UpdateProgress::default();
@@ -136,4 +281,34 @@ mod tests {
         assert_eq!(progress.total_files, 0);
         assert!(progress.current_file.is_empty());
     }
+
+    #[test]
+    fn test_progress_eta() {
+        let progress = UpdateProgress {
+            bytes_downloaded: 400,
+            total_bytes: 1000,
+            ..Default::default()
+        };
+        assert_eq!(progress.eta(100), Some(Duration::from_secs(6)));
+        assert_eq!(progress.eta(0), None);
+    }
+
+    #[test]
+    fn test_speed_history_samples_and_stalls() {
+        let start = Instant::now();
+        let mut history = SpeedHistory::default();
+        history.record(start, 0);
+        history.record(start + Duration::from_millis(500), 500);
+        assert!(history.samples().is_empty());
+
+        history.record(start + Duration::from_secs(1), 1000);
+        history.record(start + Duration::from_secs(2), 1000);
+        assert_eq!(history.samples(), &[1000, 0]);
+        assert_eq!(history.average(), 500);
+
+        for i in 3..40 {
+            history.record(start + Duration::from_secs(i), 1000);
+        }
+        assert_eq!(history.samples().len(), SPEED_HISTORY_LEN);
+    }
 }
@@ -1,5 +1,10 @@
 //! Shared utility functions
 
+use std::fs;
+use std::path::Path;
+
+use walkdir::WalkDir;
+
 /// Format a byte size for human-readable display.
 ///
 /// Uses binary units (1024-based) with common labels:
@@ -23,10 +28,148 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+/// Format a duration as a short countdown, e.g. "45s", "3m 20s", "1h 05m"
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Format a duration as a running clock, e.g. "0:45", "12:34", "1:02:03"
+pub fn format_clock(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Decode a metadata text file such as tileset.txt or soundpack.txt.
+///
+/// Reads UTF-8 with or without a byte order mark, falling back to Latin-1
+/// (which the game's own loader assumes) when the bytes aren't valid UTF-8.
+pub fn decode_text(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| char::from(b)).collect(),
+    }
+}
+
+/// Trim whitespace and zero-width characters (including stray byte order
+/// marks) from both ends of `text`
+pub fn trim_text(text: &str) -> &str {
+    text.trim_matches(|c: char| {
+        c.is_whitespace()
+            || matches!(
+                c,
+                '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}'
+            )
+    })
+}
+
+/// Read the value of a `FIELD value` (or `FIELD: value`) line from a
+/// tileset.txt or soundpack.txt, or `None` for other fields and empty values.
+///
+/// A quoted value (`"Foo, Bar"`) is taken verbatim. Otherwise an inline `#`
+/// comment (preceded by whitespace) is dropped, as are commas when
+/// `strip_commas` is set (the game does this for NAME).
+pub fn parse_info_field(line: &str, field: &str, strip_commas: bool) -> Option<String> {
+    let rest = trim_text(line).strip_prefix(field)?;
+    if !rest.is_empty() && !rest.starts_with(|c: char| c == ':' || c.is_whitespace()) {
+        return None;
+    }
+    let value = trim_text(rest.strip_prefix(':').unwrap_or(rest));
+
+    if let Some(quoted) = value.strip_prefix('"')
+        && let Some(end) = quoted.find('"')
+    {
+        let value = trim_text(&quoted[..end]);
+        return (!value.is_empty()).then(|| value.to_string());
+    }
+
+    let value = value
+        .char_indices()
+        .find(|&(i, c)| c == '#' && (i == 0 || value[..i].ends_with(char::is_whitespace)))
+        .map_or(value, |(i, _)| &value[..i]);
+    let value = trim_text(value);
+    let value = if strip_commas {
+        value.replace(',', "")
+    } else {
+        value.to_string()
+    };
+    (!value.is_empty()).then_some(value)
+}
+
+/// Delete a file or directory tree, reporting `(files_removed, total_files)`
+/// after each file.
+///
+/// Large trees (a multi-GB installation archive) take a while to delete, so
+/// this counts the files first and removes them one by one instead of in a
+/// single opaque `remove_dir_all`. Blocking; call it from `spawn_blocking`.
+pub fn remove_all_with_progress(
+    path: &Path,
+    mut on_progress: impl FnMut(usize, usize),
+) -> std::io::Result<()> {
+    if !fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_file(path)?;
+        on_progress(1, 1);
+        return Ok(());
+    }
+
+    let total = WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| !entry.file_type().is_dir())
+        .count();
+    on_progress(0, total);
+
+    let mut removed = 0;
+    for entry in WalkDir::new(path).contents_first(true) {
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            fs::remove_dir(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+            removed += 1;
+            on_progress(removed, total);
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_info_field() {
+        let name = |line| parse_info_field(line, "NAME", true);
+        assert_eq!(name("NAME  My Tiles "), Some("My Tiles".into()));
+        assert_eq!(name("NAME: My Tiles"), Some("My Tiles".into()));
+        assert_eq!(name("NAME Foo, Bar"), Some("Foo Bar".into()));
+        assert_eq!(name("NAME \"Foo, Bar\""), Some("Foo, Bar".into()));
+        assert_eq!(name("NAME \"Foo # 2\" # quoted"), Some("Foo # 2".into()));
+        assert_eq!(name("NAME Foo # old name"), Some("Foo".into()));
+        assert_eq!(name("NAME Pack#2"), Some("Pack#2".into()));
+        assert_eq!(name("NAME \"unterminated"), Some("\"unterminated".into()));
+        assert_eq!(name("NAME # comment only"), None);
+        assert_eq!(name("NAME \"\""), None);
+        assert_eq!(name("NAMES Foo"), None);
+        assert_eq!(name("VIEW Foo"), None);
+        assert_eq!(
+            parse_info_field("VIEW Foo, Bar", "VIEW", false),
+            Some("Foo, Bar".into())
+        );
+    }
+
     #[test]
     fn test_format_size_bytes() {
         assert_eq!(format_size(0), "0 B");
@@ -53,4 +196,62 @@ mod tests {
         assert_eq!(format_size(1024 * 1024 * 1024), "1.0 GB");
         assert_eq!(format_size(2 * 1024 * 1024 * 1024), "2.0 GB");
     }
+
+    #[test]
+    fn test_format_duration() {
+        use std::time::Duration;
+        assert_eq!(format_duration(Duration::from_secs(0)), "0s");
+        assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(format_duration(Duration::from_secs(200)), "3m 20s");
+        assert_eq!(format_duration(Duration::from_secs(3900)), "1h 05m");
+    }
+
+    #[test]
+    fn test_format_clock() {
+        use std::time::Duration;
+        assert_eq!(format_clock(Duration::from_secs(45)), "0:45");
+        assert_eq!(format_clock(Duration::from_secs(754)), "12:34");
+        assert_eq!(format_clock(Duration::from_secs(3723)), "1:02:03");
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(decode_text(b"NAME Foo\n"), "NAME Foo\n");
+        assert_eq!(decode_text(b"\xEF\xBB\xBFNAME Foo"), "NAME Foo");
+        assert_eq!(decode_text("NAME Café".as_bytes()), "NAME Café");
+        // Latin-1 é is not valid UTF-8
+        assert_eq!(decode_text(b"NAME Caf\xE9"), "NAME Café");
+    }
+
+    #[test]
+    fn test_trim_text() {
+        assert_eq!(trim_text("  NAME Foo \r"), "NAME Foo");
+        assert_eq!(trim_text("\u{FEFF}NAME Foo"), "NAME Foo");
+        assert_eq!(trim_text("\u{200B}Foo\u{200D}\u{2060}"), "Foo");
+        assert_eq!(trim_text("Foo\u{200B}Bar"), "Foo\u{200B}Bar");
+    }
+
+    #[test]
+    fn test_remove_all_with_progress() {
+        let dir = std::env::temp_dir().join("phoenix_test_remove_progress");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("a/b")).unwrap();
+        fs::write(dir.join("one.txt"), "1").unwrap();
+        fs::write(dir.join("a/two.txt"), "2").unwrap();
+        fs::write(dir.join("a/b/three.txt"), "3").unwrap();
+
+        let mut reports = Vec::new();
+        remove_all_with_progress(&dir, |removed, total| reports.push((removed, total))).unwrap();
+
+        assert!(!dir.exists());
+        assert_eq!(reports, vec![(0, 3), (1, 3), (2, 3), (3, 3)]);
+
+        // A single file counts as one
+        let file = std::env::temp_dir().join("phoenix_test_remove_progress.txt");
+        fs::write(&file, "x").unwrap();
+        let mut reports = Vec::new();
+        remove_all_with_progress(&file, |removed, total| reports.push((removed, total))).unwrap();
+        assert!(!file.exists());
+        assert_eq!(reports, vec![(1, 1)]);
+    }
 }